@@ -8,19 +8,48 @@
     let teams = {};
     let tasksByTeam = {};
     let messagesByKey = {};
+    let plans = {};
+    let alerts = {}; // keyed by alert key
+    let sources = {}; // remote monitors merged in (`--source`), by name
+
+    // Replay mode (`forja monitor replay`): ?replay=<session>&speed=N[&team=]
+    const params = new URLSearchParams(window.location.search);
+    const replaySession = params.get("replay");
+    let replayClock = null; // recording time (unix ms) of the last replayed event
+
+    // Monitors started with a token require it on every API request
+    const token = params.get("token");
+
+    function apiUrl(path) {
+        return token ? path + "?token=" + encodeURIComponent(token) : path;
+    }
 
     // Elements
     const statusDot = $("#status-dot");
     const statusText = $("#status-text");
+    const sourcesList = $("#sources");
     const teamsList = $("#teams-list");
+    const plansList = $("#plans-list");
+    const alertsList = $("#alerts-list");
     const tasksPending = $("#tasks-pending");
     const tasksInProgress = $("#tasks-in-progress");
     const tasksCompleted = $("#tasks-completed");
     const messagesFeed = $("#messages-feed");
     const activityLog = $("#activity-log");
+    const messageForm = $("#message-form");
+    const messageRecipient = $("#message-recipient");
+    const messageText = $("#message-text");
+
+    // Control channel: nudges written to inbox and task files (not in replays)
+    let control = null;
+    let controlSeq = 0;
 
     function connect() {
-        const es = new EventSource("/api/events");
+        if (replaySession) {
+            replay();
+            return;
+        }
+        const es = new EventSource(apiUrl("/api/events"));
 
         es.onopen = () => {
             statusDot.className = "dot connected";
@@ -42,6 +71,89 @@
         };
     }
 
+    function connectControl() {
+        const proto = window.location.protocol === "https:" ? "wss:" : "ws:";
+        const ws = new WebSocket(proto + "//" + window.location.host + apiUrl("/api/control"));
+
+        ws.onopen = () => { control = ws; };
+        ws.onclose = () => {
+            control = null;
+            setTimeout(connectControl, 3000);
+        };
+        ws.onmessage = (e) => {
+            try {
+                const reply = JSON.parse(e.data);
+                if (!reply.ok) logActivity("Control error: " + reply.error);
+            } catch (err) {
+                console.error("Failed to parse control reply:", err);
+            }
+        };
+    }
+
+    function sendControl(command) {
+        if (!control) {
+            logActivity("Control channel not connected");
+            return;
+        }
+        command.id = ++controlSeq;
+        control.send(JSON.stringify(command));
+    }
+
+    messageForm.addEventListener("submit", (e) => {
+        e.preventDefault();
+        const target = messageRecipient.value.split("/");
+        const text = messageText.value.trim();
+        if (target.length < 2 || !text) return;
+        sendControl({
+            type: "SendMessage",
+            team_name: target[0],
+            recipient: target.slice(1).join("/"),
+            text: text,
+        });
+        messageText.value = "";
+    });
+
+    function replay() {
+        const query = new URLSearchParams({
+            session: replaySession,
+            speed: params.get("speed") || "1",
+        });
+        if (params.get("team")) query.set("team", params.get("team"));
+        if (token) query.set("token", token);
+        const es = new EventSource("/api/replay?" + query.toString());
+        const label = "Replay " + replaySession + " (" + query.get("speed") + "x)";
+
+        es.onopen = () => {
+            statusDot.className = "dot connected";
+            statusText.textContent = label;
+        };
+
+        es.onerror = () => {
+            statusDot.className = "dot disconnected";
+            statusText.textContent = "Replay unavailable";
+            es.close();
+        };
+
+        es.onmessage = (e) => {
+            if (e.lastEventId) {
+                replayClock = Number(e.lastEventId);
+                statusText.textContent = label + " \u2014 " +
+                    formatTime(new Date(replayClock).toISOString());
+            }
+            try {
+                handleEvent(JSON.parse(e.data));
+            } catch (err) {
+                console.error("Failed to parse event:", err);
+            }
+        };
+
+        es.addEventListener("end", () => {
+            es.close();
+            statusDot.className = "dot disconnected";
+            statusText.textContent = "Replay of " + replaySession + " finished";
+        });
+    }
+
     function handleEvent(event) {
         switch (event.type) {
             case "Snapshot":
@@ -59,6 +171,24 @@
                 renderTasks();
                 logActivity("Team removed: " + event.team_name);
                 break;
+            case "Alert":
+                alerts[event.alert.key] = event.alert;
+                renderAlerts();
+                logActivity("\u26a0 " + event.alert.message);
+                break;
+            case "AlertCleared":
+                delete alerts[event.key];
+                renderAlerts();
+                break;
+            case "SourceUpdated":
+                sources[event.source.name] = event.source;
+                renderSources();
+                renderTeams();
+                logActivity(event.source.connected
+                    ? "Source " + event.source.name + " connected"
+                    : "Source " + event.source.name + " unreachable" +
+                        (event.source.error ? ": " + event.source.error : ""));
+                break;
             case "TaskUpdated":
                 if (!tasksByTeam[event.team_name]) tasksByTeam[event.team_name] = {};
                 tasksByTeam[event.team_name][event.task.id] = event.task;
@@ -82,6 +212,14 @@
                 renderMessages();
                 logActivity(event.message.from + " \u2192 " + event.recipient);
                 break;
+            case "PlanUpdated":
+                plans[event.plan.plan_id] = event.plan;
+                renderPlans();
+                logActivity(
+                    "Plan " + event.plan.plan_id + ": " + event.plan.phases_completed +
+                    "/" + event.plan.phases_total + " phases, " + formatCost(event.plan.usage)
+                );
+                break;
             case "Heartbeat":
                 break;
         }
@@ -105,9 +243,21 @@
             messagesByKey[key] = group.messages || [];
         });
 
+        plans = {};
+        (snapshot.plans || []).forEach(function (p) { plans[p.plan_id] = p; });
+
+        alerts = {};
+        (snapshot.alerts || []).forEach(function (a) { alerts[a.key] = a; });
+
+        sources = {};
+        (snapshot.sources || []).forEach(function (s) { sources[s.name] = s; });
+
+        renderSources();
+        renderAlerts();
         renderTeams();
         renderTasks();
         renderMessages();
+        renderPlans();
         logActivity("Dashboard connected");
     }
 
@@ -131,9 +281,15 @@
 
         names.forEach(function (name) {
             var team = teams[name];
-            var card = el("div", "team-card");
+            var source = team.source ? sources[team.source] : null;
+            var stale = team.source && !(source && source.connected);
+            var card = el("div", "team-card" + (stale ? " team-stale" : ""));
 
             card.appendChild(el("div", "team-name", team.name));
+            if (team.source) {
+                card.appendChild(el("div", "team-source",
+                    "via " + team.source + (stale ? " (disconnected)" : "")));
+            }
             if (team.description) {
                 card.appendChild(el("div", "team-desc", team.description));
             }
@@ -149,6 +305,105 @@
 
             teamsList.appendChild(card);
         });
+        renderRecipients();
+    }
+
+    function renderSources() {
+        sourcesList.textContent = "";
+        Object.keys(sources).sort().forEach(function (name) {
+            var source = sources[name];
+            var item = el("span", "source");
+            item.appendChild(el("span", "dot " + (source.connected ? "connected" : "disconnected")));
+            item.appendChild(el("span", null, name));
+            item.title = source.url + (source.error ? " \u2014 " + source.error : "");
+            sourcesList.appendChild(item);
+        });
+    }
+
+    function renderAlerts() {
+        var nodes = Object.keys(alerts).sort().map(function (key) {
+            var alert = alerts[key];
+            var card = el("div", "alert-card");
+            card.appendChild(el("div", "alert-team", alert.team_name));
+            card.appendChild(el("div", "alert-message", alert.message));
+            return card;
+        });
+        fillContainer(alertsList, nodes, "No alerts");
+    }
+
+    function renderRecipients() {
+        var current = messageRecipient.value;
+        messageRecipient.textContent = "";
+        Object.keys(teams).sort().forEach(function (name) {
+            if (teams[name].source) return; // remote teams are read-only here
+            (teams[name].members || []).forEach(function (m) {
+                var option = el("option", null, m.name + " \u00b7 " + name);
+                option.value = name + "/" + m.name;
+                messageRecipient.appendChild(option);
+            });
+        });
+        if (current) messageRecipient.value = current;
+    }
+
+    function renderPlans() {
+        var ids = Object.keys(plans).sort().reverse();
+        plansList.textContent = "";
+
+        if (ids.length === 0) {
+            plansList.appendChild(el("p", "empty", "No plan executions"));
+            return;
+        }
+
+        // Totals per profile, so fast/balanced/max can be compared at a glance
+        var byProfile = {};
+        ids.forEach(function (id) {
+            var plan = plans[id];
+            var total = byProfile[plan.profile] || { plans: 0, cost: 0 };
+            total.plans += 1;
+            total.cost += plan.usage.cost_usd || 0;
+            byProfile[plan.profile] = total;
+        });
+        Object.keys(byProfile).sort().forEach(function (profile) {
+            var total = byProfile[profile];
+            var row = el("div", "spend-profile");
+            row.appendChild(el("span", "spend-profile-name", profile));
+            row.appendChild(el("span", "spend-cost",
+                "$" + total.cost.toFixed(2) + " \u00b7 " + total.plans + " plans"));
+            plansList.appendChild(row);
+        });
+
+        ids.forEach(function (id) {
+            var plan = plans[id];
+            var card = el("div", "plan-card");
+            card.appendChild(el("div", "plan-task", plan.task));
+
+            var meta = el("div", "plan-meta");
+            meta.appendChild(el("span", null,
+                plan.phases_completed + "/" + plan.phases_total + " \u00b7 " + plan.profile));
+            meta.appendChild(el("span", "spend-cost", formatCost(plan.usage)));
+            card.appendChild(meta);
+
+            card.appendChild(el("div", "plan-tokens",
+                formatTokens(totalInput(plan.usage)) + " in \u00b7 " +
+                formatTokens(plan.usage.output_tokens) + " out"));
+            plansList.appendChild(card);
+        });
+    }
+
+    function totalInput(usage) {
+        return (usage.input_tokens || 0) + (usage.cache_creation_input_tokens || 0) +
+            (usage.cache_read_input_tokens || 0);
+    }
+
+    function formatTokens(n) {
+        n = n || 0;
+        if (n < 1000) return String(n);
+        if (n < 1000000) return (n / 1000).toFixed(1) + "k";
+        return (n / 1000000).toFixed(1) + "M";
+    }
+
+    function formatCost(usage) {
+        return "$" + ((usage && usage.cost_usd) || 0).toFixed(2);
     }
 
     function renderTasks() {
@@ -193,6 +448,28 @@
         meta.appendChild(el("span", "task-id", "#" + task.id + " \u00b7 " + teamName));
         card.appendChild(meta);
 
+        var remote = teams[teamName] && teams[teamName].source;
+        if (!replaySession && !remote) {
+            var status = el("select", "task-status");
+            [["pending", "Pending"], ["in_progress", "In progress"], ["completed", "Done"]]
+                .forEach(function (pair) {
+                    var option = el("option", null, pair[1]);
+                    option.value = pair[0];
+                    status.appendChild(option);
+                });
+            status.value = task.status;
+            status.title = "Change the task's status";
+            status.addEventListener("change", function () {
+                sendControl({
+                    type: "SetTaskStatus",
+                    team_name: teamName,
+                    task_id: task.id,
+                    status: status.value,
+                });
+            });
+            card.appendChild(status);
+        }
+
         return card;
     }
 
@@ -269,7 +546,8 @@
         if (empty) empty.remove();
 
         var item = el("div", "activity-item");
-        item.appendChild(el("span", "activity-time", formatTime(new Date().toISOString())));
+        var at = replayClock === null ? new Date() : new Date(replayClock);
+        item.appendChild(el("span", "activity-time", formatTime(at.toISOString())));
         item.appendChild(el("span", "activity-text", text));
         activityLog.appendChild(item);
 
@@ -301,4 +579,9 @@
 
     // Start
     connect();
+    if (replaySession) {
+        messageForm.style.display = "none";
+    } else {
+        connectControl();
+    }
 })();
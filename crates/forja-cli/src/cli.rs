@@ -1,5 +1,9 @@
+use std::net::IpAddr;
+
 use clap::{Parser, Subcommand};
 
+use crate::commands::execute::FailurePolicy;
+
 #[derive(Parser)]
 #[command(
     name = "forja",
@@ -27,12 +31,14 @@ pub enum Commands {
         long_about = "Initialize forja by creating a .forja/ directory, cloning the skills \
             registry, and installing all skills. By default, installs into the current project \
             (.forja/ + .claude/agents/). Use --global to install into ~/.forja/ + ~/.claude/agents/ \
-            instead. Use --wizard for interactive setup (choose mode, phases, profile).",
+            instead. Use --wizard for interactive setup (choose mode, phases, profile). Use --copy \
+            to copy skill files instead of symlinking them.",
         after_help = "\
 EXAMPLES:
   forja init                          # Project-local install (default)
   forja init --global                 # Global install (~/.forja/)
   forja init --wizard                 # Interactive setup wizard
+  forja init --copy                   # Copy skill files instead of symlinking
   forja init --registry-url <url>     # Use a custom registry"
     )]
     Init {
@@ -47,6 +53,11 @@ EXAMPLES:
         /// Install globally (~/.forja/ + ~/.claude/) instead of project-local
         #[arg(long)]
         global: bool,
+
+        /// Copy skill files into .claude/ instead of symlinking them, so they
+        /// can be committed and edited
+        #[arg(long)]
+        copy: bool,
     },
 
     /// Install a skill via symlink
@@ -79,12 +90,14 @@ EXAMPLES:
     #[command(
         long_about = "Remove a skill by deleting its symlinks from .claude/agents/ \
             and .claude/commands/. Use --global to remove from ~/.claude/ instead. \
-            Prompts for confirmation unless --yes is passed.",
+            Refuses to remove a skill that other installed skills require unless \
+            --force is passed. Prompts for confirmation unless --yes is passed.",
         after_help = "\
 EXAMPLES:
   forja uninstall code/rust/coder     # Remove from project .claude/
   forja uninstall --global coder      # Remove from ~/.claude/
-  forja uninstall coder -y            # Skip confirmation"
+  forja uninstall coder -y            # Skip confirmation
+  forja uninstall coder --force       # Remove even if a team skill requires it"
     )]
     Uninstall {
         /// Skill name or full path
@@ -97,42 +110,76 @@ EXAMPLES:
         /// Remove from ~/.claude/ instead of project-local .claude/
         #[arg(long)]
         global: bool,
+
+        /// Uninstall even if other installed skills require it
+        #[arg(long)]
+        force: bool,
     },
 
     /// Search the catalog
     #[command(
-        long_about = "Search the skills catalog by name, description, phase, or technology. \
-            Results show install status and skill descriptions.",
+        long_about = "Search the skills catalog, best matches first: exact names rank above \
+            keywords, technologies and descriptions, and small typos are tolerated. Every \
+            term must match; separate alternatives with OR. Narrow results with phase:, \
+            tech: and registry: filters. Without a query, browse the catalog interactively.",
         after_help = "\
 EXAMPLES:
   forja search rust                   # Find Rust skills
-  forja search \"code review\"          # Find review skills
-  forja search nextjs                 # Find Next.js skills"
+  forja search \"code review\"          # Skills matching both terms
+  forja search \"rust OR go\"           # Skills matching either term
+  forja search \"phase:review tech:rust\" # Filter by phase and technology
+  forja search                        # Browse interactively"
     )]
     Search {
-        /// Search query (matches name, description, phase, tech)
-        query: String,
+        /// Search query: terms, OR, and phase:/tech:/registry: filters
+        query: Option<String>,
     },
 
     /// List skills
     #[command(
         long_about = "List installed skills, or browse all available skills organized \
-            by workflow phase with --available.",
+            by workflow phase with --available. In a project, --effective shows the skills \
+            it actually gets: global installs plus project installs, with the add, exclude \
+            and override lists from the skills section of .forja/config.json applied, and \
+            the layer each one comes from.",
         after_help = "\
 EXAMPLES:
   forja list                          # Show installed skills
-  forja list --available              # Browse all skills by phase"
+  forja list --available              # Browse all skills by phase
+  forja list --effective              # Resolved global + project skills and their layer"
     )]
     List {
         /// Show all available skills instead of just installed
-        #[arg(long)]
+        #[arg(long, conflicts_with = "effective")]
         available: bool,
+
+        /// Show the resolved global + project skill set and where each skill comes from
+        #[arg(long)]
+        effective: bool,
     },
 
     /// Update the registry (git pull)
-    #[command(long_about = "Pull the latest skill definitions from the registry. \
-            In dev mode (symlinked registry), this is a no-op.")]
-    Update,
+    #[command(
+        long_about = "Pull the latest skill definitions from the registry. \
+            In dev mode (symlinked registry), this is a no-op. In project mode the new \
+            revisions and installed skill hashes are written to forja.lock; --lock checks \
+            the registries out at the locked revisions instead, and --frozen only verifies \
+            that they match.",
+        after_help = "\
+EXAMPLES:
+  forja update                        # Pull latest and re-lock
+  forja update --lock                 # Restore the revisions in forja.lock
+  forja update --frozen               # CI: fail if anything differs from forja.lock"
+    )]
+    Update {
+        /// Check registries out at the revisions in forja.lock instead of pulling
+        #[arg(long, conflicts_with = "frozen")]
+        lock: bool,
+
+        /// Change nothing; fail if registries or skills differ from forja.lock
+        #[arg(long)]
+        frozen: bool,
+    },
 
     /// Show skill details
     #[command(
@@ -198,9 +245,15 @@ EXAMPLES:
 EXAMPLES:
   forja plan                              # Open interactive TUI
   forja plan \"add user auth with JWT\"
-  forja plan \"refactor the database layer\""
+  forja plan \"refactor the database layer\"
+  forja plan check <plan-id>              # Validate the phase graph
+  forja plan check <plan-id> --json       # Machine-readable report",
+        args_conflicts_with_subcommands = true
     )]
     Plan {
+        #[command(subcommand)]
+        command: Option<PlanCommands>,
+
         /// Task description (omit to open interactive TUI)
         task: Option<String>,
     },
@@ -242,9 +295,23 @@ EXAMPLES:
   forja execute                       # Run the latest plan
   forja execute abc123                # Run a specific plan
   forja execute --resume              # Resume from last checkpoint
-  forja execute --profile fast        # Use fast model profile"
+  forja execute --profile fast        # Use fast model profile
+  forja execute --jobs 3              # Run up to 3 independent phases at once
+  forja execute --on-failure retry:2  # Retry failed phases twice with backoff
+  forja execute --non-interactive --on-failure skip   # CI: never prompt, skip failures
+  forja execute logs abc123 --phase 2 # Replay a phase's transcript
+  forja execute logs abc123 --follow  # Stream phases as they run
+  forja execute --commit              # Commit each phase on forja/<plan-id>
+  forja execute rollback abc123 --to-phase 2  # Reset the tree to phase 2's start
+  forja execute abc123 --worktree     # Run in .forja/worktrees/abc123, leaving your checkout free
+  forja execute merge abc123          # Merge the worktree's branch and remove it
+  forja execute discard abc123        # Throw the worktree away",
+        args_conflicts_with_subcommands = true
     )]
     Execute {
+        #[command(subcommand)]
+        command: Option<ExecuteCommands>,
+
         /// Plan ID (defaults to latest pending plan)
         #[arg()]
         plan_id: Option<String>,
@@ -256,27 +323,80 @@ EXAMPLES:
         /// Resume from last checkpoint (skip completed phases)
         #[arg(long)]
         resume: bool,
+
+        /// Max phases to run concurrently (>1 runs phases headless)
+        #[arg(long, short = 'j', default_value = "1", value_parser = clap::value_parser!(u16).range(1..))]
+        jobs: u16,
+
+        /// What to do when a phase fails: retry:N, skip or abort
+        #[arg(long, value_name = "POLICY", default_value = "abort")]
+        on_failure: FailurePolicy,
+
+        /// Never prompt; phases run headless and exhausted retries abort
+        #[arg(long)]
+        non_interactive: bool,
+
+        /// Commit each completed phase on a forja/<plan-id> branch
+        #[arg(long)]
+        commit: bool,
+
+        /// Run phases in a git worktree under .forja/worktrees/<plan-id>
+        #[arg(long)]
+        worktree: bool,
     },
 
     /// Real-time dashboard for monitoring agent teams
     #[command(
         long_about = "Launch a real-time web dashboard that monitors active Claude Code agent \
             teams. Watches team configs, task progress, and inter-agent messages, streaming \
-            updates live to your browser via SSE.",
+            updates live to your browser via SSE, or in the terminal with --tui. Every run \
+            records its events under ~/.forja/monitor/ so the session can be replayed later. \
+            With --source, the teams of other forja monitors (e.g. on a build box) are merged \
+            in, labeled with the source they come from; a shared --token protects the API.",
         after_help = "\
 EXAMPLES:
   forja monitor                     # Start on default port 3030
   forja monitor --port 8080         # Use custom port
-  forja monitor --no-open           # Don't auto-open browser"
+  forja monitor --no-open           # Don't auto-open browser
+  forja monitor --tui               # Terminal dashboard (e.g. over SSH)
+  forja monitor --bind 0.0.0.0 --no-open  # Share it, with FORJA_MONITOR_TOKEN set
+  forja monitor --source build=http://build-box:3030   # Merge in a shared monitor
+  forja monitor history             # List recorded sessions
+  forja monitor replay latest       # Replay the last session at 10x
+  forja monitor replay 20261018-021500 --speed 60 --team night-shift"
     )]
     Monitor {
+        #[command(subcommand)]
+        command: Option<MonitorCommands>,
+
         /// Port to bind the dashboard server
-        #[arg(long, default_value = "3030")]
+        #[arg(long, default_value = "3030", global = true)]
         port: u16,
 
         /// Don't auto-open the browser
-        #[arg(long)]
+        #[arg(long, global = true)]
         no_open: bool,
+
+        /// Address to listen on; anything but loopback requires a token
+        #[arg(long, default_value = "127.0.0.1", global = true)]
+        bind: IpAddr,
+
+        /// Shared token required by the API and sent to sources
+        #[arg(
+            long,
+            env = "FORJA_MONITOR_TOKEN",
+            hide_env_values = true,
+            global = true
+        )]
+        token: Option<String>,
+
+        /// Merge in the teams of another monitor (NAME=URL, repeatable)
+        #[arg(long = "source", value_name = "NAME=URL")]
+        sources: Vec<String>,
+
+        /// Show the dashboard in the terminal instead of serving it
+        #[arg(long, conflicts_with_all = ["port", "no_open", "bind"])]
+        tui: bool,
     },
 
     /// Quick bug fix (shortcut for: forja task --team quick-fix)
@@ -430,7 +550,9 @@ EXAMPLES:
     /// Show skill changes since last update
     #[command(
         long_about = "Compare the registry before and after the last `forja update` to show \
-            which skills were added, modified, or removed.",
+            which skills were added, modified, or removed. Modified skills are grouped by \
+            the semver bump in their skill.json (major, minor, patch, or unversioned) and \
+            show the matching entries from the skill's CHANGELOG.md.",
         after_help = "\
 EXAMPLES:
   forja update && forja diff            # Update then see changes"
@@ -439,23 +561,68 @@ EXAMPLES:
 
     /// Reinstall modified skills after an update
     #[command(
-        long_about = "Reinstall skills that were modified since the last `forja update`. \
-            Re-creates symlinks to pick up agent/command changes.",
+        long_about = "Reinstall skills that were modified since the last `forja update`, or \
+            whose catalog version is newer than the one recorded at install time. \
+            Re-creates symlinks to pick up agent/command changes. Major version bumps \
+            may change agent behavior and are held back unless --allow-major is passed.",
         after_help = "\
 EXAMPLES:
   forja upgrade                         # Upgrade all modified installed skills
   forja upgrade coder                   # Upgrade a specific skill
+  forja upgrade --allow-major           # Include breaking (major) version bumps
   forja upgrade -y                      # Skip confirmation"
     )]
     Upgrade {
         /// Filter to a specific skill (partial match)
         skill: Option<String>,
 
+        /// Also apply major version bumps
+        #[arg(long)]
+        allow_major: bool,
+
         /// Skip confirmation prompt
         #[arg(long, short = 'y')]
         yes: bool,
     },
 
+    /// Manage skill registries
+    #[command(
+        long_about = "Add, remove, and rank skill registries. Besides the default catalog, \
+            any git repository or local directory with a skills/<phase>/<tech>/<name> layout \
+            can be registered under ~/.forja/registries/<name>. When two registries ship the \
+            same skill ID, the higher priority wins; the other copy stays reachable as \
+            <registry>:<phase>/<tech>/<name>.",
+        after_help = "\
+EXAMPLES:
+  forja registry add acme git@github.com:acme/skills.git   # Private skills, priority 10
+  forja registry add local ~/src/my-skills --priority -1    # Local dir, below the default
+  forja registry list                                       # Show registries and shadowing
+  forja registry priority default 20                        # Let the public catalog win
+  forja install acme:code/general/feature                   # Pick a shadowed skill
+  forja registry remove acme                                # Forget a registry"
+    )]
+    Registry {
+        #[command(subcommand)]
+        command: RegistryCommands,
+    },
+
+    /// Set up a stack in one go from a skill bundle
+    #[command(
+        long_about = "Apply a skill bundle: a registry manifest (bundles/<name>.json) listing \
+            the skills to install, the team presets to create and the model profile to use. \
+            `forja init` proposes the bundle that matches the detected stack.",
+        after_help = "\
+EXAMPLES:
+  forja bundle list                   # Bundles from all registries
+  forja bundle show rust-service      # Skills, teams and profile it sets up
+  forja bundle apply rust-service     # Install and create all of it
+  forja bundle apply rust-service -y  # Without the confirmation prompt"
+    )]
+    Bundle {
+        #[command(subcommand)]
+        command: BundleCommands,
+    },
+
     /// Spec-driven execution pipeline
     #[command(
         long_about = "Read spec files from docs/specs/, generate AI-driven plans, and execute \
@@ -467,6 +634,7 @@ EXAMPLES:
   forja sparks show user-auth         # Display full spec details
   forja sparks plan user-auth         # Generate execution plan from spec
   forja sparks execute user-auth      # Run the plan
+  forja sparks execute user-auth --non-interactive --on-failure skip  # CI-friendly run
   forja sparks status                 # Show execution progress"
     )]
     Sparks {
@@ -533,6 +701,152 @@ pub enum TeamCommands {
     },
 }
 
+#[derive(Subcommand)]
+pub enum RegistryCommands {
+    /// Register a git URL or local directory as a skill registry
+    Add {
+        /// Registry name (lowercase letters, digits, '-' or '_')
+        name: String,
+
+        /// Git URL to clone, or a local directory to link
+        source: String,
+
+        /// Higher wins when registries share a skill ID (default catalog: 0)
+        #[arg(long, allow_negative_numbers = true)]
+        priority: Option<i32>,
+    },
+
+    /// Remove a registry and uninstall the skills that came from it
+    Remove {
+        /// Registry name
+        name: String,
+
+        /// Skip confirmation prompt
+        #[arg(long, short = 'y')]
+        yes: bool,
+    },
+
+    /// List registries, highest priority first
+    List,
+
+    /// Change a registry's priority
+    Priority {
+        /// Registry name ("default" for the built-in catalog)
+        name: String,
+
+        /// New priority; higher wins
+        #[arg(allow_negative_numbers = true)]
+        value: i32,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum BundleCommands {
+    /// List bundles from all registries
+    List,
+
+    /// Show the skills, teams and profile of a bundle
+    Show {
+        /// Bundle name
+        name: String,
+    },
+
+    /// Install a bundle's skills and create its teams
+    Apply {
+        /// Bundle name
+        name: String,
+
+        /// Skip confirmation prompt
+        #[arg(long, short = 'y')]
+        yes: bool,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum MonitorCommands {
+    /// List the sessions recorded under ~/.forja/monitor/
+    History,
+
+    /// Replay a recorded session in the dashboard
+    Replay {
+        /// Session ID from `forja monitor history`, or `latest`
+        session: String,
+
+        /// Playback speed, as a multiple of real time
+        #[arg(long, default_value = "10")]
+        speed: f64,
+
+        /// Only replay the events of this team
+        #[arg(long)]
+        team: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum PlanCommands {
+    /// Validate a plan's phases: cycles, unknown dependencies, duplicates, file conflicts
+    Check {
+        /// Plan ID
+        plan_id: String,
+
+        /// Output the report as JSON
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum ExecuteCommands {
+    /// Print the saved transcripts of a plan's phases
+    ///
+    /// Headless runs (--jobs above 1, --non-interactive, or no terminal on stdin)
+    /// record claude's events and stderr. Interactive sessions run under
+    /// script(1) and record the terminal output as-is; without script on PATH,
+    /// only their stderr is recorded.
+    Logs {
+        /// Plan ID
+        plan_id: String,
+
+        /// Only show this phase (1-based)
+        #[arg(long, short = 'p')]
+        phase: Option<usize>,
+
+        /// Keep printing new output until the phases finish
+        #[arg(long, short = 'f')]
+        follow: bool,
+    },
+
+    /// Reset the working tree to the commit a phase started from
+    Rollback {
+        /// Plan ID
+        plan_id: String,
+
+        /// Phase to roll back to (1-based); it and later phases become pending
+        #[arg(long, value_name = "N")]
+        to_phase: usize,
+
+        /// Skip confirmation prompt
+        #[arg(long, short = 'y')]
+        yes: bool,
+    },
+
+    /// Merge a plan's worktree branch into the current branch and remove the worktree
+    Merge {
+        /// Plan ID
+        plan_id: String,
+    },
+
+    /// Delete a plan's worktree and branch without merging
+    Discard {
+        /// Plan ID
+        plan_id: String,
+
+        /// Skip confirmation prompt
+        #[arg(long, short = 'y')]
+        yes: bool,
+    },
+}
+
 #[derive(Subcommand)]
 pub enum SparksCommands {
     /// List all specs with status
@@ -566,6 +880,26 @@ pub enum SparksCommands {
         /// Resume from last checkpoint
         #[arg(long)]
         resume: bool,
+
+        /// Max phases to run concurrently (>1 runs phases headless)
+        #[arg(long, short = 'j', default_value = "1", value_parser = clap::value_parser!(u16).range(1..))]
+        jobs: u16,
+
+        /// What to do when a phase fails: retry:N, skip or abort
+        #[arg(long, value_name = "POLICY", default_value = "retry:1")]
+        on_failure: FailurePolicy,
+
+        /// Never prompt; phases run headless and exhausted retries abort
+        #[arg(long)]
+        non_interactive: bool,
+
+        /// Commit each completed phase on a forja/<plan-id> branch
+        #[arg(long)]
+        commit: bool,
+
+        /// Run phases in a git worktree under .forja/worktrees/<plan-id>
+        #[arg(long)]
+        worktree: bool,
     },
 
     /// Show execution progress
@@ -0,0 +1,180 @@
+use colored::Colorize;
+use dialoguer::Confirm;
+
+use forja_core::error::{ForjaError, Result};
+use forja_core::models::bundle::Bundle;
+use forja_core::models::profile::Profile;
+use forja_core::models::state::{load_state, save_state};
+use forja_core::paths::ForjaPaths;
+use forja_core::registry::{bundles, lock};
+use forja_core::symlink::auto_install::auto_install_missing;
+use forja_core::symlink::manager::load_installed_ids;
+
+use crate::output;
+
+/// List the bundles shipped by the configured registries.
+pub fn list() -> Result<()> {
+    let paths = ForjaPaths::resolve()?;
+    let bundles = bundles::scan_all(&paths)?;
+
+    if bundles.is_empty() {
+        println!("No bundles found. Registries ship them as bundles/<name>.json.");
+        return Ok(());
+    }
+
+    println!("{}", "Skill bundles".bold());
+    println!();
+
+    for bundle in &bundles {
+        println!(
+            "  {} {} ({} skills, {} teams)",
+            "●".cyan(),
+            bundle.name.bold(),
+            bundle.skills.len(),
+            bundle.teams.len()
+        );
+        if !bundle.description.is_empty() {
+            println!("    {}", bundle.description.dimmed());
+        }
+    }
+
+    println!();
+    output::print_command_hint("forja bundle show <name>", "See what a bundle sets up");
+    Ok(())
+}
+
+/// Show the skills, teams and profile a bundle sets up.
+pub fn show(name: &str) -> Result<()> {
+    let paths = ForjaPaths::resolve()?;
+    let bundles = bundles::scan_all(&paths)?;
+    let bundle = bundles::find(&bundles, name)?;
+    let installed_ids = load_installed_ids(&paths.state);
+
+    println!("{}", bundle.name.bold());
+    if !bundle.description.is_empty() {
+        println!("  {}", bundle.description);
+    }
+    println!("  {} {}", "Registry:".dimmed(), bundle.registry);
+    if !bundle.stacks.is_empty() {
+        println!("  {} {}", "Stacks:".dimmed(), bundle.stacks.join(", "));
+    }
+    println!("  {} {}", "Profile:".dimmed(), profile_name(bundle));
+
+    output::print_section_header("Skills");
+    for id in &bundle.skills {
+        if installed_ids.contains(id) {
+            println!("  {} {} {}", "●".green(), id, "(installed)".dimmed());
+        } else {
+            println!("  {} {}", "○".dimmed(), id);
+        }
+    }
+
+    if !bundle.teams.is_empty() {
+        output::print_section_header("Teams");
+        for team in &bundle.teams {
+            println!("  {} {}", "●".cyan(), team);
+        }
+    }
+
+    println!();
+    output::print_command_hint(
+        &format!("forja bundle apply {}", bundle.name),
+        "Install and create all of it",
+    );
+    Ok(())
+}
+
+/// Install a bundle's skills, create its teams and make its profile the active one.
+pub fn apply(name: &str, yes: bool) -> Result<()> {
+    let paths = ForjaPaths::ensure_initialized()?;
+    let bundles = bundles::scan_all(&paths)?;
+    let bundle = bundles::find(&bundles, name)?;
+    let installed_ids = load_installed_ids(&paths.state);
+    let missing = bundle
+        .skills
+        .iter()
+        .filter(|id| !installed_ids.contains(id))
+        .count();
+
+    println!("{}", format!("forja bundle apply {}", bundle.name).bold());
+    println!();
+    println!(
+        "  {} skill(s) to install, {} already installed",
+        missing,
+        bundle.skills.len() - missing
+    );
+    if !bundle.teams.is_empty() {
+        println!(
+            "  Teams: {} (profile {})",
+            bundle.teams.join(", "),
+            profile_name(bundle).cyan()
+        );
+    }
+    println!();
+
+    if !yes {
+        let confirmed = Confirm::new()
+            .with_prompt(format!("Apply bundle '{}'?", bundle.name))
+            .default(true)
+            .interact()
+            .map_err(|e| ForjaError::Dialoguer(e.to_string()))?;
+
+        if !confirmed {
+            return Err(ForjaError::PromptCancelled);
+        }
+    }
+
+    apply_bundle(&paths, bundle)?;
+
+    println!();
+    println!(
+        "{} Bundle {} applied.",
+        "Done:".green().bold(),
+        bundle.name.bold()
+    );
+    Ok(())
+}
+
+/// Do the work of `forja bundle apply` without asking. Skills that can't be
+/// installed and teams that can't be created are reported and skipped.
+pub fn apply_bundle(paths: &ForjaPaths, bundle: &Bundle) -> Result<()> {
+    let profile = profile_name(bundle);
+    profile.parse::<Profile>().map_err(|_| {
+        ForjaError::InvalidArgument(format!(
+            "bundle '{}' has unknown profile '{profile}'. Use: fast, balanced, max",
+            bundle.name
+        ))
+    })?;
+
+    let ids: Vec<&str> = bundle.skills.iter().map(String::as_str).collect();
+    let result = auto_install_missing(paths, &ids)?;
+    for id in &result.installed {
+        println!("  {} {}", "installed".green(), id);
+    }
+    for id in &result.not_found {
+        println!(
+            "  {} {} is not in any registry",
+            "WARNING:".yellow().bold(),
+            id
+        );
+    }
+    for (id, error) in &result.failed {
+        println!("  {} {} — {}", "failed".red(), id, error);
+    }
+    lock::refresh(paths)?;
+
+    for team in &bundle.teams {
+        if let Err(e) = super::team::preset(team, profile) {
+            println!("  {} team {} — {}", "failed".red(), team, e);
+        }
+    }
+
+    let mut state = load_state(&paths.state);
+    state.active_profile = Some(profile.to_string());
+    save_state(&paths.state, &state)?;
+    Ok(())
+}
+
+fn profile_name(bundle: &Bundle) -> &str {
+    bundle.profile.as_deref().unwrap_or("balanced")
+}
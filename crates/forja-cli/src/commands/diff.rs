@@ -1,8 +1,10 @@
 use colored::Colorize;
 
 use forja_core::error::Result;
+use forja_core::models::version::Bump;
 use forja_core::paths::ForjaPaths;
-use forja_core::registry::diff::{ChangeType, compute_diff, load_previous_head};
+use forja_core::registry::changelog;
+use forja_core::registry::diff::{ChangeType, SkillChange, compute_diff, load_previous_head};
 use forja_core::registry::git;
 use forja_core::symlink::manager::load_installed_ids;
 
@@ -42,67 +44,116 @@ pub fn run() -> Result<()> {
         old_head[..8].dimmed(),
         new_head[..8.min(new_head.len())].dimmed()
     );
-    println!();
 
-    let mut added = 0;
-    let mut modified = 0;
-    let mut removed = 0;
-
-    for change in &changes {
-        let is_installed = installed_ids.contains(&change.skill_id);
-        let installed_marker = if is_installed {
-            " (installed)".yellow().to_string()
-        } else {
-            String::new()
-        };
-
-        match change.change_type {
-            ChangeType::Added => {
-                added += 1;
-                println!(
-                    "    {} {}{}",
-                    "+".green().bold(),
-                    change.skill_id.green(),
-                    installed_marker
-                );
-            }
-            ChangeType::Modified => {
-                modified += 1;
-                println!(
-                    "    {} {}{}",
-                    "~".yellow().bold(),
-                    change.skill_id.yellow(),
-                    installed_marker
-                );
+    let modified: Vec<&SkillChange> = changes
+        .iter()
+        .filter(|c| c.change_type == ChangeType::Modified)
+        .collect();
+    for (bump, title) in [
+        (Bump::Major, "Major (may change agent behavior)"),
+        (Bump::Minor, "Minor"),
+        (Bump::Patch, "Patch"),
+        (
+            Bump::Unversioned,
+            "Unversioned (files changed, version not bumped)",
+        ),
+    ] {
+        let group: Vec<&&SkillChange> = modified.iter().filter(|c| c.bump() == bump).collect();
+        if group.is_empty() {
+            continue;
+        }
+        println!();
+        println!("  {}", title.bold());
+        for change in group {
+            let is_installed = installed_ids.contains(&change.skill_id);
+            let label = if bump == Bump::Major {
+                change.skill_id.red().to_string()
+            } else {
+                change.skill_id.yellow().to_string()
+            };
+            println!(
+                "    {} {}{}{}",
+                "~".yellow().bold(),
+                label,
+                versions(change),
+                installed_marker(is_installed)
+            );
+
+            if bump == Bump::Unversioned {
+                continue;
             }
-            ChangeType::Removed => {
-                removed += 1;
-                println!(
-                    "    {} {}{}",
-                    "-".red().bold(),
-                    change.skill_id.red(),
-                    installed_marker
-                );
+            let skill_dir = paths.registry.join("skills").join(&change.skill_id);
+            for line in changelog::excerpt(
+                &skill_dir,
+                change.old_version.as_deref(),
+                change.new_version.as_deref(),
+            ) {
+                println!("        {}", line.dimmed());
             }
         }
     }
 
+    let added: Vec<&SkillChange> = changes
+        .iter()
+        .filter(|c| c.change_type == ChangeType::Added)
+        .collect();
+    if !added.is_empty() {
+        println!();
+        println!("  {}", "Added".bold());
+        for change in &added {
+            let version = change
+                .new_version
+                .as_ref()
+                .map(|v| format!(" {v}").dimmed().to_string())
+                .unwrap_or_default();
+            println!(
+                "    {} {}{}{}",
+                "+".green().bold(),
+                change.skill_id.green(),
+                version,
+                installed_marker(installed_ids.contains(&change.skill_id))
+            );
+        }
+    }
+
+    let removed: Vec<&SkillChange> = changes
+        .iter()
+        .filter(|c| c.change_type == ChangeType::Removed)
+        .collect();
+    if !removed.is_empty() {
+        println!();
+        println!("  {}", "Removed".bold());
+        for change in &removed {
+            println!(
+                "    {} {}{}",
+                "-".red().bold(),
+                change.skill_id.red(),
+                installed_marker(installed_ids.contains(&change.skill_id))
+            );
+        }
+    }
+
+    let majors = modified.iter().filter(|c| c.bump() == Bump::Major).count();
     println!();
     println!(
-        "  {} {} added, {} modified, {} removed",
+        "  {} {} added, {} modified ({} major), {} removed",
         "Summary:".bold(),
-        added.to_string().green(),
-        modified.to_string().yellow(),
-        removed.to_string().red(),
+        added.len().to_string().green(),
+        modified.len().to_string().yellow(),
+        majors.to_string().red(),
+        removed.len().to_string().red(),
     );
 
-    let modified_installed: Vec<&str> = changes
+    let modified_installed: Vec<&&SkillChange> = modified
         .iter()
-        .filter(|c| c.change_type == ChangeType::Modified && installed_ids.contains(&c.skill_id))
-        .map(|c| c.skill_id.as_str())
+        .filter(|c| installed_ids.contains(&c.skill_id))
         .collect();
 
     if !modified_installed.is_empty() {
+        let major_installed = modified_installed
+            .iter()
+            .filter(|c| c.bump() == Bump::Major)
+            .count();
         println!();
         println!(
             "  {} {} installed skill(s) were modified. Run: {}",
@@ -110,7 +161,33 @@ pub fn run() -> Result<()> {
             modified_installed.len(),
             "forja upgrade".cyan()
         );
+        if major_installed > 0 {
+            println!(
+                "       {} of them are major bumps, held back until: {}",
+                major_installed,
+                "forja upgrade --allow-major".cyan()
+            );
+        }
     }
 
     Ok(())
 }
+
+/// ` 1.2.0 → 2.0.0` when the version moved, ` 1.2.0` when it didn't.
+fn versions(change: &SkillChange) -> String {
+    let text = match (&change.old_version, &change.new_version) {
+        (Some(old), Some(new)) if old != new => format!(" {old} → {new}"),
+        (None, Some(new)) => format!(" → {new}"),
+        (Some(old), _) => format!(" {old}"),
+        (None, None) => String::new(),
+    };
+    text.dimmed().to_string()
+}
+
+fn installed_marker(is_installed: bool) -> String {
+    if is_installed {
+        " (installed)".yellow().to_string()
+    } else {
+        String::new()
+    }
+}
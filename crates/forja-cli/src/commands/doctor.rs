@@ -4,6 +4,7 @@ use crate::output;
 use forja_core::paths::ForjaPaths;
 use forja_core::registry::catalog;
 use forja_core::settings;
+use forja_core::symlink::copy::{self, CopyState};
 use forja_core::symlink::manager::{SymlinkManager, load_installed_ids};
 use colored::Colorize;
 
@@ -101,8 +102,7 @@ pub fn run() -> Result<()> {
 
     // Check symlinks
     if paths.claude_agents.exists() {
-        let manager =
-            SymlinkManager::new(paths.claude_agents.clone(), paths.claude_commands.clone());
+        let manager = SymlinkManager::for_paths(&paths);
         let (healthy, broken) = manager.verify()?;
 
         let symlinks_ok = broken.is_empty();
@@ -130,7 +130,7 @@ pub fn run() -> Result<()> {
     // Check catalog
     if registry_exists {
         let installed_ids = load_installed_ids(&paths.state);
-        let registry = catalog::scan(&paths.registry, &installed_ids)?;
+        let registry = catalog::scan_all(&paths, &installed_ids)?;
 
         println!(
             "  {} {} skills available, {} installed",
@@ -138,6 +138,46 @@ pub fn run() -> Result<()> {
             registry.skills.len(),
             installed_ids.len()
         );
+
+        // Copy mode: tell local edits apart from registry drift
+        let manager = SymlinkManager::for_paths(&paths);
+        if manager.objects_dir().is_some() {
+            let metadata = load_state(&paths.state).install_metadata;
+            let (mut edited, mut behind) = (0, 0);
+            for skill in registry.skills.iter().filter(|s| s.installed) {
+                let copies = metadata
+                    .get(&skill.id)
+                    .map(|m| m.copies.clone())
+                    .unwrap_or_default();
+                for file in copy::inspect(&manager, skill, &copies)? {
+                    let label = match file.state {
+                        CopyState::Clean => continue,
+                        CopyState::Edited => "EDITED:".cyan(),
+                        CopyState::Outdated => "OUTDATED:".yellow(),
+                        CopyState::Diverged => "DIVERGED:".red(),
+                        CopyState::Missing => "MISSING:".red(),
+                    };
+                    if file.state == CopyState::Edited {
+                        edited += 1;
+                    } else {
+                        behind += 1;
+                    }
+                    println!(
+                        "    {} {} ({})",
+                        label,
+                        file.name.dimmed(),
+                        file.state.as_str()
+                    );
+                }
+            }
+            results.push(CheckResult {
+                label: format!("Copies up to date with the registry ({edited} edited locally)"),
+                passed: behind == 0,
+                remediation: Some(
+                    "Run: forja upgrade (merges registry changes into your edits)".into(),
+                ),
+            });
+        }
     }
 
     // Check state.json is valid JSON
@@ -1,22 +1,39 @@
+use std::fmt;
 use std::fs;
-use std::process::Command;
+use std::io::{BufRead, BufReader, IsTerminal, Read, Write};
+use std::path::Path;
+use std::process::{Command, ExitStatus, Stdio};
+use std::str::FromStr;
+use std::sync::{Mutex, MutexGuard, mpsc};
+use std::time::Duration;
 
 use colored::Colorize;
+use dialoguer::Select;
 
 use forja_core::error::{ForjaError, Result};
+use forja_core::models::gate::QualityGate;
 use forja_core::models::plan::{
-    ExecutionCheckpoint, PhaseStatus, PlanMetadata, PlanPhase, PlanStatus, checkpoint_path,
-    find_latest_pending, initialize_checkpoint, load_checkpoint, load_plan, save_checkpoint,
-    save_plan, workspace_dir,
+    ExecutionCheckpoint, PhaseAttempt, PhaseStatus, PlanMetadata, PlanPhase, PlanStatus,
+    checkpoint_path, find_latest_pending, initialize_checkpoint, load_checkpoint, load_plan,
+    save_checkpoint, save_plan, workspace_dir,
 };
+use forja_core::models::usage::TokenUsage;
 use forja_core::paths::ForjaPaths;
 use forja_core::settings;
 use forja_core::symlink::auto_install;
+use forja_core::text;
+use forja_spark::events::ChatEvent;
+use forja_spark::parser::StreamParser;
+
+use crate::commands::gates;
+use crate::commands::git_checkpoint::GitTracking;
+use crate::commands::transcript::{self, Transcript};
+use crate::commands::worktree;
 
 /// Execute a previously created plan by launching Claude Code sessions.
 /// Plans with phases run phase-by-phase with checkpoints.
 /// Plans without phases run in legacy monolithic mode.
-pub fn run(plan_id: Option<&str>, profile: &str, resume: bool) -> Result<()> {
+pub fn run(plan_id: Option<&str>, profile: &str, opts: ExecOptions) -> Result<()> {
     let paths = ForjaPaths::ensure_initialized()?;
 
     // 1. Find plan
@@ -38,6 +55,33 @@ pub fn run(plan_id: Option<&str>, profile: &str, resume: bool) -> Result<()> {
     println!("  Team:  {}", plan.team_size);
     println!();
 
+    launch(
+        &paths,
+        &mut plan,
+        profile,
+        opts,
+        "execute",
+        build_execution_prompt,
+    )
+}
+
+/// Builds the single prompt for a plan without phases from the plan and its `.md`.
+pub type MonolithicPrompt = fn(&PlanMetadata, &str) -> String;
+
+/// Everything `forja execute` and `forja sparks execute` share once the plan is found:
+/// install its agents, then run it phase by phase, or as one session built by
+/// `monolithic_prompt` when it has no phases. `command` tags the analytics events.
+pub fn launch(
+    paths: &ForjaPaths,
+    plan: &mut PlanMetadata,
+    profile: &str,
+    opts: ExecOptions,
+    command: &str,
+    monolithic_prompt: MonolithicPrompt,
+) -> Result<()> {
+    // 1. Reject plans whose phase graph can't be scheduled
+    crate::commands::plan::ensure_valid(plan)?;
+
     // 2. Override profile if provided
     if profile != "balanced" {
         plan.profile = profile.to_string();
@@ -45,11 +89,11 @@ pub fn run(plan_id: Option<&str>, profile: &str, resume: bool) -> Result<()> {
 
     // 3. Auto-install missing agents and track analytics
     let skill_ids: Vec<&str> = plan.agents.iter().map(|a| a.skill_id.as_str()).collect();
-    auto_install::auto_install_missing(&paths, &skill_ids)?;
+    auto_install::auto_install_missing(paths, &skill_ids)?;
 
     let analytics_path = forja_core::analytics::analytics_path(&paths.forja_root);
     for agent in &plan.agents {
-        let _ = forja_core::analytics::track(&analytics_path, &agent.skill_id, "execute");
+        let _ = forja_core::analytics::track(&analytics_path, &agent.skill_id, command);
     }
 
     // 4. Ensure agent teams env var (always in ~/.claude/settings.json)
@@ -78,23 +122,50 @@ pub fn run(plan_id: Option<&str>, profile: &str, resume: bool) -> Result<()> {
 
     // 7. Decide execution mode: phased (with checkpoints) or legacy (monolithic)
     if plan.phases.is_empty() {
-        run_legacy(&paths, &mut plan, &plan_md)
+        println!(
+            "{}",
+            "No structured phases — running monolithic execution.".dimmed()
+        );
+        println!();
+        if opts.worktree {
+            print_worktree_ignored();
+        }
+        run_legacy(paths, plan, &monolithic_prompt(plan, &plan_md))
     } else {
-        run_phased(&paths, &mut plan, &plan_md, resume)
+        run_phased(paths, plan, &plan_md, opts)
     }
 }
 
-/// Legacy monolithic execution — plans without phases.
-fn run_legacy(paths: &ForjaPaths, plan: &mut PlanMetadata, plan_md: &str) -> Result<()> {
-    let prompt = build_execution_prompt(plan, plan_md);
+/// Flags shared by `forja execute` and `forja sparks execute`.
+#[derive(Debug, Clone, Copy)]
+pub struct ExecOptions {
+    pub resume: bool,
+    pub jobs: usize,
+    pub failure: FailureHandling,
+    /// Commit each completed phase on `forja/<plan-id>`.
+    pub commit: bool,
+    /// Run the phases in a git worktree of their own.
+    pub worktree: bool,
+}
+
+/// `--worktree` only applies to phased plans, which have a checkpoint to record it in.
+pub fn print_worktree_ignored() {
+    println!(
+        "  {} --worktree needs a plan with phases — running in the current checkout",
+        "NOTE:".yellow().bold()
+    );
+    println!();
+}
 
+/// Legacy monolithic execution — plans without phases.
+fn run_legacy(paths: &ForjaPaths, plan: &mut PlanMetadata, prompt: &str) -> Result<()> {
     println!("{}", "Launching Claude Code session...".bold());
     println!();
 
     let status = Command::new("claude")
         .arg("--dangerously-skip-permissions")
         .arg("--")
-        .arg(&prompt)
+        .arg(prompt)
         .status()?;
 
     if status.success() {
@@ -113,12 +184,20 @@ fn run_legacy(paths: &ForjaPaths, plan: &mut PlanMetadata, plan_md: &str) -> Res
 }
 
 /// Phase-by-phase execution with checkpoint tracking.
+/// Independent phases run concurrently (up to `jobs`) following the `depends_on` DAG.
 fn run_phased(
     paths: &ForjaPaths,
     plan: &mut PlanMetadata,
     plan_md: &str,
-    resume: bool,
+    opts: ExecOptions,
 ) -> Result<()> {
+    let ExecOptions {
+        resume,
+        jobs,
+        failure,
+        commit,
+        worktree,
+    } = opts;
     let ckpt_path = checkpoint_path(&paths.plans, &plan.id);
     let ws_dir = workspace_dir(&paths.plans, &plan.id);
 
@@ -138,6 +217,16 @@ fn run_phased(
         initialize_checkpoint(plan)
     };
 
+    worktree::enter(paths, &plan.id, worktree, &mut checkpoint)?;
+    let git = GitTracking::start(
+        &paths.plans,
+        &plan.id,
+        commit,
+        jobs,
+        resume,
+        &mut checkpoint,
+    )?;
+
     // Ensure workspace directory exists
     fs::create_dir_all(&ws_dir)?;
 
@@ -151,112 +240,764 @@ fn run_phased(
     );
     println!();
 
-    let mut all_completed = true;
+    let (quality_gates, source) = gates::resolve(plan, paths);
+    print_gate_source(&quality_gates, &source);
+
+    let checkpoint = Mutex::new(checkpoint);
+    // Concurrent phases can't share the terminal, and nobody is there to drive it
+    // when running non-interactively
+    let headless = jobs > 1 || !failure.interactive;
+
+    let all_completed = run_phase_dag(plan, &checkpoint, &ckpt_path, jobs, |i| {
+        let ctx = PhaseContext {
+            plan_md,
+            ws_dir: &ws_dir,
+            ckpt_path: &ckpt_path,
+            gates: &quality_gates,
+            failure,
+            headless,
+            git: git.as_ref(),
+        };
+        run_phase(plan, i, &checkpoint, &ctx)
+    })?;
+
+    // All phases done
+    if all_completed {
+        plan.status = PlanStatus::Executed;
+        let plan_json_path = paths.plans.join(format!("{}.json", plan.id));
+        save_plan(&plan_json_path, plan)?;
+        println!();
+        println!(
+            "{} All {} phases completed. Plan {} marked as executed.",
+            "Done:".green().bold(),
+            plan.phases.len(),
+            plan.id.cyan()
+        );
+    }
+
+    if let Some(spent) = spend_summary(&lock_checkpoint(&checkpoint)) {
+        println!("{} {}", "Spend:".bold(), spent);
+    }
 
-    for i in 0..plan.phases.len() {
-        let phase = &plan.phases[i];
-        let phase_ckpt = &checkpoint.phases[i];
+    Ok(())
+}
 
-        // Skip completed phases
-        if phase_ckpt.status == PhaseStatus::Completed {
+/// The plan's spend, flagging phases that ran interactively and so weren't measured.
+/// `None` when nothing has run yet.
+pub fn spend_summary(checkpoint: &ExecutionCheckpoint) -> Option<String> {
+    let spent = checkpoint.usage;
+    match checkpoint.unmeasured_phases() {
+        0 if spent.is_empty() => None,
+        0 => Some(spent.to_string()),
+        n if spent.is_empty() => Some(format!("not measured ({n} interactive phase(s))")),
+        n => Some(format!("{spent} (+ {n} interactive phase(s) not measured)")),
+    }
+}
+
+/// Run one phase under the failure policy, recording every attempt in the checkpoint.
+///
+/// Returns `Ok(true)` once the phase completes and `Ok(false)` if it was skipped.
+/// Aborting returns an error so the scheduler stops launching new work.
+pub fn run_phase(
+    plan: &PlanMetadata,
+    i: usize,
+    checkpoint: &Mutex<ExecutionCheckpoint>,
+    ctx: &PhaseContext,
+) -> Result<bool> {
+    let phase = &plan.phases[i];
+    let mut retries = 0;
+    println!("  {} Phase {}: {}", "▶".cyan(), i + 1, phase.name.bold());
+
+    loop {
+        let Some(reason) = attempt_phase(plan, i, checkpoint, ctx)? else {
+            return Ok(true);
+        };
+
+        if let FailurePolicy::Retry(max) = ctx.failure.policy
+            && retries < max
+        {
+            retries += 1;
+            let delay = retry_backoff(retries);
             println!(
-                "  {} Phase {}: {} (already completed)",
-                "✓".green(),
+                "  {} Phase {}: {} failed ({}) — retry {}/{} in {}s",
+                "RETRY:".yellow().bold(),
                 i + 1,
-                phase.name
+                phase.name,
+                reason,
+                retries,
+                max,
+                delay.as_secs()
             );
+            std::thread::sleep(delay);
             continue;
         }
 
-        // Skip phases whose dependencies failed
-        if has_failed_dependency(phase, &plan.phases, &checkpoint) {
-            checkpoint.phases[i].status = PhaseStatus::Skipped;
-            checkpoint.last_updated = chrono::Utc::now().to_rfc3339();
-            save_checkpoint(&ckpt_path, &checkpoint)?;
-            println!(
-                "  {} Phase {}: {} (dependency failed)",
-                "⊘".yellow(),
-                i + 1,
-                phase.name
-            );
-            all_completed = false;
-            continue;
+        let attempts = lock_checkpoint(checkpoint).phases[i].attempts.len();
+        let summary = if attempts > 1 {
+            format!("{reason} after {attempts} attempts")
+        } else {
+            reason
+        };
+        update_checkpoint(checkpoint, ctx.ckpt_path, |ckpt| {
+            ckpt.phases[i].status = PhaseStatus::Failed;
+            ckpt.phases[i].error_message = Some(summary.clone());
+        })?;
+        println!(
+            "  {} Phase {}: {} failed ({})",
+            "✗".red(),
+            i + 1,
+            phase.name,
+            summary
+        );
+        println!();
+
+        match ctx.failure.decide(&phase.name) {
+            FailureAction::Retry => {
+                retries = 0;
+                update_checkpoint(checkpoint, ctx.ckpt_path, |ckpt| {
+                    ckpt.phases[i].status = PhaseStatus::Pending;
+                    ckpt.phases[i].error_message = None;
+                })?;
+            }
+            FailureAction::Skip => {
+                update_checkpoint(checkpoint, ctx.ckpt_path, |ckpt| {
+                    ckpt.phases[i].status = PhaseStatus::Skipped;
+                })?;
+                println!("  {} Phase {}: {} skipped", "⊘".yellow(), i + 1, phase.name);
+                return Ok(false);
+            }
+            FailureAction::Abort => {
+                return Err(ForjaError::PhaseExecutionFailed(format!(
+                    "Phase '{}' failed: {summary}",
+                    phase.name
+                )));
+            }
         }
+    }
+}
 
-        // Mark in-progress
-        println!("  {} Phase {}: {}", "▶".cyan(), i + 1, phase.name.bold());
+/// Run the phase's `claude` process and quality gates once.
+/// Returns the failure reason, or `None` when the phase completed.
+fn attempt_phase(
+    plan: &PlanMetadata,
+    i: usize,
+    checkpoint: &Mutex<ExecutionCheckpoint>,
+    ctx: &PhaseContext,
+) -> Result<Option<String>> {
+    let phase = &plan.phases[i];
+    let started_at = chrono::Utc::now().to_rfc3339();
+    let attempt = lock_checkpoint(checkpoint).phases[i].attempts.len() + 1;
+    let label = format!("Phase {}: {}", i + 1, phase.name);
+
+    let git_start = match ctx.git {
+        Some(git) => git.before_attempt(&label)?,
+        None => None,
+    };
 
-        checkpoint.phases[i].status = PhaseStatus::InProgress;
-        checkpoint.phases[i].started_at = Some(chrono::Utc::now().to_rfc3339());
-        checkpoint.current_phase = Some(i);
-        checkpoint.last_updated = chrono::Utc::now().to_rfc3339();
-        save_checkpoint(&ckpt_path, &checkpoint)?;
+    let transcript = Transcript::open(ctx.ws_dir, i, ctx.headless)?;
+    transcript.begin(&label, attempt, &started_at);
+
+    // Mark in-progress
+    update_checkpoint(checkpoint, ctx.ckpt_path, |ckpt| {
+        let state = &mut ckpt.phases[i];
+        state.status = PhaseStatus::InProgress;
+        state.started_at = Some(started_at.clone());
+        state.log = Some(transcript::log_file_name(i));
+        if ctx.headless {
+            state.events_log = Some(transcript::events_file_name(i));
+        }
+        // Rollback targets the first attempt's starting point
+        if state.git_start.is_none() {
+            state.git_start = git_start;
+        }
+        ckpt.current_phase = Some(i);
+    })?;
+
+    // Build phase-specific prompt (including any gate failures from the last attempt)
+    let prompt = build_phase_prompt(
+        plan,
+        i,
+        ctx.plan_md,
+        ctx.ws_dir,
+        &lock_checkpoint(checkpoint),
+    );
+    let run = run_phase_process(&prompt, ctx.headless, &transcript)?;
+    let exit_code = run.status.code();
 
-        // Build phase-specific prompt
-        let prompt = build_phase_prompt(plan, i, plan_md, &ws_dir);
+    let failure = if run.status.success() {
+        gates::run_after_phase(ctx.gates, i, &phase.name, ctx.ws_dir)?
+    } else {
+        Some(format!("exit code {}", exit_code.unwrap_or(-1)))
+    };
 
-        // Launch Claude for this phase
-        let status = Command::new("claude")
-            .arg("--dangerously-skip-permissions")
-            .arg("--")
-            .arg(&prompt)
-            .status()?;
+    let git_end = match ctx.git {
+        Some(git) => {
+            let start_head = lock_checkpoint(checkpoint).phases[i]
+                .git_start
+                .as_ref()
+                .map(|s| s.head.clone());
+            git.after_attempt(start_head.as_deref(), &label, failure.is_none())?
+        }
+        None => None,
+    };
 
-        let exit_code = status.code().unwrap_or(-1);
+    let finished_at = chrono::Utc::now().to_rfc3339();
+    transcript.end(attempt, failure.as_deref(), &finished_at);
+    update_checkpoint(checkpoint, ctx.ckpt_path, |ckpt| {
+        if let Some(usage) = run.usage {
+            ckpt.record_usage(i, usage);
+        }
+        let state = &mut ckpt.phases[i];
+        // Interactive sessions don't report usage
+        if !ctx.headless {
+            state.unmeasured = true;
+        }
+        state.attempts.push(PhaseAttempt {
+            started_at,
+            finished_at: finished_at.clone(),
+            exit_code,
+            error: failure.clone(),
+            stderr_tail: run.stderr_tail,
+        });
+        state.completed_at = Some(finished_at);
+        state.exit_code = Some(exit_code.unwrap_or(-1));
+        if git_end.is_some() {
+            state.git_end = git_end;
+        }
+        if failure.is_none() {
+            state.status = PhaseStatus::Completed;
+            state.error_message = None;
+        }
+    })?;
 
-        if status.success() {
-            checkpoint.phases[i].status = PhaseStatus::Completed;
-            checkpoint.phases[i].completed_at = Some(chrono::Utc::now().to_rfc3339());
-            checkpoint.phases[i].exit_code = Some(exit_code);
-            checkpoint.last_updated = chrono::Utc::now().to_rfc3339();
-            save_checkpoint(&ckpt_path, &checkpoint)?;
+    if failure.is_none() {
+        println!(
+            "  {} Phase {}: {} completed",
+            "✓".green(),
+            i + 1,
+            phase.name
+        );
+    }
+    Ok(failure)
+}
 
-            println!(
-                "  {} Phase {}: {} completed",
-                "✓".green(),
-                i + 1,
-                phase.name
-            );
+/// What `--on-failure` does once a phase fails.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FailurePolicy {
+    /// Retry up to N more times with exponential backoff, then ask
+    /// (or abort when non-interactive).
+    Retry(u32),
+    Skip,
+    Abort,
+}
+
+impl FromStr for FailurePolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "skip" => Ok(Self::Skip),
+            "abort" => Ok(Self::Abort),
+            "retry" => Ok(Self::Retry(1)),
+            _ => s
+                .strip_prefix("retry:")
+                .and_then(|n| n.parse().ok())
+                .map(Self::Retry)
+                .ok_or_else(|| format!("invalid policy '{s}' (expected retry:N, skip or abort)")),
+        }
+    }
+}
+
+impl fmt::Display for FailurePolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Retry(n) => write!(f, "retry:{n}"),
+            Self::Skip => f.write_str("skip"),
+            Self::Abort => f.write_str("abort"),
+        }
+    }
+}
+
+/// Delay before the first retry; doubled for each further retry.
+const RETRY_BASE_DELAY: Duration = Duration::from_secs(2);
+const RETRY_MAX_DELAY: Duration = Duration::from_secs(60);
+
+/// Exponential backoff before retry number `retry` (1-based).
+pub fn retry_backoff(retry: u32) -> Duration {
+    let factor = 2u32.saturating_pow(retry.saturating_sub(1));
+    RETRY_BASE_DELAY.saturating_mul(factor).min(RETRY_MAX_DELAY)
+}
+
+/// The failure policy plus whether a human can be asked.
+#[derive(Debug, Clone, Copy)]
+pub struct FailureHandling {
+    pub policy: FailurePolicy,
+    pub interactive: bool,
+}
+
+impl FailureHandling {
+    /// `--non-interactive`, or stdin that isn't a terminal (CI, desktop app),
+    /// means never prompting.
+    pub fn new(policy: FailurePolicy, non_interactive: bool) -> Self {
+        Self {
+            policy,
+            interactive: !non_interactive && std::io::stdin().is_terminal(),
+        }
+    }
+
+    /// Decide what happens to a phase that failed and used up its retries.
+    fn decide(&self, phase_name: &str) -> FailureAction {
+        match self.policy {
+            FailurePolicy::Skip => FailureAction::Skip,
+            FailurePolicy::Abort => FailureAction::Abort,
+            FailurePolicy::Retry(_) if self.interactive => prompt_failure_action(phase_name),
+            FailurePolicy::Retry(_) => FailureAction::Abort,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FailureAction {
+    Retry,
+    Skip,
+    Abort,
+}
+
+/// Serializes failure prompts so concurrent phases don't fight over the terminal.
+static PROMPT_LOCK: Mutex<()> = Mutex::new(());
+
+/// Ask the user what to do with a failed phase: Retry / Skip / Abort.
+fn prompt_failure_action(phase_name: &str) -> FailureAction {
+    let items = &["Retry", "Skip this phase", "Abort execution"];
+
+    let _guard = PROMPT_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let selection = Select::new()
+        .with_prompt(format!(
+            "Phase '{phase_name}' failed. What would you like to do?"
+        ))
+        .items(items)
+        .default(0)
+        .interact_opt()
+        .unwrap_or(None);
+
+    match selection {
+        Some(0) => FailureAction::Retry,
+        Some(1) => FailureAction::Skip,
+        // Abort (including None = Esc/Ctrl+C)
+        _ => FailureAction::Abort,
+    }
+}
+
+/// Everything a phase run needs besides the plan and the shared checkpoint.
+pub struct PhaseContext<'a> {
+    pub plan_md: &'a str,
+    pub ws_dir: &'a Path,
+    pub ckpt_path: &'a Path,
+    pub gates: &'a [QualityGate],
+    pub failure: FailureHandling,
+    pub headless: bool,
+    /// Set when running inside a git work tree.
+    pub git: Option<&'a GitTracking>,
+}
+
+/// Announce which quality gates will run after each phase.
+pub fn print_gate_source(gates: &[QualityGate], source: &str) {
+    let executable = gates.iter().filter(|g| g.is_executable()).count();
+    if executable == 0 {
+        println!("{} none configured", "GATES:".bold());
+    } else {
+        println!("{} {} from {}", "GATES:".bold(), executable, source);
+    }
+    println!();
+}
+
+/// Build the `claude` invocation for a phase. Headless phases (concurrent or
+/// non-interactive) run in print mode, streaming JSON so the final result's
+/// usage can be recorded.
+fn phase_command(prompt: &str, headless: bool) -> Command {
+    let mut cmd = Command::new("claude");
+    cmd.arg("--dangerously-skip-permissions");
+    if headless {
+        cmd.args(["--output-format", "stream-json", "--verbose", "--print"])
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+    }
+    cmd.arg("--").arg(prompt);
+    cmd
+}
+
+/// How an interactive phase's output reaches its transcript.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Capture {
+    /// `claude` runs on a pseudo-terminal from `script(1)`, whose stdout is
+    /// teed to the terminal and the transcript.
+    Terminal,
+    /// No `script` on PATH: `claude` owns the terminal and only stderr is teed.
+    Stderr,
+}
+
+/// Wrap an interactive `claude` invocation so its output can be recorded
+/// while it still sees a terminal.
+fn interactive_command(prompt: &str) -> (Command, Capture) {
+    if !on_path("script") {
+        let mut cmd = phase_command(prompt, false);
+        cmd.stderr(Stdio::piped());
+        return (cmd, Capture::Stderr);
+    }
+
+    let mut cmd = Command::new("script");
+    if cfg!(target_os = "linux") {
+        // util-linux: the command is a shell string, -e passes its exit code on
+        let line = ["claude", "--dangerously-skip-permissions", "--", prompt]
+            .map(shell_quote)
+            .join(" ");
+        cmd.args(["-q", "-e", "-c", &line, "/dev/null"]);
+    } else {
+        // BSD: the command follows the file and its exit code is passed on
+        cmd.args([
+            "-q",
+            "/dev/null",
+            "claude",
+            "--dangerously-skip-permissions",
+            "--",
+        ])
+        .arg(prompt);
+    }
+    cmd.stdout(Stdio::piped());
+    (cmd, Capture::Terminal)
+}
+
+fn on_path(program: &str) -> bool {
+    std::env::var_os("PATH")
+        .is_some_and(|path| std::env::split_paths(&path).any(|dir| dir.join(program).is_file()))
+}
+
+/// Single-quote `arg` for `sh -c`.
+fn shell_quote(arg: &str) -> String {
+    format!("'{}'", arg.replace('\'', "'\\''"))
+}
+
+/// How a phase's `claude` process ended.
+pub struct PhaseRun {
+    pub status: ExitStatus,
+    /// Tokens and cost from the final result event. Interactive runs report none.
+    pub usage: Option<TokenUsage>,
+    /// Last bytes of stderr, captured while still echoed. For interactive runs
+    /// on a pseudo-terminal, where stderr can't be told apart, the last bytes
+    /// of the terminal output as plain text.
+    pub stderr_tail: Option<String>,
+}
+
+/// Run one phase's `claude` process to completion.
+///
+/// Headless runs print only the final result text, as plain `--print` would,
+/// while every event and stderr line goes to the phase's transcript.
+/// Interactive runs keep the terminal; their output is recorded as it passes
+/// through (see [`Capture`]).
+pub fn run_phase_process(
+    prompt: &str,
+    headless: bool,
+    transcript: &Transcript,
+) -> Result<PhaseRun> {
+    let mut child = if headless {
+        phase_command(prompt, true).spawn()?
+    } else {
+        let (mut cmd, capture) = interactive_command(prompt);
+        if capture == Capture::Stderr {
+            transcript.note("no `script` on PATH: only stderr was recorded");
+        }
+        cmd.spawn()?
+    };
+    let mut usage = None;
+    let mut output_tail = None;
+
+    // Echo stderr as it arrives while keeping its tail for the attempt record
+    let stderr_reader = child.stderr.take().map(|stderr| {
+        let transcript = transcript.clone();
+        std::thread::spawn(move || {
+            let mut tail = String::new();
+            for line in BufReader::new(stderr).lines().map_while(|l| l.ok()) {
+                eprintln!("{line}");
+                transcript.stderr(&line);
+                tail.push_str(&line);
+                tail.push('\n');
+                if tail.len() > 2 * STDERR_TAIL_BYTES {
+                    tail = text::tail(&tail, STDERR_TAIL_BYTES).to_string();
+                }
+            }
+            tail
+        })
+    });
+
+    if let Some(stdout) = child.stdout.take() {
+        if headless {
+            let mut parser = StreamParser::new();
+            for line in BufReader::new(stdout).lines() {
+                let line = line?;
+                transcript.raw_event(&line);
+                for event in parser.feed(&line) {
+                    transcript.event(&event);
+                    if let Some(run) = event.token_usage() {
+                        usage = Some(run);
+                    }
+                    if let ChatEvent::TurnComplete {
+                        result: Some(text), ..
+                    } = event
+                    {
+                        println!("{text}");
+                    }
+                }
+            }
         } else {
-            checkpoint.phases[i].status = PhaseStatus::Failed;
-            checkpoint.phases[i].completed_at = Some(chrono::Utc::now().to_rfc3339());
-            checkpoint.phases[i].exit_code = Some(exit_code);
-            checkpoint.phases[i].error_message =
-                Some(format!("Process exited with code {exit_code}"));
-            checkpoint.last_updated = chrono::Utc::now().to_rfc3339();
-            save_checkpoint(&ckpt_path, &checkpoint)?;
+            output_tail = Some(tee_terminal(stdout, std::io::stdout(), transcript)?);
+        }
+    }
+
+    let status = child.wait()?;
+    let stderr_tail = stderr_reader
+        .and_then(|reader| reader.join().ok())
+        .or(output_tail)
+        .map(|tail| text::tail(tail.trim_end(), STDERR_TAIL_BYTES).to_string())
+        .filter(|tail| !tail.is_empty());
+
+    Ok(PhaseRun {
+        status,
+        usage,
+        stderr_tail,
+    })
+}
+
+/// Copy a pseudo-terminal's output to `echo` and the transcript as it
+/// arrives, returning its last bytes as plain text.
+fn tee_terminal(
+    mut output: impl Read,
+    mut echo: impl Write,
+    transcript: &Transcript,
+) -> Result<String> {
+    let mut recent = Vec::new();
+    let mut buf = [0u8; 8192];
+    loop {
+        let n = match output.read(&mut buf) {
+            Ok(0) => break,
+            Ok(n) => n,
+            Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e.into()),
+        };
+        echo.write_all(&buf[..n])?;
+        echo.flush()?;
+        transcript.terminal(&buf[..n]);
+        // Escape sequences take up most of a redrawing UI's output
+        recent.extend_from_slice(&buf[..n]);
+        if recent.len() > 16 * STDERR_TAIL_BYTES {
+            recent.drain(..recent.len() - 8 * STDERR_TAIL_BYTES);
+        }
+    }
+    Ok(plain_text(&recent))
+}
+
+/// Terminal output with escape sequences and carriage returns removed.
+fn plain_text(bytes: &[u8]) -> String {
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut iter = bytes.iter().copied().peekable();
+    while let Some(b) = iter.next() {
+        match b {
+            0x1b => match iter.next() {
+                // CSI: parameters up to a final byte in @..~
+                Some(b'[') => {
+                    for c in iter.by_ref() {
+                        if (0x40..=0x7e).contains(&c) {
+                            break;
+                        }
+                    }
+                }
+                // OSC: up to BEL or ESC \
+                Some(b']') => {
+                    while let Some(c) = iter.next() {
+                        if c == 0x07 || (c == 0x1b && iter.next_if_eq(&b'\\').is_some()) {
+                            break;
+                        }
+                    }
+                }
+                _ => {}
+            },
+            b'\r' => {}
+            b => out.push(b),
+        }
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+/// Max bytes of stderr kept per attempt in the checkpoint.
+const STDERR_TAIL_BYTES: usize = 2000;
+
+/// Lock the shared checkpoint, recovering the data if a phase thread panicked.
+pub fn lock_checkpoint(
+    checkpoint: &Mutex<ExecutionCheckpoint>,
+) -> MutexGuard<'_, ExecutionCheckpoint> {
+    checkpoint.lock().unwrap_or_else(|e| e.into_inner())
+}
 
+/// Apply a change to the shared checkpoint and persist it.
+pub fn update_checkpoint(
+    checkpoint: &Mutex<ExecutionCheckpoint>,
+    ckpt_path: &Path,
+    f: impl FnOnce(&mut ExecutionCheckpoint),
+) -> Result<()> {
+    let mut ckpt = lock_checkpoint(checkpoint);
+    f(&mut ckpt);
+    ckpt.last_updated = chrono::Utc::now().to_rfc3339();
+    save_checkpoint(ckpt_path, &ckpt)
+}
+
+/// Schedule phases along the `depends_on` DAG, running up to `jobs` at once.
+///
+/// A phase starts once every dependency it names is `Completed`; phases whose
+/// dependencies failed or were skipped are marked `Skipped`. `run_phase` returns
+/// `Ok(false)` for a phase that did not complete and `Err` to abort — in-flight
+/// phases are then drained and no new ones are started. Returns whether every
+/// phase completed.
+pub fn run_phase_dag<F>(
+    plan: &PlanMetadata,
+    checkpoint: &Mutex<ExecutionCheckpoint>,
+    ckpt_path: &Path,
+    jobs: usize,
+    run_phase: F,
+) -> Result<bool>
+where
+    F: Fn(usize) -> Result<bool> + Sync,
+{
+    let total = plan.phases.len();
+    let jobs = jobs.max(1);
+    let deps: Vec<Vec<usize>> = plan
+        .phases
+        .iter()
+        .map(|p| dependency_indices(p, &plan.phases))
+        .collect();
+
+    // `settled` phases have a final status for this run; `launched` ones need no more scheduling.
+    let mut settled = vec![false; total];
+    let mut launched = vec![false; total];
+    let mut all_completed = true;
+    let mut first_error: Option<ForjaError> = None;
+
+    {
+        let ckpt = lock_checkpoint(checkpoint);
+        for (i, phase) in plan.phases.iter().enumerate() {
+            // Skip completed phases
+            if ckpt.phases[i].status == PhaseStatus::Completed {
+                println!(
+                    "  {} Phase {}: {} (already completed)",
+                    "✓".green(),
+                    i + 1,
+                    phase.name
+                );
+                settled[i] = true;
+                launched[i] = true;
+            }
+        }
+    }
+
+    std::thread::scope(|scope| -> Result<()> {
+        let (tx, rx) = mpsc::channel();
+        let mut running = 0;
+
+        loop {
+            if first_error.is_none() {
+                let mut changed = true;
+                while changed {
+                    changed = false;
+                    for i in 0..total {
+                        if launched[i] || deps[i].iter().any(|&d| !settled[d]) {
+                            continue;
+                        }
+
+                        // Skip phases whose dependencies failed
+                        let failed_dep = {
+                            let ckpt = lock_checkpoint(checkpoint);
+                            has_failed_dependency(&plan.phases[i], &plan.phases, &ckpt)
+                        };
+                        if failed_dep {
+                            update_checkpoint(checkpoint, ckpt_path, |ckpt| {
+                                ckpt.phases[i].status = PhaseStatus::Skipped;
+                            })?;
+                            println!(
+                                "  {} Phase {}: {} (dependency failed)",
+                                "⊘".yellow(),
+                                i + 1,
+                                plan.phases[i].name
+                            );
+                            settled[i] = true;
+                            launched[i] = true;
+                            all_completed = false;
+                            changed = true;
+                            continue;
+                        }
+
+                        if running >= jobs {
+                            continue;
+                        }
+
+                        launched[i] = true;
+                        running += 1;
+                        let tx = tx.clone();
+                        let run_phase = &run_phase;
+                        scope.spawn(move || {
+                            let _ = tx.send((i, run_phase(i)));
+                        });
+                    }
+                }
+            }
+
+            if running == 0 {
+                break;
+            }
+
+            let Ok((i, outcome)) = rx.recv() else {
+                break;
+            };
+            running -= 1;
+            settled[i] = true;
+            match outcome {
+                Ok(true) => {}
+                Ok(false) => all_completed = false,
+                Err(e) => {
+                    all_completed = false;
+                    first_error.get_or_insert(e);
+                }
+            }
+        }
+
+        Ok(())
+    })?;
+
+    if let Some(e) = first_error {
+        return Err(e);
+    }
+
+    // Anything never launched is stuck behind a dependency cycle
+    for (i, phase) in plan.phases.iter().enumerate() {
+        if !launched[i] {
             println!(
-                "  {} Phase {}: {} failed (exit code {})",
-                "✗".red(),
+                "  {} Phase {}: {} (unresolved dependencies)",
+                "⊘".yellow(),
                 i + 1,
-                phase.name,
-                exit_code
-            );
-            println!();
-
-            return Err(ForjaError::PhaseExecutionFailed(format!(
-                "Phase '{}' failed with exit code {exit_code}",
                 phase.name
-            )));
+            );
+            all_completed = false;
         }
     }
 
-    // All phases done
-    if all_completed {
-        plan.status = PlanStatus::Executed;
-        let plan_json_path = paths.plans.join(format!("{}.json", plan.id));
-        save_plan(&plan_json_path, plan)?;
-        println!();
-        println!(
-            "{} All {} phases completed. Plan {} marked as executed.",
-            "Done:".green().bold(),
-            plan.phases.len(),
-            plan.id.cyan()
-        );
-    }
+    Ok(all_completed)
+}
 
-    Ok(())
+/// Resolve a phase's `depends_on` names to phase indices, ignoring unknown names.
+fn dependency_indices(phase: &PlanPhase, all_phases: &[PlanPhase]) -> Vec<usize> {
+    phase
+        .depends_on
+        .iter()
+        .filter_map(|dep| all_phases.iter().position(|p| &p.name == dep))
+        .collect()
 }
 
 /// Check if any dependency of a phase has failed.
@@ -276,12 +1017,27 @@ pub fn has_failed_dependency(
     false
 }
 
-/// Build the prompt for a specific phase, including context from previous phases.
+/// Every phase `phase_index` transitively depends on, in plan order.
+fn dependency_closure(phase_index: usize, all_phases: &[PlanPhase]) -> Vec<usize> {
+    let mut seen = vec![false; all_phases.len()];
+    let mut stack = dependency_indices(&all_phases[phase_index], all_phases);
+    while let Some(i) = stack.pop() {
+        if i != phase_index && !seen[i] {
+            seen[i] = true;
+            stack.extend(dependency_indices(&all_phases[i], all_phases));
+        }
+    }
+    (0..all_phases.len()).filter(|&i| seen[i]).collect()
+}
+
+/// Build the prompt for a specific phase, including context from the completed
+/// phases it depends on.
 pub fn build_phase_prompt(
     plan: &PlanMetadata,
     phase_index: usize,
     plan_md: &str,
-    workspace_dir: &std::path::Path,
+    workspace_dir: &Path,
+    checkpoint: &ExecutionCheckpoint,
 ) -> String {
     let phase = &plan.phases[phase_index];
     let mut prompt = String::new();
@@ -305,10 +1061,14 @@ pub fn build_phase_prompt(
     }
     prompt.push('\n');
 
-    // Summary of completed previous phases
-    if phase_index > 0 {
+    // Summary of completed phases this one depends on
+    let previous: Vec<usize> = dependency_closure(phase_index, &plan.phases)
+        .into_iter()
+        .filter(|&i| checkpoint.phases[i].status == PhaseStatus::Completed)
+        .collect();
+    if !previous.is_empty() {
         prompt.push_str("## Previous Phases (completed)\n\n");
-        for i in 0..phase_index {
+        for i in previous {
             let prev = &plan.phases[i];
             prompt.push_str(&format!(
                 "### Phase {}: {} — {}\n",
@@ -351,6 +1111,14 @@ pub fn build_phase_prompt(
     }
     prompt.push_str(&format!("\nInstructions: {}\n\n", phase.instructions));
 
+    // Gate failures from the previous attempt of this phase
+    let gate_report = gates::failure_report_path(workspace_dir, phase_index);
+    if let Ok(report) = fs::read_to_string(&gate_report) {
+        prompt.push_str("## Quality Gate Failures\n\n");
+        prompt.push_str(&report);
+        prompt.push('\n');
+    }
+
     // Phase output file
     let output_file = workspace_dir.join(format!("phase-{phase_index}.md"));
     prompt.push_str("## Output\n\n");
@@ -582,10 +1350,7 @@ mod tests {
     #[test]
     fn build_prompt_with_quality_gates() {
         let mut plan = test_plan();
-        plan.quality_gates = vec![
-            "All tests must pass".to_string(),
-            "No TypeScript errors".to_string(),
-        ];
+        plan.quality_gates = vec!["All tests must pass".into(), "No TypeScript errors".into()];
 
         let prompt = build_execution_prompt(&plan, "# Plan");
 
@@ -648,7 +1413,9 @@ mod tests {
         ];
 
         let ws = tempfile::TempDir::new().unwrap();
-        let prompt = build_phase_prompt(&plan, 1, "# Full plan", ws.path());
+        let mut checkpoint = initialize_checkpoint(&plan);
+        checkpoint.phases[0].status = PhaseStatus::Completed;
+        let prompt = build_phase_prompt(&plan, 1, "# Full plan", ws.path(), &checkpoint);
 
         assert!(prompt.contains("Execute Phase 2 of 2: API"));
         assert!(prompt.contains("Overall task: Add user auth with JWT"));
@@ -672,7 +1439,8 @@ mod tests {
         }];
 
         let ws = tempfile::TempDir::new().unwrap();
-        let prompt = build_phase_prompt(&plan, 0, "# Plan", ws.path());
+        let checkpoint = initialize_checkpoint(&plan);
+        let prompt = build_phase_prompt(&plan, 0, "# Plan", ws.path(), &checkpoint);
 
         assert!(prompt.contains("Execute Phase 1 of 1: Setup"));
         assert!(!prompt.contains("## Previous Phases"));
@@ -696,7 +1464,7 @@ mod tests {
                 files_to_create: vec![],
                 files_to_modify: vec![],
                 instructions: "Do B".to_string(),
-                depends_on: vec![],
+                depends_on: vec!["Phase A".to_string()],
             },
         ];
 
@@ -707,12 +1475,44 @@ mod tests {
             "Created users table successfully",
         )
         .unwrap();
+        let mut checkpoint = initialize_checkpoint(&plan);
+        checkpoint.phases[0].status = PhaseStatus::Completed;
 
-        let prompt = build_phase_prompt(&plan, 1, "# Plan", ws.path());
+        let prompt = build_phase_prompt(&plan, 1, "# Plan", ws.path(), &checkpoint);
 
         assert!(prompt.contains("Created users table successfully"));
     }
 
+    #[test]
+    fn build_phase_prompt_includes_gate_failures() {
+        let mut plan = test_plan();
+        plan.phases = vec![PlanPhase {
+            name: "Phase A".to_string(),
+            agent_role: "coder".to_string(),
+            files_to_create: vec![],
+            files_to_modify: vec![],
+            instructions: "Do A".to_string(),
+            depends_on: vec![],
+        }];
+
+        let ws = tempfile::TempDir::new().unwrap();
+        let checkpoint = initialize_checkpoint(&plan);
+        assert!(
+            !build_phase_prompt(&plan, 0, "# Plan", ws.path(), &checkpoint)
+                .contains("Quality Gate")
+        );
+
+        fs::write(
+            gates::failure_report_path(ws.path(), 0),
+            "### tests — exit code 1\n\nexpected 2, got 3",
+        )
+        .unwrap();
+        let prompt = build_phase_prompt(&plan, 0, "# Plan", ws.path(), &checkpoint);
+
+        assert!(prompt.contains("## Quality Gate Failures"));
+        assert!(prompt.contains("expected 2, got 3"));
+    }
+
     // --- Dependency failure tests ---
 
     #[test]
@@ -782,4 +1582,294 @@ mod tests {
             &checkpoint
         ));
     }
+
+    // --- DAG scheduler tests ---
+
+    fn dag_plan(phases: &[(&str, &[&str])]) -> PlanMetadata {
+        let mut plan = test_plan();
+        plan.phases = phases
+            .iter()
+            .map(|(name, deps)| PlanPhase {
+                name: name.to_string(),
+                agent_role: "coder".to_string(),
+                files_to_create: vec![],
+                files_to_modify: vec![],
+                instructions: format!("Do {name}"),
+                depends_on: deps.iter().map(|d| d.to_string()).collect(),
+            })
+            .collect();
+        plan
+    }
+
+    #[test]
+    fn run_phase_dag_respects_dependencies() {
+        let plan = dag_plan(&[("Schema", &[]), ("API", &["Schema"]), ("UI", &["API"])]);
+        let ws = tempfile::TempDir::new().unwrap();
+        let ckpt_path = ws.path().join("plan.checkpoint.json");
+        let checkpoint = Mutex::new(initialize_checkpoint(&plan));
+        let order = Mutex::new(Vec::new());
+
+        let done = run_phase_dag(&plan, &checkpoint, &ckpt_path, 4, |i| {
+            order.lock().unwrap().push(i);
+            update_checkpoint(&checkpoint, &ckpt_path, |c| {
+                c.phases[i].status = PhaseStatus::Completed;
+            })?;
+            Ok(true)
+        })
+        .unwrap();
+
+        assert!(done);
+        assert_eq!(*order.lock().unwrap(), vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn run_phase_dag_runs_independent_phases_concurrently() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let plan = dag_plan(&[
+            ("Schema", &[]),
+            ("UI", &[]),
+            ("Docs", &[]),
+            ("Ship", &["Schema", "UI", "Docs"]),
+        ]);
+        let ws = tempfile::TempDir::new().unwrap();
+        let ckpt_path = ws.path().join("plan.checkpoint.json");
+        let checkpoint = Mutex::new(initialize_checkpoint(&plan));
+        let active = AtomicUsize::new(0);
+        let peak = AtomicUsize::new(0);
+
+        let done = run_phase_dag(&plan, &checkpoint, &ckpt_path, 2, |i| {
+            let now = active.fetch_add(1, Ordering::SeqCst) + 1;
+            peak.fetch_max(now, Ordering::SeqCst);
+            std::thread::sleep(std::time::Duration::from_millis(50));
+            active.fetch_sub(1, Ordering::SeqCst);
+            update_checkpoint(&checkpoint, &ckpt_path, |c| {
+                c.phases[i].status = PhaseStatus::Completed;
+            })?;
+            Ok(true)
+        })
+        .unwrap();
+
+        assert!(done);
+        assert_eq!(peak.load(Ordering::SeqCst), 2);
+        let saved = load_checkpoint(&ckpt_path).unwrap();
+        assert!(
+            saved
+                .phases
+                .iter()
+                .all(|p| p.status == PhaseStatus::Completed)
+        );
+    }
+
+    #[test]
+    fn build_phase_prompt_lists_only_completed_dependencies() {
+        let plan = dag_plan(&[
+            ("Schema", &[]),
+            ("Docs", &[]),
+            ("API", &["Schema"]),
+            ("UI", &["API"]),
+        ]);
+        let ws = tempfile::TempDir::new().unwrap();
+        let mut checkpoint = initialize_checkpoint(&plan);
+        for i in 0..3 {
+            checkpoint.phases[i].status = PhaseStatus::Completed;
+        }
+
+        let prompt = build_phase_prompt(&plan, 3, "# Plan", ws.path(), &checkpoint);
+
+        // Transitive dependencies are listed, unrelated completed phases are not
+        assert!(prompt.contains("### Phase 1: Schema"));
+        assert!(prompt.contains("### Phase 3: API"));
+        assert!(!prompt.contains("### Phase 2: Docs"));
+    }
+
+    #[test]
+    fn build_phase_prompt_omits_concurrent_independent_phases() {
+        let plan = dag_plan(&[
+            ("Schema", &[]),
+            ("Docs", &[]),
+            ("Ship", &["Schema", "Docs"]),
+        ]);
+        let ws = tempfile::TempDir::new().unwrap();
+        let ckpt_path = ws.path().join("plan.checkpoint.json");
+        let checkpoint = Mutex::new(initialize_checkpoint(&plan));
+        let both_running = std::sync::Barrier::new(2);
+        let prompts = Mutex::new(vec![String::new(); plan.phases.len()]);
+
+        let done = run_phase_dag(&plan, &checkpoint, &ckpt_path, 2, |i| {
+            update_checkpoint(&checkpoint, &ckpt_path, |c| {
+                c.phases[i].status = PhaseStatus::InProgress;
+            })?;
+            if i < 2 {
+                both_running.wait();
+            }
+            let prompt =
+                build_phase_prompt(&plan, i, "# Plan", ws.path(), &lock_checkpoint(&checkpoint));
+            prompts.lock().unwrap()[i] = prompt;
+            if i < 2 {
+                both_running.wait();
+            }
+            update_checkpoint(&checkpoint, &ckpt_path, |c| {
+                c.phases[i].status = PhaseStatus::Completed;
+            })?;
+            Ok(true)
+        })
+        .unwrap();
+
+        assert!(done);
+        let prompts = prompts.into_inner().unwrap();
+        assert!(!prompts[0].contains("## Previous Phases"));
+        assert!(!prompts[1].contains("## Previous Phases"));
+        assert!(prompts[2].contains("### Phase 1: Schema"));
+        assert!(prompts[2].contains("### Phase 2: Docs"));
+    }
+
+    #[test]
+    fn run_phase_dag_skips_dependents_of_failed_phase() {
+        let plan = dag_plan(&[("Schema", &[]), ("API", &["Schema"]), ("Docs", &[])]);
+        let ws = tempfile::TempDir::new().unwrap();
+        let ckpt_path = ws.path().join("plan.checkpoint.json");
+        let checkpoint = Mutex::new(initialize_checkpoint(&plan));
+
+        let done = run_phase_dag(&plan, &checkpoint, &ckpt_path, 2, |i| {
+            let status = if i == 0 {
+                PhaseStatus::Failed
+            } else {
+                PhaseStatus::Completed
+            };
+            update_checkpoint(&checkpoint, &ckpt_path, |c| c.phases[i].status = status)?;
+            Ok(i != 0)
+        })
+        .unwrap();
+
+        assert!(!done);
+        let ckpt = lock_checkpoint(&checkpoint);
+        assert_eq!(ckpt.phases[1].status, PhaseStatus::Skipped);
+        assert_eq!(ckpt.phases[2].status, PhaseStatus::Completed);
+    }
+
+    #[test]
+    fn run_phase_dag_skips_completed_phases_on_resume() {
+        let plan = dag_plan(&[("Schema", &[]), ("API", &["Schema"])]);
+        let ws = tempfile::TempDir::new().unwrap();
+        let ckpt_path = ws.path().join("plan.checkpoint.json");
+        let mut ckpt = initialize_checkpoint(&plan);
+        ckpt.phases[0].status = PhaseStatus::Completed;
+        let checkpoint = Mutex::new(ckpt);
+        let ran = Mutex::new(Vec::new());
+
+        let done = run_phase_dag(&plan, &checkpoint, &ckpt_path, 1, |i| {
+            ran.lock().unwrap().push(i);
+            Ok(true)
+        })
+        .unwrap();
+
+        assert!(done);
+        assert_eq!(*ran.lock().unwrap(), vec![1]);
+    }
+
+    #[test]
+    fn run_phase_dag_stops_launching_after_error() {
+        let plan = dag_plan(&[("Schema", &[]), ("API", &[])]);
+        let ws = tempfile::TempDir::new().unwrap();
+        let ckpt_path = ws.path().join("plan.checkpoint.json");
+        let checkpoint = Mutex::new(initialize_checkpoint(&plan));
+        let ran = Mutex::new(Vec::new());
+
+        let err = run_phase_dag(&plan, &checkpoint, &ckpt_path, 1, |i| {
+            ran.lock().unwrap().push(i);
+            Err(ForjaError::PhaseExecutionFailed("boom".into()))
+        })
+        .unwrap_err();
+
+        assert!(matches!(err, ForjaError::PhaseExecutionFailed(_)));
+        assert_eq!(*ran.lock().unwrap(), vec![0]);
+    }
+
+    #[test]
+    fn run_phase_dag_leaves_cyclic_phases_unrun() {
+        let plan = dag_plan(&[("A", &["B"]), ("B", &["A"]), ("C", &[])]);
+        let ws = tempfile::TempDir::new().unwrap();
+        let ckpt_path = ws.path().join("plan.checkpoint.json");
+        let checkpoint = Mutex::new(initialize_checkpoint(&plan));
+        let ran = Mutex::new(Vec::new());
+
+        let done = run_phase_dag(&plan, &checkpoint, &ckpt_path, 2, |i| {
+            ran.lock().unwrap().push(i);
+            Ok(true)
+        })
+        .unwrap();
+
+        assert!(!done);
+        assert_eq!(*ran.lock().unwrap(), vec![2]);
+    }
+
+    #[test]
+    fn failure_policy_parses_and_round_trips() {
+        assert_eq!("retry".parse(), Ok(FailurePolicy::Retry(1)));
+        assert_eq!("retry:3".parse(), Ok(FailurePolicy::Retry(3)));
+        assert_eq!("skip".parse(), Ok(FailurePolicy::Skip));
+        assert_eq!("abort".parse(), Ok(FailurePolicy::Abort));
+        assert!("retry:many".parse::<FailurePolicy>().is_err());
+        assert!("ignore".parse::<FailurePolicy>().is_err());
+        assert_eq!(FailurePolicy::Retry(2).to_string(), "retry:2");
+    }
+
+    #[test]
+    fn retry_backoff_doubles_up_to_cap() {
+        assert_eq!(retry_backoff(1), Duration::from_secs(2));
+        assert_eq!(retry_backoff(2), Duration::from_secs(4));
+        assert_eq!(retry_backoff(3), Duration::from_secs(8));
+        assert_eq!(retry_backoff(10), Duration::from_secs(60));
+        assert_eq!(retry_backoff(100), Duration::from_secs(60));
+    }
+
+    #[test]
+    fn spend_summary_flags_interactive_phases() {
+        let plan = dag_plan(&[("A", &[]), ("B", &[])]);
+        let mut ckpt = initialize_checkpoint(&plan);
+        assert_eq!(spend_summary(&ckpt), None);
+
+        ckpt.phases[0].unmeasured = true;
+        assert_eq!(
+            spend_summary(&ckpt).as_deref(),
+            Some("not measured (1 interactive phase(s))")
+        );
+
+        ckpt.record_usage(
+            1,
+            TokenUsage {
+                output_tokens: 10,
+                ..Default::default()
+            },
+        );
+        let summary = spend_summary(&ckpt).unwrap();
+        assert!(summary.starts_with("0 in · 10 out"));
+        assert!(summary.ends_with("(+ 1 interactive phase(s) not measured)"));
+    }
+
+    #[test]
+    fn terminal_output_is_teed_to_the_transcript() {
+        let ws = tempfile::TempDir::new().unwrap();
+        let transcript = Transcript::open(ws.path(), 0, false).unwrap();
+        let output: &[u8] = b"\x1b[2J\x1b]0;claude\x07Working\r\n\x1b[1mDone\x1b[0m\r\n";
+
+        let mut echoed = Vec::new();
+        let tail = tee_terminal(output, &mut echoed, &transcript).unwrap();
+
+        assert_eq!(tail, "Working\nDone\n");
+        assert_eq!(echoed, output);
+        let log = fs::read(ws.path().join(transcript::log_file_name(0))).unwrap();
+        assert_eq!(log, output);
+    }
+
+    #[test]
+    fn shell_quote_survives_sh() {
+        let prompt = "it's a \"phase\" with $HOME and `ticks`";
+        let out = Command::new("sh")
+            .args(["-c", &format!("printf %s {}", shell_quote(prompt))])
+            .output()
+            .unwrap();
+        assert_eq!(String::from_utf8(out.stdout).unwrap(), prompt);
+    }
 }
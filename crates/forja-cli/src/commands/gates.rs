@@ -0,0 +1,399 @@
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Stdio};
+use std::time::{Duration, Instant};
+
+use colored::Colorize;
+
+use forja_core::error::Result;
+use forja_core::models::config;
+use forja_core::models::gate::QualityGate;
+use forja_core::models::plan::PlanMetadata;
+use forja_core::paths::ForjaPaths;
+use forja_core::text::tail;
+
+use crate::commands::init::detect_stack;
+
+/// Bytes of a failed gate's output fed back into the retry prompt.
+const FAILURE_TAIL_BYTES: usize = 4000;
+
+/// Exit code shells use for "command not found".
+const COMMAND_NOT_FOUND: i32 = 127;
+
+/// Pick the gates for an execution: the plan's executable gates, else the
+/// project's `quality_gates` in `.forja/config.json`, else defaults for the
+/// detected stack. Returns the gates and a label describing where they came from.
+pub fn resolve(plan: &PlanMetadata, paths: &ForjaPaths) -> (Vec<QualityGate>, String) {
+    let from_plan: Vec<QualityGate> = plan
+        .quality_gates
+        .iter()
+        .filter(|g| g.is_executable())
+        .cloned()
+        .collect();
+    if !from_plan.is_empty() {
+        return (from_plan, "plan".to_string());
+    }
+
+    if let Some(cfg) = config::load_config(&paths.config)
+        && !cfg.quality_gates.is_empty()
+    {
+        return (cfg.quality_gates, "project config".to_string());
+    }
+
+    let cwd = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+    match detect_stack(&cwd) {
+        Some(stack) => (
+            defaults_for_stack(&stack, &cwd),
+            format!("detected {stack}"),
+        ),
+        None => (Vec::new(), "none".to_string()),
+    }
+}
+
+/// Default gates for a stack label produced by `init::detect_stack`.
+pub fn defaults_for_stack(stack: &str, cwd: &Path) -> Vec<QualityGate> {
+    let mut gates = Vec::new();
+
+    if cwd.join("package.json").exists() {
+        gates.push(QualityGate::command("npm test", "npm test"));
+    }
+    if stack.contains("Rust") {
+        gates.push(QualityGate::command("cargo test", "cargo test --workspace"));
+        gates.push(
+            QualityGate::command("cargo clippy", "cargo clippy --workspace -- -D warnings")
+                .advisory(),
+        );
+    }
+    if stack.contains("Go") {
+        gates.push(QualityGate::command("go test", "go test ./..."));
+    }
+    if stack.contains("Python") {
+        gates.push(QualityGate::command("pytest", "pytest"));
+    }
+
+    gates
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GateStatus {
+    Passed,
+    Failed(i32),
+    TimedOut,
+    /// The command (or the shell) could not be found — reported, never blocking.
+    Unavailable,
+}
+
+#[derive(Debug, Clone)]
+pub struct GateOutcome {
+    pub gate: QualityGate,
+    pub status: GateStatus,
+    /// Combined stdout/stderr of the run.
+    pub log: PathBuf,
+}
+
+impl GateOutcome {
+    pub fn blocks(&self) -> bool {
+        self.gate.blocking && matches!(self.status, GateStatus::Failed(_) | GateStatus::TimedOut)
+    }
+}
+
+/// Path of the gate failure report for a phase. `build_phase_prompt` includes
+/// it so the next attempt (retry or `--resume`) sees what went wrong.
+pub fn failure_report_path(workspace_dir: &Path, phase_index: usize) -> PathBuf {
+    workspace_dir.join(format!("phase-{phase_index}-gates.md"))
+}
+
+/// Run every executable gate after a phase, saving output under
+/// `<workspace>/gates/` and writing (or clearing) the phase's failure report.
+///
+/// Returns a one-line summary of the blocking failures, if any.
+pub fn run_after_phase(
+    gates: &[QualityGate],
+    phase_index: usize,
+    phase_name: &str,
+    workspace_dir: &Path,
+) -> Result<Option<String>> {
+    let report_path = failure_report_path(workspace_dir, phase_index);
+    let executable: Vec<&QualityGate> = gates.iter().filter(|g| g.is_executable()).collect();
+    if executable.is_empty() {
+        let _ = fs::remove_file(&report_path);
+        return Ok(None);
+    }
+
+    println!(
+        "  {} Quality gates for phase {}...",
+        "GATES:".bold(),
+        phase_index + 1
+    );
+
+    let log_dir = workspace_dir.join("gates");
+    fs::create_dir_all(&log_dir)?;
+
+    let mut outcomes = Vec::new();
+    for gate in executable {
+        let log = log_dir.join(format!("phase-{phase_index}-{}.log", slug(&gate.name)));
+        let status = run_gate(gate, &log)?;
+        print_outcome(gate, &status);
+        outcomes.push(GateOutcome {
+            gate: gate.clone(),
+            status,
+            log,
+        });
+    }
+    println!();
+
+    let failed: Vec<&GateOutcome> = outcomes.iter().filter(|o| o.blocks()).collect();
+    if failed.is_empty() {
+        let _ = fs::remove_file(&report_path);
+        return Ok(None);
+    }
+
+    fs::write(&report_path, failure_report(phase_name, &failed))?;
+    let names: Vec<&str> = failed.iter().map(|o| o.gate.name.as_str()).collect();
+    Ok(Some(format!("Quality gate failed: {}", names.join(", "))))
+}
+
+/// Run one gate through the shell, killing it once its timeout elapses.
+fn run_gate(gate: &QualityGate, log: &Path) -> Result<GateStatus> {
+    let Some(command) = gate.command.as_deref() else {
+        return Ok(GateStatus::Passed);
+    };
+
+    let out = File::create(log)?;
+    let err = out.try_clone()?;
+    let mut cmd = shell(command);
+    if let Some(cwd) = &gate.cwd {
+        cmd.current_dir(cwd);
+    }
+    // A group of its own, so a timeout also stops whatever the shell started
+    #[cfg(unix)]
+    std::os::unix::process::CommandExt::process_group(&mut cmd, 0);
+    let mut child = match cmd
+        .stdin(Stdio::null())
+        .stdout(Stdio::from(out))
+        .stderr(Stdio::from(err))
+        .spawn()
+    {
+        Ok(child) => child,
+        Err(_) => return Ok(GateStatus::Unavailable),
+    };
+
+    let deadline = Instant::now() + gate.timeout();
+    loop {
+        if let Some(status) = child.try_wait()? {
+            return Ok(match status.code() {
+                Some(0) => GateStatus::Passed,
+                Some(COMMAND_NOT_FOUND) => GateStatus::Unavailable,
+                code => GateStatus::Failed(code.unwrap_or(-1)),
+            });
+        }
+        if Instant::now() >= deadline {
+            kill_group(&mut child);
+            return Ok(GateStatus::TimedOut);
+        }
+        std::thread::sleep(Duration::from_millis(100));
+    }
+}
+
+/// Kill a gate spawned by [`run_gate`] along with every process in its group.
+fn kill_group(child: &mut Child) {
+    #[cfg(unix)]
+    let _ = Command::new("kill")
+        .args(["-KILL", "--", &format!("-{}", child.id())])
+        .stderr(Stdio::null())
+        .status();
+    let _ = child.kill();
+    let _ = child.wait();
+}
+
+#[cfg(unix)]
+pub(crate) fn shell(command: &str) -> Command {
+    let mut cmd = Command::new("sh");
+    cmd.arg("-c").arg(command);
+    cmd
+}
+
+#[cfg(not(unix))]
+pub(crate) fn shell(command: &str) -> Command {
+    let mut cmd = Command::new("cmd");
+    cmd.arg("/C").arg(command);
+    cmd
+}
+
+fn print_outcome(gate: &QualityGate, status: &GateStatus) {
+    let label = if gate.blocking {
+        gate.name.clone()
+    } else {
+        format!("{} (advisory)", gate.name)
+    };
+    match status {
+        GateStatus::Passed => println!("    {} {}", "✓".green(), label),
+        GateStatus::Failed(code) => {
+            let icon = if gate.blocking {
+                "✗".red()
+            } else {
+                "!".yellow()
+            };
+            println!("    {} {} (exit code {})", icon, label, code);
+        }
+        GateStatus::TimedOut => {
+            let icon = if gate.blocking {
+                "✗".red()
+            } else {
+                "!".yellow()
+            };
+            println!(
+                "    {} {} (timed out after {}s)",
+                icon,
+                label,
+                gate.timeout().as_secs()
+            );
+        }
+        GateStatus::Unavailable => println!("    {} {} (not available)", "·".dimmed(), label),
+    }
+}
+
+fn failure_report(phase_name: &str, failed: &[&GateOutcome]) -> String {
+    let mut report = format!(
+        "The previous attempt at phase '{phase_name}' failed these quality gates. Fix the causes before finishing.\n\n"
+    );
+    for outcome in failed {
+        let reason = match outcome.status {
+            GateStatus::TimedOut => {
+                format!("timed out after {}s", outcome.gate.timeout().as_secs())
+            }
+            GateStatus::Failed(code) => format!("exit code {code}"),
+            _ => continue,
+        };
+        let output = fs::read_to_string(&outcome.log).unwrap_or_default();
+        report.push_str(&format!(
+            "### {} — {reason}\n\n```\n{}\n```\n\n",
+            outcome.gate,
+            tail(output.trim(), FAILURE_TAIL_BYTES)
+        ));
+    }
+    report
+}
+
+fn slug(name: &str) -> String {
+    let slug: String = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '-'
+            }
+        })
+        .collect();
+    slug.trim_matches('-').to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn defaults_follow_detected_stack() {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join("package.json"), "{}").unwrap();
+
+        let names = |gates: Vec<QualityGate>| -> Vec<String> {
+            gates.into_iter().map(|g| g.name).collect()
+        };
+        assert_eq!(
+            names(defaults_for_stack("Next.js + Rust", dir.path())),
+            ["npm test", "cargo test", "cargo clippy"]
+        );
+        assert_eq!(
+            names(defaults_for_stack("Go", Path::new("/nonexistent"))),
+            ["go test"]
+        );
+        assert_eq!(
+            names(defaults_for_stack(
+                "Python + Django",
+                Path::new("/nonexistent")
+            )),
+            ["pytest"]
+        );
+        assert!(!defaults_for_stack("Rust", dir.path())[2].blocking);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn blocking_failure_writes_report_and_log() {
+        let ws = TempDir::new().unwrap();
+        let gates = vec![
+            QualityGate::command("ok", "true"),
+            QualityGate::command("tests", "echo 'assertion failed: 1 == 2'; exit 3"),
+        ];
+
+        let failure = run_after_phase(&gates, 0, "Build", ws.path()).unwrap();
+
+        assert_eq!(failure.as_deref(), Some("Quality gate failed: tests"));
+        let report = fs::read_to_string(failure_report_path(ws.path(), 0)).unwrap();
+        assert!(report.contains("exit code 3"));
+        assert!(report.contains("assertion failed: 1 == 2"));
+        assert!(ws.path().join("gates/phase-0-tests.log").exists());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn advisory_failures_and_missing_commands_do_not_block() {
+        let ws = TempDir::new().unwrap();
+        fs::write(failure_report_path(ws.path(), 1), "stale").unwrap();
+        let gates = vec![
+            QualityGate::command("lint", "exit 1").advisory(),
+            QualityGate::command("missing", "forja-no-such-command-xyz"),
+            QualityGate::from("Checklist only"),
+        ];
+
+        assert!(
+            run_after_phase(&gates, 1, "Build", ws.path())
+                .unwrap()
+                .is_none()
+        );
+        assert!(!failure_report_path(ws.path(), 1).exists());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn gate_is_killed_after_timeout() {
+        let ws = TempDir::new().unwrap();
+        let gate = QualityGate {
+            timeout_secs: Some(0),
+            ..QualityGate::command("slow", "sleep 5")
+        };
+
+        let started = Instant::now();
+        let failure = run_after_phase(&[gate], 0, "Build", ws.path()).unwrap();
+
+        assert!(started.elapsed() < Duration::from_secs(3));
+        assert!(failure.is_some());
+        let report = fs::read_to_string(failure_report_path(ws.path(), 0)).unwrap();
+        assert!(report.contains("timed out after 0s"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn gate_timeout_kills_background_processes() {
+        let ws = TempDir::new().unwrap();
+        let marker = ws.path().join("survived");
+        let gate = QualityGate {
+            timeout_secs: Some(0),
+            ..QualityGate::command(
+                "slow",
+                &format!("(sleep 1; touch {}) & sleep 5", marker.display()),
+            )
+        };
+
+        assert!(
+            run_after_phase(&[gate], 0, "Build", ws.path())
+                .unwrap()
+                .is_some()
+        );
+        std::thread::sleep(Duration::from_secs(2));
+        assert!(!marker.exists());
+    }
+}
@@ -0,0 +1,493 @@
+use std::path::{Path, PathBuf};
+
+use colored::Colorize;
+use dialoguer::Confirm;
+
+use forja_core::error::{ForjaError, Result};
+use forja_core::models::plan::{
+    ExecutionCheckpoint, GitSnapshot, PhaseCheckpoint, PhaseStatus, PlanStatus, checkpoint_path,
+    load_checkpoint, load_plan, save_checkpoint, save_plan,
+};
+use forja_core::paths::ForjaPaths;
+use forja_core::registry::git;
+
+/// Branch completed phases are committed on with `--commit`.
+pub fn branch_name(plan_id: &str) -> String {
+    format!("forja/{plan_id}")
+}
+
+/// How phased execution interacts with the project's git repository:
+/// snapshots around every attempt and, with `--commit`, a commit per
+/// completed phase and a stash of anything a failed attempt left behind.
+pub struct GitTracking {
+    repo: PathBuf,
+    plan_id: String,
+    commit: bool,
+    /// Repo-relative paths forja must never stash or commit (its plans dir).
+    exclude: Vec<String>,
+}
+
+impl GitTracking {
+    /// `None` when `repo` isn't a git work tree; phases then run untracked.
+    pub fn detect(repo: &Path, plans_dir: &Path, plan_id: &str, commit: bool) -> Option<Self> {
+        if !git::is_work_tree(repo) {
+            if commit {
+                println!(
+                    "  {} Not a git repository with commits — --commit ignored",
+                    "NOTE:".yellow().bold()
+                );
+                println!();
+            }
+            return None;
+        }
+        Some(Self {
+            repo: repo.to_path_buf(),
+            plan_id: plan_id.to_string(),
+            commit,
+            exclude: excluded_paths(repo, plans_dir),
+        })
+    }
+
+    /// Set up tracking for a phased run: refuse `--commit` with concurrent
+    /// phases, warn about leftovers on `--resume`, and record the branch.
+    pub fn start(
+        plans_dir: &Path,
+        plan_id: &str,
+        commit: bool,
+        jobs: usize,
+        resume: bool,
+        checkpoint: &mut ExecutionCheckpoint,
+    ) -> Result<Option<Self>> {
+        if commit && jobs > 1 {
+            return Err(ForjaError::InvalidArgument(
+                "--commit needs phases to run one at a time; drop --jobs or --commit".to_string(),
+            ));
+        }
+        let repo = std::env::current_dir()?;
+        let Some(git) = Self::detect(&repo, plans_dir, plan_id, commit) else {
+            return Ok(None);
+        };
+        if resume {
+            git.check_resume(checkpoint);
+        }
+        if let Some(branch) = git.prepare()? {
+            checkpoint.git_branch = Some(branch);
+        }
+        Ok(Some(git))
+    }
+
+    /// With `--commit`, switch to the plan's branch (creating it from HEAD)
+    /// and return its name.
+    pub fn prepare(&self) -> Result<Option<String>> {
+        if !self.commit {
+            return Ok(None);
+        }
+        let branch = branch_name(&self.plan_id);
+        let created = git::switch_branch(&self.repo, &branch)?;
+        println!(
+            "  {} Committing phases on {} ({})",
+            "GIT:".bold(),
+            branch.cyan(),
+            if created {
+                "new branch"
+            } else {
+                "existing branch"
+            }
+        );
+        println!();
+        Ok(Some(branch))
+    }
+
+    /// Warn when `--resume` finds changes left behind by a phase that didn't
+    /// complete. With `--commit` they are stashed before the phase reruns.
+    pub fn check_resume(&self, checkpoint: &ExecutionCheckpoint) {
+        for (i, phase) in checkpoint.phases.iter().enumerate() {
+            if matches!(phase.status, PhaseStatus::Completed | PhaseStatus::Pending) {
+                continue;
+            }
+            let Some(start) = &phase.git_start else {
+                continue;
+            };
+            let Ok(now) = git::diff_shortstat(&self.repo, &start.head) else {
+                continue;
+            };
+            if now == start.diff_stat {
+                continue;
+            }
+            println!(
+                "  {} Phase {} ({}) left changes behind: {}",
+                "WARNING:".yellow().bold(),
+                i + 1,
+                phase.phase_name,
+                now
+            );
+            if !self.commit {
+                crate::output::print_tip(&format!(
+                    "Start it over: forja execute rollback {} --to-phase {}",
+                    self.plan_id,
+                    i + 1
+                ));
+            }
+            println!();
+        }
+    }
+
+    /// Prepare the tree for an attempt and record where it started. With
+    /// `--commit`, leftovers from a failed attempt (or uncommitted work from
+    /// before the run) are stashed so each attempt starts from a commit.
+    pub fn before_attempt(&self, phase_label: &str) -> Result<Option<GitSnapshot>> {
+        if self.commit
+            && git::stash(
+                &self.repo,
+                &format!("forja {}: before {phase_label}", self.plan_id),
+                &self.exclude(),
+            )?
+        {
+            println!(
+                "  {} Stashed uncommitted changes before {} (git stash list)",
+                "GIT:".bold(),
+                phase_label
+            );
+        }
+        Ok(self.snapshot(None))
+    }
+
+    /// With `--commit`, commit a completed phase. Returns the tree's state
+    /// relative to where the phase started.
+    pub fn after_attempt(
+        &self,
+        start_head: Option<&str>,
+        phase_label: &str,
+        completed: bool,
+    ) -> Result<Option<GitSnapshot>> {
+        if self.commit
+            && completed
+            && let Some(sha) = git::commit_all(
+                &self.repo,
+                &format!("forja({}): {phase_label}", self.plan_id),
+                &self.exclude(),
+            )?
+        {
+            println!(
+                "  {} Committed {} as {}",
+                "GIT:".bold(),
+                phase_label,
+                short(&sha).cyan()
+            );
+        }
+        Ok(self.snapshot(start_head))
+    }
+
+    fn exclude(&self) -> Vec<&str> {
+        self.exclude.iter().map(String::as_str).collect()
+    }
+
+    /// HEAD plus the working tree's diff against `base` (HEAD itself when `None`).
+    fn snapshot(&self, base: Option<&str>) -> Option<GitSnapshot> {
+        let head = git::head_sha(&self.repo).ok()?;
+        let diff_stat = git::diff_shortstat(&self.repo, base.unwrap_or(&head)).ok()?;
+        Some(GitSnapshot { head, diff_stat })
+    }
+}
+
+/// The plans directory relative to the repository root, if it lives inside
+/// the work tree — checkpoints must survive stashes and resets. Ignored
+/// directories are left out: git never touches them, and `git stash` rejects
+/// pathspecs that name them.
+fn excluded_paths(repo: &Path, plans_dir: &Path) -> Vec<String> {
+    let (Ok(root), Ok(plans)) = (
+        git::toplevel(repo).and_then(|r| Ok(r.canonicalize()?)),
+        plans_dir.canonicalize(),
+    ) else {
+        return Vec::new();
+    };
+    plans
+        .strip_prefix(&root)
+        .ok()
+        .map(|rel| rel.to_string_lossy().replace('\\', "/"))
+        .filter(|rel| !git::is_ignored(&root, rel))
+        .into_iter()
+        .collect()
+}
+
+fn short(sha: &str) -> &str {
+    &sha[..sha.len().min(7)]
+}
+
+/// Reset the working tree to the commit phase `to_phase` (1-based) started
+/// from, stashing uncommitted changes first, and mark that phase and every
+/// phase started after it pending so `--resume` runs them again. Earlier
+/// phases whose work was never committed (runs without `--commit`) are lost
+/// by the reset too, so they are marked pending as well.
+pub fn rollback(plan_id: &str, to_phase: usize, skip_confirm: bool) -> Result<()> {
+    let paths = ForjaPaths::ensure_initialized()?;
+    let plan_path = paths.plans.join(format!("{plan_id}.json"));
+    if !plan_path.exists() {
+        return Err(ForjaError::PlanNotFound(plan_id.to_string()));
+    }
+    let ckpt_path = checkpoint_path(&paths.plans, plan_id);
+    if !ckpt_path.exists() {
+        return Err(ForjaError::InvalidArgument(format!(
+            "plan '{plan_id}' has not been executed yet"
+        )));
+    }
+    let mut checkpoint = load_checkpoint(&ckpt_path)?;
+
+    if to_phase == 0 || to_phase > checkpoint.phases.len() {
+        return Err(ForjaError::InvalidArgument(format!(
+            "plan '{plan_id}' has no phase {to_phase} (it has {})",
+            checkpoint.phases.len()
+        )));
+    }
+    let index = to_phase - 1;
+    let phase = &checkpoint.phases[index];
+    let Some(start) = phase.git_start.clone() else {
+        return Err(ForjaError::InvalidArgument(format!(
+            "phase {to_phase} has no recorded commit — it never started, or ran outside a git repository"
+        )));
+    };
+
+    // Plans run with --worktree are rolled back in their worktree
+    let repo = match &checkpoint.worktree {
+        Some(dir) => PathBuf::from(dir),
+        None => std::env::current_dir()?,
+    };
+    if !git::is_work_tree(&repo) {
+        return Err(ForjaError::Git(
+            "not inside a git repository with commits".to_string(),
+        ));
+    }
+
+    println!("{}", "forja execute rollback".bold());
+    println!();
+    println!("  Plan:   {}", plan_id.cyan());
+    println!("  Phase:  {} ({})", to_phase, phase.phase_name);
+    println!("  Commit: {}", short(&start.head).cyan());
+    println!();
+
+    let reset = phases_to_reset(&checkpoint, index, |phase| {
+        committed(&repo, phase, &start.head)
+    });
+    let earlier: Vec<&PhaseCheckpoint> = reset
+        .iter()
+        .map(|&i| &checkpoint.phases[i])
+        .filter(|p| p.started_at < checkpoint.phases[index].started_at)
+        .collect();
+    if !earlier.is_empty() {
+        println!(
+            "  {} Earlier phases weren't committed; their changes are reset too and they will run again:",
+            "WARNING:".yellow().bold()
+        );
+        for phase in earlier {
+            println!("    Phase {} ({})", phase.phase_index + 1, phase.phase_name);
+        }
+        println!();
+    }
+    if !start.diff_stat.is_empty() {
+        println!(
+            "  {} The phase started with uncommitted changes ({}); they can't be restored",
+            "WARNING:".yellow().bold(),
+            start.diff_stat
+        );
+        println!();
+    }
+
+    if !skip_confirm {
+        let confirmed = Confirm::new()
+            .with_prompt(format!(
+                "Reset the working tree to {}? Uncommitted changes will be stashed",
+                short(&start.head)
+            ))
+            .default(false)
+            .interact()
+            .map_err(|e| ForjaError::Dialoguer(e.to_string()))?;
+
+        if !confirmed {
+            return Err(ForjaError::PromptCancelled);
+        }
+    }
+
+    let exclude = excluded_paths(&repo, &paths.plans);
+    let exclude: Vec<&str> = exclude.iter().map(String::as_str).collect();
+    if git::stash(
+        &repo,
+        &format!("forja rollback of {plan_id} to phase {to_phase}"),
+        &exclude,
+    )? {
+        println!(
+            "  {} Stashed uncommitted changes (git stash pop to restore)",
+            "GIT:".bold()
+        );
+    }
+    if let Some(branch) = &checkpoint.git_branch
+        && git::current_branch(&repo)?.as_deref() != Some(branch.as_str())
+    {
+        git::switch_branch(&repo, branch)?;
+    }
+    git::reset_hard(&repo, &start.head)?;
+
+    reset_phases(&mut checkpoint, &reset);
+    checkpoint.current_phase = None;
+    checkpoint.last_updated = chrono::Utc::now().to_rfc3339();
+    save_checkpoint(&ckpt_path, &checkpoint)?;
+
+    let mut plan = load_plan(&plan_path)?;
+    if plan.status == PlanStatus::Executed {
+        plan.status = PlanStatus::Pending;
+        save_plan(&plan_path, &plan)?;
+    }
+
+    println!(
+        "  {} Reset to {}; {} phase(s) marked pending",
+        "✓".green(),
+        short(&start.head),
+        reset.len()
+    );
+    println!();
+    crate::output::print_tip(&format!("Run them again: forja execute {plan_id} --resume"));
+
+    Ok(())
+}
+
+/// Whether `phase` committed its work on the history leading to `target`,
+/// so resetting to `target` keeps it.
+fn committed(repo: &Path, phase: &PhaseCheckpoint, target: &str) -> bool {
+    let (Some(start), Some(end)) = (&phase.git_start, &phase.git_end) else {
+        return false;
+    };
+    end.head != start.head && git::is_ancestor(repo, &end.head, target)
+}
+
+/// Phases a rollback to phase `index` must mark pending: that phase, every
+/// phase started after it, and every earlier phase whose work isn't
+/// `committed` and is therefore wiped by the reset.
+fn phases_to_reset(
+    checkpoint: &ExecutionCheckpoint,
+    index: usize,
+    committed: impl Fn(&PhaseCheckpoint) -> bool,
+) -> Vec<usize> {
+    let cutoff = &checkpoint.phases[index].started_at;
+    checkpoint
+        .phases
+        .iter()
+        .enumerate()
+        .filter(|(i, phase)| {
+            *i == index
+                || (phase.started_at.is_some()
+                    && (phase.started_at >= *cutoff || !committed(phase)))
+        })
+        .map(|(i, _)| i)
+        .collect()
+}
+
+/// Mark the given phases pending. Their attempt history, transcripts and
+/// spend are kept.
+fn reset_phases(checkpoint: &mut ExecutionCheckpoint, indices: &[usize]) {
+    for &i in indices {
+        let phase = &mut checkpoint.phases[i];
+        phase.status = PhaseStatus::Pending;
+        phase.started_at = None;
+        phase.completed_at = None;
+        phase.exit_code = None;
+        phase.error_message = None;
+        phase.git_start = None;
+        phase.git_end = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use forja_core::models::plan::{PlanMetadata, PlanPhase, initialize_checkpoint};
+
+    fn checkpoint(started: &[Option<&str>]) -> ExecutionCheckpoint {
+        let plan = PlanMetadata {
+            id: "p1".to_string(),
+            created: "2026-02-08T14:30:22Z".to_string(),
+            status: PlanStatus::Executed,
+            task: "t".to_string(),
+            team_size: "solo-sprint".to_string(),
+            profile: "balanced".to_string(),
+            agents: vec![],
+            stack: None,
+            quality_gates: vec![],
+            phases: (0..started.len())
+                .map(|i| PlanPhase {
+                    name: format!("P{i}"),
+                    agent_role: "coder".to_string(),
+                    files_to_create: vec![],
+                    files_to_modify: vec![],
+                    instructions: String::new(),
+                    depends_on: vec![],
+                })
+                .collect(),
+            source_spec: None,
+        };
+        let mut ckpt = initialize_checkpoint(&plan);
+        for (phase, started_at) in ckpt.phases.iter_mut().zip(started) {
+            if let Some(t) = started_at {
+                phase.status = PhaseStatus::Completed;
+                phase.started_at = Some(t.to_string());
+                phase.git_start = Some(GitSnapshot {
+                    head: "abc".to_string(),
+                    diff_stat: String::new(),
+                });
+            }
+        }
+        ckpt
+    }
+
+    #[test]
+    fn reset_covers_target_and_later_phases_only() {
+        let mut ckpt = checkpoint(&[
+            Some("2026-01-01T10:00:00+00:00"),
+            Some("2026-01-01T11:00:00+00:00"),
+            Some("2026-01-01T12:00:00+00:00"),
+            None,
+        ]);
+
+        let reset = phases_to_reset(&ckpt, 1, |_| true);
+        assert_eq!(reset, [1, 2]);
+        reset_phases(&mut ckpt, &reset);
+
+        let statuses: Vec<_> = ckpt.phases.iter().map(|p| p.status.clone()).collect();
+        assert_eq!(
+            statuses,
+            [
+                PhaseStatus::Completed,
+                PhaseStatus::Pending,
+                PhaseStatus::Pending,
+                PhaseStatus::Pending
+            ]
+        );
+        assert!(ckpt.phases[0].git_start.is_some());
+        assert!(ckpt.phases[1].git_start.is_none());
+    }
+
+    #[test]
+    fn reset_includes_earlier_uncommitted_phases() {
+        let ckpt = checkpoint(&[
+            Some("2026-01-01T10:00:00+00:00"),
+            Some("2026-01-01T11:00:00+00:00"),
+            Some("2026-01-01T12:00:00+00:00"),
+        ]);
+
+        let reset = phases_to_reset(&ckpt, 2, |phase| phase.phase_index == 0);
+
+        assert_eq!(reset, [1, 2]);
+    }
+
+    #[test]
+    fn uncommitted_phase_is_not_kept() {
+        let mut phase = checkpoint(&[Some("2026-01-01T10:00:00+00:00")]).phases[0].clone();
+        // Without --commit a phase ends on the commit it started from
+        phase.git_end = phase.git_start.clone();
+
+        assert!(!committed(Path::new("."), &phase, "abc"));
+    }
+
+    #[test]
+    fn branch_is_namespaced_by_plan() {
+        assert_eq!(branch_name("20260208-auth"), "forja/20260208-auth");
+    }
+}
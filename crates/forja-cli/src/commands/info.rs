@@ -7,6 +7,7 @@ use forja_core::error::{ForjaError, Result};
 use forja_core::models::registry::ResolveResult;
 use forja_core::paths::ForjaPaths;
 use forja_core::registry::catalog;
+use forja_core::registry::deps::{self, DepState};
 use forja_core::symlink::manager::load_installed_ids;
 
 /// Display detailed information about a skill (description, phase, content types, files).
@@ -14,7 +15,7 @@ pub fn run(skill_path: &str) -> Result<()> {
     let paths = ForjaPaths::ensure_initialized()?;
 
     let installed_ids = load_installed_ids(&paths.state);
-    let registry = catalog::scan(&paths.registry, &installed_ids)?;
+    let registry = catalog::scan_all(&paths, &installed_ids)?;
 
     let skill = match registry.resolve(skill_path) {
         ResolveResult::Found(s) => s,
@@ -39,6 +40,10 @@ pub fn run(skill_path: &str) -> Result<()> {
     println!();
     println!("  Phase:     {}", skill.phase.as_str().cyan());
     println!("  Tech:      {}", skill.tech.cyan());
+    println!("  Registry:  {}", skill.registry.cyan());
+    if let Some(version) = &skill.version {
+        println!("  Version:   {}", version.cyan());
+    }
     println!(
         "  Installed: {}",
         if skill.installed {
@@ -52,6 +57,44 @@ pub fn run(skill_path: &str) -> Result<()> {
     let types: Vec<_> = skill.content_types.iter().map(|t| t.to_string()).collect();
     println!("  Content:   {}", types.join(", "));
 
+    let settings = skill.settings.summary();
+    if !settings.is_empty() {
+        println!();
+        println!("  {}:", "Settings".bold());
+        for line in &settings {
+            println!("    {line}");
+        }
+    }
+
+    let requires = deps::tree(&registry, skill);
+    if !requires.is_empty() {
+        println!();
+        println!("  {}:", "Requires".bold());
+        for line in &requires {
+            let indent = "  ".repeat(line.depth);
+            let marker = match line.state {
+                DepState::Found { installed: true } => "✓".green().to_string(),
+                DepState::Found { installed: false } => "○".dimmed().to_string(),
+                DepState::Missing => "✗".red().to_string(),
+                DepState::Cycle => "↻".yellow().to_string(),
+            };
+            let note = match line.state {
+                DepState::Found { installed: false } => " (not installed)".dimmed().to_string(),
+                DepState::Missing => " (not in any registry)".red().to_string(),
+                DepState::Cycle => " (cycle)".yellow().to_string(),
+                DepState::Found { installed: true } => String::new(),
+            };
+            println!("    {indent}{marker} {}{note}", line.id);
+        }
+    }
+
+    let dependents = deps::dependents(&registry, &skill.id);
+    if !dependents.is_empty() {
+        let ids: Vec<&str> = dependents.iter().map(|s| s.id.as_str()).collect();
+        println!();
+        println!("  {}: {}", "Required by".bold(), ids.join(", "));
+    }
+
     list_dir_entries(&skill.path.join("agents"), "Agents", |name| {
         if name.ends_with(".md") {
             Some(name.to_string())
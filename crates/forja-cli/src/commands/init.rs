@@ -1,17 +1,24 @@
-use forja_core::error::Result;
-use forja_core::models::config::{self, ForjaConfig};
+use forja_core::error::{ForjaError, Result};
+use forja_core::models::config::{self, ForjaConfig, InstallStrategy};
 use crate::output;
 use forja_core::paths::{ForjaMode, ForjaPaths};
-use forja_core::registry::catalog;
+use forja_core::models::lock::{LOCK_FILE, load_project_lock};
+use forja_core::registry::{bundles, catalog, lock};
 use forja_core::settings;
 use forja_core::symlink::manager::save_installed_ids;
 use crate::wizard;
 use colored::Colorize;
+use dialoguer::Confirm;
 use std::fs;
 use std::path::Path;
 
 /// Initialize forja with sensible defaults or `--wizard` for interactive setup.
-pub fn run(registry_url: Option<String>, use_wizard: bool, force_global: bool) -> Result<()> {
+pub fn run(
+    registry_url: Option<String>,
+    use_wizard: bool,
+    force_global: bool,
+    copy: bool,
+) -> Result<()> {
     let cwd = std::env::current_dir()?;
 
     // Check for existing .forja/ in cwd (restore flow)
@@ -59,8 +66,16 @@ pub fn run(registry_url: Option<String>, use_wizard: bool, force_global: bool) -
         forja_core::registry::git::clone(&url, &paths.registry)?;
     }
 
+    // A committed forja.lock pins the registries before anything is installed
+    if mode == ForjaMode::Project {
+        restore_lock(&paths)?;
+    }
+
     // Write config.json (new format with version + mode)
-    let forja_config = ForjaConfig::new(mode, url, is_local);
+    let mut forja_config = ForjaConfig::new(mode, url, is_local);
+    if copy {
+        forja_config.install_strategy = InstallStrategy::Copy;
+    }
     config::save_config(&paths.config, &forja_config)?;
 
     // Create plans directory
@@ -90,16 +105,16 @@ pub fn run(registry_url: Option<String>, use_wizard: bool, force_global: bool) -
         let gitignore_path = paths.forja_root.join(".gitignore");
         fs::write(
             &gitignore_path,
-            "# Managed by forja - do not edit\nregistry/\nplans/\n",
+            "# Managed by forja - do not edit\nregistry/\nregistries/\nregistries.json\nplans/\nworktrees/\n",
         )?;
 
-        // Exclude symlinked dirs from version control
+        // Exclude symlinked dirs from version control (copies are meant to be committed)
         let claude_gitignore = paths.claude_dir.join(".gitignore");
-        if !claude_gitignore.exists() {
+        if !copy && !claude_gitignore.exists() {
             fs::create_dir_all(&paths.claude_dir)?;
             fs::write(
                 &claude_gitignore,
-                "# Managed by forja\nagents/\ncommands/\n",
+                "# Managed by forja\nagents/\ncommands/\nskills/forja--*\n",
             )?;
         }
     }
@@ -115,9 +130,32 @@ pub fn run(registry_url: Option<String>, use_wizard: bool, force_global: bool) -
 
     // Sync symlinks to ~/.claude/
     let _sync_result = forja_core::symlink::sync::sync_symlinks(&paths)?;
+    lock::refresh(&paths)?;
 
-    // Detect project stack
+    // Detect project stack and the bundle that sets it up
     let stack = detect_stack(&cwd);
+    let suggested = match stack.as_deref() {
+        Some(label) => bundles::suggest(&bundles::scan_all(&paths)?, label).cloned(),
+        None => None,
+    };
+
+    let mut applied = false;
+    if use_wizard && let Some(bundle) = &suggested {
+        let confirmed = Confirm::new()
+            .with_prompt(format!(
+                "Apply the {} bundle for this stack? ({} skills, {} teams)",
+                bundle.name,
+                bundle.skills.len(),
+                bundle.teams.len()
+            ))
+            .default(true)
+            .interact()
+            .map_err(|e| ForjaError::Dialoguer(e.to_string()))?;
+        if confirmed {
+            super::bundle::apply_bundle(&paths, bundle)?;
+            applied = true;
+        }
+    }
 
     // Output
     output::print_divider();
@@ -140,18 +178,36 @@ pub fn run(registry_url: Option<String>, use_wizard: bool, force_global: bool) -
         println!("  {}  {}", "Stack:".cyan().bold(), detected);
     }
     println!("  {}  {}", "Profile:".cyan().bold(), profile);
+    if applied && let Some(ref bundle) = suggested {
+        println!("  {}  {}", "Bundle:".cyan().bold(), bundle.name);
+    }
+    if copy {
+        println!("  {}  copies (edits are merged on upgrade)", "Install:".cyan().bold());
+    }
     if mode == ForjaMode::Project {
         println!("  {}  .forja/", "Location:".cyan().bold());
     }
 
     output::print_section_header("Next Steps");
+    if !applied && let Some(ref bundle) = suggested {
+        output::print_command_hint(
+            &format!("forja bundle apply {}", bundle.name),
+            "Set up the skills and teams for your stack",
+        );
+    }
     output::print_command_hint("forja task \"your task\"", "Run a task with AI skills");
     output::print_command_hint("forja doctor", "Verify your setup");
     output::print_command_hint("forja guide", "Learn the 5-phase workflow");
 
     if mode == ForjaMode::Project {
         println!();
-        output::print_tip(".forja/ created — commit config.json and state.json to git");
+        if copy {
+            output::print_tip(
+                ".forja/ created — commit config.json, state.json, forja.lock, objects/ and .claude/ to git",
+            );
+        } else {
+            output::print_tip(".forja/ created — commit config.json, state.json and forja.lock to git");
+        }
     }
 
     Ok(())
@@ -192,8 +248,16 @@ fn restore_flow(cwd: &Path, registry_url: Option<String>) -> Result<()> {
         }
     }
 
+    // Pin registries to forja.lock, if the project has one
+    let pinned = restore_lock(&paths)?;
+
     // Sync symlinks
     forja_core::symlink::sync::sync_symlinks(&paths)?;
+    if pinned {
+        warn_lock_drift(&paths)?;
+    } else {
+        lock::refresh(&paths)?;
+    }
 
     output::print_section_header("Results");
     output::print_success(&format!(
@@ -211,6 +275,30 @@ fn restore_flow(cwd: &Path, registry_url: Option<String>) -> Result<()> {
     Ok(())
 }
 
+/// Check registries out at the revisions in `forja.lock`. Returns whether a lock was found.
+fn restore_lock(paths: &ForjaPaths) -> Result<bool> {
+    let Some(locked) = load_project_lock(paths)? else {
+        return Ok(false);
+    };
+    for (name, rev) in lock::restore(paths, &locked)? {
+        output::print_success(&format!(
+            "Registry {name} pinned at {} from {LOCK_FILE}",
+            &rev[..rev.len().min(7)]
+        ));
+    }
+    Ok(true)
+}
+
+fn warn_lock_drift(paths: &ForjaPaths) -> Result<()> {
+    let Some(locked) = load_project_lock(paths)? else {
+        return Ok(());
+    };
+    for drift in lock::verify(paths, &locked)? {
+        println!("  {} {}", "WARN:".yellow().bold(), drift);
+    }
+    Ok(())
+}
+
 fn all_phases() -> Vec<forja_core::models::phase::Phase> {
     use forja_core::models::phase::Phase;
     vec![
@@ -229,7 +317,8 @@ fn mode_label(mode: ForjaMode) -> &'static str {
     }
 }
 
-fn detect_stack(cwd: &Path) -> Option<String> {
+/// Short label for the project's stack (e.g. `Next.js + TypeScript`), or `None`.
+pub fn detect_stack(cwd: &Path) -> Option<String> {
     let mut components = Vec::new();
 
     if has_file(cwd, "next.config.js")
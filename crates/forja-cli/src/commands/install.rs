@@ -1,9 +1,11 @@
 use forja_core::error::{ForjaError, Result};
 use forja_core::models::registry::ResolveResult;
+use forja_core::models::state::record_installs;
 use crate::output;
 use forja_core::paths::ForjaPaths;
-use forja_core::registry::catalog;
+use forja_core::registry::{catalog, deps, lock};
 use forja_core::symlink::manager::{SymlinkManager, load_installed_ids, save_installed_ids};
+use forja_core::symlink::install::{Installed, install_with_requirements};
 use colored::Colorize;
 
 /// Resolve paths based on --global flag: force global or auto-detect.
@@ -27,14 +29,15 @@ struct InstallCounts {
 
 fn install_all_skills(paths: &ForjaPaths, verbose: bool) -> Result<InstallCounts> {
     let mut installed_ids = load_installed_ids(&paths.state);
-    let registry = catalog::scan(&paths.registry, &installed_ids)?;
-    let manager = SymlinkManager::new(paths.claude_agents.clone(), paths.claude_commands.clone());
+    let registry = catalog::scan_all(paths, &installed_ids)?;
+    let manager = SymlinkManager::for_paths(paths);
 
     let mut counts = InstallCounts {
         installed: 0,
         skipped: 0,
         failed: 0,
     };
+    let mut fresh = Vec::new();
 
     for skill in &registry.skills {
         if installed_ids.contains(&skill.id) {
@@ -45,6 +48,7 @@ fn install_all_skills(paths: &ForjaPaths, verbose: bool) -> Result<InstallCounts
         match manager.install(skill) {
             Ok(_) => {
                 installed_ids.push(skill.id.clone());
+                fresh.push(skill);
                 counts.installed += 1;
                 if verbose {
                     println!("  {} {}", "✓".green(), skill.name);
@@ -60,6 +64,7 @@ fn install_all_skills(paths: &ForjaPaths, verbose: bool) -> Result<InstallCounts
     }
 
     save_installed_ids(&paths.state, &installed_ids)?;
+    record_installs(&paths.state, &fresh)?;
 
     Ok(counts)
 }
@@ -68,6 +73,7 @@ fn install_all_skills(paths: &ForjaPaths, verbose: bool) -> Result<InstallCounts
 pub fn run_all(force_global: bool) -> Result<()> {
     let paths = resolve_paths(force_global)?;
     let counts = install_all_skills(&paths, true)?;
+    lock::refresh(&paths)?;
 
     println!();
     println!(
@@ -90,11 +96,12 @@ pub fn install_by_phases(
     phases: &[forja_core::models::phase::Phase],
 ) -> Result<(usize, usize)> {
     let mut installed_ids = load_installed_ids(&paths.state);
-    let registry = catalog::scan(&paths.registry, &installed_ids)?;
-    let manager = SymlinkManager::new(paths.claude_agents.clone(), paths.claude_commands.clone());
+    let registry = catalog::scan_all(paths, &installed_ids)?;
+    let manager = SymlinkManager::for_paths(paths);
 
     let mut installed = 0;
     let mut skipped = 0;
+    let mut fresh = Vec::new();
 
     for skill in &registry.skills {
         if !phases.contains(&skill.phase) {
@@ -105,18 +112,30 @@ pub fn install_by_phases(
             skipped += 1;
             continue;
         }
-        match manager.install(skill) {
-            Ok(_) => {
-                installed_ids.push(skill.id.clone());
-                installed += 1;
+        // Required skills come along even when their phase wasn't selected.
+        let Ok(order) = deps::install_order(&registry, &skill.id) else {
+            skipped += 1;
+            continue;
+        };
+        for skill in order {
+            if installed_ids.contains(&skill.id) {
+                continue;
             }
-            Err(_) => {
-                skipped += 1;
+            match manager.install(skill) {
+                Ok(_) => {
+                    installed_ids.push(skill.id.clone());
+                    fresh.push(skill);
+                    installed += 1;
+                }
+                Err(_) => {
+                    skipped += 1;
+                }
             }
         }
     }
 
     save_installed_ids(&paths.state, &installed_ids)?;
+    record_installs(&paths.state, &fresh)?;
     Ok((installed, skipped))
 }
 
@@ -124,8 +143,8 @@ pub fn install_by_phases(
 pub fn run(skill_path: &str, force_global: bool) -> Result<()> {
     let paths = resolve_paths(force_global)?;
 
-    let mut installed_ids = load_installed_ids(&paths.state);
-    let registry = catalog::scan(&paths.registry, &installed_ids)?;
+    let installed_ids = load_installed_ids(&paths.state);
+    let registry = catalog::scan_all(&paths, &installed_ids)?;
 
     let skill = match registry.resolve(skill_path) {
         ResolveResult::Found(s) => s,
@@ -149,13 +168,12 @@ pub fn run(skill_path: &str, force_global: bool) -> Result<()> {
         return Err(ForjaError::AlreadyInstalled(skill.id.clone()));
     }
 
-    let manager = SymlinkManager::new(paths.claude_agents.clone(), paths.claude_commands.clone());
-    let created = manager.install(skill)?;
-
-    installed_ids.push(skill.id.clone());
-    save_installed_ids(&paths.state, &installed_ids)?;
+    let Installed { created, required } = install_with_requirements(&paths, &registry, skill)?;
 
     println!("{} {}", "Installed:".green().bold(), skill.name.bold());
+    if let Some(version) = &skill.version {
+        println!("  Version: {}", version.cyan());
+    }
     println!("  Phase: {}", skill.phase.as_str().cyan());
     println!("  Tech:  {}", skill.tech.cyan());
     println!("  {}", skill.description.dimmed());
@@ -172,6 +190,17 @@ pub fn run(skill_path: &str, force_global: bool) -> Result<()> {
     if !types.is_empty() {
         println!("  Content: {}", types.join(", "));
     }
+    for line in skill.settings.summary() {
+        println!("  Settings {}", line);
+    }
+
+    if !required.is_empty() {
+        println!();
+        println!("  Also installed (required by {}):", skill.id);
+        for dep in &required {
+            println!("    {} {}", "✓".green(), dep.id);
+        }
+    }
 
     println!();
     output::print_tip("Use 'forja task \"your task\"' to run a task with this skill");
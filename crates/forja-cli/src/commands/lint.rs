@@ -104,18 +104,24 @@ fn lint_single(path: &Path) -> Result<Vec<forja_core::models::lint::LintResult>>
 
 fn lint_all(paths: &ForjaPaths) -> Result<Vec<forja_core::models::lint::LintResult>> {
     let installed_ids = load_installed_ids(&paths.state);
-    let registry = catalog::scan(&paths.registry, &installed_ids)?;
+    let registry = catalog::scan_all(paths, &installed_ids)?;
 
     println!("{}", "forja lint".bold());
     println!();
     println!("  Scanning {} skills...", registry.skills.len());
     println!();
 
-    let results: Vec<_> = registry
+    let mut results: Vec<_> = registry
         .skills
         .iter()
         .map(|skill| lint::lint_skill(&skill.path, &skill.id))
         .collect();
 
+    for (id, issue) in lint::lint_dependencies(&registry) {
+        if let Some(result) = results.iter_mut().find(|r| r.skill_id == id) {
+            result.issues.push(issue);
+        }
+    }
+
     Ok(results)
 }
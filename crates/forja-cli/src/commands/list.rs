@@ -3,15 +3,72 @@ use forja_core::models::phase::Phase;
 use crate::output;
 use forja_core::paths::ForjaPaths;
 use forja_core::registry::catalog;
+use forja_core::symlink::layers::{self, Layer};
 use forja_core::symlink::manager::load_installed_ids;
 use colored::Colorize;
 
-/// List installed skills, or all available skills with `--available`.
-pub fn run(available: bool) -> Result<()> {
+/// List installed skills, all available skills with `--available`, or the
+/// layered global + project set with `--effective`.
+pub fn run(available: bool, effective: bool) -> Result<()> {
     let paths = ForjaPaths::ensure_initialized()?;
 
     let installed_ids = load_installed_ids(&paths.state);
-    let registry = catalog::scan(&paths.registry, &installed_ids)?;
+    let registry = catalog::scan_all(&paths, &installed_ids)?;
+
+    if effective {
+        let resolved = layers::effective(&paths)?;
+        println!("{}", "Effective skills".bold());
+        println!();
+
+        if resolved.skills.is_empty() {
+            println!("  {}", "No skills in any layer.".dimmed());
+        }
+        for entry in &resolved.skills {
+            let layer = match entry.layer {
+                Layer::Global => entry.layer.label().dimmed(),
+                _ => entry.layer.label().cyan(),
+            };
+            let note = if registry.find_by_id(&entry.id).is_some() {
+                String::new()
+            } else {
+                format!(" {}", "(not found in catalog)".red())
+            };
+            println!("  {} {}{}", entry.id.bold(), layer, note);
+        }
+
+        if !resolved.hidden.is_empty() {
+            println!();
+            println!("{}", "Hidden by the project config".bold());
+            println!();
+            for hidden in &resolved.hidden {
+                let reason = match &hidden.by {
+                    Some(by) => format!("overridden by {by}"),
+                    None => "excluded".to_string(),
+                };
+                // Only the agents of a global skill can be hidden per project
+                let active = registry
+                    .find_by_id(&hidden.id)
+                    .filter(|_| hidden.layer == Layer::Global)
+                    .map(layers::still_active)
+                    .unwrap_or_default();
+                let note = if active.is_empty() {
+                    String::new()
+                } else {
+                    format!(" — its {} stay active", active.join(" and "))
+                        .dimmed()
+                        .to_string()
+                };
+                println!(
+                    "  {} {} {}{}",
+                    hidden.id.dimmed(),
+                    hidden.layer.label().dimmed(),
+                    reason.yellow(),
+                    note
+                );
+            }
+        }
+        return Ok(());
+    }
 
     if available {
         println!("{}", "Available skills".bold());
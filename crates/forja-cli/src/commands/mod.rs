@@ -1,10 +1,13 @@
 pub mod build;
+pub mod bundle;
 pub mod chronicle;
 pub mod diff;
 pub mod docs;
 pub mod doctor;
 pub mod execute;
 pub mod fix;
+pub mod gates;
+pub mod git_checkpoint;
 pub mod guide;
 pub mod info;
 pub mod init;
@@ -14,6 +17,7 @@ pub mod list;
 pub mod monitor;
 pub mod new;
 pub mod plan;
+pub mod registry;
 pub mod review;
 pub mod search;
 pub mod ship;
@@ -22,6 +26,8 @@ pub mod stats;
 pub mod status;
 pub mod task;
 pub mod team;
+pub mod transcript;
 pub mod uninstall;
 pub mod update;
 pub mod upgrade;
+pub mod worktree;
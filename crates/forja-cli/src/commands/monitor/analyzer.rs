@@ -0,0 +1,493 @@
+use std::collections::{BTreeSet, HashMap};
+use std::process::Stdio;
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+
+use forja_core::models::config::MonitorSettings;
+
+use super::events::{AlertKind, AlertSnapshot, MessageGroupSnapshot, TaskSnapshot, TeamSnapshot};
+use super::state::DashboardState;
+
+/// How often the analyzer looks at the state again.
+pub const ANALYZE_EVERY: Duration = Duration::from_secs(15);
+
+/// The state an analysis runs on, per team.
+pub struct Teams<'a> {
+    pub teams: &'a HashMap<String, TeamSnapshot>,
+    pub tasks: &'a HashMap<String, Vec<TaskSnapshot>>,
+    pub messages: &'a HashMap<String, Vec<MessageGroupSnapshot>>,
+}
+
+/// Looks for stuck teams. Remembers when each task was first seen in
+/// progress, since task files don't record it.
+pub struct Analyzer {
+    settings: MonitorSettings,
+    in_progress_since: HashMap<(String, String), i64>,
+}
+
+impl Analyzer {
+    pub fn new(settings: MonitorSettings) -> Self {
+        Self {
+            settings,
+            in_progress_since: HashMap::new(),
+        }
+    }
+
+    /// Alerts that hold at `now` (unix ms).
+    pub fn analyze(&mut self, state: &Teams, now: i64) -> Vec<AlertSnapshot> {
+        let mut seen = HashMap::new();
+        for (team_name, tasks) in state.tasks {
+            for task in tasks.iter().filter(|t| t.status == "in_progress") {
+                let key = (team_name.clone(), task.id.clone());
+                let since = self.in_progress_since.get(&key).copied().unwrap_or(now);
+                seen.insert(key, since);
+            }
+        }
+        self.in_progress_since = seen;
+
+        let mut alerts = Vec::new();
+        for (team_name, tasks) in state.tasks {
+            let inboxes = state
+                .messages
+                .get(team_name)
+                .map(Vec::as_slice)
+                .unwrap_or_default();
+            alerts.extend(cycles(team_name, tasks));
+            alerts.extend(self.stalled(team_name, tasks, inboxes, now));
+            if let Some(team) = state.teams.get(team_name) {
+                alerts.extend(unknown_owners(team, tasks));
+            }
+        }
+        if self.settings.unread_limit > 0 {
+            for (team_name, inboxes) in state.messages {
+                alerts.extend(unread_pileups(
+                    team_name,
+                    inboxes,
+                    self.settings.unread_limit,
+                ));
+            }
+        }
+        alerts.sort_by(|a, b| a.key.cmp(&b.key));
+        alerts
+    }
+
+    fn stalled(
+        &self,
+        team_name: &str,
+        tasks: &[TaskSnapshot],
+        inboxes: &[MessageGroupSnapshot],
+        now: i64,
+    ) -> Vec<AlertSnapshot> {
+        if self.settings.stall_minutes == 0 {
+            return Vec::new();
+        }
+        let limit = self.settings.stall_minutes as i64 * 60_000;
+
+        tasks
+            .iter()
+            .filter(|t| t.status == "in_progress" && !t.owner.is_empty())
+            .filter_map(|task| {
+                let key = (team_name.to_string(), task.id.clone());
+                let since = *self.in_progress_since.get(&key)?;
+                let last_message = inboxes
+                    .iter()
+                    .flat_map(|g| &g.messages)
+                    .filter(|m| m.from == task.owner)
+                    .filter_map(|m| timestamp_ms(&m.timestamp))
+                    .max();
+                let active = last_message.map_or(since, |at| at.max(since));
+                let idle = now - active;
+                (idle >= limit).then(|| AlertSnapshot {
+                    key: format!(
+                        "{}:{team_name}:{}",
+                        AlertKind::StalledTask.as_str(),
+                        task.id
+                    ),
+                    team_name: team_name.to_string(),
+                    kind: AlertKind::StalledTask,
+                    message: format!(
+                        "Task #{} \"{}\" in progress for {} min without a message from {}",
+                        task.id,
+                        task.subject,
+                        idle / 60_000,
+                        task.owner
+                    ),
+                })
+            })
+            .collect()
+    }
+}
+
+/// Dependency loops among the team's unfinished tasks, one alert per loop.
+fn cycles(team_name: &str, tasks: &[TaskSnapshot]) -> Vec<AlertSnapshot> {
+    let open: Vec<&TaskSnapshot> = tasks.iter().filter(|t| t.status != "completed").collect();
+    let index: HashMap<&str, usize> = open
+        .iter()
+        .enumerate()
+        .map(|(i, t)| (t.id.as_str(), i))
+        .collect();
+
+    // Edge a → b: a waits for b. `blocks` says the same from the other side.
+    let mut waits_for: Vec<BTreeSet<usize>> = vec![BTreeSet::new(); open.len()];
+    for (i, task) in open.iter().enumerate() {
+        for id in &task.blocked_by {
+            if let Some(&j) = index.get(id.as_str()) {
+                waits_for[i].insert(j);
+            }
+        }
+        for id in &task.blocks {
+            if let Some(&j) = index.get(id.as_str()) {
+                waits_for[j].insert(i);
+            }
+        }
+    }
+
+    strongly_connected(&waits_for)
+        .into_iter()
+        .filter(|component| component.len() > 1 || waits_for[component[0]].contains(&component[0]))
+        .map(|component| {
+            let mut ids: Vec<&str> = component.iter().map(|&i| open[i].id.as_str()).collect();
+            ids.sort_by_key(|id| (id.parse::<u64>().unwrap_or(u64::MAX), id.to_string()));
+            let refs: Vec<String> = ids.iter().map(|id| format!("#{id}")).collect();
+            AlertSnapshot {
+                key: format!(
+                    "{}:{team_name}:{}",
+                    AlertKind::DependencyCycle.as_str(),
+                    ids.join(",")
+                ),
+                team_name: team_name.to_string(),
+                kind: AlertKind::DependencyCycle,
+                message: format!(
+                    "Tasks {} block each other; none of them can start",
+                    refs.join(", ")
+                ),
+            }
+        })
+        .collect()
+}
+
+/// Tarjan's algorithm over an adjacency list.
+fn strongly_connected(graph: &[BTreeSet<usize>]) -> Vec<Vec<usize>> {
+    struct Search<'g> {
+        graph: &'g [BTreeSet<usize>],
+        next: usize,
+        order: Vec<Option<usize>>,
+        low: Vec<usize>,
+        stack: Vec<usize>,
+        on_stack: Vec<bool>,
+        components: Vec<Vec<usize>>,
+    }
+
+    impl Search<'_> {
+        fn visit(&mut self, v: usize) {
+            self.order[v] = Some(self.next);
+            self.low[v] = self.next;
+            self.next += 1;
+            self.stack.push(v);
+            self.on_stack[v] = true;
+
+            for &w in self.graph[v].iter() {
+                match self.order[w] {
+                    None => {
+                        self.visit(w);
+                        self.low[v] = self.low[v].min(self.low[w]);
+                    }
+                    Some(order) if self.on_stack[w] => self.low[v] = self.low[v].min(order),
+                    Some(_) => {}
+                }
+            }
+
+            if Some(self.low[v]) == self.order[v] {
+                let mut component = Vec::new();
+                while let Some(w) = self.stack.pop() {
+                    self.on_stack[w] = false;
+                    component.push(w);
+                    if w == v {
+                        break;
+                    }
+                }
+                self.components.push(component);
+            }
+        }
+    }
+
+    let mut search = Search {
+        graph,
+        next: 0,
+        order: vec![None; graph.len()],
+        low: vec![0; graph.len()],
+        stack: Vec::new(),
+        on_stack: vec![false; graph.len()],
+        components: Vec::new(),
+    };
+    for v in 0..graph.len() {
+        if search.order[v].is_none() {
+            search.visit(v);
+        }
+    }
+    search.components
+}
+
+/// Tasks whose owner isn't in the team config.
+fn unknown_owners(team: &TeamSnapshot, tasks: &[TaskSnapshot]) -> Vec<AlertSnapshot> {
+    tasks
+        .iter()
+        .filter(|t| t.status != "completed" && !t.owner.is_empty())
+        .filter(|t| !team.members.iter().any(|m| m.name == t.owner))
+        .map(|task| AlertSnapshot {
+            key: format!(
+                "{}:{}:{}",
+                AlertKind::UnknownOwner.as_str(),
+                team.name,
+                task.id
+            ),
+            team_name: team.name.clone(),
+            kind: AlertKind::UnknownOwner,
+            message: format!(
+                "Task #{} is owned by {}, who isn't a member of {}",
+                task.id, task.owner, team.name
+            ),
+        })
+        .collect()
+}
+
+fn unread_pileups(
+    team_name: &str,
+    inboxes: &[MessageGroupSnapshot],
+    limit: usize,
+) -> Vec<AlertSnapshot> {
+    inboxes
+        .iter()
+        .filter_map(|inbox| {
+            let unread = inbox.messages.iter().filter(|m| !m.read).count();
+            (unread >= limit).then(|| AlertSnapshot {
+                key: format!(
+                    "{}:{team_name}:{}",
+                    AlertKind::UnreadPileup.as_str(),
+                    inbox.recipient
+                ),
+                team_name: team_name.to_string(),
+                kind: AlertKind::UnreadPileup,
+                message: format!("{} has {unread} unread messages", inbox.recipient),
+            })
+        })
+        .collect()
+}
+
+fn timestamp_ms(timestamp: &str) -> Option<i64> {
+    DateTime::parse_from_rfc3339(timestamp)
+        .ok()
+        .map(|t| t.timestamp_millis())
+}
+
+/// Analyze the state every `ANALYZE_EVERY`, publishing alert changes and
+/// running the alert hook for each new alert.
+pub async fn run(state: Arc<DashboardState>, settings: MonitorSettings) {
+    let hook = settings.alert_hook.clone();
+    let mut analyzer = Analyzer::new(settings);
+    let mut interval = tokio::time::interval(ANALYZE_EVERY);
+    loop {
+        interval.tick().await;
+        let alerts = {
+            let teams = state.teams.read().await;
+            let tasks = state.tasks.read().await;
+            let messages = state.messages.read().await;
+            let view = Teams {
+                teams: &teams,
+                tasks: &tasks,
+                messages: &messages,
+            };
+            analyzer.analyze(&view, Utc::now().timestamp_millis())
+        };
+        let raised = state.set_alerts(alerts).await;
+        if let Some(hook) = &hook {
+            for alert in &raised {
+                run_hook(hook, alert);
+            }
+        }
+    }
+}
+
+/// Fire and forget: a failing hook shouldn't disturb the dashboard.
+fn run_hook(hook: &str, alert: &AlertSnapshot) {
+    let mut cmd = tokio::process::Command::from(crate::commands::gates::shell(hook));
+    cmd.env("FORJA_ALERT_KIND", alert.kind.as_str())
+        .env("FORJA_ALERT_TEAM", &alert.team_name)
+        .env("FORJA_ALERT_MESSAGE", &alert.message)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null());
+    let _ = cmd.spawn();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::monitor::events::{MemberSnapshot, MessageSnapshot};
+
+    fn task(id: &str, status: &str, owner: &str, blocked_by: &[&str]) -> TaskSnapshot {
+        TaskSnapshot {
+            id: id.to_string(),
+            subject: format!("Task {id}"),
+            status: status.to_string(),
+            owner: owner.to_string(),
+            active_form: String::new(),
+            blocks: vec![],
+            blocked_by: blocked_by.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+
+    fn team(name: &str, members: &[&str]) -> TeamSnapshot {
+        TeamSnapshot {
+            name: name.to_string(),
+            description: String::new(),
+            created_at: 0,
+            members: members
+                .iter()
+                .map(|m| MemberSnapshot {
+                    name: m.to_string(),
+                    agent_type: "general-purpose".to_string(),
+                    model: "sonnet".to_string(),
+                    color: "blue".to_string(),
+                })
+                .collect(),
+            source: None,
+        }
+    }
+
+    fn inbox(recipient: &str, messages: &[(&str, &str, bool)]) -> MessageGroupSnapshot {
+        MessageGroupSnapshot {
+            team_name: "night".to_string(),
+            recipient: recipient.to_string(),
+            messages: messages
+                .iter()
+                .map(|(from, timestamp, read)| MessageSnapshot {
+                    from: from.to_string(),
+                    text: "hi".to_string(),
+                    timestamp: timestamp.to_string(),
+                    color: String::new(),
+                    read: *read,
+                })
+                .collect(),
+        }
+    }
+
+    fn kinds(alerts: &[AlertSnapshot]) -> Vec<&str> {
+        alerts.iter().map(|a| a.key.as_str()).collect()
+    }
+
+    #[test]
+    fn analyze_finds_cycles_unknown_owners_and_pileups() {
+        let teams = HashMap::from([("night".to_string(), team("night", &["coder", "tester"]))]);
+        // 3 waits for 5, 5 for 4, and 3 says it blocks 4
+        let mut blocks = task("3", "pending", "tester", &["5"]);
+        blocks.blocks = vec!["4".to_string()];
+        let tasks = HashMap::from([(
+            "night".to_string(),
+            vec![
+                task("1", "pending", "coder", &["2"]),
+                task("2", "pending", "coder", &["1"]),
+                blocks,
+                task("4", "pending", "tester", &[]),
+                task("5", "pending", "tester", &["4"]),
+                task("6", "pending", "ghost", &[]),
+                task("7", "completed", "ghost", &["7"]),
+            ],
+        )]);
+        let unread = [("coder", "2026-10-18T02:00:00Z", false); 3];
+        let messages = HashMap::from([(
+            "night".to_string(),
+            vec![inbox("team-lead", &unread), inbox("coder", &unread[..1])],
+        )]);
+        let view = Teams {
+            teams: &teams,
+            tasks: &tasks,
+            messages: &messages,
+        };
+
+        let mut analyzer = Analyzer::new(MonitorSettings {
+            unread_limit: 3,
+            ..Default::default()
+        });
+        let alerts = analyzer.analyze(&view, 0);
+        assert_eq!(
+            kinds(&alerts),
+            [
+                "dependency_cycle:night:1,2",
+                "dependency_cycle:night:3,4,5",
+                "unknown_owner:night:6",
+                "unread_pileup:night:team-lead",
+            ]
+        );
+        assert_eq!(
+            alerts[0].message,
+            "Tasks #1, #2 block each other; none of them can start"
+        );
+    }
+
+    #[test]
+    fn analyze_flags_quiet_in_progress_tasks() {
+        let teams = HashMap::from([("night".to_string(), team("night", &["coder"]))]);
+        let tasks = HashMap::from([(
+            "night".to_string(),
+            vec![task("1", "in_progress", "coder", &[])],
+        )]);
+        let mut messages = HashMap::new();
+        let minute = 60_000;
+        let start = timestamp_ms("2026-10-18T02:00:00Z").unwrap();
+        let mut analyzer = Analyzer::new(MonitorSettings::default());
+
+        let view = Teams {
+            teams: &teams,
+            tasks: &tasks,
+            messages: &messages,
+        };
+        assert!(analyzer.analyze(&view, start).is_empty());
+        assert!(analyzer.analyze(&view, start + 14 * minute).is_empty());
+        let alerts = analyzer.analyze(&view, start + 15 * minute);
+        assert_eq!(kinds(&alerts), ["stalled_task:night:1"]);
+        assert!(alerts[0].message.contains("for 15 min"));
+
+        // A message from the owner counts as activity
+        messages.insert(
+            "night".to_string(),
+            vec![inbox(
+                "team-lead",
+                &[("coder", "2026-10-18T02:10:00Z", true)],
+            )],
+        );
+        let view = Teams {
+            teams: &teams,
+            tasks: &tasks,
+            messages: &messages,
+        };
+        assert!(analyzer.analyze(&view, start + 20 * minute).is_empty());
+        assert_eq!(analyzer.analyze(&view, start + 25 * minute).len(), 1);
+    }
+
+    #[tokio::test]
+    async fn set_alerts_announces_changes_only() {
+        let state = DashboardState::new();
+        let mut rx = state.subscribe();
+        let alert = |key: &str| AlertSnapshot {
+            key: key.to_string(),
+            team_name: "night".to_string(),
+            kind: AlertKind::UnknownOwner,
+            message: String::new(),
+        };
+
+        assert_eq!(
+            state.set_alerts(vec![alert("a"), alert("b")]).await.len(),
+            2
+        );
+        assert_eq!(state.set_alerts(vec![alert("b")]).await.len(), 0);
+
+        let mut events = Vec::new();
+        while let Ok(event) = rx.try_recv() {
+            events.push(serde_json::to_value(&event).unwrap()["type"].clone());
+        }
+        assert_eq!(events, ["Alert", "Alert", "AlertCleared"]);
+    }
+}
@@ -0,0 +1,294 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use chrono::{SecondsFormat, Utc};
+use serde::{Deserialize, Serialize};
+
+use forja_core::error::{ForjaError, Result};
+use forja_core::models::claude::ClaudeInboxMessage;
+
+use super::state::DashboardState;
+
+/// Sender recorded on messages posted from the dashboard.
+pub const DASHBOARD_SENDER: &str = "forja-monitor";
+
+/// Statuses Claude Code tasks move through.
+pub const TASK_STATUSES: [&str; 3] = ["pending", "in_progress", "completed"];
+
+/// The Claude Code directories the control channel writes to.
+#[derive(Debug, Clone)]
+pub struct ClaudeDirs {
+    pub teams: PathBuf,
+    pub tasks: PathBuf,
+}
+
+/// A message on the `/api/control` WebSocket. `id` is echoed in the reply.
+#[derive(Debug, Deserialize)]
+pub struct ControlRequest {
+    #[serde(default)]
+    pub id: Option<u64>,
+    #[serde(flatten)]
+    pub command: ControlCommand,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type")]
+pub enum ControlCommand {
+    /// Append a message to a member's inbox file.
+    SendMessage {
+        team_name: String,
+        recipient: String,
+        text: String,
+    },
+    /// Change the status of a task file.
+    SetTaskStatus {
+        team_name: String,
+        task_id: String,
+        status: String,
+    },
+}
+
+#[derive(Debug, Serialize)]
+pub struct ControlReply {
+    pub id: Option<u64>,
+    pub ok: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Run one control message and build its reply. The change lands on disk
+/// only: the watcher picks it up and broadcasts it like any other edit.
+pub async fn handle(state: &DashboardState, message: &str) -> ControlReply {
+    let request = match serde_json::from_str::<ControlRequest>(message) {
+        Ok(request) => request,
+        Err(e) => {
+            return ControlReply {
+                id: None,
+                ok: false,
+                error: Some(format!("invalid control message: {e}")),
+            };
+        }
+    };
+    let result = match &state.control {
+        Some(dirs) => execute(state, dirs, request.command).await,
+        None => Err(ForjaError::Monitor(
+            "this dashboard is read-only (replay)".to_string(),
+        )),
+    };
+    ControlReply {
+        id: request.id,
+        ok: result.is_ok(),
+        error: result.err().map(|e| e.to_string()),
+    }
+}
+
+async fn execute(state: &DashboardState, dirs: &ClaudeDirs, command: ControlCommand) -> Result<()> {
+    let team_name = match &command {
+        ControlCommand::SendMessage { team_name, .. }
+        | ControlCommand::SetTaskStatus { team_name, .. } => team_name,
+    };
+    // Remote teams are only mirrored here; their files are on the other machine
+    if let Some(source) = state
+        .teams
+        .read()
+        .await
+        .get(team_name)
+        .and_then(|t| t.source.clone())
+    {
+        return Err(ForjaError::Monitor(format!(
+            "team '{team_name}' runs on {source}; control it from that monitor"
+        )));
+    }
+    match command {
+        ControlCommand::SendMessage {
+            team_name,
+            recipient,
+            text,
+        } => send_message(&dirs.teams, &team_name, &recipient, &text),
+        ControlCommand::SetTaskStatus {
+            team_name,
+            task_id,
+            status,
+        } => {
+            // Tasks live under the lead's session ID when the team config has one
+            let task_dir = state
+                .team_task_mapping
+                .read()
+                .await
+                .get(&team_name)
+                .cloned()
+                .unwrap_or(team_name);
+            set_task_status(&dirs.tasks, &task_dir, &task_id, &status)
+        }
+    }
+}
+
+/// Append `text` to `teams/<team>/inboxes/<recipient>.json`, creating the inbox if needed.
+pub fn send_message(teams_dir: &Path, team_name: &str, recipient: &str, text: &str) -> Result<()> {
+    check_name("team", team_name)?;
+    check_name("recipient", recipient)?;
+    if text.trim().is_empty() {
+        return Err(ForjaError::InvalidArgument(
+            "message text is empty".to_string(),
+        ));
+    }
+    let team_dir = teams_dir.join(team_name);
+    if !team_dir.is_dir() {
+        return Err(ForjaError::InvalidArgument(format!(
+            "no team '{team_name}' in {}",
+            teams_dir.display()
+        )));
+    }
+
+    let inboxes = team_dir.join("inboxes");
+    fs::create_dir_all(&inboxes)?;
+    let path = inboxes.join(format!("{recipient}.json"));
+    // Edit as raw JSON so fields forja doesn't model survive the rewrite
+    let mut messages: Vec<serde_json::Value> = match fs::read_to_string(&path) {
+        Ok(content) => serde_json::from_str(&content)?,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+        // Anything else would drop the messages already there
+        Err(e) => return Err(e.into()),
+    };
+    messages.push(serde_json::to_value(ClaudeInboxMessage {
+        from: DASHBOARD_SENDER.to_string(),
+        text: text.to_string(),
+        timestamp: Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true),
+        color: String::new(),
+        read: false,
+    })?);
+    write_json(&path, &messages)
+}
+
+/// Set the `status` of `tasks/<task_dir>/<task_id>.json`.
+pub fn set_task_status(
+    tasks_dir: &Path,
+    task_dir: &str,
+    task_id: &str,
+    status: &str,
+) -> Result<()> {
+    check_name("task directory", task_dir)?;
+    check_name("task", task_id)?;
+    if !TASK_STATUSES.contains(&status) {
+        return Err(ForjaError::InvalidArgument(format!(
+            "unknown task status '{status}'. Use: {}",
+            TASK_STATUSES.join(", ")
+        )));
+    }
+
+    let path = tasks_dir.join(task_dir).join(format!("{task_id}.json"));
+    let content = fs::read_to_string(&path).map_err(|_| {
+        ForjaError::InvalidArgument(format!("no task #{task_id} in {}", path.display()))
+    })?;
+    let mut task: serde_json::Value = serde_json::from_str(&content)?;
+    let Some(fields) = task.as_object_mut() else {
+        return Err(ForjaError::InvalidArgument(format!(
+            "{} is not a task object",
+            path.display()
+        )));
+    };
+    fields.insert("status".to_string(), status.into());
+    write_json(&path, &task)
+}
+
+/// Names become path components, so they can't climb out of their directory.
+fn check_name(what: &str, name: &str) -> Result<()> {
+    if name.is_empty() || name == "." || name == ".." || name.contains(['/', '\\']) {
+        return Err(ForjaError::InvalidArgument(format!(
+            "invalid {what} name '{name}'"
+        )));
+    }
+    Ok(())
+}
+
+/// Temp file + rename, so Claude Code never reads a half-written file.
+fn write_json<T: Serialize>(path: &Path, value: &T) -> Result<()> {
+    let tmp = path.with_extension("json.tmp");
+    fs::write(&tmp, serde_json::to_string_pretty(value)?)?;
+    fs::rename(&tmp, path)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn send_message_appends_to_the_inbox() {
+        let dir = TempDir::new().unwrap();
+        let inboxes = dir.path().join("night-shift/inboxes");
+        fs::create_dir_all(&inboxes).unwrap();
+        fs::write(
+            inboxes.join("coder.json"),
+            r#"[{"from":"team-lead","text":"start","timestamp":"2026-10-18T02:00:00.000Z","summary":"kept"}]"#,
+        )
+        .unwrap();
+
+        send_message(dir.path(), "night-shift", "coder", "you look stuck").unwrap();
+
+        let content = fs::read_to_string(inboxes.join("coder.json")).unwrap();
+        let messages: Vec<ClaudeInboxMessage> = serde_json::from_str(&content).unwrap();
+        assert_eq!(messages.len(), 2);
+        assert_eq!(messages[1].from, DASHBOARD_SENDER);
+        assert_eq!(messages[1].text, "you look stuck");
+        assert!(!messages[1].read);
+        assert!(content.contains("\"summary\": \"kept\""));
+
+        assert!(send_message(dir.path(), "night-shift", "../coder", "hi").is_err());
+        assert!(send_message(dir.path(), "day-shift", "coder", "hi").is_err());
+    }
+
+    #[test]
+    fn send_message_leaves_an_unreadable_inbox_alone() {
+        let dir = TempDir::new().unwrap();
+        let inboxes = dir.path().join("night-shift/inboxes");
+        fs::create_dir_all(&inboxes).unwrap();
+        fs::write(inboxes.join("coder.json"), [0xff, 0xfe, b'[', b']']).unwrap();
+
+        assert!(send_message(dir.path(), "night-shift", "coder", "hi").is_err());
+        assert_eq!(
+            fs::read(inboxes.join("coder.json")).unwrap(),
+            [0xff, 0xfe, b'[', b']']
+        );
+    }
+
+    #[test]
+    fn set_task_status_rewrites_only_the_status() {
+        let dir = TempDir::new().unwrap();
+        let tasks = dir.path().join("session-1");
+        fs::create_dir_all(&tasks).unwrap();
+        fs::write(
+            tasks.join("3.json"),
+            r#"{"id":"3","subject":"Ship it","status":"in_progress","blockedBy":["2"]}"#,
+        )
+        .unwrap();
+
+        set_task_status(dir.path(), "session-1", "3", "pending").unwrap();
+
+        let task: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(tasks.join("3.json")).unwrap()).unwrap();
+        assert_eq!(task["status"], "pending");
+        assert_eq!(task["blockedBy"][0], "2");
+        assert!(!tasks.join("3.json.tmp").exists());
+
+        assert!(set_task_status(dir.path(), "session-1", "3", "stuck").is_err());
+        assert!(set_task_status(dir.path(), "session-1", "4", "pending").is_err());
+    }
+
+    #[tokio::test]
+    async fn handle_replies_with_the_request_id() {
+        let state = DashboardState::new();
+        let reply = handle(
+            &state,
+            r#"{"id":7,"type":"SetTaskStatus","team_name":"t","task_id":"1","status":"pending"}"#,
+        )
+        .await;
+        assert_eq!(reply.id, Some(7));
+        assert!(!reply.ok);
+        assert!(reply.error.unwrap().contains("read-only"));
+
+        let reply = handle(&state, r#"{"type":"Nope"}"#).await;
+        assert!(reply.error.unwrap().starts_with("invalid control message"));
+    }
+}
@@ -1,7 +1,9 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
+
+use forja_core::models::usage::TokenUsage;
 
 /// Events streamed to the dashboard via SSE.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type")]
 #[allow(dead_code)]
 pub enum DashboardEvent {
@@ -10,6 +12,11 @@ pub enum DashboardEvent {
         teams: Vec<TeamSnapshot>,
         tasks: Vec<TaskGroupSnapshot>,
         messages: Vec<MessageGroupSnapshot>,
+        plans: Vec<PlanSnapshot>,
+        #[serde(default)]
+        alerts: Vec<AlertSnapshot>,
+        #[serde(default)]
+        sources: Vec<SourceSnapshot>,
     },
 
     TeamUpdated {
@@ -34,18 +41,59 @@ pub enum DashboardEvent {
         message: MessageSnapshot,
     },
 
+    PlanUpdated {
+        plan: PlanSnapshot,
+    },
+
+    /// The analyzer found a problem with a team.
+    Alert {
+        alert: AlertSnapshot,
+    },
+    /// A problem reported earlier no longer holds.
+    AlertCleared {
+        team_name: String,
+        key: String,
+    },
+
+    /// A remote monitor connected or dropped.
+    SourceUpdated {
+        source: SourceSnapshot,
+    },
+
     Heartbeat,
 }
 
-#[derive(Debug, Clone, Serialize)]
+impl DashboardEvent {
+    /// Team the event is about; `None` for snapshots, plans, sources and heartbeats.
+    pub fn team_name(&self) -> Option<&str> {
+        match self {
+            DashboardEvent::TeamUpdated { team } => Some(&team.name),
+            DashboardEvent::Alert { alert } => Some(&alert.team_name),
+            DashboardEvent::TeamDeleted { team_name }
+            | DashboardEvent::TaskUpdated { team_name, .. }
+            | DashboardEvent::TaskDeleted { team_name, .. }
+            | DashboardEvent::MessageReceived { team_name, .. }
+            | DashboardEvent::AlertCleared { team_name, .. } => Some(team_name),
+            DashboardEvent::Snapshot { .. }
+            | DashboardEvent::PlanUpdated { .. }
+            | DashboardEvent::SourceUpdated { .. }
+            | DashboardEvent::Heartbeat => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TeamSnapshot {
     pub name: String,
     pub description: String,
     pub created_at: u64,
     pub members: Vec<MemberSnapshot>,
+    /// Remote monitor the team comes from; `None` for local teams.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MemberSnapshot {
     pub name: String,
     pub agent_type: String,
@@ -53,13 +101,13 @@ pub struct MemberSnapshot {
     pub color: String,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TaskGroupSnapshot {
     pub team_name: String,
     pub tasks: Vec<TaskSnapshot>,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TaskSnapshot {
     pub id: String,
     pub subject: String,
@@ -70,14 +118,14 @@ pub struct TaskSnapshot {
     pub blocked_by: Vec<String>,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MessageGroupSnapshot {
     pub team_name: String,
     pub recipient: String,
     pub messages: Vec<MessageSnapshot>,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MessageSnapshot {
     pub from: String,
     pub text: String,
@@ -85,3 +133,59 @@ pub struct MessageSnapshot {
     pub color: String,
     pub read: bool,
 }
+
+/// Progress and spend of a plan execution, read from its checkpoint.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlanSnapshot {
+    pub plan_id: String,
+    pub task: String,
+    pub profile: String,
+    pub phases_completed: usize,
+    pub phases_total: usize,
+    pub usage: TokenUsage,
+}
+
+/// What the analyzer found.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AlertKind {
+    /// Tasks blocking each other in a loop.
+    DependencyCycle,
+    /// An in-progress task whose owner has gone quiet.
+    StalledTask,
+    /// An inbox with unread messages piling up.
+    UnreadPileup,
+    /// A task owned by someone who isn't a member of the team.
+    UnknownOwner,
+}
+
+impl AlertKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AlertKind::DependencyCycle => "dependency_cycle",
+            AlertKind::StalledTask => "stalled_task",
+            AlertKind::UnreadPileup => "unread_pileup",
+            AlertKind::UnknownOwner => "unknown_owner",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AlertSnapshot {
+    /// Identifies the problem across scans, e.g. `stalled_task:night-shift:3`.
+    pub key: String,
+    pub team_name: String,
+    pub kind: AlertKind,
+    pub message: String,
+}
+
+/// A remote monitor whose teams this one shows.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SourceSnapshot {
+    pub name: String,
+    pub url: String,
+    pub connected: bool,
+    /// Why the last connection attempt failed or dropped.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
@@ -0,0 +1,440 @@
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, ErrorKind, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Weak;
+use std::time::Duration;
+
+use chrono::{DateTime, Local, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::sync::broadcast;
+
+use forja_core::error::{ForjaError, Result};
+use forja_core::paths::ForjaPaths;
+
+use super::events::DashboardEvent;
+use super::state::DashboardState;
+
+/// Longest pause between two replayed events, whatever the speed, so idle
+/// stretches of an overnight session don't stall the replay.
+pub const MAX_REPLAY_GAP: Duration = Duration::from_secs(5);
+
+/// One line of a session log: an event and when it was seen (unix ms).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoryRecord {
+    pub at: i64,
+    pub event: DashboardEvent,
+}
+
+/// A recorded `forja monitor` run.
+#[derive(Debug, Clone, Serialize)]
+pub struct SessionSummary {
+    pub id: String,
+    pub events: usize,
+    pub started: Option<i64>,
+    pub ended: Option<i64>,
+    pub teams: Vec<String>,
+}
+
+/// `~/.forja/monitor/`, with one `<session>.jsonl` log per monitor run.
+pub fn history_dir() -> Result<PathBuf> {
+    Ok(ForjaPaths::global_forja_root()?.join("monitor"))
+}
+
+fn session_path(dir: &Path, session: &str) -> PathBuf {
+    dir.join(format!("{session}.jsonl"))
+}
+
+/// Open a new session log named after the local time, e.g. `20261018-021500`.
+pub fn start_session(dir: &Path) -> Result<(String, File)> {
+    fs::create_dir_all(dir)?;
+    let stamp = Local::now().format("%Y%m%d-%H%M%S").to_string();
+    let mut id = stamp.clone();
+    let mut n = 1;
+    loop {
+        let opened = OpenOptions::new()
+            .append(true)
+            .create_new(true)
+            .open(session_path(dir, &id));
+        match opened {
+            Ok(file) => return Ok((id, file)),
+            Err(e) if e.kind() == ErrorKind::AlreadyExists => {
+                n += 1;
+                id = format!("{stamp}-{n}");
+            }
+            Err(e) => return Err(e.into()),
+        }
+    }
+}
+
+/// Append every event sent on `rx` to the session log until the channel
+/// closes. A snapshot of `state` opens the log and follows any stretch the
+/// recorder fell behind on, so a replay can always rebuild the dashboard;
+/// snapshots sent to clients and heartbeats aren't logged.
+pub async fn record(
+    state: Weak<DashboardState>,
+    mut rx: broadcast::Receiver<DashboardEvent>,
+    mut file: File,
+) {
+    let mut snapshot_due = true;
+    loop {
+        if std::mem::take(&mut snapshot_due)
+            && let Some(state) = state.upgrade()
+        {
+            append(&mut file, state.snapshot().await);
+        }
+        match rx.recv().await {
+            Ok(DashboardEvent::Snapshot { .. } | DashboardEvent::Heartbeat) => {}
+            Ok(event) => append(&mut file, event),
+            Err(broadcast::error::RecvError::Lagged(_)) => snapshot_due = true,
+            Err(broadcast::error::RecvError::Closed) => break,
+        }
+    }
+}
+
+fn append(file: &mut File, event: DashboardEvent) {
+    let record = HistoryRecord {
+        at: Utc::now().timestamp_millis(),
+        event,
+    };
+    if let Ok(line) = serde_json::to_string(&record) {
+        let _ = writeln!(file, "{line}");
+    }
+}
+
+/// Recorded sessions, oldest first.
+pub fn list_sessions(dir: &Path) -> Vec<SessionSummary> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut ids: Vec<String> = entries
+        .flatten()
+        .filter_map(|e| {
+            let name = e.file_name().to_string_lossy().to_string();
+            name.strip_suffix(".jsonl").map(str::to_string)
+        })
+        .collect();
+    ids.sort();
+
+    ids.into_iter()
+        .map(|id| {
+            let records = read_records(&session_path(dir, &id));
+            let mut teams: Vec<String> = records
+                .iter()
+                .filter_map(|r| r.event.team_name().map(str::to_string))
+                .collect();
+            teams.sort();
+            teams.dedup();
+            SessionSummary {
+                events: records.len(),
+                started: records.first().map(|r| r.at),
+                ended: records.last().map(|r| r.at),
+                teams,
+                id,
+            }
+        })
+        .collect()
+}
+
+/// The session ID `name` refers to; `latest` is the most recent one.
+/// Names that could reach outside `dir` are rejected.
+pub fn resolve_session(dir: &Path, name: &str) -> Result<String> {
+    if name.is_empty() || name.contains(['/', '\\']) || name.contains("..") {
+        return Err(ForjaError::InvalidArgument(format!(
+            "invalid session name '{name}'"
+        )));
+    }
+    if name == "latest" {
+        return list_sessions(dir)
+            .pop()
+            .map(|s| s.id)
+            .ok_or_else(|| ForjaError::SessionNotFound(name.to_string()));
+    }
+    if session_path(dir, name).is_file() {
+        Ok(name.to_string())
+    } else {
+        Err(ForjaError::SessionNotFound(name.to_string()))
+    }
+}
+
+/// Every event of a session, in the order it was recorded.
+pub fn load(dir: &Path, session: &str) -> Result<Vec<HistoryRecord>> {
+    let session = resolve_session(dir, session)?;
+    Ok(read_records(&session_path(dir, &session)))
+}
+
+/// Lines that don't parse (a torn last write) are skipped.
+fn read_records(path: &Path) -> Vec<HistoryRecord> {
+    let Ok(file) = File::open(path) else {
+        return Vec::new();
+    };
+    BufReader::new(file)
+        .lines()
+        .map_while(|line| line.ok())
+        .filter_map(|line| serde_json::from_str(&line).ok())
+        .collect()
+}
+
+/// Keep the records about `team` (when given) seen between `from` and `to`,
+/// inclusive. Snapshots are kept, cut down to the team.
+pub fn filter(
+    records: Vec<HistoryRecord>,
+    team: Option<&str>,
+    from: Option<i64>,
+    to: Option<i64>,
+) -> Vec<HistoryRecord> {
+    records
+        .into_iter()
+        .filter(|r| from.is_none_or(|from| r.at >= from))
+        .filter(|r| to.is_none_or(|to| r.at <= to))
+        .filter_map(|r| match team {
+            Some(team) => Some(HistoryRecord {
+                at: r.at,
+                event: for_team(r.event, team)?,
+            }),
+            None => Some(r),
+        })
+        .collect()
+}
+
+/// The part of `event` about `team`: a snapshot keeps only its teams, tasks,
+/// messages and alerts, other events are kept or dropped whole.
+fn for_team(event: DashboardEvent, team: &str) -> Option<DashboardEvent> {
+    match event {
+        DashboardEvent::Snapshot {
+            mut teams,
+            mut tasks,
+            mut messages,
+            mut alerts,
+            ..
+        } => {
+            teams.retain(|t| t.name == team);
+            tasks.retain(|g| g.team_name == team);
+            messages.retain(|g| g.team_name == team);
+            alerts.retain(|a| a.team_name == team);
+            Some(DashboardEvent::Snapshot {
+                teams,
+                tasks,
+                messages,
+                plans: Vec::new(),
+                alerts,
+                sources: Vec::new(),
+            })
+        }
+        event => (event.team_name() == Some(team)).then_some(event),
+    }
+}
+
+/// How the dashboard stood just before `from`: the records up to then folded
+/// into an empty state, as a snapshot cut down to `team` when given.
+pub async fn state_at(
+    records: &[HistoryRecord],
+    team: Option<&str>,
+    from: Option<i64>,
+) -> DashboardEvent {
+    let state = DashboardState::new();
+    if let Some(from) = from {
+        for record in records.iter().take_while(|r| r.at < from) {
+            state.replay(record.event.clone()).await;
+        }
+    }
+    let snapshot = state.snapshot().await;
+    team.and_then(|team| for_team(snapshot.clone(), team))
+        .unwrap_or(snapshot)
+}
+
+/// A `from`/`to` bound: unix milliseconds or an RFC 3339 timestamp.
+pub fn parse_time(value: &str) -> Result<i64> {
+    if let Ok(ms) = value.parse::<i64>() {
+        return Ok(ms);
+    }
+    DateTime::parse_from_rfc3339(value)
+        .map(|t| t.timestamp_millis())
+        .map_err(|_| {
+            ForjaError::InvalidArgument(format!(
+                "'{value}' is not a unix timestamp in milliseconds or an RFC 3339 time"
+            ))
+        })
+}
+
+/// How long to wait before replaying an event recorded `gap_ms` after the
+/// previous one, at `speed` times real time.
+pub fn replay_delay(gap_ms: i64, speed: f64) -> Duration {
+    // A zero or tiny speed overflows the conversion
+    Duration::try_from_secs_f64(gap_ms.max(0) as f64 / 1000.0 / speed)
+        .unwrap_or(MAX_REPLAY_GAP)
+        .min(MAX_REPLAY_GAP)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::monitor::events::{PlanSnapshot, TeamSnapshot};
+    use forja_core::models::usage::TokenUsage;
+    use std::sync::Arc;
+    use tempfile::TempDir;
+
+    fn plan(id: &str) -> PlanSnapshot {
+        PlanSnapshot {
+            plan_id: id.to_string(),
+            task: String::new(),
+            profile: String::new(),
+            phases_completed: 0,
+            phases_total: 1,
+            usage: TokenUsage::default(),
+        }
+    }
+
+    fn deleted(team: &str, at: i64) -> HistoryRecord {
+        HistoryRecord {
+            at,
+            event: DashboardEvent::TeamDeleted {
+                team_name: team.to_string(),
+            },
+        }
+    }
+
+    #[tokio::test]
+    async fn recorded_sessions_load_back() {
+        let dir = TempDir::new().unwrap();
+        let (id, file) = start_session(dir.path()).unwrap();
+        let (second, _) = start_session(dir.path()).unwrap();
+        assert_ne!(id, second);
+
+        let state = Arc::new(DashboardState::new());
+        let recorder = tokio::spawn(record(Arc::downgrade(&state), state.subscribe(), file));
+        tokio::task::yield_now().await;
+        state.tx.send(DashboardEvent::Heartbeat).unwrap();
+        state.remove_team("night-shift").await;
+        drop(state);
+        recorder.await.unwrap();
+
+        let records = load(dir.path(), &id).unwrap();
+        assert_eq!(records.len(), 2);
+        assert!(matches!(records[0].event, DashboardEvent::Snapshot { .. }));
+        assert_eq!(records[1].event.team_name(), Some("night-shift"));
+
+        let sessions = list_sessions(dir.path());
+        assert_eq!(sessions.len(), 2);
+        assert_eq!(sessions[0].teams, ["night-shift"]);
+        assert_eq!(resolve_session(dir.path(), "latest").unwrap(), second);
+        assert!(matches!(
+            load(dir.path(), "nope"),
+            Err(ForjaError::SessionNotFound(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn recorder_snapshots_after_falling_behind() {
+        let dir = TempDir::new().unwrap();
+        let (id, file) = start_session(dir.path()).unwrap();
+        let state = Arc::new(DashboardState::new());
+        let rx = state.subscribe();
+        // More events than the channel holds before the recorder runs at all
+        for n in 0..300 {
+            state.put_plan(plan(&format!("plan-{n}"))).await;
+        }
+        let recorder = tokio::spawn(record(Arc::downgrade(&state), rx, file));
+        tokio::task::yield_now().await;
+        drop(state);
+        recorder.await.unwrap();
+
+        let records = load(dir.path(), &id).unwrap();
+        let snapshots: Vec<usize> = records
+            .iter()
+            .filter_map(|r| match &r.event {
+                DashboardEvent::Snapshot { plans, .. } => Some(plans.len()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(snapshots, [300, 300]);
+    }
+
+    #[tokio::test]
+    async fn replay_starts_from_the_state_at_from() {
+        let team = |name: &str| TeamSnapshot {
+            name: name.to_string(),
+            description: String::new(),
+            created_at: 0,
+            members: Vec::new(),
+            source: None,
+        };
+        let records = vec![
+            HistoryRecord {
+                at: 100,
+                event: DashboardEvent::Snapshot {
+                    teams: vec![team("a"), team("b")],
+                    tasks: Vec::new(),
+                    messages: Vec::new(),
+                    plans: vec![plan("p")],
+                    alerts: Vec::new(),
+                    sources: Vec::new(),
+                },
+            },
+            HistoryRecord {
+                at: 200,
+                event: DashboardEvent::TeamUpdated { team: team("c") },
+            },
+            deleted("b", 300),
+            deleted("a", 400),
+        ];
+        let names = |event: DashboardEvent| match event {
+            DashboardEvent::Snapshot { teams, plans, .. } => {
+                let mut names: Vec<String> = teams.into_iter().map(|t| t.name).collect();
+                names.sort();
+                (names, plans.len())
+            }
+            _ => panic!("not a snapshot"),
+        };
+
+        let start = state_at(&records, None, Some(350)).await;
+        assert_eq!(names(start), (vec!["a".to_string(), "c".to_string()], 1));
+        let start = state_at(&records, Some("a"), Some(350)).await;
+        assert_eq!(names(start), (vec!["a".to_string()], 0));
+        let start = state_at(&records, None, None).await;
+        assert_eq!(names(start), (Vec::new(), 0));
+
+        let kept = filter(records, Some("b"), None, None);
+        assert_eq!(kept.iter().map(|r| r.at).collect::<Vec<_>>(), [100, 300]);
+        assert_eq!(names(kept[0].event.clone()), (vec!["b".to_string()], 0));
+    }
+
+    #[test]
+    fn session_names_outside_the_history_dir_are_rejected() {
+        let root = TempDir::new().unwrap();
+        let dir = root.path().join("history");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(root.path().join("secret.jsonl"), "").unwrap();
+
+        for name in ["../secret", "..", "a/b", "a\\b", ""] {
+            assert!(
+                matches!(load(&dir, name), Err(ForjaError::InvalidArgument(_))),
+                "{name:?} was accepted"
+            );
+        }
+    }
+
+    #[test]
+    fn filter_by_team_and_window() {
+        let records = vec![deleted("a", 100), deleted("b", 200), deleted("a", 300)];
+        let kept = filter(records.clone(), Some("a"), None, None);
+        assert_eq!(kept.iter().map(|r| r.at).collect::<Vec<_>>(), [100, 300]);
+        let kept = filter(records, None, Some(150), Some(300));
+        assert_eq!(kept.iter().map(|r| r.at).collect::<Vec<_>>(), [200, 300]);
+    }
+
+    #[test]
+    fn parse_time_and_replay_delay() {
+        assert_eq!(parse_time("1700000000000").unwrap(), 1_700_000_000_000);
+        assert_eq!(
+            parse_time("2026-10-18T02:00:00Z").unwrap(),
+            1_792_288_800_000
+        );
+        assert!(parse_time("yesterday").is_err());
+
+        assert_eq!(replay_delay(2000, 2.0), Duration::from_secs(1));
+        assert_eq!(replay_delay(3_600_000, 10.0), MAX_REPLAY_GAP);
+        assert_eq!(replay_delay(-5, 1.0), Duration::ZERO);
+        assert_eq!(replay_delay(1_000, 0.0), MAX_REPLAY_GAP);
+        assert_eq!(replay_delay(1_000, f64::MIN_POSITIVE), MAX_REPLAY_GAP);
+    }
+}
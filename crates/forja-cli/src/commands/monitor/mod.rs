@@ -1,17 +1,68 @@
+mod analyzer;
+mod control;
 mod events;
+mod history;
+mod remote;
 mod server;
 mod state;
+mod tui;
 mod watcher;
 
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
 use std::sync::Arc;
 
 use colored::Colorize;
 
 use forja_core::error::{ForjaError, Result};
+use forja_core::models::config::load_config;
+use forja_core::paths::ForjaPaths;
 
+use crate::output;
+
+use remote::RemoteSource;
 use state::DashboardState;
 
-pub async fn run(port: u16, auto_open: bool) -> Result<()> {
+/// Where and how the dashboard is served.
+pub struct ServeOptions {
+    pub port: u16,
+    pub bind: IpAddr,
+    pub auto_open: bool,
+    /// Shared token the API requires, and that remote sources are asked with.
+    pub token: Option<String>,
+}
+
+impl ServeOptions {
+    /// Refuse to expose the dashboard beyond this machine without a token.
+    /// Tokens travel in URLs and headers, so they're kept to URL-safe characters.
+    fn check(&self) -> Result<()> {
+        let valid = |t: &str| {
+            !t.is_empty()
+                && t.chars()
+                    .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | '~'))
+        };
+        if self.token.as_deref().is_some_and(|t| !valid(t)) {
+            return Err(ForjaError::InvalidArgument(
+                "the monitor token may only use letters, digits and - _ . ~ \
+                 (e.g. `openssl rand -hex 32`)"
+                    .to_string(),
+            ));
+        }
+        // Anyone who can reach the port could read the teams and write to their inboxes
+        if !self.bind.is_loopback() && self.token.is_none() {
+            return Err(ForjaError::InvalidArgument(format!(
+                "listening on {} needs a token: pass --token or set FORJA_MONITOR_TOKEN",
+                self.bind
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Watch the agent teams and show them in the browser dashboard, or in the
+/// terminal when `tui` is set. Teams of the monitors in `sources` are merged in.
+pub async fn run(options: ServeOptions, tui: bool, sources: &[String]) -> Result<()> {
+    options.check()?;
+    let sources = parse_sources(sources)?;
     let home = dirs::home_dir().ok_or(ForjaError::NoHomeDir)?;
     let claude_dir = home.join(".claude");
     let teams_dir = claude_dir.join("teams");
@@ -21,24 +72,179 @@ pub async fn run(port: u16, auto_open: bool) -> Result<()> {
     std::fs::create_dir_all(&teams_dir).ok();
     std::fs::create_dir_all(&tasks_dir).ok();
 
-    let state = Arc::new(DashboardState::new());
+    // Plan checkpoints carry execution progress and spend; optional outside a forja setup
+    let paths = ForjaPaths::resolve().ok();
+    let plans_dir = paths.as_ref().map(|p| p.plans.clone());
+    let settings = paths
+        .and_then(|p| load_config(&p.config))
+        .map(|c| c.monitor)
+        .unwrap_or_default();
+
+    let history_dir = history::history_dir()?;
+    let (session, log) = history::start_session(&history_dir)?;
+    let state = Arc::new(
+        DashboardState::new()
+            .with_session(session.clone())
+            .with_control(control::ClaudeDirs {
+                teams: teams_dir.clone(),
+                tasks: tasks_dir.clone(),
+            })
+            .with_token(options.token.clone()),
+    );
 
     // Initial scan to populate state with existing data
-    state.initial_scan(&teams_dir, &tasks_dir).await;
+    state
+        .initial_scan(&teams_dir, &tasks_dir, plans_dir.as_deref())
+        .await;
+
+    // Record every event from here on, starting from a snapshot of the scan
+    tokio::spawn(history::record(
+        Arc::downgrade(&state),
+        state.subscribe(),
+        log,
+    ));
 
     // Start the file watcher in the background
     let watcher_state = Arc::clone(&state);
     let watcher_teams = teams_dir.clone();
     let watcher_tasks = tasks_dir.clone();
     tokio::spawn(async move {
-        if let Err(e) = watcher::watch(watcher_state, watcher_teams, watcher_tasks).await {
+        if let Err(e) = watcher::watch(watcher_state, watcher_teams, watcher_tasks, plans_dir).await
+        {
             eprintln!("{} File watcher error: {}", "Warning:".yellow().bold(), e);
         }
     });
 
+    // Merge in the teams of other monitors
+    let mut status = format!("Recording: {session}");
+    for source in sources {
+        status.push_str(&format!("\n  Following: {} ({})", source.name, source.url));
+        tokio::spawn(remote::follow(
+            Arc::clone(&state),
+            source,
+            options.token.clone(),
+        ));
+    }
+
+    // Look for stuck teams from now on
+    tokio::spawn(analyzer::run(Arc::clone(&state), settings));
+
+    if tui {
+        return tui::run(state).await;
+    }
+
+    serve(state, &options, "", &status).await
+}
+
+fn parse_sources(values: &[String]) -> Result<Vec<RemoteSource>> {
+    let mut sources: Vec<RemoteSource> = Vec::new();
+    for value in values {
+        let source = RemoteSource::parse(value)?;
+        if sources.iter().any(|s| s.name == source.name) {
+            return Err(ForjaError::InvalidArgument(format!(
+                "two sources are named '{}'; name them with NAME=URL",
+                source.name
+            )));
+        }
+        sources.push(source);
+    }
+    Ok(sources)
+}
+
+/// Replay a recorded session in the dashboard, `speed` times faster than
+/// it happened. Nothing is watched or recorded meanwhile.
+pub async fn replay(
+    session: &str,
+    speed: f64,
+    team: Option<&str>,
+    options: ServeOptions,
+) -> Result<()> {
+    if speed.is_nan() || speed <= 0.0 {
+        return Err(ForjaError::InvalidArgument(format!(
+            "speed must be above 0, got {speed}"
+        )));
+    }
+    options.check()?;
+    let history_dir = history::history_dir()?;
+    let session = history::resolve_session(&history_dir, session)?;
+    let records = history::filter(history::load(&history_dir, &session)?, team, None, None);
+    if records.is_empty() {
+        return Err(ForjaError::Monitor(format!(
+            "session {session} has no events to replay"
+        )));
+    }
+
+    let mut query = format!("?replay={session}&speed={speed}");
+    if let Some(team) = team {
+        query.push_str(&format!("&team={team}"));
+    }
+    let status = format!("Replaying: {session} ({} events, {speed}x)", records.len());
+    let state = DashboardState::new().with_token(options.token.clone());
+    serve(Arc::new(state), &options, &query, &status).await
+}
+
+/// List the sessions recorded under `~/.forja/monitor/`.
+pub fn history() -> Result<()> {
+    let history_dir = history::history_dir()?;
+    let sessions = history::list_sessions(&history_dir);
+    if sessions.is_empty() {
+        println!("No recorded sessions. Every `forja monitor` run records one.");
+        return Ok(());
+    }
+
+    println!("{}", "Recorded sessions".bold());
+    println!();
+    for session in &sessions {
+        let span = match (session.started, session.ended) {
+            (Some(started), Some(ended)) => format_span(started, ended),
+            _ => "empty".to_string(),
+        };
+        println!(
+            "  {} {}  {} events, {}",
+            "●".cyan(),
+            session.id.bold(),
+            session.events,
+            span
+        );
+        if !session.teams.is_empty() {
+            println!("    {}", session.teams.join(", ").dimmed());
+        }
+    }
+    println!();
+    output::print_command_hint(
+        "forja monitor replay <session>",
+        "Replay one in the dashboard (or `latest`)",
+    );
+    Ok(())
+}
+
+fn format_span(started: i64, ended: i64) -> String {
+    let start = chrono::DateTime::from_timestamp_millis(started)
+        .map(|t| t.with_timezone(&chrono::Local).format("%H:%M").to_string())
+        .unwrap_or_default();
+    let minutes = (ended - started) / 60_000;
+    format!("from {start}, {minutes} min")
+}
+
+/// Serve the dashboard, opening it at `query` (empty or starting with `?`).
+async fn serve(
+    state: Arc<DashboardState>,
+    options: &ServeOptions,
+    query: &str,
+    status: &str,
+) -> Result<()> {
+    let addr = SocketAddr::new(options.bind, options.port);
+    let host = if options.bind.is_unspecified() {
+        SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), options.port)
+    } else {
+        addr
+    };
+    let mut url = format!("http://{host}/{query}");
+    if let Some(token) = &options.token {
+        url.push(if query.is_empty() { '?' } else { '&' });
+        url.push_str(&format!("token={token}"));
+    }
     let app = server::create_router(state);
-    let addr = format!("127.0.0.1:{port}");
-    let url = format!("http://{addr}");
 
     println!();
     println!(
@@ -47,10 +253,13 @@ pub async fn run(port: u16, auto_open: bool) -> Result<()> {
         format!("v{}", env!("CARGO_PKG_VERSION")).dimmed()
     );
     println!("  {} {}", "Dashboard:".cyan().bold(), url.bold());
+    println!("  {}", status.dimmed());
     println!("  {}", "Press Ctrl+C to stop".dimmed());
     println!();
 
-    if auto_open && let Err(e) = open::that(&url) {
+    if options.auto_open
+        && let Err(e) = open::that(&url)
+    {
         eprintln!(
             "  {} Could not open browser: {}",
             "Warning:".yellow().bold(),
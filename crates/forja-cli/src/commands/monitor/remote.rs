@@ -0,0 +1,432 @@
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::Duration;
+
+use http_body_util::{BodyExt, Empty};
+use hyper::body::Bytes;
+use hyper::client::conn::http1;
+use hyper::{Request, StatusCode, Uri, header};
+use hyper_util::rt::TokioIo;
+use tokio::net::TcpStream;
+use tokio::time::timeout;
+
+use forja_core::error::{ForjaError, Result};
+
+use super::events::{DashboardEvent, SourceSnapshot};
+use super::state::DashboardState;
+
+/// Wait between attempts to reach a source that failed or dropped.
+pub const RECONNECT_EVERY: Duration = Duration::from_secs(5);
+
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// A monitor sends a keepalive every 15s; a stream silent for longer is dead.
+const SILENCE_LIMIT: Duration = Duration::from_secs(45);
+
+/// Another `forja monitor` whose teams this one shows, as `--source NAME=URL`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RemoteSource {
+    pub name: String,
+    pub url: String,
+    /// `host:port` to connect to.
+    addr: String,
+    authority: String,
+    events_path: String,
+}
+
+impl RemoteSource {
+    /// Parse `NAME=http://host:port` or a bare URL, named after its host.
+    pub fn parse(value: &str) -> Result<Self> {
+        let (name, url) = match value.split_once('=') {
+            Some((name, url)) if !name.contains([':', '/']) => (Some(name), url),
+            _ => (None, value),
+        };
+        let url = url.trim_end_matches('/');
+        let uri: Uri = url
+            .parse()
+            .map_err(|_| ForjaError::InvalidArgument(format!("'{url}' is not a URL")))?;
+        match uri.scheme_str() {
+            Some("http") => {}
+            Some("https") => {
+                return Err(ForjaError::InvalidArgument(format!(
+                    "{url}: https sources aren't supported. Reach the remote monitor over \
+                     http, e.g. through an SSH tunnel"
+                )));
+            }
+            _ => {
+                return Err(ForjaError::InvalidArgument(format!(
+                    "'{url}' is not an http:// URL"
+                )));
+            }
+        }
+        let (Some(host), Some(authority)) = (uri.host(), uri.authority()) else {
+            return Err(ForjaError::InvalidArgument(format!("'{url}' has no host")));
+        };
+
+        let name = name.unwrap_or(host).to_string();
+        if name.is_empty() || name.contains(['@', '/']) {
+            return Err(ForjaError::InvalidArgument(format!(
+                "invalid source name '{name}'"
+            )));
+        }
+        Ok(Self {
+            addr: format!("{host}:{}", uri.port_u16().unwrap_or(80)),
+            authority: authority.to_string(),
+            events_path: format!("{}/api/events", uri.path().trim_end_matches('/')),
+            url: url.to_string(),
+            name,
+        })
+    }
+
+    fn snapshot(&self, connected: bool, error: Option<String>) -> SourceSnapshot {
+        SourceSnapshot {
+            name: self.name.clone(),
+            url: self.url.clone(),
+            connected,
+            error,
+        }
+    }
+}
+
+/// Name a team of `source` apart from local teams of the same name.
+pub fn qualify(name: &str, source: &str) -> String {
+    format!("{name}@{source}")
+}
+
+/// Mirror `source` into `state` for as long as the monitor runs, trying
+/// again every `RECONNECT_EVERY` when it can't be reached or drops.
+pub async fn follow(state: Arc<DashboardState>, source: RemoteSource, token: Option<String>) {
+    state.set_source(source.snapshot(false, None)).await;
+    loop {
+        let error = match stream(&state, &source, token.as_deref()).await {
+            Ok(()) => "the remote monitor closed the stream".to_string(),
+            Err(e) => e,
+        };
+        state.set_source(source.snapshot(false, Some(error))).await;
+        tokio::time::sleep(RECONNECT_EVERY).await;
+    }
+}
+
+/// Read the source's `/api/events` until it ends, applying every event.
+async fn stream(
+    state: &DashboardState,
+    source: &RemoteSource,
+    token: Option<&str>,
+) -> std::result::Result<(), String> {
+    let tcp = timeout(CONNECT_TIMEOUT, TcpStream::connect(&source.addr))
+        .await
+        .map_err(|_| "connection timed out".to_string())?
+        .map_err(|e| e.to_string())?;
+    let (mut sender, connection) = http1::handshake(TokioIo::new(tcp))
+        .await
+        .map_err(|e| e.to_string())?;
+    tokio::spawn(connection);
+
+    let mut request = Request::get(&source.events_path)
+        .header(header::HOST, &source.authority)
+        .header(header::ACCEPT, "text/event-stream");
+    if let Some(token) = token {
+        request = request.header(header::AUTHORIZATION, format!("Bearer {token}"));
+    }
+    let request = request
+        .body(Empty::<Bytes>::new())
+        .map_err(|e| e.to_string())?;
+    let response = sender
+        .send_request(request)
+        .await
+        .map_err(|e| e.to_string())?;
+    match response.status() {
+        StatusCode::OK => {}
+        StatusCode::UNAUTHORIZED => return Err("the remote monitor refused the token".to_string()),
+        status => return Err(format!("the remote monitor answered {status}")),
+    }
+    state.set_source(source.snapshot(true, None)).await;
+
+    let mut body = response.into_body();
+    let mut parser = SseParser::default();
+    loop {
+        let frame = match timeout(SILENCE_LIMIT, body.frame()).await {
+            Err(_) => {
+                return Err(format!("nothing received for {}s", SILENCE_LIMIT.as_secs()));
+            }
+            Ok(None) => return Ok(()),
+            Ok(Some(frame)) => frame.map_err(|e| e.to_string())?,
+        };
+        let Some(chunk) = frame.data_ref() else {
+            continue;
+        };
+        for data in parser.push(chunk) {
+            if let Ok(event) = serde_json::from_str::<DashboardEvent>(&data) {
+                apply(state, &source.name, event).await;
+            }
+        }
+    }
+}
+
+/// Fold an event of `source` into the local state, under qualified team
+/// names. A snapshot (sent on every reconnect) also drops the source's teams
+/// and tasks that went away meanwhile. Alerts aren't taken over: the local
+/// analyzer looks at remote teams like at any other.
+pub async fn apply(state: &DashboardState, source: &str, event: DashboardEvent) {
+    match event {
+        DashboardEvent::Snapshot {
+            teams,
+            tasks,
+            messages,
+            plans,
+            ..
+        } => {
+            let names: HashSet<String> = teams.iter().map(|t| qualify(&t.name, source)).collect();
+            let stale: Vec<String> = state
+                .teams
+                .read()
+                .await
+                .values()
+                .filter(|t| t.source.as_deref() == Some(source) && !names.contains(&t.name))
+                .map(|t| t.name.clone())
+                .collect();
+            for team_name in stale {
+                state.remove_team(&team_name).await;
+            }
+            for mut team in teams {
+                team.name = qualify(&team.name, source);
+                team.source = Some(source.to_string());
+                state.put_team(team).await;
+            }
+
+            for group in tasks {
+                let team_name = qualify(&group.team_name, source);
+                let ids: HashSet<&str> = group.tasks.iter().map(|t| t.id.as_str()).collect();
+                let stale: Vec<String> = state
+                    .tasks
+                    .read()
+                    .await
+                    .get(&team_name)
+                    .into_iter()
+                    .flatten()
+                    .filter(|t| !ids.contains(t.id.as_str()))
+                    .map(|t| t.id.clone())
+                    .collect();
+                for task_id in stale {
+                    state.remove_task(&team_name, &task_id).await;
+                }
+                for task in group.tasks {
+                    state.put_task(&team_name, task).await;
+                }
+            }
+            for group in messages {
+                let team_name = qualify(&group.team_name, source);
+                state
+                    .put_inbox(&team_name, &group.recipient, group.messages)
+                    .await;
+            }
+            for mut plan in plans {
+                plan.plan_id = qualify(&plan.plan_id, source);
+                state.put_plan(plan).await;
+            }
+        }
+        DashboardEvent::TeamUpdated { mut team } => {
+            team.name = qualify(&team.name, source);
+            team.source = Some(source.to_string());
+            state.put_team(team).await;
+        }
+        DashboardEvent::TeamDeleted { team_name } => {
+            state.remove_team(&qualify(&team_name, source)).await;
+        }
+        DashboardEvent::TaskUpdated { team_name, task } => {
+            state.put_task(&qualify(&team_name, source), task).await;
+        }
+        DashboardEvent::TaskDeleted { team_name, task_id } => {
+            state
+                .remove_task(&qualify(&team_name, source), &task_id)
+                .await;
+        }
+        DashboardEvent::MessageReceived {
+            team_name,
+            recipient,
+            message,
+        } => {
+            state
+                .push_message(&qualify(&team_name, source), &recipient, message)
+                .await;
+        }
+        DashboardEvent::PlanUpdated { mut plan } => {
+            plan.plan_id = qualify(&plan.plan_id, source);
+            state.put_plan(plan).await;
+        }
+        DashboardEvent::Alert { .. }
+        | DashboardEvent::AlertCleared { .. }
+        | DashboardEvent::SourceUpdated { .. }
+        | DashboardEvent::Heartbeat => {}
+    }
+}
+
+/// Splits a `text/event-stream` body into the data of its events.
+#[derive(Default)]
+struct SseParser {
+    buffer: Vec<u8>,
+    data: String,
+}
+
+impl SseParser {
+    /// Feed the next chunk; returns the events it completed.
+    fn push(&mut self, chunk: &[u8]) -> Vec<String> {
+        self.buffer.extend_from_slice(chunk);
+        let mut events = Vec::new();
+        while let Some(end) = self.buffer.iter().position(|&b| b == b'\n') {
+            let line: Vec<u8> = self.buffer.drain(..=end).collect();
+            let line = String::from_utf8_lossy(&line);
+            let line = line.trim_end_matches(['\n', '\r']);
+            if line.is_empty() {
+                if !self.data.is_empty() {
+                    events.push(std::mem::take(&mut self.data));
+                }
+            } else if let Some(value) = line.strip_prefix("data:") {
+                if !self.data.is_empty() {
+                    self.data.push('\n');
+                }
+                self.data.push_str(value.strip_prefix(' ').unwrap_or(value));
+            }
+            // `event:`, `id:` and `:` comments (keepalives) carry nothing needed here
+        }
+        events
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::events::{MemberSnapshot, TaskGroupSnapshot, TaskSnapshot, TeamSnapshot};
+    use super::super::server;
+    use super::*;
+
+    fn team(name: &str) -> TeamSnapshot {
+        TeamSnapshot {
+            name: name.to_string(),
+            description: String::new(),
+            created_at: 0,
+            members: vec![MemberSnapshot {
+                name: "coder".to_string(),
+                agent_type: "general-purpose".to_string(),
+                model: "sonnet".to_string(),
+                color: "blue".to_string(),
+            }],
+            source: None,
+        }
+    }
+
+    fn task(id: &str) -> TaskSnapshot {
+        TaskSnapshot {
+            id: id.to_string(),
+            subject: format!("Task {id}"),
+            status: "pending".to_string(),
+            owner: String::new(),
+            active_form: String::new(),
+            blocks: vec![],
+            blocked_by: vec![],
+        }
+    }
+
+    fn snapshot(teams: &[&str], tasks: &[&str]) -> DashboardEvent {
+        DashboardEvent::Snapshot {
+            teams: teams.iter().map(|name| team(name)).collect(),
+            tasks: vec![TaskGroupSnapshot {
+                team_name: "night-shift".to_string(),
+                tasks: tasks.iter().map(|id| task(id)).collect(),
+            }],
+            messages: vec![],
+            plans: vec![],
+            alerts: vec![],
+            sources: vec![],
+        }
+    }
+
+    #[test]
+    fn parse_sources() {
+        let source = RemoteSource::parse("build=http://build-box:3030/").unwrap();
+        assert_eq!(source.name, "build");
+        assert_eq!(source.url, "http://build-box:3030");
+        assert_eq!(source.addr, "build-box:3030");
+        assert_eq!(source.events_path, "/api/events");
+
+        let source = RemoteSource::parse("http://10.0.0.5/monitor").unwrap();
+        assert_eq!(source.name, "10.0.0.5");
+        assert_eq!(source.addr, "10.0.0.5:80");
+        assert_eq!(source.events_path, "/monitor/api/events");
+
+        assert!(RemoteSource::parse("build=https://build-box:3030").is_err());
+        assert!(RemoteSource::parse("build-box:3030").is_err());
+        assert!(RemoteSource::parse("a@b=http://build-box:3030").is_err());
+    }
+
+    #[test]
+    fn sse_parser_handles_split_chunks_and_keepalives() {
+        let mut parser = SseParser::default();
+        assert!(parser.push(b"event: message\ndata: {\"type\":").is_empty());
+        assert_eq!(
+            parser.push(b"\"Heartbeat\"}\r\n\r\n:heartbeat\n\n"),
+            [r#"{"type":"Heartbeat"}"#]
+        );
+        assert_eq!(parser.push(b"data: a\ndata: b\n\n"), ["a\nb"]);
+    }
+
+    #[tokio::test]
+    async fn snapshots_replace_what_the_source_had() {
+        let state = DashboardState::new();
+        apply(
+            &state,
+            "build",
+            snapshot(&["night-shift", "day-shift"], &["1", "2"]),
+        )
+        .await;
+        {
+            let teams = state.teams.read().await;
+            assert_eq!(teams.len(), 2);
+            assert_eq!(teams["night-shift@build"].source.as_deref(), Some("build"));
+        }
+
+        // Reconnected: day-shift ended and task 1 was deleted meanwhile
+        let mut rx = state.subscribe();
+        apply(&state, "build", snapshot(&["night-shift"], &["2"])).await;
+        let teams = state.teams.read().await;
+        assert_eq!(teams.keys().collect::<Vec<_>>(), ["night-shift@build"]);
+        let tasks = state.tasks.read().await;
+        assert_eq!(tasks["night-shift@build"].len(), 1);
+        assert!(matches!(
+            rx.try_recv().unwrap(),
+            DashboardEvent::TeamDeleted { team_name } if team_name == "day-shift@build"
+        ));
+    }
+
+    #[tokio::test]
+    async fn follow_mirrors_a_remote_monitor() {
+        let remote = Arc::new(DashboardState::new().with_token(Some("s3cret".to_string())));
+        remote.put_team(team("night-shift")).await;
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            axum::serve(listener, server::create_router(remote))
+                .await
+                .unwrap();
+        });
+
+        let source = RemoteSource::parse(&format!("build={url}")).unwrap();
+        let state = DashboardState::new();
+        let refused = stream(&state, &source, Some("wrong")).await.unwrap_err();
+        assert!(refused.contains("refused the token"));
+
+        let state = Arc::new(state);
+        tokio::spawn(follow(
+            Arc::clone(&state),
+            source,
+            Some("s3cret".to_string()),
+        ));
+        for _ in 0..100 {
+            if state.teams.read().await.contains_key("night-shift@build") {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        assert!(state.teams.read().await.contains_key("night-shift@build"));
+        assert!(state.sources.read().await["build"].connected);
+    }
+}
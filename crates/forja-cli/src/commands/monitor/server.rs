@@ -2,16 +2,25 @@ use std::convert::Infallible;
 use std::sync::Arc;
 use std::time::Duration;
 
-use axum::Router;
-use axum::extract::State;
-use axum::http::{StatusCode, header};
+use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
+use axum::extract::{Path, Query, Request, State};
+use axum::http::{HeaderMap, StatusCode, Uri, header};
+use axum::middleware::{self, Next};
 use axum::response::sse::{Event, KeepAlive, Sse};
 use axum::response::{Html, IntoResponse, Response};
 use axum::routing::get;
+use axum::{Json, Router};
 use rust_embed::Embed;
+use serde::Deserialize;
+use tokio::sync::mpsc;
 use tokio_stream::StreamExt;
-use tokio_stream::wrappers::BroadcastStream;
+use tokio_stream::wrappers::{BroadcastStream, ReceiverStream};
 
+use forja_core::error::ForjaError;
+
+use super::control;
+use super::events::{DashboardEvent, MessageSnapshot, TaskSnapshot, TeamSnapshot};
+use super::history::{self, HistoryRecord};
 use super::state::DashboardState;
 
 #[derive(Embed)]
@@ -23,9 +32,66 @@ pub fn create_router(state: Arc<DashboardState>) -> Router {
         .route("/", get(index_handler))
         .route("/assets/{*path}", get(asset_handler))
         .route("/api/events", get(sse_handler))
+        .route("/api/sessions", get(sessions_handler))
+        .route("/api/history", get(history_handler))
+        .route("/api/replay", get(replay_handler))
+        .route("/api/teams", get(teams_handler))
+        .route("/api/teams/{name}/tasks", get(team_tasks_handler))
+        .route("/api/teams/{name}/inboxes/{member}", get(inbox_handler))
+        .route("/api/control", get(control_handler))
+        .layer(middleware::from_fn_with_state(
+            Arc::clone(&state),
+            require_token,
+        ))
         .with_state(state)
 }
 
+/// With a token set, `/api/` requests must carry it, as `Authorization:
+/// Bearer <token>` or, for browser EventSource and WebSocket clients that
+/// can't set headers, `?token=`. The page and its assets stay public.
+async fn require_token(
+    State(state): State<Arc<DashboardState>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    if let Some(token) = &state.token
+        && request.uri().path().starts_with("/api/")
+        && !authorized(request.headers(), request.uri(), token)
+    {
+        return (StatusCode::UNAUTHORIZED, "missing or wrong monitor token").into_response();
+    }
+    next.run(request).await
+}
+
+#[derive(Deserialize)]
+struct TokenParam {
+    token: Option<String>,
+}
+
+fn authorized(headers: &HeaderMap, uri: &Uri, token: &str) -> bool {
+    let bearer = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "))
+        .map(str::to_string);
+    let param = Query::<TokenParam>::try_from_uri(uri)
+        .ok()
+        .and_then(|q| q.0.token);
+    bearer
+        .or(param)
+        .is_some_and(|given| same_token(&given, token))
+}
+
+/// Compares every byte, so response times don't tell how much of a guess was right.
+fn same_token(given: &str, token: &str) -> bool {
+    given.len() == token.len()
+        && given
+            .bytes()
+            .zip(token.bytes())
+            .fold(0, |diff, (a, b)| diff | (a ^ b))
+            == 0
+}
+
 async fn index_handler() -> impl IntoResponse {
     match Assets::get("index.html") {
         Some(content) => {
@@ -35,7 +101,7 @@ async fn index_handler() -> impl IntoResponse {
     }
 }
 
-async fn asset_handler(axum::extract::Path(path): axum::extract::Path<String>) -> Response {
+async fn asset_handler(Path(path): Path<String>) -> Response {
     match Assets::get(&path) {
         Some(content) => {
             let mime = mime_guess::from_path(&path)
@@ -76,3 +142,266 @@ async fn sse_handler(
             .text("heartbeat"),
     )
 }
+
+/// Query of `/api/history` and `/api/replay`. `from` and `to` take unix
+/// milliseconds or RFC 3339 times; `speed` only applies to replays.
+#[derive(Debug, Default, Deserialize)]
+struct HistoryParams {
+    session: Option<String>,
+    team: Option<String>,
+    from: Option<String>,
+    to: Option<String>,
+    speed: Option<f64>,
+}
+
+type ApiError = (StatusCode, String);
+
+/// A session's records and the requested window.
+struct HistoryQuery {
+    records: Vec<HistoryRecord>,
+    from: Option<i64>,
+    to: Option<i64>,
+}
+
+/// The records of the requested session, or of the one this dashboard is
+/// recording when none is named.
+fn query_history(state: &DashboardState, params: &HistoryParams) -> Result<HistoryQuery, ApiError> {
+    let bad_request = |e: ForjaError| (StatusCode::BAD_REQUEST, e.to_string());
+    let session = params
+        .session
+        .clone()
+        .or_else(|| state.session.clone())
+        .ok_or((
+            StatusCode::BAD_REQUEST,
+            "no session given and none is being recorded".to_string(),
+        ))?;
+    let from = params
+        .from
+        .as_deref()
+        .map(history::parse_time)
+        .transpose()
+        .map_err(bad_request)?;
+    let to = params
+        .to
+        .as_deref()
+        .map(history::parse_time)
+        .transpose()
+        .map_err(bad_request)?;
+
+    let dir =
+        history::history_dir().map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    let records = history::load(&dir, &session).map_err(|e| match e {
+        ForjaError::SessionNotFound(_) => (StatusCode::NOT_FOUND, e.to_string()),
+        ForjaError::InvalidArgument(_) => (StatusCode::BAD_REQUEST, e.to_string()),
+        e => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()),
+    })?;
+    Ok(HistoryQuery { records, from, to })
+}
+
+async fn sessions_handler() -> Response {
+    match history::history_dir() {
+        Ok(dir) => Json(history::list_sessions(&dir)).into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+    }
+}
+
+async fn history_handler(
+    State(state): State<Arc<DashboardState>>,
+    Query(params): Query<HistoryParams>,
+) -> Response {
+    match query_history(&state, &params) {
+        Ok(query) => Json(history::filter(
+            query.records,
+            params.team.as_deref(),
+            query.from,
+            query.to,
+        ))
+        .into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+/// Re-stream a recorded session as SSE, `speed` times faster than it
+/// happened. A snapshot of the state at `from`, folded from the records
+/// before it, goes first; each event's ID is its recording time and an `end`
+/// event closes the replay.
+async fn replay_handler(
+    State(state): State<Arc<DashboardState>>,
+    Query(params): Query<HistoryParams>,
+) -> Response {
+    let speed = params.speed.unwrap_or(1.0);
+    if speed.is_nan() || speed <= 0.0 {
+        return (StatusCode::BAD_REQUEST, "speed must be above 0").into_response();
+    }
+    let query = match query_history(&state, &params) {
+        Ok(query) => query,
+        Err(e) => return e.into_response(),
+    };
+    let team = params.team.as_deref();
+    let start = history::state_at(&query.records, team, query.from).await;
+    let records = history::filter(query.records, team, query.from, query.to);
+
+    let (tx, rx) = mpsc::channel(16);
+    tokio::spawn(async move {
+        if tx.send(Ok(message(&start))).await.is_err() {
+            return;
+        }
+        let mut prev = records.first().map_or(0, |r| r.at);
+        for record in records {
+            tokio::time::sleep(history::replay_delay(record.at - prev, speed)).await;
+            prev = record.at;
+            let event = message(&record.event).id(record.at.to_string());
+            if tx.send(Ok(event)).await.is_err() {
+                return;
+            }
+        }
+        let _ = tx.send(Ok(Event::default().event("end").data(""))).await;
+    });
+
+    Sse::new(ReceiverStream::<Result<Event, Infallible>>::new(rx))
+        .keep_alive(KeepAlive::new().interval(Duration::from_secs(15)))
+        .into_response()
+}
+
+fn message(event: &DashboardEvent) -> Event {
+    Event::default()
+        .event("message")
+        .data(serde_json::to_string(event).unwrap_or_default())
+}
+
+async fn teams_handler(State(state): State<Arc<DashboardState>>) -> Json<Vec<TeamSnapshot>> {
+    let mut teams: Vec<TeamSnapshot> = state.teams.read().await.values().cloned().collect();
+    teams.sort_by(|a, b| a.name.cmp(&b.name));
+    Json(teams)
+}
+
+/// Tasks of a team, by numeric ID.
+async fn team_tasks_handler(
+    State(state): State<Arc<DashboardState>>,
+    Path(name): Path<String>,
+) -> Response {
+    let tasks = state.tasks.read().await;
+    let Some(tasks) = tasks.get(&name) else {
+        if state.teams.read().await.contains_key(&name) {
+            return Json(Vec::<TaskSnapshot>::new()).into_response();
+        }
+        return team_not_found(&name);
+    };
+    let mut tasks = tasks.clone();
+    tasks.sort_by_key(|t| (t.id.parse::<u64>().unwrap_or(u64::MAX), t.id.clone()));
+    Json(tasks).into_response()
+}
+
+/// Messages in a member's inbox, oldest first.
+async fn inbox_handler(
+    State(state): State<Arc<DashboardState>>,
+    Path((name, member)): Path<(String, String)>,
+) -> Response {
+    let messages = state.messages.read().await;
+    let inbox = messages
+        .get(&name)
+        .and_then(|groups| groups.iter().find(|g| g.recipient == member));
+    match inbox {
+        Some(group) => Json(group.messages.clone()).into_response(),
+        None if state.teams.read().await.contains_key(&name) => {
+            Json(Vec::<MessageSnapshot>::new()).into_response()
+        }
+        None => team_not_found(&name),
+    }
+}
+
+fn team_not_found(name: &str) -> Response {
+    (StatusCode::NOT_FOUND, format!("no team '{name}'")).into_response()
+}
+
+/// WebSocket the dashboard sends `ControlRequest`s on, one JSON text
+/// message each, getting a `ControlReply` back for every one.
+async fn control_handler(
+    ws: WebSocketUpgrade,
+    headers: HeaderMap,
+    State(state): State<Arc<DashboardState>>,
+) -> Response {
+    // A token check already passed proves the page isn't some other site
+    if state.token.is_none() && !local_origin(&headers) {
+        return (
+            StatusCode::FORBIDDEN,
+            "control connections are only accepted from the dashboard itself",
+        )
+            .into_response();
+    }
+    ws.on_upgrade(move |socket| control_socket(socket, state))
+}
+
+async fn control_socket(mut socket: WebSocket, state: Arc<DashboardState>) {
+    while let Some(Ok(message)) = socket.recv().await {
+        let Message::Text(text) = message else {
+            continue;
+        };
+        let reply = control::handle(&state, text.as_str()).await;
+        let reply = serde_json::to_string(&reply).unwrap_or_default();
+        if socket.send(Message::Text(reply.into())).await.is_err() {
+            break;
+        }
+    }
+}
+
+/// Browsers send `Origin` on WebSocket handshakes, so any page could reach a
+/// localhost port; only pages served from this machine may write to inboxes
+/// and tasks. Clients without an `Origin` (scripts, curl) are let through.
+fn local_origin(headers: &HeaderMap) -> bool {
+    let Some(origin) = headers.get(header::ORIGIN) else {
+        return true;
+    };
+    let Some(host) = origin.to_str().ok().and_then(|o| o.strip_prefix("http://")) else {
+        return false;
+    };
+    let host = host.rsplit_once(':').map_or(host, |(host, _)| host);
+    matches!(host, "127.0.0.1" | "localhost" | "[::1]")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+
+    #[test]
+    fn authorized_takes_a_bearer_header_or_query_token() {
+        let uri = |value: &str| value.parse::<Uri>().unwrap();
+        let mut headers = HeaderMap::new();
+        assert!(!authorized(&headers, &uri("/api/events"), "s3cret"));
+        assert!(authorized(
+            &headers,
+            &uri("/api/events?token=s3cret"),
+            "s3cret"
+        ));
+        assert!(!authorized(
+            &headers,
+            &uri("/api/events?token=s3cre"),
+            "s3cret"
+        ));
+
+        headers.insert(
+            header::AUTHORIZATION,
+            HeaderValue::from_static("Bearer s3cret"),
+        );
+        assert!(authorized(&headers, &uri("/api/events"), "s3cret"));
+        headers.insert(header::AUTHORIZATION, HeaderValue::from_static("s3cret"));
+        assert!(!authorized(&headers, &uri("/api/events"), "s3cret"));
+    }
+
+    #[test]
+    fn local_origin_refuses_other_sites() {
+        let origin = |value: &str| {
+            let mut headers = HeaderMap::new();
+            headers.insert(header::ORIGIN, HeaderValue::from_str(value).unwrap());
+            local_origin(&headers)
+        };
+        assert!(local_origin(&HeaderMap::new()));
+        assert!(origin("http://127.0.0.1:3030"));
+        assert!(origin("http://localhost:8080"));
+        assert!(origin("http://[::1]:3030"));
+        assert!(!origin("https://evil.example"));
+        assert!(!origin("http://localhost.evil.example:3030"));
+        assert!(!origin("null"));
+    }
+}
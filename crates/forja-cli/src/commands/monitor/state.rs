@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
@@ -6,10 +6,12 @@ use std::sync::Arc;
 use tokio::sync::{RwLock, broadcast};
 
 use forja_core::models::claude::{ClaudeInboxMessage, ClaudeTask, ClaudeTeamConfig};
+use forja_core::models::plan::{self, ExecutionCheckpoint, PhaseStatus, PlanMetadata};
 
+use super::control::ClaudeDirs;
 use super::events::{
-    DashboardEvent, MemberSnapshot, MessageGroupSnapshot, MessageSnapshot, TaskGroupSnapshot,
-    TaskSnapshot, TeamSnapshot,
+    AlertSnapshot, DashboardEvent, MemberSnapshot, MessageGroupSnapshot, MessageSnapshot,
+    PlanSnapshot, SourceSnapshot, TaskGroupSnapshot, TaskSnapshot, TeamSnapshot,
 };
 
 /// Shared dashboard state, safe for concurrent access.
@@ -20,7 +22,18 @@ pub struct DashboardState {
     pub messages: Arc<RwLock<HashMap<String, Vec<MessageGroupSnapshot>>>>,
     /// Maps team name → task directory name (UUID or human-readable).
     pub team_task_mapping: Arc<RwLock<HashMap<String, String>>>,
+    pub plans: Arc<RwLock<HashMap<String, PlanSnapshot>>>,
+    /// Alerts raised by the analyzer and still holding, by key.
+    pub alerts: Arc<RwLock<BTreeMap<String, AlertSnapshot>>>,
+    /// Remote monitors merged into this one, by name.
+    pub sources: Arc<RwLock<BTreeMap<String, SourceSnapshot>>>,
     pub tx: broadcast::Sender<DashboardEvent>,
+    /// History session this run records to, if any.
+    pub session: Option<String>,
+    /// Where control messages write; `None` makes the dashboard read-only.
+    pub control: Option<ClaudeDirs>,
+    /// Shared token API requests must carry; `None` leaves the API open.
+    pub token: Option<String>,
 }
 
 impl DashboardState {
@@ -31,10 +44,31 @@ impl DashboardState {
             tasks: Arc::new(RwLock::new(HashMap::new())),
             messages: Arc::new(RwLock::new(HashMap::new())),
             team_task_mapping: Arc::new(RwLock::new(HashMap::new())),
+            plans: Arc::new(RwLock::new(HashMap::new())),
+            alerts: Arc::new(RwLock::new(BTreeMap::new())),
+            sources: Arc::new(RwLock::new(BTreeMap::new())),
             tx,
+            session: None,
+            control: None,
+            token: None,
         }
     }
 
+    pub fn with_session(mut self, session: String) -> Self {
+        self.session = Some(session);
+        self
+    }
+
+    pub fn with_control(mut self, dirs: ClaudeDirs) -> Self {
+        self.control = Some(dirs);
+        self
+    }
+
+    pub fn with_token(mut self, token: Option<String>) -> Self {
+        self.token = token;
+        self
+    }
+
     pub fn subscribe(&self) -> broadcast::Receiver<DashboardEvent> {
         self.tx.subscribe()
     }
@@ -44,6 +78,9 @@ impl DashboardState {
         let teams = self.teams.read().await;
         let tasks = self.tasks.read().await;
         let messages = self.messages.read().await;
+        let plans = self.plans.read().await;
+        let alerts = self.alerts.read().await;
+        let sources = self.sources.read().await;
 
         DashboardEvent::Snapshot {
             teams: teams.values().cloned().collect(),
@@ -55,6 +92,9 @@ impl DashboardState {
                 })
                 .collect(),
             messages: messages.values().flatten().cloned().collect(),
+            plans: plans.values().cloned().collect(),
+            alerts: alerts.values().cloned().collect(),
+            sources: sources.values().cloned().collect(),
         }
     }
 
@@ -74,13 +114,9 @@ impl DashboardState {
                     color: m.color.clone(),
                 })
                 .collect(),
+            source: None,
         };
 
-        self.teams
-            .write()
-            .await
-            .insert(config.name.clone(), snapshot.clone());
-
         // Update team→task mapping if we have a leadSessionId
         if !config.lead_session_id.is_empty() {
             self.team_task_mapping
@@ -89,7 +125,17 @@ impl DashboardState {
                 .insert(config.name.clone(), config.lead_session_id.clone());
         }
 
-        let _ = self.tx.send(DashboardEvent::TeamUpdated { team: snapshot });
+        self.put_team(snapshot).await;
+    }
+
+    /// Update (or insert) a team snapshot.
+    pub async fn put_team(&self, team: TeamSnapshot) {
+        self.teams
+            .write()
+            .await
+            .insert(team.name.clone(), team.clone());
+
+        let _ = self.tx.send(DashboardEvent::TeamUpdated { team });
     }
 
     /// Remove a team by name.
@@ -115,20 +161,36 @@ impl DashboardState {
             blocks: task.blocks.clone(),
             blocked_by: task.blocked_by.clone(),
         };
+        self.put_task(team_name, snapshot).await;
+    }
 
+    /// Update (or insert) a task snapshot for a team.
+    pub async fn put_task(&self, team_name: &str, task: TaskSnapshot) {
         let mut tasks = self.tasks.write().await;
         let task_list = tasks.entry(team_name.to_string()).or_default();
 
         // Replace existing task with same ID or append
-        if let Some(pos) = task_list.iter().position(|t| t.id == snapshot.id) {
-            task_list[pos] = snapshot.clone();
+        if let Some(pos) = task_list.iter().position(|t| t.id == task.id) {
+            task_list[pos] = task.clone();
         } else {
-            task_list.push(snapshot.clone());
+            task_list.push(task.clone());
         }
 
         let _ = self.tx.send(DashboardEvent::TaskUpdated {
             team_name: team_name.to_string(),
-            task: snapshot,
+            task,
+        });
+    }
+
+    /// Remove a task of a team by ID.
+    pub async fn remove_task(&self, team_name: &str, task_id: &str) {
+        if let Some(task_list) = self.tasks.write().await.get_mut(team_name) {
+            task_list.retain(|t| t.id != task_id);
+        }
+
+        let _ = self.tx.send(DashboardEvent::TaskDeleted {
+            team_name: team_name.to_string(),
+            task_id: task_id.to_string(),
         });
     }
 
@@ -149,7 +211,16 @@ impl DashboardState {
                 read: m.read,
             })
             .collect();
+        self.put_inbox(team_name, recipient, messages).await;
+    }
 
+    /// Replace a member's inbox, announcing the messages past the ones it had.
+    pub async fn put_inbox(
+        &self,
+        team_name: &str,
+        recipient: &str,
+        messages: Vec<MessageSnapshot>,
+    ) {
         let group = MessageGroupSnapshot {
             team_name: team_name.to_string(),
             recipient: recipient.to_string(),
@@ -185,10 +256,160 @@ impl DashboardState {
         }
     }
 
-    /// Perform initial scan of teams and tasks directories.
-    pub async fn initial_scan(&self, teams_dir: &Path, tasks_dir: &Path) {
+    /// Append a single message to a member's inbox.
+    pub async fn push_message(&self, team_name: &str, recipient: &str, message: MessageSnapshot) {
+        let mut all_messages = self.messages.write().await;
+        let groups = all_messages.entry(team_name.to_string()).or_default();
+        match groups.iter_mut().find(|g| g.recipient == recipient) {
+            Some(group) => group.messages.push(message.clone()),
+            None => groups.push(MessageGroupSnapshot {
+                team_name: team_name.to_string(),
+                recipient: recipient.to_string(),
+                messages: vec![message.clone()],
+            }),
+        }
+
+        let _ = self.tx.send(DashboardEvent::MessageReceived {
+            team_name: team_name.to_string(),
+            recipient: recipient.to_string(),
+            message,
+        });
+    }
+
+    /// Update (or insert) a plan's progress and spend from its checkpoint.
+    pub async fn update_plan(&self, plan: &PlanMetadata, checkpoint: &ExecutionCheckpoint) {
+        let snapshot = PlanSnapshot {
+            plan_id: plan.id.clone(),
+            task: plan.task.clone(),
+            profile: plan.profile.clone(),
+            phases_completed: checkpoint
+                .phases
+                .iter()
+                .filter(|p| p.status == PhaseStatus::Completed)
+                .count(),
+            phases_total: checkpoint.phases.len(),
+            usage: checkpoint.usage,
+        };
+        self.put_plan(snapshot).await;
+    }
+
+    /// Update (or insert) a plan snapshot.
+    pub async fn put_plan(&self, plan: PlanSnapshot) {
+        self.plans
+            .write()
+            .await
+            .insert(plan.plan_id.clone(), plan.clone());
+
+        let _ = self.tx.send(DashboardEvent::PlanUpdated { plan });
+    }
+
+    /// Record the connection state of a remote monitor.
+    pub async fn set_source(&self, source: SourceSnapshot) {
+        let mut sources = self.sources.write().await;
+        if sources.get(&source.name) == Some(&source) {
+            return;
+        }
+        sources.insert(source.name.clone(), source.clone());
+
+        let _ = self.tx.send(DashboardEvent::SourceUpdated { source });
+    }
+
+    /// Fold a recorded event back into the state, as the dashboard applies it.
+    pub async fn replay(&self, event: DashboardEvent) {
+        match event {
+            DashboardEvent::Snapshot {
+                teams,
+                tasks,
+                messages,
+                plans,
+                alerts,
+                sources,
+            } => {
+                *self.teams.write().await =
+                    teams.into_iter().map(|t| (t.name.clone(), t)).collect();
+                *self.tasks.write().await = tasks
+                    .into_iter()
+                    .map(|group| (group.team_name, group.tasks))
+                    .collect();
+                let mut by_team: HashMap<String, Vec<MessageGroupSnapshot>> = HashMap::new();
+                for group in messages {
+                    by_team
+                        .entry(group.team_name.clone())
+                        .or_default()
+                        .push(group);
+                }
+                *self.messages.write().await = by_team;
+                *self.plans.write().await =
+                    plans.into_iter().map(|p| (p.plan_id.clone(), p)).collect();
+                *self.alerts.write().await =
+                    alerts.into_iter().map(|a| (a.key.clone(), a)).collect();
+                *self.sources.write().await =
+                    sources.into_iter().map(|s| (s.name.clone(), s)).collect();
+            }
+            DashboardEvent::TeamUpdated { team } => self.put_team(team).await,
+            DashboardEvent::TeamDeleted { team_name } => self.remove_team(&team_name).await,
+            DashboardEvent::TaskUpdated { team_name, task } => {
+                self.put_task(&team_name, task).await
+            }
+            DashboardEvent::TaskDeleted { team_name, task_id } => {
+                self.remove_task(&team_name, &task_id).await
+            }
+            DashboardEvent::MessageReceived {
+                team_name,
+                recipient,
+                message,
+            } => self.push_message(&team_name, &recipient, message).await,
+            DashboardEvent::PlanUpdated { plan } => self.put_plan(plan).await,
+            DashboardEvent::Alert { alert } => {
+                self.alerts.write().await.insert(alert.key.clone(), alert);
+            }
+            DashboardEvent::AlertCleared { key, .. } => {
+                self.alerts.write().await.remove(&key);
+            }
+            DashboardEvent::SourceUpdated { source } => self.set_source(source).await,
+            DashboardEvent::Heartbeat => {}
+        }
+    }
+
+    /// Replace the active alerts with `current`, announcing the ones that are
+    /// new and clearing the ones that went away. Returns the new ones.
+    pub async fn set_alerts(&self, current: Vec<AlertSnapshot>) -> Vec<AlertSnapshot> {
+        let mut alerts = self.alerts.write().await;
+        let current: BTreeMap<String, AlertSnapshot> =
+            current.into_iter().map(|a| (a.key.clone(), a)).collect();
+
+        for (key, alert) in alerts.iter() {
+            if !current.contains_key(key) {
+                let _ = self.tx.send(DashboardEvent::AlertCleared {
+                    team_name: alert.team_name.clone(),
+                    key: key.clone(),
+                });
+            }
+        }
+        let raised: Vec<AlertSnapshot> = current
+            .values()
+            .filter(|a| !alerts.contains_key(&a.key))
+            .cloned()
+            .collect();
+        for alert in &raised {
+            let _ = self.tx.send(DashboardEvent::Alert {
+                alert: alert.clone(),
+            });
+        }
+
+        *alerts = current;
+        raised
+    }
+
+    /// Perform initial scan of teams, tasks and (when resolvable) plan checkpoints.
+    pub async fn initial_scan(&self, teams_dir: &Path, tasks_dir: &Path, plans_dir: Option<&Path>) {
         self.scan_teams(teams_dir).await;
         self.scan_tasks(tasks_dir).await;
+        if let Some(plans_dir) = plans_dir {
+            for (plan, checkpoint) in plan::load_executions(plans_dir) {
+                self.update_plan(&plan, &checkpoint).await;
+            }
+        }
     }
 
     async fn scan_teams(&self, teams_dir: &Path) {
@@ -0,0 +1,623 @@
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::sync::Arc;
+use std::time::Duration;
+
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph},
+};
+use tokio::runtime::Handle;
+use tokio::sync::broadcast::{Receiver, error::TryRecvError};
+
+use forja_core::error::{ForjaError, Result};
+use forja_tui::terminal::{self, Tui};
+
+use super::events::{
+    AlertSnapshot, DashboardEvent, MessageSnapshot, SourceSnapshot, TaskSnapshot, TeamSnapshot,
+};
+use super::state::DashboardState;
+
+/// How long to wait for a key before redrawing with the events received meanwhile.
+const TICK: Duration = Duration::from_millis(200);
+
+/// Inbox messages kept for display, across all teams.
+const INBOX_LIMIT: usize = 500;
+
+struct InboxEntry {
+    team_name: String,
+    recipient: String,
+    message: MessageSnapshot,
+}
+
+/// What the terminal dashboard shows, kept up to date from `DashboardEvent`s
+/// the same way the web client is.
+pub struct MonitorApp {
+    teams: BTreeMap<String, TeamSnapshot>,
+    tasks: HashMap<String, Vec<TaskSnapshot>>,
+    /// Oldest first.
+    inbox: VecDeque<InboxEntry>,
+    alerts: BTreeMap<String, AlertSnapshot>,
+    sources: BTreeMap<String, SourceSnapshot>,
+    pub selected: usize,
+    pub should_quit: bool,
+}
+
+impl MonitorApp {
+    pub fn new() -> Self {
+        Self {
+            teams: BTreeMap::new(),
+            tasks: HashMap::new(),
+            inbox: VecDeque::new(),
+            alerts: BTreeMap::new(),
+            sources: BTreeMap::new(),
+            selected: 0,
+            should_quit: false,
+        }
+    }
+
+    pub fn apply(&mut self, event: DashboardEvent) {
+        match event {
+            DashboardEvent::Snapshot {
+                teams,
+                tasks,
+                messages,
+                alerts,
+                sources,
+                ..
+            } => {
+                self.alerts = alerts.into_iter().map(|a| (a.key.clone(), a)).collect();
+                self.sources = sources.into_iter().map(|s| (s.name.clone(), s)).collect();
+                self.teams = teams.into_iter().map(|t| (t.name.clone(), t)).collect();
+                self.tasks = tasks
+                    .into_iter()
+                    .map(|group| (group.team_name, group.tasks))
+                    .collect();
+                let mut inbox: Vec<InboxEntry> = messages
+                    .into_iter()
+                    .flat_map(|group| {
+                        group.messages.into_iter().map(move |message| InboxEntry {
+                            team_name: group.team_name.clone(),
+                            recipient: group.recipient.clone(),
+                            message,
+                        })
+                    })
+                    .collect();
+                inbox.sort_by(|a, b| a.message.timestamp.cmp(&b.message.timestamp));
+                self.inbox = inbox.into();
+                self.trim_inbox();
+            }
+            DashboardEvent::TeamUpdated { team } => {
+                self.teams.insert(team.name.clone(), team);
+            }
+            DashboardEvent::TeamDeleted { team_name } => {
+                self.teams.remove(&team_name);
+                self.tasks.remove(&team_name);
+                self.inbox.retain(|e| e.team_name != team_name);
+                self.alerts.retain(|_, a| a.team_name != team_name);
+            }
+            DashboardEvent::TaskUpdated { team_name, task } => {
+                let tasks = self.tasks.entry(team_name).or_default();
+                match tasks.iter_mut().find(|t| t.id == task.id) {
+                    Some(existing) => *existing = task,
+                    None => tasks.push(task),
+                }
+            }
+            DashboardEvent::TaskDeleted { team_name, task_id } => {
+                if let Some(tasks) = self.tasks.get_mut(&team_name) {
+                    tasks.retain(|t| t.id != task_id);
+                }
+            }
+            DashboardEvent::MessageReceived {
+                team_name,
+                recipient,
+                message,
+            } => {
+                self.inbox.push_back(InboxEntry {
+                    team_name,
+                    recipient,
+                    message,
+                });
+                self.trim_inbox();
+            }
+            DashboardEvent::Alert { alert } => {
+                self.alerts.insert(alert.key.clone(), alert);
+            }
+            DashboardEvent::AlertCleared { key, .. } => {
+                self.alerts.remove(&key);
+            }
+            DashboardEvent::SourceUpdated { source } => {
+                self.sources.insert(source.name.clone(), source);
+            }
+            DashboardEvent::PlanUpdated { .. } | DashboardEvent::Heartbeat => {}
+        }
+        self.selected = self.selected.min(self.teams.len().saturating_sub(1));
+    }
+
+    fn trim_inbox(&mut self) {
+        while self.inbox.len() > INBOX_LIMIT {
+            self.inbox.pop_front();
+        }
+    }
+
+    fn selected_team(&self) -> Option<&TeamSnapshot> {
+        self.teams.values().nth(self.selected)
+    }
+
+    /// Whether the team's data is live: local, or from a connected source.
+    fn is_live(&self, team: &TeamSnapshot) -> bool {
+        team.source
+            .as_ref()
+            .is_none_or(|name| self.sources.get(name).is_some_and(|s| s.connected))
+    }
+
+    fn team_alerts(&self) -> Vec<&AlertSnapshot> {
+        let team = self.selected_team().map(|t| t.name.as_str());
+        self.alerts
+            .values()
+            .filter(|a| Some(a.team_name.as_str()) == team)
+            .collect()
+    }
+
+    /// Tasks of the selected team with `status`, by numeric ID.
+    fn tasks_with_status(&self, status: &str) -> Vec<&TaskSnapshot> {
+        let Some(team) = self.selected_team() else {
+            return Vec::new();
+        };
+        let mut tasks: Vec<&TaskSnapshot> = self
+            .tasks
+            .get(&team.name)
+            .into_iter()
+            .flatten()
+            .filter(|t| t.status == status)
+            .collect();
+        tasks.sort_by_key(|t| (t.id.parse::<u64>().unwrap_or(u64::MAX), t.id.clone()));
+        tasks
+    }
+
+    pub fn select_next(&mut self) {
+        if !self.teams.is_empty() {
+            self.selected = (self.selected + 1) % self.teams.len();
+        }
+    }
+
+    pub fn select_prev(&mut self) {
+        if !self.teams.is_empty() {
+            self.selected = self.selected.checked_sub(1).unwrap_or(self.teams.len() - 1);
+        }
+    }
+}
+
+/// Render the dashboard in the terminal until the user quits. The loop runs
+/// on a blocking thread, applying state events between key polls.
+pub async fn run(state: Arc<DashboardState>) -> Result<()> {
+    let mut rx = state.subscribe();
+    let snapshot = state.snapshot().await;
+    let handle = Handle::current();
+
+    tokio::task::spawn_blocking(move || {
+        let mut app = MonitorApp::new();
+        app.apply(snapshot);
+
+        let mut terminal = terminal::enter("forja monitor")?;
+        let result = event_loop(&mut terminal, &mut app, &mut rx, &state, &handle);
+        terminal::restore(&mut terminal);
+        result
+    })
+    .await
+    .map_err(|e| ForjaError::Monitor(format!("TUI stopped unexpectedly: {e}")))?
+}
+
+fn event_loop(
+    terminal: &mut Tui,
+    app: &mut MonitorApp,
+    rx: &mut Receiver<DashboardEvent>,
+    state: &DashboardState,
+    handle: &Handle,
+) -> Result<()> {
+    loop {
+        loop {
+            match rx.try_recv() {
+                Ok(event) => app.apply(event),
+                // Missed events: start over from the current state
+                Err(TryRecvError::Lagged(_)) => app.apply(handle.block_on(state.snapshot())),
+                Err(TryRecvError::Empty | TryRecvError::Closed) => break,
+            }
+        }
+
+        terminal
+            .draw(|frame| render(frame, app))
+            .map_err(|e| ForjaError::Dialoguer(format!("draw: {e}")))?;
+
+        if event::poll(TICK).map_err(ForjaError::Io)?
+            && let Event::Key(key) = event::read().map_err(ForjaError::Io)?
+        {
+            handle_key(app, key);
+        }
+
+        if app.should_quit {
+            return Ok(());
+        }
+    }
+}
+
+pub fn handle_key(app: &mut MonitorApp, key: KeyEvent) {
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('q') => app.should_quit = true,
+        KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            app.should_quit = true;
+        }
+        KeyCode::Down | KeyCode::Tab | KeyCode::Char('j') => app.select_next(),
+        KeyCode::Up | KeyCode::BackTab | KeyCode::Char('k') => app.select_prev(),
+        _ => {}
+    }
+}
+
+pub fn render(frame: &mut Frame, app: &MonitorApp) {
+    let alerts = app.team_alerts();
+    let alerts_height = if alerts.is_empty() {
+        0
+    } else {
+        alerts.len().min(4) as u16 + 2
+    };
+    let rows = Layout::vertical([
+        Constraint::Length(1),             // title
+        Constraint::Min(8),                // teams + task board
+        Constraint::Length(alerts_height), // alerts of the selected team
+        Constraint::Percentage(30),        // inbox
+        Constraint::Length(1),             // help bar
+    ])
+    .split(frame.area());
+
+    let mut title = vec![
+        Span::styled(
+            " forja monitor ",
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(
+            format!("{} teams", app.teams.len()),
+            Style::default().fg(Color::DarkGray),
+        ),
+    ];
+    for source in app.sources.values() {
+        let color = if source.connected {
+            Color::Green
+        } else {
+            Color::Red
+        };
+        title.push(Span::styled(
+            format!("  ● {}", source.name),
+            Style::default().fg(color),
+        ));
+    }
+    if !app.alerts.is_empty() {
+        title.push(Span::styled(
+            format!("  {} alerts", app.alerts.len()),
+            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+        ));
+    }
+    frame.render_widget(Paragraph::new(Line::from(title)), rows[0]);
+
+    let columns = Layout::horizontal([Constraint::Length(30), Constraint::Min(30)]).split(rows[1]);
+    render_teams(frame, app, columns[0]);
+    render_board(frame, app, columns[1]);
+    if !alerts.is_empty() {
+        let lines: Vec<Line> = alerts
+            .iter()
+            .map(|a| {
+                Line::from(Span::styled(
+                    format!("⚠ {}", a.message),
+                    Style::default().fg(Color::Red),
+                ))
+            })
+            .collect();
+        let block = panel(" Alerts ").border_style(Style::default().fg(Color::Red));
+        frame.render_widget(Paragraph::new(lines).block(block), rows[2]);
+    }
+    render_inbox(frame, app, rows[3]);
+
+    let help = Paragraph::new(Line::from(vec![
+        Span::styled(" ↑/↓", Style::default().fg(Color::Cyan)),
+        Span::raw(": team  "),
+        Span::styled("q", Style::default().fg(Color::Red)),
+        Span::raw(": quit"),
+    ]));
+    frame.render_widget(help, rows[4]);
+}
+
+fn render_teams(frame: &mut Frame, app: &MonitorApp, area: Rect) {
+    let parts =
+        Layout::vertical([Constraint::Percentage(50), Constraint::Percentage(50)]).split(area);
+
+    let items: Vec<ListItem> = app
+        .teams
+        .values()
+        .map(|team| {
+            let style = if app.is_live(team) {
+                Style::default()
+            } else {
+                Style::default().fg(Color::DarkGray)
+            };
+            ListItem::new(Line::from(Span::styled(team.name.as_str(), style)))
+        })
+        .collect();
+    let teams = List::new(items)
+        .block(panel(" Teams "))
+        .highlight_style(Style::default().bg(Color::DarkGray));
+    let mut state = ListState::default().with_selected(Some(app.selected));
+    frame.render_stateful_widget(teams, parts[0], &mut state);
+
+    let members: Vec<ListItem> = app
+        .selected_team()
+        .map(|team| team.members.as_slice())
+        .unwrap_or_default()
+        .iter()
+        .map(|m| {
+            ListItem::new(vec![
+                Line::from(Span::styled(
+                    format!("● {}", m.name),
+                    Style::default().fg(member_color(&m.color)),
+                )),
+                Line::from(Span::styled(
+                    format!("  {} · {}", m.agent_type, m.model),
+                    Style::default().fg(Color::DarkGray),
+                )),
+            ])
+        })
+        .collect();
+    frame.render_widget(List::new(members).block(panel(" Members ")), parts[1]);
+}
+
+fn render_board(frame: &mut Frame, app: &MonitorApp, area: Rect) {
+    let columns = Layout::horizontal([
+        Constraint::Ratio(1, 3),
+        Constraint::Ratio(1, 3),
+        Constraint::Ratio(1, 3),
+    ])
+    .split(area);
+
+    let lanes = [
+        ("pending", " Pending ", Color::DarkGray),
+        ("in_progress", " In progress ", Color::Yellow),
+        ("completed", " Completed ", Color::Green),
+    ];
+    for ((status, title, color), column) in lanes.into_iter().zip(columns.iter()) {
+        let items: Vec<ListItem> = app
+            .tasks_with_status(status)
+            .into_iter()
+            .map(task_item)
+            .collect();
+        let block = panel(title).border_style(Style::default().fg(color));
+        frame.render_widget(List::new(items).block(block), *column);
+    }
+}
+
+fn task_item(task: &TaskSnapshot) -> ListItem<'_> {
+    let mut lines = vec![Line::from(vec![
+        Span::styled(
+            format!("#{} ", task.id),
+            Style::default().fg(Color::DarkGray),
+        ),
+        Span::styled(
+            task.subject.as_str(),
+            Style::default().add_modifier(Modifier::BOLD),
+        ),
+    ])];
+    if task.status == "in_progress" && !task.active_form.is_empty() {
+        lines.push(Line::from(Span::styled(
+            format!("  {}", task.active_form),
+            Style::default().fg(Color::Yellow),
+        )));
+    }
+    if !task.owner.is_empty() {
+        lines.push(Line::from(Span::styled(
+            format!("  @{}", task.owner),
+            Style::default().fg(Color::Cyan),
+        )));
+    }
+    if !task.blocked_by.is_empty() {
+        lines.push(Line::from(Span::styled(
+            format!("  blocked by {}", task_refs(&task.blocked_by)),
+            Style::default().fg(Color::Red),
+        )));
+    }
+    if !task.blocks.is_empty() {
+        lines.push(Line::from(Span::styled(
+            format!("  blocks {}", task_refs(&task.blocks)),
+            Style::default().fg(Color::DarkGray),
+        )));
+    }
+    ListItem::new(lines)
+}
+
+fn task_refs(ids: &[String]) -> String {
+    ids.iter()
+        .map(|id| format!("#{id}"))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn render_inbox(frame: &mut Frame, app: &MonitorApp, area: Rect) {
+    let team = app.selected_team().map(|t| t.name.as_str());
+    let entries: Vec<&InboxEntry> = app
+        .inbox
+        .iter()
+        .filter(|e| Some(e.team_name.as_str()) == team)
+        .collect();
+    // Newest at the bottom, keeping as many as fit
+    let visible = area.height.saturating_sub(2) as usize;
+    let skip = entries.len().saturating_sub(visible);
+
+    let lines: Vec<Line> = entries[skip..]
+        .iter()
+        .map(|entry| {
+            let text = entry.message.text.lines().next().unwrap_or_default();
+            Line::from(vec![
+                Span::styled(
+                    format!("{} ", clock(&entry.message.timestamp)),
+                    Style::default().fg(Color::DarkGray),
+                ),
+                Span::styled(
+                    entry.message.from.as_str(),
+                    Style::default().fg(member_color(&entry.message.color)),
+                ),
+                Span::styled(
+                    format!(" → {}: ", entry.recipient),
+                    Style::default().fg(Color::DarkGray),
+                ),
+                Span::raw(text),
+            ])
+        })
+        .collect();
+    frame.render_widget(Paragraph::new(lines).block(panel(" Inbox ")), area);
+}
+
+/// `HH:MM:SS` out of an ISO 8601 timestamp.
+fn clock(timestamp: &str) -> &str {
+    timestamp
+        .split_once('T')
+        .and_then(|(_, time)| time.get(..8))
+        .unwrap_or(timestamp)
+}
+
+/// Claude Code names member colors (`blue`, `green`, ...); unknown ones render white.
+fn member_color(name: &str) -> Color {
+    name.parse().unwrap_or(Color::White)
+}
+
+fn panel(title: &str) -> Block<'_> {
+    Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::DarkGray))
+        .title(title)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::monitor::events::{MemberSnapshot, MessageGroupSnapshot};
+    use ratatui::{Terminal, backend::TestBackend};
+
+    fn task(id: &str, status: &str, blocked_by: &[&str]) -> TaskSnapshot {
+        TaskSnapshot {
+            id: id.to_string(),
+            subject: format!("Task {id}"),
+            status: status.to_string(),
+            owner: "coder".to_string(),
+            active_form: String::new(),
+            blocks: vec![],
+            blocked_by: blocked_by.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+
+    fn message(from: &str, text: &str, timestamp: &str) -> MessageSnapshot {
+        MessageSnapshot {
+            from: from.to_string(),
+            text: text.to_string(),
+            timestamp: timestamp.to_string(),
+            color: "blue".to_string(),
+            read: false,
+        }
+    }
+
+    fn snapshot() -> DashboardEvent {
+        DashboardEvent::Snapshot {
+            teams: vec![TeamSnapshot {
+                name: "night-shift".to_string(),
+                description: String::new(),
+                created_at: 0,
+                members: vec![MemberSnapshot {
+                    name: "coder".to_string(),
+                    agent_type: "general-purpose".to_string(),
+                    model: "sonnet".to_string(),
+                    color: "blue".to_string(),
+                }],
+                source: None,
+            }],
+            tasks: vec![],
+            messages: vec![MessageGroupSnapshot {
+                team_name: "night-shift".to_string(),
+                recipient: "team-lead".to_string(),
+                messages: vec![message("coder", "done with #1", "2026-10-18T02:15:00Z")],
+            }],
+            plans: vec![],
+            alerts: vec![],
+            sources: vec![],
+        }
+    }
+
+    #[test]
+    fn apply_tracks_tasks_and_messages_per_team() {
+        let mut app = MonitorApp::new();
+        app.apply(snapshot());
+        app.apply(DashboardEvent::TaskUpdated {
+            team_name: "night-shift".to_string(),
+            task: task("2", "pending", &["1"]),
+        });
+        app.apply(DashboardEvent::TaskUpdated {
+            team_name: "night-shift".to_string(),
+            task: task("1", "in_progress", &[]),
+        });
+        app.apply(DashboardEvent::TaskUpdated {
+            team_name: "night-shift".to_string(),
+            task: task("1", "completed", &[]),
+        });
+        app.apply(DashboardEvent::MessageReceived {
+            team_name: "night-shift".to_string(),
+            recipient: "team-lead".to_string(),
+            message: message("coder", "starting #2", "2026-10-18T02:16:00Z"),
+        });
+
+        assert_eq!(app.tasks_with_status("completed").len(), 1);
+        assert_eq!(app.tasks_with_status("pending")[0].blocked_by, ["1"]);
+        assert!(app.tasks_with_status("in_progress").is_empty());
+        assert_eq!(app.inbox.len(), 2);
+
+        app.apply(DashboardEvent::TeamDeleted {
+            team_name: "night-shift".to_string(),
+        });
+        assert!(app.selected_team().is_none());
+        assert!(app.inbox.is_empty());
+    }
+
+    #[test]
+    fn render_shows_board_and_inbox() {
+        let mut app = MonitorApp::new();
+        app.apply(snapshot());
+        app.apply(DashboardEvent::TaskUpdated {
+            team_name: "night-shift".to_string(),
+            task: task("2", "pending", &["1"]),
+        });
+
+        let mut terminal = Terminal::new(TestBackend::new(120, 30)).unwrap();
+        terminal.draw(|frame| render(frame, &app)).unwrap();
+        let screen: String = terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect();
+
+        assert!(screen.contains("night-shift"));
+        assert!(screen.contains("general-purpose · sonnet"));
+        assert!(screen.contains("#2 Task 2"));
+        assert!(screen.contains("blocked by #1"));
+        assert!(screen.contains("02:15:00 coder → team-lead: done with #1"));
+    }
+
+    #[test]
+    fn keys_cycle_teams_and_quit() {
+        let mut app = MonitorApp::new();
+        app.apply(snapshot());
+        handle_key(&mut app, KeyEvent::from(KeyCode::Down));
+        assert_eq!(app.selected, 0);
+        handle_key(&mut app, KeyEvent::from(KeyCode::Char('q')));
+        assert!(app.should_quit);
+    }
+}
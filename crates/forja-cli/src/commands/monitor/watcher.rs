@@ -7,6 +7,7 @@ use notify_debouncer_mini::{DebouncedEventKind, new_debouncer};
 use tokio::sync::mpsc;
 
 use forja_core::models::claude::{ClaudeInboxMessage, ClaudeTask, ClaudeTeamConfig};
+use forja_core::models::plan::{ExecutionCheckpoint, PlanMetadata};
 
 use super::state::DashboardState;
 
@@ -24,6 +25,8 @@ enum FileChange {
     TaskUpdate { task_dir: String, path: PathBuf },
     /// A team directory was deleted.
     TeamDeleted { team_name: String },
+    /// A plan's execution checkpoint was written.
+    PlanCheckpoint { plan_id: String, path: PathBuf },
 }
 
 /// Classify a filesystem event path into a meaningful `FileChange`.
@@ -33,12 +36,18 @@ enum FileChange {
 ///   teams/<name>/inboxes/<member>.json → InboxUpdate
 ///   tasks/<dir>/<n>.json              → TaskUpdate
 ///   teams/<name>/ (removed)           → TeamDeleted
+///   plans/<id>.checkpoint.json        → PlanCheckpoint
 ///
 /// TODO(daniel): This is the classify_event function from the plan.
 /// The design choice here is: we match on path components relative to
 /// the watched root (~/.claude/). Corrupted files are handled downstream
 /// by read_json returning None. We only classify .json files.
-fn classify_event(path: &Path, teams_dir: &Path, tasks_dir: &Path) -> Option<FileChange> {
+fn classify_event(
+    path: &Path,
+    teams_dir: &Path,
+    tasks_dir: &Path,
+    plans_dir: Option<&Path>,
+) -> Option<FileChange> {
     // Check if this path is under teams/
     if let Ok(rel) = path.strip_prefix(teams_dir) {
         let components: Vec<&str> = rel
@@ -84,6 +93,17 @@ fn classify_event(path: &Path, teams_dir: &Path, tasks_dir: &Path) -> Option<Fil
             }
             _ => None,
         }
+    }
+    // Check if this path is a plan checkpoint
+    else if let Some(plans_dir) = plans_dir
+        && path.parent() == Some(plans_dir)
+    {
+        let filename = path.file_name()?.to_str()?;
+        let plan_id = filename.strip_suffix(".checkpoint.json")?;
+        Some(FileChange::PlanCheckpoint {
+            plan_id: plan_id.to_string(),
+            path: path.to_path_buf(),
+        })
     } else {
         None
     }
@@ -94,11 +114,13 @@ pub async fn watch(
     state: Arc<DashboardState>,
     teams_dir: PathBuf,
     tasks_dir: PathBuf,
+    plans_dir: Option<PathBuf>,
 ) -> notify::Result<()> {
     let (tx, mut rx) = mpsc::channel::<Vec<PathBuf>>(100);
 
     let teams_dir_clone = teams_dir.clone();
     let tasks_dir_clone = tasks_dir.clone();
+    let plans_dir_clone = plans_dir.clone();
 
     // Spawn the debounced file watcher on a blocking thread
     // (notify uses its own threads, but the callback bridges to our async channel)
@@ -134,6 +156,12 @@ pub async fn watch(
                 .watch(&tasks_dir_clone, RecursiveMode::Recursive)
                 .ok();
         }
+        if let Some(plans_dir) = plans_dir_clone.filter(|d| d.exists()) {
+            debouncer
+                .watcher()
+                .watch(&plans_dir, RecursiveMode::NonRecursive)
+                .ok();
+        }
 
         // Keep the debouncer alive until the channel is dropped
         loop {
@@ -144,7 +172,7 @@ pub async fn watch(
     // Process file change events
     while let Some(paths) = rx.recv().await {
         for path in paths {
-            let change = classify_event(&path, &teams_dir, &tasks_dir);
+            let change = classify_event(&path, &teams_dir, &tasks_dir, plans_dir.as_deref());
             match change {
                 Some(FileChange::TeamConfig { path }) => {
                     if let Some(config) = read_json::<ClaudeTeamConfig>(&path) {
@@ -170,6 +198,14 @@ pub async fn watch(
                 Some(FileChange::TeamDeleted { team_name }) => {
                     state.remove_team(&team_name).await;
                 }
+                Some(FileChange::PlanCheckpoint { plan_id, path }) => {
+                    let plan_path = path.with_file_name(format!("{plan_id}.json"));
+                    if let Some(plan) = read_json::<PlanMetadata>(&plan_path)
+                        && let Some(checkpoint) = read_json::<ExecutionCheckpoint>(&path)
+                    {
+                        state.update_plan(&plan, &checkpoint).await;
+                    }
+                }
                 None => {}
             }
         }
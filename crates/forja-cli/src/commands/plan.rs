@@ -4,6 +4,8 @@ use std::process::Command;
 use colored::Colorize;
 
 use forja_core::error::{ForjaError, Result};
+use forja_core::models::lint::LintLevel;
+use forja_core::models::plan::{PlanMetadata, PlanValidation, load_plan, validate_plan};
 use forja_core::paths::ForjaPaths;
 
 /// Launch an interactive Claude Code session to create an implementation plan.
@@ -54,6 +56,73 @@ pub fn run(task: Option<&str>) -> Result<()> {
     Ok(())
 }
 
+/// Validate a saved plan's phase graph and report the issues found.
+pub fn check(plan_id: &str, json: bool) -> Result<()> {
+    let paths = ForjaPaths::ensure_initialized()?;
+    let plan_path = paths.plans.join(format!("{plan_id}.json"));
+    if !plan_path.exists() {
+        return Err(ForjaError::PlanNotFound(plan_id.to_string()));
+    }
+    let plan = load_plan(&plan_path)?;
+    let validation = validate_plan(&plan);
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&validation)?);
+    } else {
+        println!("{}", "forja plan check".bold());
+        println!();
+        println!("  Plan:    {}", plan.id.cyan());
+        println!("  Phases:  {}", plan.phases.len());
+        println!();
+        if validation.issues.is_empty() {
+            println!("  {} No issues found", "✓".green());
+        } else {
+            print_plan_issues(&validation);
+        }
+    }
+
+    if validation.has_errors() {
+        return Err(ForjaError::InvalidPlan(format!(
+            "{} error(s) in plan '{}'",
+            validation.error_count(),
+            plan.id
+        )));
+    }
+
+    Ok(())
+}
+
+/// Validate a plan before execution: errors abort, warnings are printed and execution continues.
+pub fn ensure_valid(plan: &PlanMetadata) -> Result<()> {
+    let validation = validate_plan(plan);
+    if validation.issues.is_empty() {
+        return Ok(());
+    }
+
+    print_plan_issues(&validation);
+    println!();
+
+    if validation.has_errors() {
+        return Err(ForjaError::InvalidPlan(format!(
+            "{} error(s) in plan '{}'",
+            validation.error_count(),
+            plan.id
+        )));
+    }
+
+    Ok(())
+}
+
+fn print_plan_issues(validation: &PlanValidation) {
+    for issue in &validation.issues {
+        let prefix = match issue.level() {
+            LintLevel::Error => "error".red().to_string(),
+            LintLevel::Warning => "warn".yellow().to_string(),
+        };
+        println!("  {prefix} {issue}");
+    }
+}
+
 #[cfg(test)]
 mod tests {
     #[test]
@@ -0,0 +1,281 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use colored::Colorize;
+use dialoguer::Confirm;
+
+use forja_core::error::{ForjaError, Result};
+use forja_core::models::source::{
+    self, DEFAULT_ADDED_PRIORITY, DEFAULT_REGISTRY, RegistrySource, RegistrySources,
+};
+use forja_core::models::state::forget_installs;
+use forja_core::paths::ForjaPaths;
+use forja_core::registry::{catalog, git, lock};
+use forja_core::symlink::manager::{SymlinkManager, load_installed_ids, save_installed_ids};
+use forja_core::symlink::sync::sync_symlinks;
+
+/// Register a git URL or local directory as a named skill registry.
+pub fn add(name: &str, location: &str, priority: Option<i32>) -> Result<()> {
+    source::validate_name(name)?;
+    let global_root = ForjaPaths::global_forja_root()?;
+    let manifest_path = source::sources_path(&global_root);
+    let mut sources = source::load_sources(&manifest_path);
+    if sources.find(name).is_some() {
+        return Err(ForjaError::RegistryAlreadyExists(name.to_string()));
+    }
+
+    let dir = source::source_dir(&global_root, name);
+    if dir.exists() || dir.is_symlink() {
+        return Err(ForjaError::InvalidArgument(format!(
+            "{} already exists — remove it or pick another name",
+            dir.display()
+        )));
+    }
+    if let Some(parent) = dir.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let origins = installed_origins();
+    let local = Path::new(location).is_dir();
+    let url = if local {
+        let target = fs::canonicalize(location)?;
+        source::link_local(&target, &dir)?;
+        target.to_string_lossy().to_string()
+    } else {
+        println!("Cloning {}...", location.cyan());
+        git::clone(location, &dir)?;
+        location.to_string()
+    };
+
+    let priority = priority.unwrap_or(DEFAULT_ADDED_PRIORITY);
+    sources.registries.push(RegistrySource {
+        name: name.to_string(),
+        url,
+        local,
+        priority,
+    });
+    source::save_sources(&manifest_path, &sources)?;
+
+    let found = catalog::scan(&dir, &[])?.skills.len();
+    println!(
+        "{} Registry {} added ({} skills, priority {}).",
+        "SUCCESS:".green().bold(),
+        name.bold(),
+        found,
+        priority
+    );
+    if found == 0 {
+        println!(
+            "  {} no skills found under {}",
+            "WARNING:".yellow().bold(),
+            dir.join("skills").display()
+        );
+    }
+    resync(&origins);
+    Ok(())
+}
+
+/// Forget a named registry, uninstalling the skills that came from it.
+pub fn remove(name: &str, skip_confirm: bool) -> Result<()> {
+    if name == DEFAULT_REGISTRY {
+        return Err(ForjaError::InvalidArgument(
+            "the default registry can't be removed".to_string(),
+        ));
+    }
+    let global_root = ForjaPaths::global_forja_root()?;
+    let manifest_path = source::sources_path(&global_root);
+    let mut sources = source::load_sources(&manifest_path);
+    if sources.find(name).is_none() {
+        return Err(ForjaError::RegistryNotFound(name.to_string()));
+    }
+
+    if !skip_confirm {
+        let confirmed = Confirm::new()
+            .with_prompt(format!(
+                "Remove registry '{name}'? Skills installed from it are uninstalled"
+            ))
+            .default(true)
+            .interact()
+            .map_err(|e| ForjaError::Dialoguer(e.to_string()))?;
+
+        if !confirmed {
+            return Err(ForjaError::PromptCancelled);
+        }
+    }
+
+    let origins = installed_origins();
+    let uninstalled = uninstall_from(name)?;
+
+    let dir = source::source_dir(&global_root, name);
+    if dir.is_symlink() {
+        fs::remove_file(&dir)?;
+    } else if dir.exists() {
+        fs::remove_dir_all(&dir)?;
+    }
+    sources.remove(name);
+    source::save_sources(&manifest_path, &sources)?;
+
+    println!(
+        "{} Registry {} removed.",
+        "SUCCESS:".green().bold(),
+        name.bold()
+    );
+    for id in &uninstalled {
+        println!("  {} uninstalled {}", "-".dimmed(), id);
+    }
+    resync(&origins);
+    Ok(())
+}
+
+/// List registries in the order they win ID clashes.
+pub fn list() -> Result<()> {
+    let global_root = ForjaPaths::global_forja_root()?;
+    let sources = source::load_sources(&source::sources_path(&global_root));
+    let paths = ForjaPaths::resolve()?;
+    let installed_ids = load_installed_ids(&paths.state);
+    let registry = catalog::scan_all(&paths, &installed_ids)?;
+
+    println!("{}", "Skill registries".bold());
+    println!();
+
+    for entry in ranked(&paths, &sources) {
+        let skills: Vec<_> = registry
+            .skills
+            .iter()
+            .filter(|s| s.registry == entry.name)
+            .collect();
+        let shadowed = skills.iter().filter(|s| s.is_shadowed()).count();
+        let shadowed = if shadowed > 0 {
+            format!(", {shadowed} shadowed").yellow().to_string()
+        } else {
+            String::new()
+        };
+        let kind = if entry.local { " (local)" } else { "" };
+
+        println!(
+            "  {} {} (priority: {}, skills: {}{})",
+            "●".cyan(),
+            entry.name.bold(),
+            entry.priority,
+            skills.len(),
+            shadowed
+        );
+        println!("    {}{}", entry.url.dimmed(), kind.dimmed());
+    }
+
+    println!();
+    println!(
+        "  {} Shadowed skills stay reachable as {}",
+        "Tip:".dimmed(),
+        "<registry>:<phase>/<tech>/<name>".cyan()
+    );
+    Ok(())
+}
+
+/// Change which registry wins when two ship the same skill ID.
+pub fn priority(name: &str, value: i32) -> Result<()> {
+    let global_root = ForjaPaths::global_forja_root()?;
+    let manifest_path = source::sources_path(&global_root);
+    let mut sources = source::load_sources(&manifest_path);
+    let origins = installed_origins();
+
+    if name == DEFAULT_REGISTRY {
+        sources.default_priority = value;
+    } else {
+        let entry = sources
+            .find_mut(name)
+            .ok_or_else(|| ForjaError::RegistryNotFound(name.to_string()))?;
+        entry.priority = value;
+    }
+    source::save_sources(&manifest_path, &sources)?;
+
+    println!(
+        "{} Registry {} now has priority {}.",
+        "SUCCESS:".green().bold(),
+        name.bold(),
+        value
+    );
+    resync(&origins);
+    Ok(())
+}
+
+/// Every registry, the default catalog included, highest priority first.
+fn ranked(paths: &ForjaPaths, sources: &RegistrySources) -> Vec<RegistrySource> {
+    let config = forja_core::models::config::load_config(&paths.config);
+    let mut all = vec![RegistrySource {
+        name: DEFAULT_REGISTRY.to_string(),
+        url: config
+            .as_ref()
+            .map(|c| c.registry_url.clone())
+            .unwrap_or_else(|| paths.registry.display().to_string()),
+        local: config.is_some_and(|c| c.local),
+        priority: sources.default_priority,
+    }];
+    all.extend(sources.registries.iter().cloned());
+    all.sort_by_key(|r| std::cmp::Reverse(r.priority));
+    all
+}
+
+/// Drop skills that came from `name` from the current context's install state.
+fn uninstall_from(name: &str) -> Result<Vec<String>> {
+    let paths = ForjaPaths::resolve()?;
+    let mut installed_ids = load_installed_ids(&paths.state);
+    let registry = catalog::scan_all(&paths, &installed_ids)?;
+    let manager = SymlinkManager::for_paths(&paths);
+
+    let mut removed = Vec::new();
+    for skill in registry
+        .skills
+        .iter()
+        .filter(|s| s.registry == name && s.installed)
+    {
+        manager.uninstall(&skill.id)?;
+        removed.push(skill.id.clone());
+    }
+    if !removed.is_empty() {
+        installed_ids.retain(|id| !removed.contains(id));
+        save_installed_ids(&paths.state, &installed_ids)?;
+        forget_installs(&paths.state, &removed)?;
+        lock::refresh(&paths)?;
+    }
+    Ok(removed)
+}
+
+/// Registry each installed bare ID resolves to in the current context.
+fn installed_origins() -> BTreeMap<String, String> {
+    let Ok(paths) = ForjaPaths::resolve() else {
+        return BTreeMap::new();
+    };
+    let installed_ids = load_installed_ids(&paths.state);
+    let Ok(registry) = catalog::scan_all(&paths, &installed_ids) else {
+        return BTreeMap::new();
+    };
+    registry
+        .skills
+        .into_iter()
+        .filter(|s| s.installed && !s.is_shadowed())
+        .map(|s| (s.id, s.registry))
+        .collect()
+}
+
+/// Relink installed skills and report the ones that now resolve to
+/// another registry than in `before`.
+fn resync(before: &BTreeMap<String, String>) {
+    if let Ok(paths) = ForjaPaths::ensure_initialized() {
+        let _ = sync_symlinks(&paths);
+    }
+    for (id, registry) in installed_origins() {
+        if let Some(was) = before.get(&id)
+            && *was != registry
+        {
+            println!(
+                "  {} {} now comes from {} (was {})",
+                "~".yellow(),
+                id,
+                registry.bold(),
+                was
+            );
+        }
+    }
+}
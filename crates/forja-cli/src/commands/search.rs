@@ -2,16 +2,25 @@ use forja_core::error::Result;
 use crate::output;
 use forja_core::paths::ForjaPaths;
 use forja_core::registry::catalog;
+use forja_core::registry::search::{self, Field, Highlight};
 use forja_core::symlink::manager::load_installed_ids;
 use colored::Colorize;
 
-/// Search the skill catalog by name, description, phase, or tech.
-pub fn run(query: &str) -> Result<()> {
+/// Search the skill catalog, best matches first. Without a query, browse it
+/// interactively and show the picked skill.
+pub fn run(query: Option<&str>) -> Result<()> {
     let paths = ForjaPaths::ensure_initialized()?;
 
     let installed_ids = load_installed_ids(&paths.state);
-    let registry = catalog::scan(&paths.registry, &installed_ids)?;
-    let results = registry.search(query);
+    let registry = catalog::scan_all(&paths, &installed_ids)?;
+
+    let Some(query) = query else {
+        return match forja_tui::launch_search(&registry)? {
+            Some(skill_id) => super::info::run(&skill_id),
+            None => Ok(()), // user pressed Esc
+        };
+    };
+    let results = search::search(&registry, query);
 
     if results.is_empty() {
         println!("No skills matching \"{}\"", query.yellow());
@@ -28,7 +37,8 @@ pub fn run(query: &str) -> Result<()> {
     );
     println!();
 
-    for skill in &results {
+    for hit in &results {
+        let skill = hit.skill;
         let status = if skill.installed {
             " [installed]".green().to_string()
         } else {
@@ -36,8 +46,15 @@ pub fn run(query: &str) -> Result<()> {
         };
         println!(
             "  {} {}{}",
-            skill.id.bold(),
-            skill.description.dimmed(),
+            highlight(&skill.id, &hit.highlights, Field::Id, |s| s
+                .bold()
+                .to_string()),
+            highlight(
+                &skill.description,
+                &hit.highlights,
+                Field::Description,
+                |s| { s.dimmed().to_string() }
+            ),
             status
         );
     }
@@ -47,3 +64,31 @@ pub fn run(query: &str) -> Result<()> {
 
     Ok(())
 }
+
+/// `text` styled with `style`, with the ranges matched in `field` in yellow.
+fn highlight(
+    text: &str,
+    highlights: &[Highlight],
+    field: Field,
+    style: impl Fn(&str) -> String,
+) -> String {
+    let mut ranges: Vec<(usize, usize)> = highlights
+        .iter()
+        .filter(|h| h.field == field)
+        .map(|h| (h.start, h.end))
+        .collect();
+    ranges.sort();
+
+    let mut out = String::new();
+    let mut at = 0;
+    for (start, end) in ranges {
+        if start < at {
+            continue;
+        }
+        out.push_str(&style(&text[at..start]));
+        out.push_str(&text[start..end].yellow().bold().to_string());
+        at = end;
+    }
+    out.push_str(&style(&text[at..]));
+    out
+}
@@ -3,18 +3,11 @@ use std::path::Path;
 use std::process::Command;
 
 use colored::Colorize;
-use dialoguer::Select;
 
 use forja_core::error::{ForjaError, Result};
-use forja_core::models::plan::{
-    self, PhaseStatus, PlanMetadata, PlanStatus,
-    checkpoint_path, initialize_checkpoint, load_checkpoint, save_checkpoint, save_plan,
-    workspace_dir,
-};
+use forja_core::models::plan::{self, PhaseStatus, PlanMetadata, PlanStatus};
 use forja_core::models::spec::{self, SpecFile};
 use forja_core::paths::ForjaPaths;
-use forja_core::settings;
-use forja_core::symlink::auto_install;
 
 use crate::commands::execute as exec;
 use crate::output;
@@ -177,7 +170,11 @@ pub fn plan(spec_id: &str) -> Result<()> {
     Ok(())
 }
 
-pub fn execute(spec_id: &str, profile: &str, resume: bool) -> Result<()> {
+pub fn execute(
+    spec_id: &str,
+    profile: &str,
+    opts: exec::ExecOptions,
+) -> Result<()> {
     let dir = specs_dir(None);
     let _spec = spec::find_spec(dir, spec_id)?;
 
@@ -203,391 +200,24 @@ pub fn execute(spec_id: &str, profile: &str, resume: bool) -> Result<()> {
     println!("  Team:  {}", plan.team_size);
     println!();
 
-    // Override profile if provided
-    if profile != "balanced" {
-        plan.profile = profile.to_string();
-    }
-
-    // Auto-install missing agents
-    let skill_ids: Vec<&str> = plan.agents.iter().map(|a| a.skill_id.as_str()).collect();
-    auto_install::auto_install_missing(&paths, &skill_ids)?;
-
-    // Track analytics
-    let analytics_path = forja_core::analytics::analytics_path(&paths.forja_root);
-    for agent in &plan.agents {
-        let _ = forja_core::analytics::track(&analytics_path, &agent.skill_id, "sparks-execute");
-    }
-
-    // Ensure agent teams env var
-    let global_claude = ForjaPaths::global_claude_dir()?;
-    if !settings::has_teams_env_var(&global_claude) {
-        settings::enable_teams_env_var(&global_claude)?;
-        println!(
-            "  {} Agent teams env var enabled in settings.json",
-            "NOTE:".yellow().bold()
-        );
-        println!();
-    }
-
-    // Read plan .md content
-    let plan_md_path = paths.plans.join(format!("{}.md", plan.id));
-    let plan_md = if plan_md_path.exists() {
-        fs::read_to_string(&plan_md_path)?
-    } else {
-        format!("# Plan: {}\n\nNo detailed plan file found.", plan.task)
-    };
-
-    // Check claude CLI exists
-    if Command::new("claude").arg("--version").output().is_err() {
-        return Err(ForjaError::ClaudeCliNotFound);
-    }
-
-    if plan.phases.is_empty() {
-        println!("{}", "No structured phases — running monolithic execution.".dimmed());
-        println!();
-        return exec_monolithic(&paths, &mut plan, &plan_md);
-    }
-
-    exec_phased(&paths, &mut plan, &plan_md, resume)
+    exec::launch(
+        &paths,
+        &mut plan,
+        profile,
+        opts,
+        "sparks-execute",
+        monolithic_prompt,
+    )
 }
 
-/// Monolithic execution for plans without phases.
-fn exec_monolithic(paths: &ForjaPaths, plan: &mut PlanMetadata, plan_md: &str) -> Result<()> {
-    let prompt = format!(
+/// Prompt for plans without phases.
+fn monolithic_prompt(_plan: &PlanMetadata, plan_md: &str) -> String {
+    format!(
         "Execute this implementation plan.\n\n## Plan\n\n{plan_md}\n\n\
          ## Rules\n\n\
          - Read CLAUDE.md before starting\n\
          - Stop and report if blocked\n"
-    );
-
-    println!("{}", "Launching Claude Code session...".bold());
-    println!();
-
-    let status = Command::new("claude")
-        .arg("--dangerously-skip-permissions")
-        .arg("--")
-        .arg(&prompt)
-        .status()?;
-
-    if status.success() {
-        plan.status = PlanStatus::Executed;
-        let plan_json_path = paths.plans.join(format!("{}.json", plan.id));
-        save_plan(&plan_json_path, plan)?;
-        println!();
-        println!(
-            "{} Plan {} marked as executed.",
-            "Done:".green().bold(),
-            plan.id.cyan()
-        );
-    }
-
-    Ok(())
-}
-
-/// Phase-by-phase execution with retry-then-ask and quality gates.
-fn exec_phased(
-    paths: &ForjaPaths,
-    plan: &mut PlanMetadata,
-    plan_md: &str,
-    resume: bool,
-) -> Result<()> {
-    let ckpt_path = checkpoint_path(&paths.plans, &plan.id);
-    let ws_dir = workspace_dir(&paths.plans, &plan.id);
-
-    // Load or initialize checkpoint
-    let mut checkpoint = if resume && ckpt_path.exists() {
-        let ckpt = load_checkpoint(&ckpt_path)?;
-        println!("  {} Resuming from checkpoint", "RESUME:".cyan().bold());
-        let completed = ckpt
-            .phases
-            .iter()
-            .filter(|p| p.status == PhaseStatus::Completed)
-            .count();
-        println!("  {}/{} phases completed", completed, ckpt.phases.len());
-        println!();
-        ckpt
-    } else {
-        initialize_checkpoint(plan)
-    };
-
-    fs::create_dir_all(&ws_dir)?;
-    save_checkpoint(&ckpt_path, &checkpoint)?;
-
-    println!(
-        "{} Executing {} phases...",
-        "PHASES:".bold(),
-        plan.phases.len()
-    );
-    println!();
-
-    let mut all_completed = true;
-
-    for i in 0..plan.phases.len() {
-        let phase_ckpt = &checkpoint.phases[i];
-
-        // Skip completed phases
-        if phase_ckpt.status == PhaseStatus::Completed {
-            println!(
-                "  {} Phase {}: {} (already completed)",
-                "✓".green(),
-                i + 1,
-                plan.phases[i].name
-            );
-            continue;
-        }
-
-        // Skip phases whose dependencies failed
-        if exec::has_failed_dependency(&plan.phases[i], &plan.phases, &checkpoint) {
-            checkpoint.phases[i].status = PhaseStatus::Skipped;
-            checkpoint.last_updated = chrono::Utc::now().to_rfc3339();
-            save_checkpoint(&ckpt_path, &checkpoint)?;
-            println!(
-                "  {} Phase {}: {} (dependency failed)",
-                "⊘".yellow(),
-                i + 1,
-                plan.phases[i].name
-            );
-            all_completed = false;
-            continue;
-        }
-
-        // Execute phase with retry-then-ask
-        let succeeded = run_phase_with_retry(paths, plan, &mut checkpoint, i, plan_md, &ws_dir, &ckpt_path)?;
-
-        if !succeeded {
-            all_completed = false;
-        }
-
-        // Quality gates after successful phase
-        if succeeded {
-            run_quality_gates(i, &plan.phases[i].name);
-        }
-    }
-
-    // All phases done
-    if all_completed {
-        plan.status = PlanStatus::Executed;
-        let plan_json_path = paths.plans.join(format!("{}.json", plan.id));
-        save_plan(&plan_json_path, plan)?;
-        println!();
-        println!(
-            "{} All {} phases completed. Plan {} marked as executed.",
-            "Done:".green().bold(),
-            plan.phases.len(),
-            plan.id.cyan()
-        );
-    }
-
-    Ok(())
-}
-
-/// Run a single phase, retrying once on failure, then prompting the user.
-fn run_phase_with_retry(
-    paths: &ForjaPaths,
-    plan: &PlanMetadata,
-    checkpoint: &mut plan::ExecutionCheckpoint,
-    phase_index: usize,
-    plan_md: &str,
-    ws_dir: &Path,
-    ckpt_path: &Path,
-) -> Result<bool> {
-    let phase_name = &plan.phases[phase_index].name;
-
-    for attempt in 0..2 {
-        if attempt == 1 {
-            println!(
-                "  {} Retrying phase {}...",
-                "RETRY:".yellow().bold(),
-                phase_index + 1
-            );
-        } else {
-            println!("  {} Phase {}: {}", "▶".cyan(), phase_index + 1, phase_name.bold());
-        }
-
-        // Mark in-progress
-        checkpoint.phases[phase_index].status = PhaseStatus::InProgress;
-        checkpoint.phases[phase_index].started_at = Some(chrono::Utc::now().to_rfc3339());
-        checkpoint.current_phase = Some(phase_index);
-        checkpoint.last_updated = chrono::Utc::now().to_rfc3339();
-        save_checkpoint(ckpt_path, checkpoint)?;
-
-        // Build and run prompt
-        let prompt = exec::build_phase_prompt(plan, phase_index, plan_md, ws_dir);
-        let status = Command::new("claude")
-            .arg("--dangerously-skip-permissions")
-            .arg("--")
-            .arg(&prompt)
-            .status()?;
-
-        let exit_code = status.code().unwrap_or(-1);
-
-        if status.success() {
-            checkpoint.phases[phase_index].status = PhaseStatus::Completed;
-            checkpoint.phases[phase_index].completed_at = Some(chrono::Utc::now().to_rfc3339());
-            checkpoint.phases[phase_index].exit_code = Some(exit_code);
-            checkpoint.last_updated = chrono::Utc::now().to_rfc3339();
-            save_checkpoint(ckpt_path, checkpoint)?;
-
-            println!(
-                "  {} Phase {}: {} completed",
-                "✓".green(),
-                phase_index + 1,
-                phase_name
-            );
-            return Ok(true);
-        }
-
-        // First attempt failed — will retry
-        if attempt == 0 {
-            println!(
-                "  {} Phase {}: {} failed (exit code {}) — retrying...",
-                "✗".red(),
-                phase_index + 1,
-                phase_name,
-                exit_code
-            );
-            continue;
-        }
-
-        // Second attempt also failed — prompt user
-        checkpoint.phases[phase_index].status = PhaseStatus::Failed;
-        checkpoint.phases[phase_index].completed_at = Some(chrono::Utc::now().to_rfc3339());
-        checkpoint.phases[phase_index].exit_code = Some(exit_code);
-        checkpoint.phases[phase_index].error_message =
-            Some(format!("Failed after retry (exit code {exit_code})"));
-        checkpoint.last_updated = chrono::Utc::now().to_rfc3339();
-        save_checkpoint(ckpt_path, checkpoint)?;
-
-        println!(
-            "  {} Phase {}: {} failed after retry (exit code {})",
-            "✗".red(),
-            phase_index + 1,
-            phase_name,
-            exit_code
-        );
-        println!();
-
-        return handle_phase_failure(paths, plan, checkpoint, phase_index, plan_md, ws_dir, ckpt_path);
-    }
-
-    unreachable!()
-}
-
-/// Prompt user after a phase fails twice: Retry / Skip / Abort.
-fn handle_phase_failure(
-    paths: &ForjaPaths,
-    plan: &PlanMetadata,
-    checkpoint: &mut plan::ExecutionCheckpoint,
-    phase_index: usize,
-    plan_md: &str,
-    ws_dir: &Path,
-    ckpt_path: &Path,
-) -> Result<bool> {
-    let items = &["Retry", "Skip this phase", "Abort execution"];
-
-    let selection = Select::new()
-        .with_prompt(format!(
-            "Phase '{}' failed. What would you like to do?",
-            plan.phases[phase_index].name
-        ))
-        .items(items)
-        .default(0)
-        .interact_opt()
-        .unwrap_or(None);
-
-    match selection {
-        Some(0) => {
-            // Retry — recursive call with fresh retry counter
-            checkpoint.phases[phase_index].status = PhaseStatus::Pending;
-            checkpoint.phases[phase_index].error_message = None;
-            save_checkpoint(ckpt_path, checkpoint)?;
-            run_phase_with_retry(paths, plan, checkpoint, phase_index, plan_md, ws_dir, ckpt_path)
-        }
-        Some(1) => {
-            // Skip
-            checkpoint.phases[phase_index].status = PhaseStatus::Skipped;
-            checkpoint.last_updated = chrono::Utc::now().to_rfc3339();
-            save_checkpoint(ckpt_path, checkpoint)?;
-            println!(
-                "  {} Phase {}: {} skipped",
-                "⊘".yellow(),
-                phase_index + 1,
-                plan.phases[phase_index].name
-            );
-            Ok(false)
-        }
-        _ => {
-            // Abort (including None = Esc/Ctrl+C)
-            Err(ForjaError::PhaseExecutionFailed(format!(
-                "Execution aborted at phase '{}'",
-                plan.phases[phase_index].name
-            )))
-        }
-    }
-}
-
-/// Run quality gates (cargo test + cargo clippy) after a completed phase.
-fn run_quality_gates(phase_index: usize, phase_name: &str) {
-    println!(
-        "  {} Quality gates for phase {}...",
-        "GATES:".bold(),
-        phase_index + 1
-    );
-
-    // cargo test
-    let test_result = Command::new("cargo")
-        .args(["test", "--workspace"])
-        .stdout(std::process::Stdio::null())
-        .stderr(std::process::Stdio::null())
-        .status();
-
-    match test_result {
-        Ok(s) if s.success() => {
-            println!("    {} cargo test", "✓".green());
-        }
-        Ok(s) => {
-            let code = s.code().unwrap_or(-1);
-            println!(
-                "    {} cargo test (exit code {})",
-                "✗".red(),
-                code
-            );
-            println!(
-                "      {} Tests failed after phase '{}' — review before continuing",
-                "Warning:".yellow().bold(),
-                phase_name
-            );
-        }
-        Err(_) => {
-            println!("    {} cargo test (not available)", "·".dimmed());
-        }
-    }
-
-    // cargo clippy
-    let clippy_result = Command::new("cargo")
-        .args(["clippy", "--workspace", "--", "-D", "warnings"])
-        .stdout(std::process::Stdio::null())
-        .stderr(std::process::Stdio::null())
-        .status();
-
-    match clippy_result {
-        Ok(s) if s.success() => {
-            println!("    {} cargo clippy", "✓".green());
-        }
-        Ok(s) => {
-            let code = s.code().unwrap_or(-1);
-            println!(
-                "    {} cargo clippy (exit code {})",
-                "✗".red(),
-                code
-            );
-        }
-        Err(_) => {
-            println!("    {} cargo clippy (not available)", "·".dimmed());
-        }
-    }
-
-    println!();
+    )
 }
 
 pub fn status(spec_id: Option<&str>) -> Result<()> {
@@ -709,9 +339,30 @@ fn status_detail(dir: &Path, spec_id: &str) -> Result<()> {
             && let Some(ref err) = ckpt.phases[i].error_message
         {
             println!("      {}", err.red());
+            println!(
+                "      {}",
+                format!("logs: forja execute logs {} --phase {}", plan.id, i + 1).dimmed()
+            );
+        }
+
+        if let Some(state) = checkpoint.as_ref().map(|c| &c.phases[i]) {
+            let spend = match (state.usage, state.unmeasured) {
+                (Some(usage), false) => Some(usage.to_string()),
+                (Some(usage), true) => Some(format!("{usage} + interactive runs not measured")),
+                (None, true) => Some("spend not measured (interactive)".to_string()),
+                (None, false) => None,
+            };
+            if let Some(spend) = spend {
+                println!("      {}", spend.dimmed());
+            }
         }
     }
 
+    if let Some(spent) = checkpoint.as_ref().and_then(exec::spend_summary) {
+        println!();
+        println!("  {}:  {}", "Spend".bold(), spent);
+    }
+
     // Quality gates
     if !plan.quality_gates.is_empty() {
         println!();
@@ -1,9 +1,12 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
 
 use colored::Colorize;
 
 use forja_core::analytics;
 use forja_core::error::Result;
+use forja_core::models::plan;
+use forja_core::models::usage::{TokenUsage, format_tokens};
 use forja_core::paths::ForjaPaths;
 use forja_core::registry::catalog;
 use forja_core::symlink::manager::load_installed_ids;
@@ -18,6 +21,7 @@ pub fn run() -> Result<()> {
     println!();
 
     if events.is_empty() {
+        print_spend_by_profile(&paths.plans);
         println!("  No usage data yet. Run some tasks to start tracking!");
         println!();
         println!(
@@ -63,6 +67,8 @@ pub fn run() -> Result<()> {
     }
     println!();
 
+    print_spend_by_profile(&paths.plans);
+
     // Recent activity (last 10)
     println!("  {}", "Recent activity".bold().underline());
     println!();
@@ -83,7 +89,7 @@ pub fn run() -> Result<()> {
 
     // Installed but never used
     let installed_ids = load_installed_ids(&paths.state);
-    let registry = catalog::scan(&paths.registry, &installed_ids)?;
+    let registry = catalog::scan_all(&paths, &installed_ids)?;
     let used_skills: Vec<&str> = skill_counts.keys().copied().collect();
     let unused: Vec<&str> = registry
         .skills
@@ -107,3 +113,35 @@ pub fn run() -> Result<()> {
 
     Ok(())
 }
+
+/// Token and dollar spend of executed plans, grouped by model profile.
+/// Only runs that reported usage (headless phases) are counted.
+fn print_spend_by_profile(plans_dir: &Path) {
+    let mut by_profile: BTreeMap<String, (usize, TokenUsage)> = BTreeMap::new();
+    for (plan, checkpoint) in plan::load_executions(plans_dir) {
+        if checkpoint.usage.is_empty() {
+            continue;
+        }
+        let entry = by_profile.entry(plan.profile).or_default();
+        entry.0 += 1;
+        entry.1 += checkpoint.usage;
+    }
+
+    if by_profile.is_empty() {
+        return;
+    }
+
+    println!("  {}", "Spend by profile".bold().underline());
+    println!();
+    for (profile, (plans, usage)) in &by_profile {
+        println!(
+            "    {:<10} {:>3} plans  {:>7} tokens  ${:>7.2}  (${:.2}/plan)",
+            profile.cyan(),
+            plans,
+            format_tokens(usage.total_tokens()),
+            usage.cost_usd,
+            usage.cost_usd / *plans as f64
+        );
+    }
+    println!();
+}
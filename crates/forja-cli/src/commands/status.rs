@@ -37,12 +37,13 @@ fn print_welcome() {
 
 fn print_status(paths: &ForjaPaths) -> Result<()> {
     let installed_ids = load_installed_ids(&paths.state);
-    let registry = catalog::scan(&paths.registry, &installed_ids)?;
+    let registry = catalog::scan_all(paths, &installed_ids)?;
     let total = registry.skills.len();
     let installed = installed_ids.len();
 
-    let manager = SymlinkManager::new(paths.claude_agents.clone(), paths.claude_commands.clone());
+    let manager = SymlinkManager::for_paths(paths);
     let (_healthy, broken) = manager.verify()?;
+    let counts = manager.counts()?;
 
     let health = if broken.is_empty() {
         "all symlinks OK".green().to_string()
@@ -66,6 +67,10 @@ fn print_status(paths: &ForjaPaths) -> Result<()> {
     println!();
     println!("  Mode:    {}", mode_label.cyan());
     println!("  Skills:  {}/{} installed", installed, total);
+    println!(
+        "  Content: {} agents, {} commands, {} skills",
+        counts.agents, counts.commands, counts.skills
+    );
     println!("  Health:  {}", health);
     println!();
 
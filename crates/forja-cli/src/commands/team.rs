@@ -254,7 +254,7 @@ pub fn create(name: &str) -> Result<()> {
 
     // Scan catalog for installed skills (exclude Teams phase)
     let installed_ids = load_installed_ids(&paths.state);
-    let registry = catalog::scan(&paths.registry, &installed_ids)?;
+    let registry = catalog::scan_all(&paths, &installed_ids)?;
     let installed_skills: Vec<&Skill> = registry
         .skills
         .iter()
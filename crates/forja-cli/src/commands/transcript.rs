@@ -0,0 +1,366 @@
+use std::fs::{self, File, OpenOptions};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+
+use colored::Colorize;
+
+use forja_core::error::{ForjaError, Result};
+use forja_core::models::plan::{
+    ExecutionCheckpoint, PhaseStatus, checkpoint_path, load_checkpoint, workspace_dir,
+};
+use forja_core::paths::ForjaPaths;
+use forja_spark::events::ChatEvent;
+
+/// Bytes of a tool call's input or result kept in the readable transcript.
+/// The raw events file has them in full.
+const TOOL_EXCERPT_BYTES: usize = 1000;
+
+/// How often `--follow` checks for new output.
+const FOLLOW_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Readable transcript of a phase, relative to the plan workspace.
+pub fn log_file_name(phase_index: usize) -> String {
+    format!("phase-{phase_index}.log")
+}
+
+/// Raw stream-json events of a phase, relative to the plan workspace.
+pub fn events_file_name(phase_index: usize) -> String {
+    format!("phase-{phase_index}.jsonl")
+}
+
+/// Append-only record of one phase attempt: a readable `phase-N.log` (the
+/// terminal recording, for interactive runs) and, for headless runs, the raw
+/// events in `phase-N.jsonl`.
+///
+/// Cheap to clone so the stderr reader thread can write to it too. Write
+/// errors are ignored — a full disk shouldn't fail the phase.
+#[derive(Clone)]
+pub struct Transcript {
+    log: Arc<File>,
+    events: Option<Arc<File>>,
+}
+
+impl Transcript {
+    /// Open the phase's transcript for appending, so retries add to it.
+    pub fn open(workspace_dir: &Path, phase_index: usize, headless: bool) -> Result<Self> {
+        let append = |name: String| {
+            OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(workspace_dir.join(name))
+                .map(Arc::new)
+        };
+        Ok(Self {
+            log: append(log_file_name(phase_index))?,
+            events: if headless {
+                Some(append(events_file_name(phase_index))?)
+            } else {
+                None
+            },
+        })
+    }
+
+    pub fn begin(&self, phase_label: &str, attempt: usize, started_at: &str) {
+        self.write(&format!(
+            "=== {phase_label} · attempt {attempt} · started {started_at} ===\n"
+        ));
+    }
+
+    pub fn end(&self, attempt: usize, failure: Option<&str>, finished_at: &str) {
+        let outcome = match failure {
+            Some(reason) => format!("failed: {reason}"),
+            None => "completed".to_string(),
+        };
+        self.write(&format!(
+            "=== attempt {attempt} {outcome} · finished {finished_at} ===\n\n"
+        ));
+    }
+
+    /// Record one line of `claude`'s stdout as received.
+    pub fn raw_event(&self, line: &str) {
+        if let Some(events) = &self.events {
+            let _ = (&**events).write_all(format!("{line}\n").as_bytes());
+        }
+    }
+
+    pub fn stderr(&self, line: &str) {
+        self.write(&format!("[stderr] {line}\n"));
+    }
+
+    /// Record a chunk of an interactive session's terminal output as-is,
+    /// escape sequences included, so `forja execute logs` redraws it.
+    pub fn terminal(&self, bytes: &[u8]) {
+        let _ = (&*self.log).write_all(bytes);
+    }
+
+    /// Record a line about the run itself rather than `claude`'s output.
+    pub fn note(&self, text: &str) {
+        self.write(&format!("({text})\n"));
+    }
+
+    pub fn event(&self, event: &ChatEvent) {
+        if let Some(text) = render_event(event) {
+            self.write(&text);
+        }
+    }
+
+    fn write(&self, text: &str) {
+        let _ = (&*self.log).write_all(text.as_bytes());
+    }
+}
+
+/// Readable form of an event, newline-terminated. `None` for framing events.
+fn render_event(event: &ChatEvent) -> Option<String> {
+    let text = match event {
+        ChatEvent::SessionInit {
+            session_id, model, ..
+        } => format!("[session {session_id}] model {model}"),
+        ChatEvent::TextDelta { text, .. } => text.trim_end().to_string(),
+        ChatEvent::ToolUseStart { name, input, .. } => {
+            format!("→ {name} {}", excerpt(&input.to_string()))
+        }
+        ChatEvent::ToolResult {
+            content, is_error, ..
+        } => {
+            let label = if *is_error { "← error" } else { "←" };
+            format!("{label} {}", excerpt(content.trim()))
+        }
+        ChatEvent::TurnComplete {
+            subtype,
+            result,
+            duration_ms,
+            num_turns,
+            total_cost_usd,
+            ..
+        } => {
+            let mut summary = format!(
+                "[result {subtype}] {num_turns} turns · {:.1}s · ${total_cost_usd:.2}",
+                *duration_ms as f64 / 1000.0
+            );
+            if let Some(result) = result {
+                summary.push('\n');
+                summary.push_str(result.trim_end());
+            }
+            summary
+        }
+        ChatEvent::Error { message } => format!("[error] {message}"),
+        ChatEvent::MessageStart { .. }
+        | ChatEvent::ContentBlockStop { .. }
+        | ChatEvent::MessageStop => {
+            return None;
+        }
+    };
+    Some(text + "\n")
+}
+
+/// First `TOOL_EXCERPT_BYTES` of `s`, cut back to a char boundary.
+fn excerpt(s: &str) -> String {
+    if s.len() <= TOOL_EXCERPT_BYTES {
+        return s.to_string();
+    }
+    let mut end = TOOL_EXCERPT_BYTES;
+    while !s.is_char_boundary(end) {
+        end -= 1;
+    }
+    format!("{}… ({} bytes)", &s[..end], s.len())
+}
+
+/// Print the transcripts of a plan's phases, optionally following them
+/// like `tail -f` until the phases being shown stop running.
+///
+/// `phase` is 1-based, as phases are numbered everywhere else in the CLI.
+pub fn show(plan_id: &str, phase: Option<usize>, follow: bool) -> Result<()> {
+    let paths = ForjaPaths::ensure_initialized()?;
+    if !paths.plans.join(format!("{plan_id}.json")).exists() {
+        return Err(ForjaError::PlanNotFound(plan_id.to_string()));
+    }
+
+    let ckpt_path = checkpoint_path(&paths.plans, plan_id);
+    if !ckpt_path.exists() {
+        println!(
+            "  {}",
+            "No logs yet — this plan hasn't been executed.".dimmed()
+        );
+        crate::output::print_tip(&format!("Run it: forja execute {plan_id}"));
+        return Ok(());
+    }
+    let ws_dir = workspace_dir(&paths.plans, plan_id);
+    let mut checkpoint = load_checkpoint(&ckpt_path)?;
+
+    let selected: Vec<usize> = match phase {
+        Some(n) if n >= 1 && n <= checkpoint.phases.len() => vec![n - 1],
+        Some(n) => {
+            return Err(ForjaError::InvalidArgument(format!(
+                "plan '{plan_id}' has no phase {n} (it has {})",
+                checkpoint.phases.len()
+            )));
+        }
+        None => (0..checkpoint.phases.len()).collect(),
+    };
+
+    let mut offsets = vec![0u64; checkpoint.phases.len()];
+    let mut current = None;
+    let mut printed_any = false;
+    loop {
+        // Decide whether to stop before reading, so output written just
+        // before a phase finished is still drained
+        let finished = !follow || selected.iter().all(|&i| is_finished(&checkpoint, i));
+
+        for &i in &selected {
+            let name = checkpoint.phases[i]
+                .log
+                .clone()
+                .unwrap_or_else(|| log_file_name(i));
+            let chunk = read_from(&ws_dir.join(name), &mut offsets[i])?;
+            if chunk.is_empty() {
+                continue;
+            }
+            if current != Some(i) {
+                if printed_any {
+                    println!();
+                }
+                let state = &checkpoint.phases[i];
+                println!(
+                    "{} {} ({})",
+                    format!("Phase {}:", i + 1).bold(),
+                    state.phase_name.cyan(),
+                    format!("{:?}", state.status).to_lowercase().dimmed()
+                );
+                current = Some(i);
+            }
+            std::io::stdout().write_all(&chunk)?;
+            std::io::stdout().flush()?;
+            printed_any = true;
+        }
+
+        if finished {
+            break;
+        }
+        std::thread::sleep(FOLLOW_INTERVAL);
+        if let Ok(latest) = load_checkpoint(&ckpt_path) {
+            checkpoint = latest;
+        }
+    }
+
+    if !printed_any {
+        let which = match phase {
+            Some(n) => format!("phase {n}"),
+            None => "any phase".to_string(),
+        };
+        println!(
+            "  {}",
+            format!("No output recorded for {which} of plan {plan_id}.").dimmed()
+        );
+    }
+    Ok(())
+}
+
+fn is_finished(checkpoint: &ExecutionCheckpoint, i: usize) -> bool {
+    matches!(
+        checkpoint.phases[i].status,
+        PhaseStatus::Completed | PhaseStatus::Failed | PhaseStatus::Skipped
+    )
+}
+
+/// Bytes appended to `path` since `offset`, advancing `offset` past them.
+/// A missing file reads as empty.
+fn read_from(path: &Path, offset: &mut u64) -> Result<Vec<u8>> {
+    let mut file = match File::open(path) {
+        Ok(file) => file,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e.into()),
+    };
+    // The transcript was replaced (e.g. the workspace was cleared): start over
+    if fs::metadata(path)?.len() < *offset {
+        *offset = 0;
+    }
+    file.seek(SeekFrom::Start(*offset))?;
+    let mut chunk = Vec::new();
+    file.read_to_end(&mut chunk)?;
+    *offset += chunk.len() as u64;
+    Ok(chunk)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use forja_spark::parser::StreamParser;
+    use tempfile::TempDir;
+
+    #[test]
+    fn headless_attempts_append_readable_log_and_raw_events() {
+        let ws = TempDir::new().unwrap();
+        let lines = [
+            r#"{"type":"assistant","message":{"id":"m1","role":"assistant","content":[{"type":"text","text":"Creating schema"},{"type":"tool_use","id":"t1","name":"Bash","input":{"command":"ls"}}]}}"#,
+            r#"{"type":"result","subtype":"success","is_error":false,"result":"Done","duration_ms":1500,"duration_api_ms":1200,"num_turns":2,"total_cost_usd":0.05,"usage":{"input_tokens":10,"output_tokens":5}}"#,
+        ];
+
+        for attempt in 1..=2 {
+            let transcript = Transcript::open(ws.path(), 0, true).unwrap();
+            transcript.begin("Phase 1: Schema", attempt, "t0");
+            let mut parser = StreamParser::new();
+            for line in lines {
+                transcript.raw_event(line);
+                for event in parser.feed(line) {
+                    transcript.event(&event);
+                }
+            }
+            transcript.stderr("warning: slow");
+            transcript.end(attempt, (attempt == 1).then_some("exit code 1"), "t1");
+        }
+
+        let log = fs::read_to_string(ws.path().join(log_file_name(0))).unwrap();
+        assert!(log.contains("=== Phase 1: Schema · attempt 1 · started t0 ==="));
+        assert!(log.contains("Creating schema\n→ Bash {\"command\":\"ls\"}\n"));
+        assert!(log.contains("[result success] 2 turns · 1.5s · $0.05\nDone\n"));
+        assert!(log.contains("[stderr] warning: slow\n"));
+        assert!(log.contains("=== attempt 1 failed: exit code 1 · finished t1 ==="));
+        assert!(log.contains("=== attempt 2 completed · finished t1 ==="));
+
+        let events = fs::read_to_string(ws.path().join(events_file_name(0))).unwrap();
+        assert_eq!(events.lines().count(), 4);
+    }
+
+    #[test]
+    fn interactive_attempts_record_terminal_output() {
+        let ws = TempDir::new().unwrap();
+        let transcript = Transcript::open(ws.path(), 2, false).unwrap();
+        transcript.begin("Phase 3: UI", 1, "t0");
+        transcript.terminal(b"\x1b[1mBuilding\x1b[0m\r\n");
+        transcript.end(1, None, "t1");
+
+        let log = fs::read_to_string(ws.path().join(log_file_name(2))).unwrap();
+        assert!(log.contains("===\n\x1b[1mBuilding\x1b[0m\r\n=== attempt 1 completed"));
+        assert!(!ws.path().join(events_file_name(2)).exists());
+    }
+
+    #[test]
+    fn read_from_returns_only_new_bytes() {
+        let ws = TempDir::new().unwrap();
+        let path = ws.path().join("phase-0.log");
+        let mut offset = 0;
+        assert!(read_from(&path, &mut offset).unwrap().is_empty());
+
+        fs::write(&path, "one\n").unwrap();
+        assert_eq!(read_from(&path, &mut offset).unwrap(), b"one\n");
+        OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .unwrap()
+            .write_all(b"two\n")
+            .unwrap();
+        assert_eq!(read_from(&path, &mut offset).unwrap(), b"two\n");
+        assert!(read_from(&path, &mut offset).unwrap().is_empty());
+    }
+
+    #[test]
+    fn excerpt_truncates_long_tool_output() {
+        let long = "é".repeat(TOOL_EXCERPT_BYTES);
+        let cut = excerpt(&long);
+        assert!(cut.len() < long.len());
+        assert!(cut.ends_with(&format!("… ({} bytes)", long.len())));
+        assert_eq!(excerpt("short"), "short");
+    }
+}
@@ -3,12 +3,14 @@ use dialoguer::Confirm;
 use forja_core::error::{ForjaError, Result};
 use forja_core::models::registry::ResolveResult;
 use forja_core::paths::ForjaPaths;
-use forja_core::registry::catalog;
-use forja_core::symlink::manager::{SymlinkManager, load_installed_ids, save_installed_ids};
+use forja_core::registry::{catalog, deps};
+use forja_core::symlink::install::{self, Uninstalled};
+use forja_core::symlink::manager::load_installed_ids;
 use colored::Colorize;
 
 /// Remove an installed skill by deleting its symlinks and updating state.
-pub fn run(skill_path: &str, skip_confirm: bool, force_global: bool) -> Result<()> {
+/// Skills other installed skills require are kept unless `force` is set.
+pub fn run(skill_path: &str, skip_confirm: bool, force_global: bool, force: bool) -> Result<()> {
     let paths = if force_global {
         let p = ForjaPaths::global()?;
         if !p.forja_root.exists() {
@@ -19,14 +21,14 @@ pub fn run(skill_path: &str, skip_confirm: bool, force_global: bool) -> Result<(
         ForjaPaths::ensure_initialized()?
     };
 
-    let mut installed_ids = load_installed_ids(&paths.state);
+    let installed_ids = load_installed_ids(&paths.state);
 
     // Fast path: exact ID match in installed state (works even if skill was removed from catalog)
     let resolved_id = if installed_ids.contains(&skill_path.to_string()) {
         skill_path.to_string()
     } else {
         // Fallback: resolve via registry for name-based lookup
-        let registry = catalog::scan(&paths.registry, &installed_ids)?;
+        let registry = catalog::scan_all(&paths, &installed_ids)?;
         match registry.resolve(skill_path) {
             ResolveResult::Found(s) => {
                 let id = s.id.clone();
@@ -52,6 +54,11 @@ pub fn run(skill_path: &str, skip_confirm: bool, force_global: bool) -> Result<(
         }
     };
 
+    if !force {
+        let registry = catalog::scan_all(&paths, &installed_ids)?;
+        deps::check_removable(&registry, &resolved_id, &installed_ids)?;
+    }
+
     if !skip_confirm {
         let confirmed = Confirm::new()
             .with_prompt(format!("Uninstall skill '{}'?", resolved_id))
@@ -64,11 +71,7 @@ pub fn run(skill_path: &str, skip_confirm: bool, force_global: bool) -> Result<(
         }
     }
 
-    let manager = SymlinkManager::new(paths.claude_agents.clone(), paths.claude_commands.clone());
-    let removed = manager.uninstall(&resolved_id)?;
-
-    installed_ids.retain(|id| id != &resolved_id);
-    save_installed_ids(&paths.state, &installed_ids)?;
+    let Uninstalled { removed, kept } = install::uninstall(&paths, &resolved_id)?;
 
     println!("{} {}", "Uninstalled:".yellow().bold(), resolved_id.bold());
 
@@ -79,5 +82,13 @@ pub fn run(skill_path: &str, skip_confirm: bool, force_global: bool) -> Result<(
         }
     }
 
+    if !kept.is_empty() {
+        println!(
+            "  {} edited since install, left in place: {}",
+            "WARNING:".yellow().bold(),
+            kept.join(", ")
+        );
+    }
+
     Ok(())
 }
@@ -1,48 +1,46 @@
-use forja_core::error::Result;
+use forja_core::error::{ForjaError, Result};
+use forja_core::models::lock::{LOCK_FILE, load_project_lock, lock_path};
 use forja_core::paths::ForjaPaths;
-use forja_core::registry::catalog;
+use forja_core::registry::{catalog, git, lock};
 use forja_core::symlink::manager::{SymlinkManager, load_installed_ids};
 use colored::Colorize;
 
 /// Update the registry via `git pull` and re-verify installed symlinks.
-pub fn run() -> Result<()> {
+/// `--lock` restores the revisions in `forja.lock` instead; `--frozen` only checks them.
+pub fn run(restore_lock: bool, frozen: bool) -> Result<()> {
     let paths = ForjaPaths::ensure_initialized()?;
 
-    // Check if local development mode (symlink, not cloned)
-    if paths.registry.is_symlink() {
-        println!(
-            "{}",
-            "Registry is a local symlink — no update needed.".yellow()
-        );
-        println!(
-            "  Source: {}",
-            std::fs::read_link(&paths.registry)
-                .unwrap_or_default()
-                .display()
-        );
-    } else {
-        // Save HEAD before pull for `forja diff`
-        if let Ok(head) = forja_core::registry::git::head_sha(&paths.registry) {
-            let last_update_path = paths.forja_root.join("last_update.json");
-            let data = serde_json::json!({ "head_before": head });
-            let _ = std::fs::write(&last_update_path, serde_json::to_string_pretty(&data).unwrap_or_default());
-        }
+    if frozen {
+        return verify_frozen(&paths);
+    }
 
-        println!("Updating registry...");
-        let output = forja_core::registry::git::pull(&paths.registry)?;
-        println!("{output}");
+    if restore_lock {
+        restore_locked(&paths)?;
+    } else {
+        pull_latest(&paths)?;
     }
 
     // Sync symlinks after update to refresh any changed skill files
     forja_core::symlink::sync::sync_symlinks(&paths)?;
 
+    if restore_lock {
+        if let Some(locked) = load_project_lock(&paths)? {
+            for drift in lock::verify(&paths, &locked)? {
+                println!("  {} {}", "WARNING:".yellow().bold(), drift);
+            }
+        }
+    } else if lock_path(&paths).is_some() {
+        lock::refresh(&paths)?;
+        println!("{} {}", "Locked:".bold(), LOCK_FILE.cyan());
+    }
+
     // Check symlink health
-    let manager = SymlinkManager::new(paths.claude_agents.clone(), paths.claude_commands.clone());
+    let manager = SymlinkManager::for_paths(&paths);
     let (healthy, broken) = manager.verify()?;
 
     // Show catalog stats
     let installed_ids = load_installed_ids(&paths.state);
-    let registry = catalog::scan(&paths.registry, &installed_ids)?;
+    let registry = catalog::scan_all(&paths, &installed_ids)?;
 
     println!();
     println!(
@@ -74,3 +72,99 @@ pub fn run() -> Result<()> {
 
     Ok(())
 }
+
+fn pull_latest(paths: &ForjaPaths) -> Result<()> {
+    // Check if local development mode (symlink, not cloned)
+    if paths.registry.is_symlink() {
+        println!(
+            "{}",
+            "Registry is a local symlink — no update needed.".yellow()
+        );
+        println!(
+            "  Source: {}",
+            std::fs::read_link(&paths.registry)
+                .unwrap_or_default()
+                .display()
+        );
+    } else {
+        record_head(paths);
+
+        println!("Updating registry...");
+        git::attach_default_branch(&paths.registry)?;
+        let output = git::pull(&paths.registry)?;
+        println!("{output}");
+    }
+
+    // Pull named registries too (the project's pinned checkouts, if any);
+    // local ones are symlinks and always current
+    for (entry, dir) in catalog::named_registries(paths) {
+        if entry.local {
+            continue;
+        }
+        println!("Updating registry {}...", entry.name.cyan());
+        git::attach_default_branch(&dir)?;
+        let output = git::pull(&dir)?;
+        println!("{output}");
+    }
+    Ok(())
+}
+
+fn restore_locked(paths: &ForjaPaths) -> Result<()> {
+    let Some(locked) = load_project_lock(paths)? else {
+        return Err(no_lock());
+    };
+    if !paths.registry.is_symlink() {
+        record_head(paths);
+    }
+
+    let moved = lock::restore(paths, &locked)?;
+    if moved.is_empty() {
+        println!("Registries already at the revisions in {LOCK_FILE}.");
+    }
+    for (name, rev) in &moved {
+        println!(
+            "  {} {} pinned at {}",
+            "✓".green(),
+            name.bold(),
+            &rev[..rev.len().min(7)]
+        );
+    }
+    Ok(())
+}
+
+fn verify_frozen(paths: &ForjaPaths) -> Result<()> {
+    let Some(locked) = load_project_lock(paths)? else {
+        return Err(no_lock());
+    };
+    let drift = lock::verify(paths, &locked)?;
+    if drift.is_empty() {
+        println!(
+            "{} {} is up to date ({} registries, {} skills)",
+            "OK:".green().bold(),
+            LOCK_FILE,
+            locked.registries.len(),
+            locked.skills.len()
+        );
+        return Ok(());
+    }
+
+    for d in &drift {
+        println!("  {} {}", "✗".red(), d);
+    }
+    Err(ForjaError::LockMismatch(drift.len()))
+}
+
+fn no_lock() -> ForjaError {
+    ForjaError::InvalidArgument(format!(
+        "no {LOCK_FILE} found — run `forja update` in a project to create one"
+    ))
+}
+
+/// Save HEAD before moving the registry, for `forja diff`.
+fn record_head(paths: &ForjaPaths) {
+    if let Ok(head) = git::head_sha(&paths.registry) {
+        let last_update_path = paths.forja_root.join("last_update.json");
+        let data = serde_json::json!({ "head_before": head });
+        let _ = std::fs::write(&last_update_path, serde_json::to_string_pretty(&data).unwrap_or_default());
+    }
+}
@@ -1,15 +1,21 @@
 use colored::Colorize;
-use dialoguer::Confirm;
+use dialoguer::{Confirm, Select};
 
 use forja_core::error::{ForjaError, Result};
+use forja_core::models::version::Bump;
 use forja_core::paths::ForjaPaths;
-use forja_core::symlink::upgrade;
+use forja_core::symlink::copy::{CopiedFile, Outcome, Resolution};
+use forja_core::symlink::manager::SymlinkManager;
+use forja_core::symlink::upgrade::{self, Upgrade};
 
 /// Reinstall skills that were modified since the last `forja update`.
-pub fn run(skill_filter: Option<&str>, yes: bool) -> Result<()> {
+/// Major version bumps are held back unless `allow_major` is set. In copy
+/// mode, files edited locally are merged with the registry version.
+pub fn run(skill_filter: Option<&str>, allow_major: bool, yes: bool) -> Result<()> {
     let paths = ForjaPaths::ensure_initialized()?;
+    let copy_mode = SymlinkManager::for_paths(&paths).objects_dir().is_some();
 
-    if paths.registry.is_symlink() {
+    if paths.registry.is_symlink() && !copy_mode {
         println!(
             "{}",
             "Registry is a local symlink — upgrade is not needed in dev mode.".yellow()
@@ -25,33 +31,54 @@ pub fn run(skill_filter: Option<&str>, yes: bool) -> Result<()> {
     }
 
     // Filter to specific skill if requested
-    let to_upgrade: Vec<&str> = match skill_filter {
+    let matching: Vec<&Upgrade> = match skill_filter {
         Some(filter) => {
-            let matches: Vec<&str> = upgradable
+            let matches: Vec<&Upgrade> = upgradable
                 .iter()
-                .filter(|id| id.contains(filter))
-                .map(|s| s.as_str())
+                .filter(|u| u.skill_id.contains(filter))
                 .collect();
             if matches.is_empty() {
+                let available: Vec<&str> = upgradable.iter().map(|u| u.skill_id.as_str()).collect();
                 println!(
                     "  No upgradable skills match '{}'. Available: {}",
                     filter,
-                    upgradable.join(", ")
+                    available.join(", ")
                 );
                 return Ok(());
             }
             matches
         }
-        None => upgradable.iter().map(|s| s.as_str()).collect(),
+        None => upgradable.iter().collect(),
     };
 
+    let (to_upgrade, held_back): (Vec<&Upgrade>, Vec<&Upgrade>) = matching
+        .into_iter()
+        .partition(|u| allow_major || u.bump != Bump::Major);
+
     println!("{}", "forja upgrade".bold());
     println!();
-    println!("  {} skill(s) to upgrade:", to_upgrade.len());
-    for id in &to_upgrade {
-        println!("    {}", id.cyan());
+    if !to_upgrade.is_empty() {
+        println!("  {} skill(s) to upgrade:", to_upgrade.len());
+        for upgrade in &to_upgrade {
+            println!("    {}{}", upgrade.skill_id.cyan(), describe(upgrade));
+        }
+        println!();
+    }
+    if !held_back.is_empty() {
+        println!(
+            "  {} {} major upgrade(s) held back:",
+            "WARNING:".yellow().bold(),
+            held_back.len()
+        );
+        for upgrade in &held_back {
+            println!("    {}{}", upgrade.skill_id.yellow(), describe(upgrade));
+        }
+        println!();
+    }
+
+    if to_upgrade.is_empty() {
+        return Err(held_back_error(&held_back));
     }
-    println!();
 
     if !yes {
         let confirmed = Confirm::new()
@@ -65,15 +92,132 @@ pub fn run(skill_filter: Option<&str>, yes: bool) -> Result<()> {
         }
     }
 
-    for id in &to_upgrade {
-        match upgrade::reinstall_skill(&paths, id) {
-            Ok(()) => println!("  {} {}", "upgraded".green(), id),
+    let (mut conflicts, mut skipped) = (0, 0);
+    for upgrade in &to_upgrade {
+        let id = &upgrade.skill_id;
+        if !copy_mode {
+            match upgrade::reinstall_skill(&paths, id) {
+                Ok(kept) => {
+                    println!("  {} {}", "upgraded".green(), id);
+                    print_kept_settings(&kept);
+                }
+                Err(e) => println!("  {} {} — {}", "failed".red(), id, e),
+            }
+            continue;
+        }
+        let mut resolve = |file: &CopiedFile, clean: bool| resolve_diverged(file, clean, yes);
+        match upgrade::upgrade_copies(&paths, id, &mut resolve) {
+            Ok((outcomes, kept)) => {
+                println!("  {} {}", "upgraded".green(), id);
+                print_kept_settings(&kept);
+                for (file, outcome) in &outcomes {
+                    match outcome {
+                        Outcome::Conflicted => conflicts += 1,
+                        Outcome::Skipped => skipped += 1,
+                        _ => {}
+                    }
+                    if let Some(note) = describe_outcome(*outcome) {
+                        println!("    {} {}", note, file.name.dimmed());
+                    }
+                }
+            }
             Err(e) => println!("  {} {} — {}", "failed".red(), id, e),
         }
     }
 
     println!();
     println!("{} Upgrade complete.", "Done:".green().bold());
+    if conflicts > 0 {
+        println!(
+            "  {} {} file(s) have conflict markers (<<<<<<< local ... >>>>>>> registry) to resolve.",
+            "WARNING:".yellow().bold(),
+            conflicts
+        );
+    }
+    if skipped > 0 {
+        println!(
+            "  {} {} file(s) conflict with your local edits and were left as they are. Run {} without --yes to merge them.",
+            "Tip:".cyan().bold(),
+            skipped,
+            "forja upgrade".cyan()
+        );
+    }
+
+    if held_back.is_empty() {
+        Ok(())
+    } else {
+        Err(held_back_error(&held_back))
+    }
+}
+
+/// ` 1.2.0 → 2.0.0 (major)`, or ` (unversioned)` when there's nothing to compare.
+fn describe(upgrade: &Upgrade) -> String {
+    let bump = format!("({})", upgrade.bump);
+    let bump = match upgrade.bump {
+        Bump::Major => bump.red().to_string(),
+        _ => bump.dimmed().to_string(),
+    };
+    match (&upgrade.from, &upgrade.to) {
+        (Some(from), Some(to)) if from != to => format!(" {from} → {to} {bump}"),
+        _ => format!(" {bump}"),
+    }
+}
+
+/// Settle a copied file edited both locally and in the registry. With
+/// `--yes`, clean merges are applied and conflicting files are skipped.
+fn resolve_diverged(file: &CopiedFile, clean: bool, yes: bool) -> Result<Resolution> {
+    if yes {
+        return Ok(if clean {
+            Resolution::Merge
+        } else {
+            Resolution::Skip
+        });
+    }
+
+    let merge = if clean {
+        "Merge both versions (no conflicts)"
+    } else {
+        "Merge with conflict markers to resolve by hand"
+    };
+    let items = [merge, "Keep my version", "Take the registry version"];
+    let selection = Select::new()
+        .with_prompt(format!(
+            "{} was edited locally and changed in the registry",
+            file.name
+        ))
+        .items(&items)
+        .default(if clean { 0 } else { 1 })
+        .interact()
+        .map_err(|e| ForjaError::Dialoguer(e.to_string()))?;
+
+    Ok(match selection {
+        0 => Resolution::Merge,
+        1 => Resolution::KeepLocal,
+        _ => Resolution::TakeRegistry,
+    })
+}
+
+fn describe_outcome(outcome: Outcome) -> Option<String> {
+    let note = match outcome {
+        Outcome::Unchanged => return None,
+        Outcome::Updated => "updated".green(),
+        Outcome::Merged => "merged".green(),
+        Outcome::Conflicted => "conflict".red(),
+        Outcome::KeptLocal => "kept local".yellow(),
+        Outcome::TookRegistry => "replaced".yellow(),
+        Outcome::Skipped => "skipped".yellow(),
+    };
+    Some(format!("{note:>10}"))
+}
+
+/// Settings entries the user edited are left as they are, upstream changes included.
+fn print_kept_settings(kept: &[String]) {
+    for entry in kept {
+        println!("    {:>10} {}", "kept local".yellow(), entry.dimmed());
+    }
+}
 
-    Ok(())
+fn held_back_error(held_back: &[&Upgrade]) -> ForjaError {
+    let ids: Vec<&str> = held_back.iter().map(|u| u.skill_id.as_str()).collect();
+    ForjaError::MajorUpgradeBlocked(ids.join(", "))
 }
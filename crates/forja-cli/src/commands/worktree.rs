@@ -0,0 +1,319 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use colored::Colorize;
+use dialoguer::Confirm;
+
+use forja_core::error::{ForjaError, Result};
+use forja_core::models::plan::{
+    ExecutionCheckpoint, PhaseStatus, checkpoint_path, load_checkpoint, save_checkpoint,
+};
+use forja_core::paths::{ForjaMode, ForjaPaths};
+use forja_core::registry::git;
+
+use crate::commands::git_checkpoint::branch_name;
+
+/// Where a plan's worktree lives: `.forja/worktrees/<plan-id>` in the project,
+/// or at the top of `repo` in global mode so plans of unrelated repositories
+/// never share a directory.
+pub fn worktree_path(paths: &ForjaPaths, repo: &Path, plan_id: &str) -> PathBuf {
+    let forja_root = match paths.mode {
+        ForjaMode::Project => paths.forja_root.clone(),
+        ForjaMode::Global => repo.join(".forja"),
+    };
+    forja_root.join("worktrees").join(plan_id)
+}
+
+/// Move the process into the plan's worktree: the one a resumed checkpoint
+/// recorded, or a new one on `forja/<plan-id>` when `requested`. Every phase,
+/// gate and git operation after this runs there.
+pub fn enter(
+    paths: &ForjaPaths,
+    plan_id: &str,
+    requested: bool,
+    checkpoint: &mut ExecutionCheckpoint,
+) -> Result<()> {
+    let dir = match &checkpoint.worktree {
+        Some(dir) if Path::new(dir).exists() => PathBuf::from(dir),
+        _ if requested => create(paths, plan_id)?,
+        _ => return Ok(()),
+    };
+
+    link_agents(paths, &dir)?;
+    std::env::set_current_dir(&dir)?;
+    checkpoint.worktree = Some(dir.to_string_lossy().to_string());
+
+    println!("  {} {}", "WORKTREE:".bold(), dir.display());
+    println!();
+    Ok(())
+}
+
+/// Create the worktree, or reuse it if an earlier run left it in place.
+fn create(paths: &ForjaPaths, plan_id: &str) -> Result<PathBuf> {
+    let cwd = std::env::current_dir()?;
+    if !git::is_work_tree(&cwd) {
+        return Err(ForjaError::Git(
+            "--worktree needs a git repository with at least one commit".to_string(),
+        ));
+    }
+    let repo = git::toplevel(&cwd)?;
+    let dir = worktree_path(paths, &repo, plan_id);
+    if dir.join(".git").exists() {
+        return Ok(dir);
+    }
+
+    ensure_ignored(paths, &repo)?;
+    if let Some(parent) = dir.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    git::add_worktree(&repo, &dir, &branch_name(plan_id))?;
+    Ok(dir)
+}
+
+/// Keep worktrees out of the main checkout's `git status`: through the
+/// project's `.forja/.gitignore`, or the repository's `info/exclude` in
+/// global mode, which leaves tracked files alone.
+fn ensure_ignored(paths: &ForjaPaths, repo: &Path) -> Result<()> {
+    match paths.mode {
+        ForjaMode::Project => append_line(&paths.forja_root.join(".gitignore"), "worktrees/"),
+        ForjaMode::Global => {
+            let exclude = git::common_dir(repo)?.join("info").join("exclude");
+            if let Some(parent) = exclude.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            append_line(&exclude, "/.forja/worktrees/")
+        }
+    }
+}
+
+/// Add `line` to `file` unless it's already there.
+fn append_line(file: &Path, line: &str) -> Result<()> {
+    let current = fs::read_to_string(file).unwrap_or_default();
+    if current.lines().any(|l| l.trim() == line) {
+        return Ok(());
+    }
+    let separator = if current.is_empty() || current.ends_with('\n') {
+        ""
+    } else {
+        "\n"
+    };
+    fs::write(file, format!("{current}{separator}{line}\n"))?;
+    Ok(())
+}
+
+/// Installed agents, commands and skills are symlinks git ignores, so a
+/// fresh worktree lacks them. Point its `.claude/` dirs at the project's.
+fn link_agents(paths: &ForjaPaths, dir: &Path) -> Result<()> {
+    let Some(root) = &paths.project_root else {
+        return Ok(());
+    };
+    for installed in [
+        &paths.claude_agents,
+        &paths.claude_commands,
+        &paths.claude_skills,
+    ] {
+        let Ok(rel) = installed.strip_prefix(root) else {
+            continue;
+        };
+        let link = dir.join(rel);
+        if !installed.exists() || link.exists() || link.is_symlink() {
+            continue;
+        }
+        if let Some(parent) = link.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        #[cfg(unix)]
+        std::os::unix::fs::symlink(installed, &link)?;
+    }
+    Ok(())
+}
+
+/// Commit anything left in the plan's worktree, merge its branch into the
+/// current checkout, then remove the worktree and branch.
+pub fn merge(plan_id: &str) -> Result<()> {
+    let (ckpt_path, mut checkpoint, dir) = load_worktree(plan_id)?;
+    let repo = main_checkout(&dir)?;
+    let branch = git::current_branch(&dir)?.unwrap_or_else(|| branch_name(plan_id));
+
+    println!("{}", "forja execute merge".bold());
+    println!();
+    println!("  Plan:     {}", plan_id.cyan());
+    println!("  Worktree: {}", dir.display());
+    println!("  Branch:   {}", branch.cyan());
+    println!();
+
+    if let Some(sha) =
+        git::commit_all(&dir, &format!("forja({plan_id}): uncommitted changes"), &[])?
+    {
+        println!(
+            "  {} Committed leftover changes as {}",
+            "✓".green(),
+            &sha[..sha.len().min(7)]
+        );
+    }
+
+    git::merge(&repo, &branch, &format!("Merge forja plan {plan_id}"))?;
+    println!(
+        "  {} Merged {} into the current branch",
+        "✓".green(),
+        branch
+    );
+
+    git::remove_worktree(&repo, &dir, false)?;
+    git::delete_branch(&repo, &branch, false)?;
+    println!("  {} Removed worktree and branch", "✓".green());
+
+    checkpoint.worktree = None;
+    save_checkpoint(&ckpt_path, &checkpoint)?;
+    Ok(())
+}
+
+/// Delete the plan's worktree and branch, throwing its changes away.
+pub fn discard(plan_id: &str, skip_confirm: bool) -> Result<()> {
+    let (ckpt_path, mut checkpoint, dir) = load_worktree(plan_id)?;
+    let repo = main_checkout(&dir)?;
+    let branch = git::current_branch(&dir)?.unwrap_or_else(|| branch_name(plan_id));
+
+    if !skip_confirm {
+        let confirmed = Confirm::new()
+            .with_prompt(format!(
+                "Delete worktree {} and branch '{branch}'? Its changes will be lost",
+                dir.display()
+            ))
+            .default(false)
+            .interact()
+            .map_err(|e| ForjaError::Dialoguer(e.to_string()))?;
+
+        if !confirmed {
+            return Err(ForjaError::PromptCancelled);
+        }
+    }
+
+    git::remove_worktree(&repo, &dir, true)?;
+    git::delete_branch(&repo, &branch, true)?;
+
+    checkpoint.worktree = None;
+    save_checkpoint(&ckpt_path, &checkpoint)?;
+
+    println!(
+        "{} Worktree for plan {} discarded.",
+        "SUCCESS:".green().bold(),
+        plan_id.bold()
+    );
+    Ok(())
+}
+
+/// The checkpoint of a plan that ran in a worktree which still exists and
+/// isn't in use.
+fn load_worktree(plan_id: &str) -> Result<(PathBuf, ExecutionCheckpoint, PathBuf)> {
+    let paths = ForjaPaths::ensure_initialized()?;
+    if !paths.plans.join(format!("{plan_id}.json")).exists() {
+        return Err(ForjaError::PlanNotFound(plan_id.to_string()));
+    }
+    let ckpt_path = checkpoint_path(&paths.plans, plan_id);
+    if !ckpt_path.exists() {
+        return Err(ForjaError::InvalidArgument(format!(
+            "plan '{plan_id}' has not been executed yet"
+        )));
+    }
+    let checkpoint = load_checkpoint(&ckpt_path)?;
+    let Some(dir) = checkpoint.worktree.clone().map(PathBuf::from) else {
+        return Err(ForjaError::InvalidArgument(format!(
+            "plan '{plan_id}' has no worktree (run it with --worktree)"
+        )));
+    };
+    if !dir.exists() {
+        return Err(ForjaError::InvalidArgument(format!(
+            "worktree {} no longer exists",
+            dir.display()
+        )));
+    }
+    if let Some(phase) = checkpoint
+        .phases
+        .iter()
+        .find(|p| p.status == PhaseStatus::InProgress)
+    {
+        return Err(ForjaError::InvalidArgument(format!(
+            "phase '{}' of plan '{plan_id}' is still running — wait for it to finish first",
+            phase.phase_name
+        )));
+    }
+    Ok((ckpt_path, checkpoint, dir))
+}
+
+/// The checkout the command runs from, which must not be the worktree itself.
+fn main_checkout(worktree: &Path) -> Result<PathBuf> {
+    let cwd = std::env::current_dir()?;
+    let top = git::toplevel(&cwd)?;
+    if top == git::toplevel(worktree)? {
+        return Err(ForjaError::InvalidArgument(
+            "run this from the main checkout, not from inside the plan's worktree".to_string(),
+        ));
+    }
+    Ok(top)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn ensure_ignored_appends_once() {
+        let dir = TempDir::new().unwrap();
+        let paths = ForjaPaths::from_project(dir.path().to_path_buf()).unwrap();
+        fs::create_dir_all(&paths.forja_root).unwrap();
+        fs::write(paths.forja_root.join(".gitignore"), "registry/\nplans/").unwrap();
+
+        ensure_ignored(&paths, dir.path()).unwrap();
+        ensure_ignored(&paths, dir.path()).unwrap();
+
+        assert_eq!(
+            fs::read_to_string(paths.forja_root.join(".gitignore")).unwrap(),
+            "registry/\nplans/\nworktrees/\n"
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn link_agents_points_worktree_at_installed_skills() {
+        let dir = TempDir::new().unwrap();
+        let paths = ForjaPaths::from_project(dir.path().to_path_buf()).unwrap();
+        fs::create_dir_all(&paths.claude_agents).unwrap();
+        let wt = worktree_path(&paths, dir.path(), "p1");
+        fs::create_dir_all(&wt).unwrap();
+
+        link_agents(&paths, &wt).unwrap();
+
+        assert!(wt.join(".claude/agents").is_symlink());
+        assert!(!wt.join(".claude/commands").exists());
+    }
+
+    #[test]
+    fn global_worktrees_live_in_their_repository() {
+        let paths = ForjaPaths::global().unwrap();
+        let a = worktree_path(&paths, Path::new("/src/a"), "p1");
+
+        assert_eq!(a, Path::new("/src/a/.forja/worktrees/p1"));
+        assert_ne!(a, worktree_path(&paths, Path::new("/src/b"), "p1"));
+    }
+
+    #[test]
+    fn global_mode_ignores_worktrees_through_info_exclude() {
+        let dir = TempDir::new().unwrap();
+        let init = std::process::Command::new("git")
+            .args(["init", "-q"])
+            .arg(dir.path())
+            .status()
+            .unwrap();
+        assert!(init.success());
+        let paths = ForjaPaths::global().unwrap();
+
+        ensure_ignored(&paths, dir.path()).unwrap();
+        ensure_ignored(&paths, dir.path()).unwrap();
+
+        let exclude = fs::read_to_string(dir.path().join(".git/info/exclude")).unwrap();
+        assert_eq!(exclude.matches("/.forja/worktrees/").count(), 1);
+        assert!(!dir.path().join(".forja").exists());
+    }
+}
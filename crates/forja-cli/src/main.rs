@@ -12,7 +12,11 @@ mod tips;
 mod wizard;
 
 use clap::Parser;
-use cli::{Cli, Commands, SparksCommands, TeamCommands};
+use cli::{
+    BundleCommands, Cli, Commands, ExecuteCommands, MonitorCommands, PlanCommands,
+    RegistryCommands, SparksCommands, TeamCommands,
+};
+use commands::execute::{ExecOptions, FailureHandling};
 
 fn run() -> forja_core::error::Result<()> {
     let cli = Cli::parse();
@@ -29,7 +33,8 @@ fn dispatch(command: Commands) -> forja_core::error::Result<()> {
             registry_url,
             wizard,
             global,
-        } => commands::init::run(registry_url, wizard, global),
+            copy,
+        } => commands::init::run(registry_url, wizard, global, copy),
         Commands::Install { skill, all, global } => {
             if all {
                 commands::install::run_all(global)
@@ -42,14 +47,21 @@ fn dispatch(command: Commands) -> forja_core::error::Result<()> {
             ref skill,
             yes,
             global,
-        } => commands::uninstall::run(skill, yes, global),
-        Commands::Search { ref query } => commands::search::run(query),
-        Commands::List { available } => commands::list::run(available),
-        Commands::Update => commands::update::run(),
+            force,
+        } => commands::uninstall::run(skill, yes, global, force),
+        Commands::Search { ref query } => commands::search::run(query.as_deref()),
+        Commands::List {
+            available,
+            effective,
+        } => commands::list::run(available, effective),
+        Commands::Update { lock, frozen } => commands::update::run(lock, frozen),
         Commands::Info { ref skill } => commands::info::run(skill),
         Commands::Doctor => commands::doctor::run(),
         Commands::Guide { ref phase } => commands::guide::run(phase.as_deref()),
-        Commands::Plan { ref task } => commands::plan::run(task.as_deref()),
+        Commands::Plan { command, ref task } => match command {
+            Some(PlanCommands::Check { ref plan_id, json }) => commands::plan::check(plan_id, json),
+            None => commands::plan::run(task.as_deref()),
+        },
         Commands::Task {
             ref task,
             print,
@@ -57,10 +69,45 @@ fn dispatch(command: Commands) -> forja_core::error::Result<()> {
             ref profile,
         } => commands::task::run(task.as_deref(), print, team.as_deref(), profile.as_deref()),
         Commands::Execute {
+            command: Some(command),
+            ..
+        } => match command {
+            ExecuteCommands::Logs {
+                ref plan_id,
+                phase,
+                follow,
+            } => commands::transcript::show(plan_id, phase, follow),
+            ExecuteCommands::Rollback {
+                ref plan_id,
+                to_phase,
+                yes,
+            } => commands::git_checkpoint::rollback(plan_id, to_phase, yes),
+            ExecuteCommands::Merge { ref plan_id } => commands::worktree::merge(plan_id),
+            ExecuteCommands::Discard { ref plan_id, yes } => {
+                commands::worktree::discard(plan_id, yes)
+            }
+        },
+        Commands::Execute {
+            command: None,
             ref plan_id,
             ref profile,
             resume,
-        } => commands::execute::run(plan_id.as_deref(), profile, resume),
+            jobs,
+            on_failure,
+            non_interactive,
+            commit,
+            worktree,
+        } => commands::execute::run(
+            plan_id.as_deref(),
+            profile,
+            ExecOptions {
+                resume,
+                jobs: jobs.into(),
+                failure: FailureHandling::new(on_failure, non_interactive),
+                commit,
+                worktree,
+            },
+        ),
         Commands::Fix {
             ref description,
             ref profile,
@@ -94,12 +141,66 @@ fn dispatch(command: Commands) -> forja_core::error::Result<()> {
         Commands::Stats => commands::stats::run(),
         Commands::Diff => commands::diff::run(),
         Commands::Docs { ref scope } => commands::docs::run(scope.as_deref()),
-        Commands::Upgrade { ref skill, yes } => commands::upgrade::run(skill.as_deref(), yes),
-        Commands::Monitor { port, no_open } => {
+        Commands::Upgrade {
+            ref skill,
+            allow_major,
+            yes,
+        } => commands::upgrade::run(skill.as_deref(), allow_major, yes),
+        Commands::Monitor {
+            command: Some(MonitorCommands::History),
+            ..
+        } => commands::monitor::history(),
+        Commands::Monitor {
+            command,
+            port,
+            no_open,
+            bind,
+            token,
+            ref sources,
+            tui,
+        } => {
             let rt = tokio::runtime::Runtime::new()
                 .map_err(|e| forja_core::error::ForjaError::Monitor(format!("Failed to start runtime: {e}")))?;
-            rt.block_on(commands::monitor::run(port, !no_open))
+            let options = commands::monitor::ServeOptions {
+                port,
+                bind,
+                auto_open: !no_open,
+                token,
+            };
+            let result = match command {
+                Some(MonitorCommands::Replay {
+                    ref session,
+                    speed,
+                    ref team,
+                }) => rt.block_on(commands::monitor::replay(
+                    session,
+                    speed,
+                    team.as_deref(),
+                    options,
+                )),
+                _ => rt.block_on(commands::monitor::run(options, tui, sources)),
+            };
+            // The file watcher parks a blocking thread for good; don't wait on it
+            rt.shutdown_background();
+            result
         }
+        Commands::Registry { command } => match command {
+            RegistryCommands::Add {
+                ref name,
+                ref source,
+                priority,
+            } => commands::registry::add(name, source, priority),
+            RegistryCommands::Remove { ref name, yes } => commands::registry::remove(name, yes),
+            RegistryCommands::List => commands::registry::list(),
+            RegistryCommands::Priority { ref name, value } => {
+                commands::registry::priority(name, value)
+            }
+        },
+        Commands::Bundle { command } => match command {
+            BundleCommands::List => commands::bundle::list(),
+            BundleCommands::Show { ref name } => commands::bundle::show(name),
+            BundleCommands::Apply { ref name, yes } => commands::bundle::apply(name, yes),
+        },
         Commands::Sparks { command } => match command {
             SparksCommands::List { ref path } => commands::sparks::list(path.as_deref()),
             SparksCommands::Show { ref spec_id } => commands::sparks::show(spec_id),
@@ -108,7 +209,22 @@ fn dispatch(command: Commands) -> forja_core::error::Result<()> {
                 ref spec_id,
                 ref profile,
                 resume,
-            } => commands::sparks::execute(spec_id, profile, resume),
+                jobs,
+                on_failure,
+                non_interactive,
+                commit,
+                worktree,
+            } => commands::sparks::execute(
+                spec_id,
+                profile,
+                ExecOptions {
+                    resume,
+                    jobs: jobs.into(),
+                    failure: FailureHandling::new(on_failure, non_interactive),
+                    commit,
+                    worktree,
+                },
+            ),
             SparksCommands::Status { ref spec_id } => {
                 commands::sparks::status(spec_id.as_deref())
             }
@@ -66,7 +66,7 @@ pub fn get_tips(ctx: &TipContext) -> Vec<Tip> {
         priority: 1,
     });
 
-    tips.sort_by(|a, b| b.priority.cmp(&a.priority));
+    tips.sort_by_key(|t| std::cmp::Reverse(t.priority));
     tips
 }
 
@@ -9,6 +9,13 @@ fn forja() -> Command {
     cmd
 }
 
+/// `forja` run from `cwd`, with `home` standing in for the user's home.
+fn forja_in(home: &std::path::Path, cwd: &std::path::Path, args: &[&str]) -> Command {
+    let mut cmd = forja();
+    cmd.args(args).current_dir(cwd).env("HOME", home);
+    cmd
+}
+
 #[test]
 fn help_contains_all_subcommands() {
     forja().arg("--help").assert().success().stdout(
@@ -121,3 +128,1047 @@ fn team_help_shows_examples() {
         .success()
         .stdout(predicate::str::contains("EXAMPLES:"));
 }
+
+// --- plan check ---
+
+fn project_with_plan(phases: &str) -> tempfile::TempDir {
+    let dir = tempfile::TempDir::new().unwrap();
+    let plans = dir.path().join(".forja").join("plans");
+    std::fs::create_dir_all(&plans).unwrap();
+    std::fs::write(dir.path().join(".forja").join("config.json"), "{}").unwrap();
+    std::fs::write(
+        plans.join("p1.json"),
+        format!(
+            r#"{{"id":"p1","created":"2026-02-08T14:30:22Z","status":"pending","task":"t","team_size":"solo-sprint","profile":"balanced","agents":[],"phases":{phases}}}"#
+        ),
+    )
+    .unwrap();
+    dir
+}
+
+#[test]
+fn plan_check_reports_cycle_as_json() {
+    let dir = project_with_plan(
+        r#"[{"name":"A","agent_role":"coder","instructions":"a","depends_on":["B"]},
+            {"name":"B","agent_role":"coder","instructions":"b","depends_on":["A"]}]"#,
+    );
+
+    forja()
+        .args(["plan", "check", "p1", "--json"])
+        .current_dir(dir.path())
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains(r#""kind": "cycle""#));
+}
+
+#[test]
+fn plan_check_passes_valid_plan() {
+    let dir = project_with_plan(
+        r#"[{"name":"A","agent_role":"coder","instructions":"a"},
+            {"name":"B","agent_role":"coder","instructions":"b","depends_on":["A"]}]"#,
+    );
+
+    forja()
+        .args(["plan", "check", "p1"])
+        .current_dir(dir.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No issues found"));
+}
+
+#[test]
+fn stats_reports_spend_by_profile() {
+    let dir = project_with_plan(r#"[{"name":"A","agent_role":"coder","instructions":"a"}]"#);
+    std::fs::write(
+        dir.path().join(".forja/plans/p1.checkpoint.json"),
+        r#"{"plan_id":"p1","started_at":"t","last_updated":"t","current_phase":0,
+            "phases":[{"phase_index":0,"phase_name":"A","status":"completed",
+                       "usage":{"input_tokens":1200,"output_tokens":300,"cost_usd":0.42}}],
+            "usage":{"input_tokens":1200,"output_tokens":300,"cost_usd":0.42}}"#,
+    )
+    .unwrap();
+
+    forja()
+        .arg("stats")
+        .current_dir(dir.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Spend by profile"))
+        .stdout(predicate::str::contains("balanced"))
+        .stdout(predicate::str::contains("$   0.42"));
+}
+
+// --- execute logs ---
+
+#[test]
+fn execute_logs_prints_phase_transcript() {
+    let dir = project_with_plan(
+        r#"[{"name":"A","agent_role":"coder","instructions":"a"},
+            {"name":"B","agent_role":"coder","instructions":"b"}]"#,
+    );
+    let plans = dir.path().join(".forja/plans");
+    std::fs::write(
+        plans.join("p1.checkpoint.json"),
+        r#"{"plan_id":"p1","started_at":"t","last_updated":"t","current_phase":1,
+            "phases":[{"phase_index":0,"phase_name":"A","status":"completed","log":"phase-0.log"},
+                      {"phase_index":1,"phase_name":"B","status":"failed","log":"phase-1.log"}]}"#,
+    )
+    .unwrap();
+    std::fs::create_dir_all(plans.join("p1-workspace")).unwrap();
+    std::fs::write(plans.join("p1-workspace/phase-0.log"), "schema created\n").unwrap();
+    std::fs::write(
+        plans.join("p1-workspace/phase-1.log"),
+        "[stderr] rate limited\n",
+    )
+    .unwrap();
+
+    forja()
+        .args(["execute", "logs", "p1", "--phase", "2"])
+        .current_dir(dir.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Phase 2: B (failed)"))
+        .stdout(predicate::str::contains("[stderr] rate limited"))
+        .stdout(predicate::str::contains("schema created").not());
+
+    forja()
+        .args(["execute", "logs", "p1", "--phase", "3"])
+        .current_dir(dir.path())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("has no phase 3"));
+}
+
+// --- execute rollback ---
+
+fn git(dir: &std::path::Path, args: &[&str]) -> String {
+    let out = std::process::Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(args)
+        .output()
+        .unwrap();
+    assert!(out.status.success(), "git {args:?} failed");
+    String::from_utf8_lossy(&out.stdout).trim().to_string()
+}
+
+/// Turn `dir` into a git repository on `main` with a committer configured.
+fn git_init(dir: &std::path::Path) {
+    git(dir, &["init", "-q", "-b", "main"]);
+    git(dir, &["config", "user.email", "forja@example.com"]);
+    git(dir, &["config", "user.name", "forja"]);
+}
+
+/// Turn `dir` into a registry repository with everything in it committed.
+/// Returns the commit.
+fn git_registry(dir: &std::path::Path) -> String {
+    git_init(dir);
+    git(dir, &["add", "-A"]);
+    git(dir, &["commit", "-qm", "initial"]);
+    git(dir, &["rev-parse", "HEAD"])
+}
+
+/// Check out the registry at `origin` into `dest`.
+fn clone_registry(origin: &std::path::Path, dest: &std::path::Path) {
+    git(
+        origin,
+        &[
+            "clone",
+            "-q",
+            origin.to_str().unwrap(),
+            dest.to_str().unwrap(),
+        ],
+    );
+}
+
+#[test]
+fn execute_rollback_resets_tree_and_phases() {
+    let dir = project_with_plan(
+        r#"[{"name":"A","agent_role":"coder","instructions":"a"},
+            {"name":"B","agent_role":"coder","instructions":"b"}]"#,
+    );
+    let root = dir.path();
+    git_init(root);
+    std::fs::write(root.join("app.txt"), "v1\n").unwrap();
+    git(root, &["add", "app.txt"]);
+    git(root, &["commit", "-qm", "base"]);
+    let base = git(root, &["rev-parse", "HEAD"]);
+
+    std::fs::write(
+        root.join(".forja/plans/p1.checkpoint.json"),
+        format!(
+            r#"{{"plan_id":"p1","started_at":"t","last_updated":"t","current_phase":1,
+                "phases":[{{"phase_index":0,"phase_name":"A","status":"completed",
+                            "started_at":"2026-01-01T10:00:00+00:00","git_start":{{"head":"{base}"}}}},
+                          {{"phase_index":1,"phase_name":"B","status":"failed",
+                            "started_at":"2026-01-01T11:00:00+00:00","git_start":{{"head":"{base}"}}}}]}}"#
+        ),
+    )
+    .unwrap();
+    std::fs::write(root.join("app.txt"), "half-applied\n").unwrap();
+
+    // Phase A ran without --commit, so the reset wipes its work as well
+    forja()
+        .args(["execute", "rollback", "p1", "--to-phase", "2", "--yes"])
+        .current_dir(root)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Phase 1 (A)"))
+        .stdout(predicate::str::contains("2 phase(s) marked pending"));
+
+    assert_eq!(
+        std::fs::read_to_string(root.join("app.txt")).unwrap(),
+        "v1\n"
+    );
+    assert!(git(root, &["stash", "list"]).contains("forja rollback of p1 to phase 2"));
+    let ckpt = std::fs::read_to_string(root.join(".forja/plans/p1.checkpoint.json")).unwrap();
+    assert!(!ckpt.contains(r#""status": "completed""#));
+    assert!(ckpt.contains(r#""status": "pending""#));
+}
+
+#[test]
+fn execute_rollback_keeps_committed_earlier_phases() {
+    let dir = project_with_plan(
+        r#"[{"name":"A","agent_role":"coder","instructions":"a"},
+            {"name":"B","agent_role":"coder","instructions":"b"}]"#,
+    );
+    let root = dir.path();
+    // An ignored plans dir must not break the stash
+    std::fs::write(root.join(".forja/.gitignore"), "plans/\n").unwrap();
+    git_init(root);
+    std::fs::write(root.join("app.txt"), "v1\n").unwrap();
+    git(root, &["add", "-A"]);
+    git(root, &["commit", "-qm", "base"]);
+    let base = git(root, &["rev-parse", "HEAD"]);
+    std::fs::write(root.join("app.txt"), "v2\n").unwrap();
+    git(root, &["commit", "-qam", "forja(p1): phase 1"]);
+    let phase_a = git(root, &["rev-parse", "HEAD"]);
+
+    std::fs::write(
+        root.join(".forja/plans/p1.checkpoint.json"),
+        format!(
+            r#"{{"plan_id":"p1","started_at":"t","last_updated":"t","current_phase":1,
+                "phases":[{{"phase_index":0,"phase_name":"A","status":"completed",
+                            "started_at":"2026-01-01T10:00:00+00:00",
+                            "git_start":{{"head":"{base}"}},"git_end":{{"head":"{phase_a}"}}}},
+                          {{"phase_index":1,"phase_name":"B","status":"failed",
+                            "started_at":"2026-01-01T11:00:00+00:00","git_start":{{"head":"{phase_a}"}}}}]}}"#
+        ),
+    )
+    .unwrap();
+    std::fs::write(root.join("app.txt"), "half-applied\n").unwrap();
+
+    forja()
+        .args(["execute", "rollback", "p1", "--to-phase", "2", "--yes"])
+        .current_dir(root)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("1 phase(s) marked pending"));
+
+    assert_eq!(
+        std::fs::read_to_string(root.join("app.txt")).unwrap(),
+        "v2\n"
+    );
+    let ckpt = std::fs::read_to_string(root.join(".forja/plans/p1.checkpoint.json")).unwrap();
+    assert!(ckpt.contains(r#""status": "completed""#));
+}
+
+// --- execute merge / discard ---
+
+#[test]
+fn execute_merge_brings_worktree_changes_back() {
+    let dir = project_with_plan(r#"[{"name":"A","agent_role":"coder","instructions":"a"}]"#);
+    let root = dir.path();
+    std::fs::write(root.join(".forja/.gitignore"), "plans/\nworktrees/\n").unwrap();
+    git_init(root);
+    git(root, &["add", "-A"]);
+    git(root, &["commit", "-qm", "base"]);
+
+    let wt = root.join(".forja/worktrees/p1");
+    git(
+        root,
+        &[
+            "worktree",
+            "add",
+            "-q",
+            "-b",
+            "forja/p1",
+            wt.to_str().unwrap(),
+        ],
+    );
+    std::fs::write(wt.join("feature.txt"), "done\n").unwrap();
+    std::fs::write(
+        root.join(".forja/plans/p1.checkpoint.json"),
+        format!(
+            r#"{{"plan_id":"p1","started_at":"t","last_updated":"t","current_phase":0,
+                "phases":[{{"phase_index":0,"phase_name":"A","status":"completed"}}],
+                "worktree":"{}"}}"#,
+            wt.display()
+        ),
+    )
+    .unwrap();
+
+    forja()
+        .args(["execute", "merge", "p1"])
+        .current_dir(root)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Merged forja/p1"));
+
+    assert!(root.join("feature.txt").exists());
+    assert!(!wt.exists());
+    let ckpt = std::fs::read_to_string(root.join(".forja/plans/p1.checkpoint.json")).unwrap();
+    assert!(!ckpt.contains("worktree"));
+
+    forja()
+        .args(["execute", "discard", "p1", "--yes"])
+        .current_dir(root)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("has no worktree"));
+}
+fn write_skill(root: &std::path::Path, id: &str, description: &str) {
+    let dir = root.join("skills").join(id);
+    std::fs::create_dir_all(dir.join("agents")).unwrap();
+    let name = id.rsplit('/').next().unwrap();
+    std::fs::write(
+        dir.join("skill.json"),
+        format!(r#"{{"name":"{name}","description":"{description}"}}"#),
+    )
+    .unwrap();
+}
+
+#[test]
+fn registry_add_shadows_default_skill_until_removed() {
+    let home = tempfile::TempDir::new().unwrap();
+    let project = tempfile::TempDir::new().unwrap();
+    let private = tempfile::TempDir::new().unwrap();
+    let root = project.path();
+    std::fs::create_dir_all(root.join(".forja")).unwrap();
+    std::fs::write(root.join(".forja/config.json"), "{}").unwrap();
+    write_skill(
+        &root.join(".forja/registry"),
+        "code/general/feature",
+        "Public feature",
+    );
+    write_skill(private.path(), "code/general/feature", "Private feature");
+    forja_in(home.path(), root, &["install", "code/general/feature"])
+        .assert()
+        .success();
+
+    forja_in(
+        home.path(),
+        root,
+        &["registry", "add", "acme", private.path().to_str().unwrap()],
+    )
+    .assert()
+    .success()
+    .stdout(predicate::str::contains(
+        "Registry acme added (1 skills, priority 10)",
+    ))
+    .stdout(predicate::str::contains(
+        "code/general/feature now comes from acme (was default)",
+    ));
+    assert!(home.path().join(".forja/registries/acme").is_symlink());
+
+    forja_in(home.path(), root, &["info", "code/general/feature"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Private feature").and(predicate::str::contains("acme")));
+    forja_in(home.path(), root, &["info", "default:code/general/feature"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Public feature"));
+    forja_in(home.path(), root, &["registry", "list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "default (priority: 0, skills: 1, 1 shadowed)",
+        ));
+
+    forja_in(home.path(), root, &["registry", "priority", "acme", "-1"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "code/general/feature now comes from default (was acme)",
+        ));
+    forja_in(home.path(), root, &["info", "code/general/feature"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Public feature"));
+
+    forja_in(home.path(), root, &["registry", "remove", "acme", "--yes"])
+        .assert()
+        .success();
+    assert!(!home.path().join(".forja/registries/acme").exists());
+    forja_in(home.path(), root, &["registry", "remove", "acme", "--yes"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Registry not found: acme"));
+}
+
+#[test]
+fn forja_lock_pins_registry_revision() {
+    let home = tempfile::TempDir::new().unwrap();
+    let origin = tempfile::TempDir::new().unwrap();
+    let project = tempfile::TempDir::new().unwrap();
+    let root = project.path();
+
+    write_skill(origin.path(), "code/general/feature", "v1");
+    let v1 = git_registry(origin.path());
+
+    std::fs::create_dir_all(root.join(".forja")).unwrap();
+    std::fs::write(root.join(".forja/config.json"), "{}").unwrap();
+    let registry = root.join(".forja/registry");
+    clone_registry(origin.path(), &registry);
+
+    forja_in(home.path(), root, &["install", "code/general/feature"])
+        .assert()
+        .success();
+    let lock = std::fs::read_to_string(root.join("forja.lock")).unwrap();
+    assert!(lock.contains(&v1));
+    assert!(lock.contains(r#""id": "code/general/feature""#));
+    assert!(lock.contains("sha256:"));
+    forja_in(home.path(), root, &["update", "--frozen"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("forja.lock is up to date"));
+
+    // A teammate's checkout moved on
+    write_skill(origin.path(), "code/general/feature", "v2");
+    git(origin.path(), &["commit", "-qam", "v2"]);
+    let v2 = git(origin.path(), &["rev-parse", "HEAD"]);
+    git(&registry, &["pull", "-q", "--ff-only"]);
+
+    forja_in(home.path(), root, &["update", "--frozen"])
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains(format!("locked at {}", &v1[..7])))
+        .stderr(predicate::str::contains("does not match"));
+
+    forja_in(home.path(), root, &["update", "--lock"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("default pinned at"));
+    assert_eq!(git(&registry, &["rev-parse", "HEAD"]), v1);
+    forja_in(home.path(), root, &["update", "--frozen"])
+        .assert()
+        .success();
+
+    // A plain update moves to the latest revision and re-locks
+    forja_in(home.path(), root, &["update"]).assert().success();
+    assert_eq!(git(&registry, &["rev-parse", "HEAD"]), v2);
+    let lock = std::fs::read_to_string(root.join("forja.lock")).unwrap();
+    assert!(lock.contains(&v2));
+    forja_in(home.path(), root, &["update", "--frozen"])
+        .assert()
+        .success();
+}
+
+#[test]
+fn forja_lock_pins_named_registries_per_project() {
+    let home = tempfile::TempDir::new().unwrap();
+    let origin = tempfile::TempDir::new().unwrap();
+    let project = tempfile::TempDir::new().unwrap();
+    let root = project.path();
+
+    write_skill(origin.path(), "code/general/feature", "v1");
+    let v1 = git_registry(origin.path());
+    write_skill(origin.path(), "code/general/feature", "v2");
+    git(origin.path(), &["commit", "-qam", "v2"]);
+    let v2 = git(origin.path(), &["rev-parse", "HEAD"]);
+
+    // The machine-wide checkout is at v2; the project locks acme at v1 and
+    // also names a registry this machine has never seen
+    let global = home.path().join(".forja/registries/acme");
+    clone_registry(origin.path(), &global);
+    let manifest = format!(
+        r#"{{"registries":[{{"name":"acme","url":"{}","priority":10}}]}}"#,
+        origin.path().display()
+    );
+    std::fs::write(home.path().join(".forja/registries.json"), &manifest).unwrap();
+    std::fs::create_dir_all(root.join(".forja/registry")).unwrap();
+    std::fs::write(root.join(".forja/config.json"), "{}").unwrap();
+    std::fs::write(
+        root.join("forja.lock"),
+        format!(
+            r#"{{"version":1,"registries":[
+                {{"name":"acme","url":"{url}","rev":"{v1}","priority":10}},
+                {{"name":"extra","url":"{url}","rev":"{v1}","priority":99}}],"skills":[]}}"#,
+            url = origin.path().display()
+        ),
+    )
+    .unwrap();
+
+    forja_in(home.path(), root, &["update", "--lock"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("acme pinned at"));
+
+    let pinned = root.join(".forja/registries");
+    assert_eq!(git(&pinned.join("acme"), &["rev-parse", "HEAD"]), v1);
+    assert_eq!(git(&pinned.join("extra"), &["rev-parse", "HEAD"]), v1);
+    assert_eq!(git(&global, &["rev-parse", "HEAD"]), v2);
+    assert_eq!(
+        std::fs::read_to_string(home.path().join(".forja/registries.json")).unwrap(),
+        manifest
+    );
+    assert!(!home.path().join(".forja/registries/extra").exists());
+    assert!(
+        std::fs::read_to_string(root.join(".forja/registries.json"))
+            .unwrap()
+            .contains(r#""name": "extra""#)
+    );
+    forja_in(home.path(), root, &["info", "acme:code/general/feature"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("v1"));
+}
+
+fn write_versioned_skill(root: &std::path::Path, id: &str, version: &str, changelog: &str) {
+    let dir = root.join("skills").join(id);
+    std::fs::create_dir_all(dir.join("agents")).unwrap();
+    std::fs::write(
+        dir.join("skill.json"),
+        format!(
+            r#"{{ "name": "feature", "description": "Writes features", "version": "{version}" }}"#
+        ),
+    )
+    .unwrap();
+    std::fs::write(
+        dir.join("agents/feature.md"),
+        format!("# Feature {version}"),
+    )
+    .unwrap();
+    std::fs::write(dir.join("CHANGELOG.md"), changelog).unwrap();
+}
+
+#[test]
+fn upgrade_holds_back_major_bumps() {
+    let home = tempfile::TempDir::new().unwrap();
+    let origin = tempfile::TempDir::new().unwrap();
+    let project = tempfile::TempDir::new().unwrap();
+    let root = project.path();
+
+    write_versioned_skill(
+        origin.path(),
+        "code/general/feature",
+        "1.0.0",
+        "## 1.0.0\n\n- Initial release\n",
+    );
+    git_registry(origin.path());
+
+    std::fs::create_dir_all(root.join(".forja")).unwrap();
+    std::fs::write(root.join(".forja/config.json"), "{}").unwrap();
+    let registry = root.join(".forja/registry");
+    clone_registry(origin.path(), &registry);
+
+    forja_in(home.path(), root, &["install", "code/general/feature"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Version: 1.0.0"));
+
+    write_versioned_skill(
+        origin.path(),
+        "code/general/feature",
+        "2.0.0",
+        "## 2.0.0\n\n- Writes tests before code\n\n## 1.0.0\n\n- Initial release\n",
+    );
+    git(origin.path(), &["commit", "-qam", "2.0.0"]);
+    forja_in(home.path(), root, &["update"]).assert().success();
+
+    forja_in(home.path(), root, &["diff"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Major"))
+        .stdout(predicate::str::contains("1.0.0 → 2.0.0"))
+        .stdout(predicate::str::contains("- Writes tests before code"))
+        .stdout(predicate::str::contains("- Initial release").not());
+
+    forja_in(home.path(), root, &["upgrade", "-y"])
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("held back"))
+        .stderr(predicate::str::contains("--allow-major"));
+
+    forja_in(home.path(), root, &["upgrade", "--allow-major", "-y"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("upgraded code/general/feature"));
+    let state = std::fs::read_to_string(root.join(".forja/state.json")).unwrap();
+    assert!(state.contains(r#""version": "2.0.0""#));
+    forja_in(home.path(), root, &["upgrade", "-y"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("up to date"));
+}
+
+fn write_skill_requiring(root: &std::path::Path, id: &str, requires: &[&str]) {
+    write_skill(root, id, "Needs others");
+    let requires: Vec<String> = requires.iter().map(|r| format!("\"{r}\"")).collect();
+    std::fs::write(
+        root.join("skills").join(id).join("skill.json"),
+        format!(
+            r#"{{"name":"{}","description":"Needs others","requires":[{}]}}"#,
+            id.rsplit('/').next().unwrap(),
+            requires.join(",")
+        ),
+    )
+    .unwrap();
+}
+
+#[test]
+fn install_pulls_in_requirements_and_uninstall_guards_them() {
+    let home = tempfile::TempDir::new().unwrap();
+    let project = tempfile::TempDir::new().unwrap();
+    let root = project.path();
+    let registry = root.join(".forja/registry");
+    std::fs::create_dir_all(&registry).unwrap();
+    std::fs::write(root.join(".forja/config.json"), "{}").unwrap();
+    write_skill(&registry, "code/general/feature", "Coder");
+    write_skill(&registry, "deploy/git/commit", "Committer");
+    write_skill_requiring(
+        &registry,
+        "teams/quick-fix/team",
+        &["code/general/feature", "deploy/git/commit"],
+    );
+
+    forja_in(home.path(), root, &["install", "teams/quick-fix/team"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Also installed"))
+        .stdout(predicate::str::contains("code/general/feature"))
+        .stdout(predicate::str::contains("deploy/git/commit"));
+    let state = std::fs::read_to_string(root.join(".forja/state.json")).unwrap();
+    assert!(state.contains("code/general/feature") && state.contains("deploy/git/commit"));
+
+    forja_in(
+        home.path(),
+        root,
+        &["uninstall", "code/general/feature", "-y"],
+    )
+    .assert()
+    .failure()
+    .stderr(predicate::str::contains(
+        "required by: teams/quick-fix/team",
+    ));
+    forja_in(
+        home.path(),
+        root,
+        &["uninstall", "code/general/feature", "-y", "--force"],
+    )
+    .assert()
+    .success();
+
+    forja_in(home.path(), root, &["info", "teams/quick-fix/team"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Requires"))
+        .stdout(predicate::str::contains(
+            "code/general/feature (not installed)",
+        ));
+
+    write_skill_requiring(&registry, "code/a/one", &["code/a/two"]);
+    write_skill_requiring(&registry, "code/a/two", &["code/a/one"]);
+    forja_in(home.path(), root, &["lint"])
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("requires-cycle"))
+        .stdout(predicate::str::contains(
+            "code/a/one -> code/a/two -> code/a/one",
+        ));
+}
+
+#[test]
+fn copy_mode_detects_edits_and_merges_on_upgrade() {
+    let home = tempfile::TempDir::new().unwrap();
+    let project = tempfile::TempDir::new().unwrap();
+    let root = project.path();
+    let registry = root.join(".forja/registry");
+    std::fs::create_dir_all(&registry).unwrap();
+    std::fs::write(
+        root.join(".forja/config.json"),
+        r#"{"install_strategy":"copy"}"#,
+    )
+    .unwrap();
+    write_skill(&registry, "code/general/feature", "Coder");
+    let source = registry.join("skills/code/general/feature/agents/feature.md");
+    std::fs::write(
+        &source,
+        "---\nname: feature\n---\n\none\ntwo\nthree\nfour\nfive\n",
+    )
+    .unwrap();
+
+    forja_in(home.path(), root, &["install", "code/general/feature"])
+        .assert()
+        .success();
+    let copy = root.join(".claude/agents/forja--code--general--feature--feature.md");
+    assert!(!copy.is_symlink());
+    let installed = std::fs::read_to_string(&copy).unwrap();
+    assert!(
+        installed
+            .starts_with("---\nname: feature\n---\n<!-- forja:copy code/general/feature -->\n")
+    );
+
+    std::fs::write(&copy, installed.replace("one", "one (mine)")).unwrap();
+    forja_in(home.path(), root, &["doctor"])
+        .assert()
+        .stdout(predicate::str::contains("EDITED:"));
+
+    std::fs::write(
+        &source,
+        "---\nname: feature\n---\n\none\ntwo\nthree\nfour\nfive (registry)\n",
+    )
+    .unwrap();
+    forja_in(home.path(), root, &["doctor"])
+        .assert()
+        .stdout(predicate::str::contains("DIVERGED:"));
+    forja_in(home.path(), root, &["upgrade", "-y"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("merged"));
+    let merged = std::fs::read_to_string(&copy).unwrap();
+    assert!(merged.contains("one (mine)") && merged.contains("five (registry)"));
+    forja_in(home.path(), root, &["doctor"])
+        .assert()
+        .stdout(predicate::str::contains("DIVERGED:").not());
+
+    std::fs::write(
+        &source,
+        "---\nname: feature\n---\n\none (theirs)\ntwo\nthree\nfour\nfive (registry)\n",
+    )
+    .unwrap();
+    forja_in(home.path(), root, &["upgrade", "-y"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("skipped"));
+    assert_eq!(std::fs::read_to_string(&copy).unwrap(), merged);
+    forja_in(home.path(), root, &["doctor"])
+        .assert()
+        .stdout(predicate::str::contains("DIVERGED:"));
+
+    forja_in(
+        home.path(),
+        root,
+        &["uninstall", "code/general/feature", "-y"],
+    )
+    .assert()
+    .success();
+    assert!(!copy.exists());
+}
+
+#[test]
+fn skill_settings_merge_on_install_and_unmerge_on_uninstall() {
+    let home = tempfile::TempDir::new().unwrap();
+    let project = tempfile::TempDir::new().unwrap();
+    let root = project.path();
+    let registry = root.join(".forja/registry");
+    std::fs::create_dir_all(&registry).unwrap();
+    std::fs::write(root.join(".forja/config.json"), "{}").unwrap();
+    write_skill(&registry, "code/rust/fmt", "Formatter");
+    std::fs::write(
+        registry.join("skills/code/rust/fmt/skill.json"),
+        r#"{"name":"fmt","description":"Formatter",
+            "hooks":{"PostToolUse":[{"matcher":"Write","hooks":[{"type":"command","command":"cargo fmt"}]}]},
+            "permissions":{"allow":["Bash(cargo fmt:*)"]},
+            "mcpServers":{"rust-docs":{"command":"rust-docs-mcp"}}}"#,
+    )
+    .unwrap();
+    std::fs::create_dir_all(root.join(".claude")).unwrap();
+    std::fs::write(root.join(".claude/settings.json"), r#"{"model":"opus"}"#).unwrap();
+
+    let read = |path: &str| -> serde_json::Value {
+        serde_json::from_str(&std::fs::read_to_string(root.join(path)).unwrap()).unwrap()
+    };
+
+    forja_in(home.path(), root, &["install", "code/rust/fmt"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("hooks: PostToolUse"));
+    let settings = read(".claude/settings.json");
+    assert_eq!(settings["model"], "opus");
+    assert_eq!(settings["permissions"]["allow"][0], "Bash(cargo fmt:*)");
+    assert_eq!(
+        settings["hooks"]["PostToolUse"][0]["hooks"][0]["command"],
+        "cargo fmt"
+    );
+    assert_eq!(
+        read(".mcp.json")["mcpServers"]["rust-docs"]["command"],
+        "rust-docs-mcp"
+    );
+
+    forja_in(home.path(), root, &["uninstall", "code/rust/fmt", "-y"])
+        .assert()
+        .success();
+    assert_eq!(
+        read(".claude/settings.json"),
+        serde_json::json!({"model":"opus"})
+    );
+    assert!(read(".mcp.json").get("mcpServers").is_none());
+
+    std::fs::write(
+        root.join(".mcp.json"),
+        r#"{"mcpServers":{"rust-docs":{"command":"my-docs"}}}"#,
+    )
+    .unwrap();
+    forja_in(home.path(), root, &["install", "code/rust/fmt"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("mcpServers.rust-docs"));
+    let state = std::fs::read_to_string(root.join(".forja/state.json")).unwrap();
+    assert!(!state.contains("code/rust/fmt"));
+}
+
+#[test]
+fn failed_install_takes_back_the_requirements_it_installed() {
+    let home = tempfile::TempDir::new().unwrap();
+    let project = tempfile::TempDir::new().unwrap();
+    let root = project.path();
+    let registry = root.join(".forja/registry");
+    std::fs::create_dir_all(&registry).unwrap();
+    std::fs::write(root.join(".forja/config.json"), "{}").unwrap();
+    write_skill(&registry, "code/general/feature", "Coder");
+    std::fs::write(
+        registry.join("skills/code/general/feature/agents/feature.md"),
+        "---\nname: feature\n---\n",
+    )
+    .unwrap();
+    std::fs::write(
+        registry.join("skills/code/general/feature/skill.json"),
+        r#"{"name":"feature","description":"Coder","permissions":{"allow":["Bash(cargo test:*)"]}}"#,
+    )
+    .unwrap();
+    write_skill(&registry, "code/rust/fmt", "Formatter");
+    std::fs::write(
+        registry.join("skills/code/rust/fmt/skill.json"),
+        r#"{"name":"fmt","description":"Formatter","requires":["code/general/feature"],
+            "mcpServers":{"rust-docs":{"command":"rust-docs-mcp"}}}"#,
+    )
+    .unwrap();
+    std::fs::write(
+        root.join(".mcp.json"),
+        r#"{"mcpServers":{"rust-docs":{"command":"my-docs"}}}"#,
+    )
+    .unwrap();
+
+    forja_in(home.path(), root, &["install", "code/rust/fmt"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("mcpServers.rust-docs"));
+
+    assert!(
+        !root
+            .join(".claude/agents/forja--code--general--feature--feature.md")
+            .exists()
+    );
+    let settings = root.join(".claude/settings.json");
+    assert!(
+        !settings.exists()
+            || !std::fs::read_to_string(&settings)
+                .unwrap()
+                .contains("cargo test")
+    );
+    let state = root.join(".forja/state.json");
+    assert!(
+        !state.exists()
+            || !std::fs::read_to_string(&state)
+                .unwrap()
+                .contains("code/general/feature")
+    );
+}
+
+#[test]
+fn bundle_apply_installs_its_skills() {
+    let home = tempfile::TempDir::new().unwrap();
+    let project = tempfile::TempDir::new().unwrap();
+    let root = project.path();
+    let registry = root.join(".forja/registry");
+    std::fs::create_dir_all(registry.join("bundles")).unwrap();
+    std::fs::write(root.join(".forja/config.json"), "{}").unwrap();
+    write_skill(&registry, "code/rust/feature", "Rust coder");
+    write_skill(&registry, "test/tdd/workflow", "TDD");
+    std::fs::write(
+        registry.join("bundles/rust-service.json"),
+        r#"{"description":"Rust service","skills":["code/rust/feature","test/tdd/workflow"],"profile":"fast","stacks":["rust"]}"#,
+    )
+    .unwrap();
+
+    forja_in(home.path(), root, &["bundle", "list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("rust-service"));
+    forja_in(
+        home.path(),
+        root,
+        &["bundle", "apply", "rust-service", "--yes"],
+    )
+    .assert()
+    .success()
+    .stdout(predicate::str::contains("installed"));
+    let state = std::fs::read_to_string(root.join(".forja/state.json")).unwrap();
+    assert!(state.contains("code/rust/feature"));
+    assert!(state.contains("test/tdd/workflow"));
+    assert!(state.contains(r#""active_profile": "fast""#));
+
+    forja_in(home.path(), root, &["bundle", "show", "rust-service"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("(installed)"));
+    forja_in(home.path(), root, &["bundle", "apply", "nope", "--yes"])
+        .assert()
+        .code(3);
+}
+
+#[test]
+fn project_overrides_layer_on_top_of_global_installs() {
+    let home = tempfile::TempDir::new().unwrap();
+    let project = tempfile::TempDir::new().unwrap();
+    let root = project.path();
+    let registry = root.join(".forja/registry");
+    for (id, description) in [
+        ("code/general/feature", "General coder"),
+        ("code/rust/feature", "Rust coder"),
+        ("deploy/git/pr", "PRs"),
+        ("test/tdd/workflow", "TDD"),
+    ] {
+        write_skill(&registry, id, description);
+    }
+    std::fs::write(
+        registry.join("skills/code/general/feature/agents/coder.md"),
+        "---\nname: coder\n---\n\n# Coder\n",
+    )
+    .unwrap();
+    std::fs::create_dir_all(registry.join("skills/deploy/git/pr/commands")).unwrap();
+    std::fs::write(
+        registry.join("skills/deploy/git/pr/commands/pr.md"),
+        "# PR\n",
+    )
+    .unwrap();
+    std::fs::create_dir_all(home.path().join(".forja")).unwrap();
+    std::fs::write(
+        home.path().join(".forja/state.json"),
+        r#"{"version":2,"installed":["code/general/feature","deploy/git/pr"]}"#,
+    )
+    .unwrap();
+    std::fs::write(
+        root.join(".forja/config.json"),
+        r#"{"mode":"project","skills":{"add":["test/tdd/workflow"],"exclude":["deploy/git/pr"],
+            "override":{"code/general/feature":"code/rust/feature"}}}"#,
+    )
+    .unwrap();
+
+    forja_in(home.path(), root, &["list", "--effective"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "project (overrides code/general/feature)",
+        ))
+        .stdout(predicate::str::contains("project (added)"))
+        .stdout(predicate::str::contains(
+            "excluded — its commands stay active",
+        ));
+
+    // Re-running init in the project syncs it, applying the overrides
+    forja_in(home.path(), root, &["init"]).assert().success();
+    let state = std::fs::read_to_string(root.join(".forja/state.json")).unwrap();
+    assert!(state.contains("code/rust/feature"));
+    assert!(state.contains("test/tdd/workflow"));
+    let settings = std::fs::read_to_string(root.join(".claude/settings.json")).unwrap();
+    assert!(settings.contains("Task(coder)"));
+}
+
+#[test]
+fn excluding_a_project_skill_hides_it_without_uninstalling() {
+    let home = tempfile::TempDir::new().unwrap();
+    let project = tempfile::TempDir::new().unwrap();
+    let root = project.path();
+    let registry = root.join(".forja/registry");
+    write_skill(&registry, "deploy/git/commit", "Commits");
+    std::fs::write(
+        registry.join("skills/deploy/git/commit/agents/committer.md"),
+        "---\nname: committer\n---\n\n# Committer\n",
+    )
+    .unwrap();
+    std::fs::write(
+        root.join(".forja/state.json"),
+        r#"{"version":2,"installed":["deploy/git/commit"]}"#,
+    )
+    .unwrap();
+    let config = root.join(".forja/config.json");
+    let agents = root.join(".claude/agents");
+    let linked = || {
+        std::fs::read_dir(&agents)
+            .map(|entries| {
+                entries
+                    .filter_map(|e| e.ok())
+                    .any(|e| e.file_name().to_string_lossy().contains("committer"))
+            })
+            .unwrap_or(false)
+    };
+
+    std::fs::write(
+        &config,
+        r#"{"mode":"project","skills":{"exclude":["deploy/git/commit"]}}"#,
+    )
+    .unwrap();
+    forja_in(home.path(), root, &["init"]).assert().success();
+    assert!(!linked());
+    let state = std::fs::read_to_string(root.join(".forja/state.json")).unwrap();
+    assert!(state.contains("deploy/git/commit"));
+
+    // Dropping the exclude brings it back
+    std::fs::write(&config, r#"{"mode":"project"}"#).unwrap();
+    forja_in(home.path(), root, &["init"]).assert().success();
+    assert!(linked());
+}
+
+#[test]
+fn monitor_history_lists_recorded_sessions() {
+    let home = tempfile::TempDir::new().unwrap();
+    let dir = home.path().join(".forja/monitor");
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(
+        dir.join("20261018-021500.jsonl"),
+        r#"{"at":1792289700000,"event":{"type":"TeamDeleted","team_name":"night-shift"}}
+{"at":1792293300000,"event":{"type":"TeamDeleted","team_name":"night-shift"}}
+"#,
+    )
+    .unwrap();
+
+    forja()
+        .args(["monitor", "history"])
+        .env("HOME", home.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("20261018-021500"))
+        .stdout(predicate::str::contains("2 events"))
+        .stdout(predicate::str::contains("60 min"))
+        .stdout(predicate::str::contains("night-shift"));
+
+    forja()
+        .args(["monitor", "replay", "nope", "--no-open"])
+        .env("HOME", home.path())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("forja monitor history"));
+}
+
+#[test]
+fn monitor_needs_a_token_beyond_localhost() {
+    let home = tempfile::TempDir::new().unwrap();
+    forja()
+        .args(["monitor", "--bind", "0.0.0.0", "--no-open"])
+        .env("HOME", home.path())
+        .env_remove("FORJA_MONITOR_TOKEN")
+        .assert()
+        .code(8)
+        .stderr(predicate::str::contains("FORJA_MONITOR_TOKEN"));
+    assert!(!home.path().join(".forja/monitor").exists());
+
+    forja()
+        .args(["monitor", "--source", "build=https://build-box:3030"])
+        .env("HOME", home.path())
+        .assert()
+        .code(8)
+        .stderr(predicate::str::contains("SSH tunnel"));
+}
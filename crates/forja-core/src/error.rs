@@ -82,6 +82,39 @@ pub enum ForjaError {
 
     #[error("Spec not found: {0}")]
     SpecNotFound(String),
+
+    #[error("Invalid plan: {0}")]
+    InvalidPlan(String),
+
+    #[error("Registry not found: {0}")]
+    RegistryNotFound(String),
+
+    #[error("Registry already exists: {0}")]
+    RegistryAlreadyExists(String),
+
+    #[error("forja.lock does not match the installed skills: {0} difference(s)")]
+    LockMismatch(usize),
+
+    #[error("Major version upgrade held back: {0}")]
+    MajorUpgradeBlocked(String),
+
+    #[error("Skill dependency cycle: {0}")]
+    DependencyCycle(String),
+
+    #[error("Missing skill dependency: {0}, which is not in any registry")]
+    MissingDependency(String),
+
+    #[error("Skill {0} is required by: {1}")]
+    RequiredBy(String, String),
+
+    #[error("Skill settings conflict: {0}")]
+    SettingsConflict(String),
+
+    #[error("Bundle not found: {0}")]
+    BundleNotFound(String),
+
+    #[error("Monitor session not found: {0}")]
+    SessionNotFound(String),
 }
 
 impl ForjaError {
@@ -120,6 +153,29 @@ impl ForjaError {
             Self::Yaml(_) => "Check the YAML frontmatter syntax in the spec file",
             Self::InvalidSpec(_) => "Spec files need YAML frontmatter between --- delimiters",
             Self::SpecNotFound(_) => "Check the path and run: forja sparks list",
+            Self::InvalidPlan(_) => "Fix the phases in the plan JSON and run: forja plan check <id>",
+            Self::RegistryNotFound(_) => "List configured registries: forja registry list",
+            Self::RegistryAlreadyExists(_) => {
+                "Pick another name or remove it first: forja registry remove <name>"
+            }
+            Self::LockMismatch(_) => {
+                "Restore the locked revisions with: forja update --lock (or re-lock with: forja update)"
+            }
+            Self::MajorUpgradeBlocked(_) => {
+                "Review the changes with: forja diff, then run: forja upgrade --allow-major"
+            }
+            Self::DependencyCycle(_) => {
+                "Break the cycle in the skills' `requires` lists, then run: forja lint"
+            }
+            Self::MissingDependency(_) => {
+                "Add the registry that ships it (forja registry add) or fix `requires` in skill.json"
+            }
+            Self::RequiredBy(_, _) => "Uninstall the dependent skills first, or pass --force",
+            Self::SettingsConflict(_) => {
+                "Remove or rename the conflicting entry, or uninstall the skill that set it"
+            }
+            Self::BundleNotFound(_) => "List available bundles: forja bundle list",
+            Self::SessionNotFound(_) => "List recorded sessions: forja monitor history",
         }
     }
 
@@ -130,7 +186,10 @@ impl ForjaError {
             Self::SkillNotFound(_)
             | Self::AmbiguousSkillName(_)
             | Self::TeamNotFound(_)
-            | Self::PlanNotFound(_) => 3,
+            | Self::PlanNotFound(_)
+            | Self::RegistryNotFound(_)
+            | Self::BundleNotFound(_)
+            | Self::SessionNotFound(_) => 3,
             Self::Io(_) | Self::Json(_) => 4,
             Self::Monitor(_) => 5,
             Self::NoChangesToReview => 6,
@@ -139,6 +198,7 @@ impl ForjaError {
             Self::Yaml(_) => 4,
             Self::InvalidSpec(_) => 9,
             Self::SpecNotFound(_) => 3,
+            Self::InvalidPlan(_) => 9,
             _ => 1,
         }
     }
@@ -179,6 +239,17 @@ mod tests {
             ForjaError::Yaml(serde_yaml::from_str::<String>("invalid: [").unwrap_err()),
             ForjaError::InvalidSpec("test".into()),
             ForjaError::SpecNotFound("test".into()),
+            ForjaError::InvalidPlan("test".into()),
+            ForjaError::RegistryNotFound("test".into()),
+            ForjaError::RegistryAlreadyExists("test".into()),
+            ForjaError::LockMismatch(1),
+            ForjaError::MajorUpgradeBlocked("test".into()),
+            ForjaError::DependencyCycle("test".into()),
+            ForjaError::MissingDependency("test".into()),
+            ForjaError::RequiredBy("test".into(), "test".into()),
+            ForjaError::SettingsConflict("test".into()),
+            ForjaError::BundleNotFound("test".into()),
+            ForjaError::SessionNotFound("test".into()),
         ];
 
         for variant in &variants {
@@ -14,3 +14,4 @@ pub mod registry;
 pub mod settings;
 pub mod symlink;
 pub mod templates;
+pub mod text;
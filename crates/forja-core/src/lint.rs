@@ -2,10 +2,16 @@ use std::fs;
 use std::path::Path;
 
 use crate::models::lint::{LintIssue, LintLevel, LintResult};
+use crate::models::registry::Registry;
+use crate::registry::deps;
 
 const MANIFEST_FILE: &str = "skill.json";
 const LEGACY_MANIFEST_DIR: &str = ".claude-plugin";
 const LEGACY_MANIFEST_FILE: &str = "plugin.json";
+const SKILL_FILE: &str = "SKILL.md";
+/// Limits Claude Code enforces on `SKILL.md` frontmatter.
+const SKILL_NAME_MAX: usize = 64;
+const SKILL_DESCRIPTION_MAX: usize = 1024;
 
 /// Lint a single skill directory and return all issues found.
 pub fn lint_skill(skill_path: &Path, skill_id: &str) -> LintResult {
@@ -13,6 +19,7 @@ pub fn lint_skill(skill_path: &Path, skill_id: &str) -> LintResult {
 
     check_manifest(skill_path, &mut issues);
     check_agents(skill_path, &mut issues);
+    check_skill_dirs(skill_path, &mut issues);
     check_readme(skill_path, &mut issues);
     check_name_convention(skill_id, &mut issues);
 
@@ -23,6 +30,43 @@ pub fn lint_skill(skill_path: &Path, skill_id: &str) -> LintResult {
     }
 }
 
+/// Cross-skill checks a single directory can't answer: `requires` entries no
+/// registry ships, and dependency cycles. Issues are keyed by skill ID.
+pub fn lint_dependencies(registry: &Registry) -> Vec<(String, LintIssue)> {
+    let mut issues = Vec::new();
+
+    for skill in &registry.skills {
+        for required in &skill.requires {
+            if registry.find_by_id(required).is_none() {
+                issues.push((
+                    skill.id.clone(),
+                    LintIssue {
+                        level: LintLevel::Error,
+                        rule: "requires-missing".to_string(),
+                        message: format!("Requires '{required}', which is not in any registry"),
+                    },
+                ));
+            }
+        }
+    }
+
+    for cycle in deps::find_cycles(registry) {
+        let message = format!("Dependency cycle: {}", cycle.join(" -> "));
+        for id in &cycle[..cycle.len() - 1] {
+            issues.push((
+                id.clone(),
+                LintIssue {
+                    level: LintLevel::Error,
+                    rule: "requires-cycle".to_string(),
+                    message: message.clone(),
+                },
+            ));
+        }
+    }
+
+    issues
+}
+
 fn check_manifest(skill_path: &Path, issues: &mut Vec<LintIssue>) {
     let modern = skill_path.join(MANIFEST_FILE);
     let legacy = skill_path.join(LEGACY_MANIFEST_DIR).join(LEGACY_MANIFEST_FILE);
@@ -125,6 +169,104 @@ fn check_agents(skill_path: &Path, issues: &mut Vec<LintIssue>) {
     }
 }
 
+fn check_skill_dirs(skill_path: &Path, issues: &mut Vec<LintIssue>) {
+    let Ok(entries) = fs::read_dir(skill_path.join("skills")) else {
+        return;
+    };
+
+    for entry in entries.flatten() {
+        let dir = entry.path();
+        if !dir.is_dir() {
+            continue;
+        }
+        let dir_name = entry.file_name().to_string_lossy().to_string();
+
+        let Ok(content) = fs::read_to_string(dir.join(SKILL_FILE)) else {
+            issues.push(LintIssue {
+                level: LintLevel::Error,
+                rule: "skill-file-missing".to_string(),
+                message: format!(
+                    "skills/{dir_name}/ has no {SKILL_FILE}, so it won't be installed"
+                ),
+            });
+            continue;
+        };
+
+        let yaml = match crate::frontmatter::split_frontmatter(&content) {
+            Ok((yaml, _)) => yaml,
+            Err(_) => {
+                issues.push(LintIssue {
+                    level: LintLevel::Error,
+                    rule: "skill-frontmatter-missing".to_string(),
+                    message: format!("skills/{dir_name}/{SKILL_FILE} has no YAML frontmatter"),
+                });
+                continue;
+            }
+        };
+        let frontmatter: serde_yaml::Value = match serde_yaml::from_str(yaml) {
+            Ok(v) => v,
+            Err(e) => {
+                issues.push(LintIssue {
+                    level: LintLevel::Error,
+                    rule: "skill-frontmatter-invalid".to_string(),
+                    message: format!(
+                        "Malformed frontmatter in skills/{dir_name}/{SKILL_FILE}: {e}"
+                    ),
+                });
+                continue;
+            }
+        };
+        let field = |key: &str| {
+            frontmatter
+                .get(key)
+                .and_then(|v| v.as_str())
+                .map(str::trim)
+                .filter(|v| !v.is_empty())
+        };
+
+        match field("name") {
+            None => issues.push(LintIssue {
+                level: LintLevel::Error,
+                rule: "skill-name-missing".to_string(),
+                message: format!("Missing or empty 'name' in skills/{dir_name}/{SKILL_FILE}"),
+            }),
+            Some(name) if !is_kebab_case(name) || name.len() > SKILL_NAME_MAX => {
+                issues.push(LintIssue {
+                    level: LintLevel::Error,
+                    rule: "skill-name-invalid".to_string(),
+                    message: format!(
+                        "Skill name '{name}' must be kebab-case and at most {SKILL_NAME_MAX} characters"
+                    ),
+                })
+            }
+            Some(name) if name != dir_name => issues.push(LintIssue {
+                level: LintLevel::Warning,
+                rule: "skill-name-mismatch".to_string(),
+                message: format!("Skill name '{name}' differs from its directory skills/{dir_name}/"),
+            }),
+            Some(_) => {}
+        }
+
+        match field("description") {
+            None => issues.push(LintIssue {
+                level: LintLevel::Error,
+                rule: "skill-description-missing".to_string(),
+                message: format!("Missing or empty 'description' in skills/{dir_name}/{SKILL_FILE}"),
+            }),
+            Some(description) if description.chars().count() > SKILL_DESCRIPTION_MAX => {
+                issues.push(LintIssue {
+                    level: LintLevel::Error,
+                    rule: "skill-description-too-long".to_string(),
+                    message: format!(
+                        "Description in skills/{dir_name}/{SKILL_FILE} exceeds {SKILL_DESCRIPTION_MAX} characters"
+                    ),
+                })
+            }
+            Some(_) => {}
+        }
+    }
+}
+
 fn check_readme(skill_path: &Path, issues: &mut Vec<LintIssue>) {
     if !skill_path.join("README.md").exists() {
         issues.push(LintIssue {
@@ -188,6 +330,75 @@ mod tests {
         assert!(result.issues.iter().any(|i| i.rule == "manifest-missing"));
     }
 
+    #[test]
+    fn lint_skill_dirs_checks_skill_md_frontmatter() {
+        let dir = TempDir::new().unwrap();
+        let skills = dir.path().join("skills");
+        for (name, content) in [
+            (
+                "commit",
+                Some("---\nname: commit\ndescription: Commit staged changes\n---\n"),
+            ),
+            ("empty", None),
+            ("bare", Some("# No frontmatter\n")),
+            ("Renamed", Some("---\nname: other\ndescription: x\n---\n")),
+            ("bad-name", Some("---\nname: Bad_Name\n---\n")),
+        ] {
+            fs::create_dir_all(skills.join(name)).unwrap();
+            if let Some(content) = content {
+                fs::write(skills.join(name).join(SKILL_FILE), content).unwrap();
+            }
+        }
+
+        let mut issues = Vec::new();
+        check_skill_dirs(dir.path(), &mut issues);
+        let mut rules: Vec<&str> = issues.iter().map(|i| i.rule.as_str()).collect();
+        rules.sort();
+        assert_eq!(
+            rules,
+            [
+                "skill-description-missing",
+                "skill-file-missing",
+                "skill-frontmatter-missing",
+                "skill-name-invalid",
+                "skill-name-mismatch",
+            ]
+        );
+    }
+
+    #[test]
+    fn lint_dependencies_flags_missing_and_cyclic_requires() {
+        let dir = TempDir::new().unwrap();
+        for (id, requires) in [
+            ("code/a/one", r#"["code/a/two"]"#),
+            ("code/a/two", r#"["code/a/one"]"#),
+            ("code/a/three", r#"["code/a/gone"]"#),
+        ] {
+            let skill_dir = dir.path().join("skills").join(id);
+            fs::create_dir_all(&skill_dir).unwrap();
+            fs::write(
+                skill_dir.join(MANIFEST_FILE),
+                format!(r#"{{ "name": "x", "description": "x", "requires": {requires} }}"#),
+            )
+            .unwrap();
+        }
+        let registry = crate::registry::catalog::scan(dir.path(), &[]).unwrap();
+
+        let issues = lint_dependencies(&registry);
+        let rules: Vec<(&str, &str)> = issues
+            .iter()
+            .map(|(id, issue)| (id.as_str(), issue.rule.as_str()))
+            .collect();
+        assert_eq!(
+            rules,
+            [
+                ("code/a/three", "requires-missing"),
+                ("code/a/one", "requires-cycle"),
+                ("code/a/two", "requires-cycle"),
+            ]
+        );
+    }
+
     #[test]
     fn lint_valid_skill() {
         let dir = TempDir::new().unwrap();
@@ -0,0 +1,28 @@
+use serde::{Deserialize, Serialize};
+
+/// A one-shot stack setup shipped by a registry as `bundles/<name>.json`:
+/// the skills to install, the team presets to create and the model profile
+/// to use for them.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Bundle {
+    /// File stem of the manifest.
+    #[serde(skip)]
+    pub name: String,
+    /// Registry the manifest was read from.
+    #[serde(skip)]
+    pub registry: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub skills: Vec<String>,
+    /// Team preset names (`solo-sprint`, `full-product`, ...).
+    #[serde(default)]
+    pub teams: Vec<String>,
+    /// Model profile for the teams; `balanced` when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub profile: Option<String>,
+    /// Stack components `forja init` suggests the bundle for, as detected
+    /// in the project (`rust`, `next.js`, `typescript`, ...).
+    #[serde(default)]
+    pub stacks: Vec<String>,
+}
@@ -1,4 +1,4 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 /// Claude Code team config (`~/.claude/teams/<name>/config.json`).
 #[derive(Debug, Clone, Deserialize)]
@@ -41,7 +41,7 @@ pub struct ClaudeTeamMember {
 
 /// Claude Code inbox message (`~/.claude/teams/<name>/inboxes/<member>.json`).
 /// The file contains a JSON array of these.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ClaudeInboxMessage {
     #[serde(default)]
     pub from: String,
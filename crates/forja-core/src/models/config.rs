@@ -1,6 +1,8 @@
 use crate::error::Result;
+use crate::models::gate::QualityGate;
 use crate::paths::ForjaMode;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::Path;
 
@@ -14,6 +16,87 @@ fn default_registry_url() -> String {
     "https://github.com/dmend3z/forja.git".to_string()
 }
 
+/// How installed skill files get into `.claude/agents` and `.claude/commands`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InstallStrategy {
+    /// `forja--` symlinks into the registry.
+    #[default]
+    Symlink,
+    /// Marked copies, with their registry originals kept in `.forja/objects/`.
+    Copy,
+}
+
+impl InstallStrategy {
+    pub fn is_symlink(&self) -> bool {
+        *self == InstallStrategy::Symlink
+    }
+}
+
+/// How a project changes the skills it inherits from the global install
+/// (`skills` in `.forja/config.json`).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SkillOverrides {
+    /// Skills to install in the project on top of the global ones.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub add: Vec<String>,
+    /// Skills to leave out of the project, wherever they're installed.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub exclude: Vec<String>,
+    /// Skills to swap for another one, e.g. `code/general/feature` → `code/rust/feature`.
+    #[serde(
+        default,
+        rename = "override",
+        skip_serializing_if = "BTreeMap::is_empty"
+    )]
+    pub replace: BTreeMap<String, String>,
+}
+
+impl SkillOverrides {
+    pub fn is_empty(&self) -> bool {
+        self.add.is_empty() && self.exclude.is_empty() && self.replace.is_empty()
+    }
+}
+
+fn default_stall_minutes() -> u64 {
+    15
+}
+fn default_unread_limit() -> usize {
+    5
+}
+
+/// When `forja monitor` raises alerts and what it runs for them (`monitor`
+/// in `.forja/config.json`). A limit of 0 turns its check off.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MonitorSettings {
+    /// Minutes an in-progress task may go without a message from its owner.
+    #[serde(default = "default_stall_minutes")]
+    pub stall_minutes: u64,
+    /// Unread messages in one inbox that get it reported.
+    #[serde(default = "default_unread_limit")]
+    pub unread_limit: usize,
+    /// Shell command run for each new alert, e.g. a desktop notification.
+    /// Gets `FORJA_ALERT_KIND`, `FORJA_ALERT_TEAM` and `FORJA_ALERT_MESSAGE`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub alert_hook: Option<String>,
+}
+
+impl Default for MonitorSettings {
+    fn default() -> Self {
+        Self {
+            stall_minutes: default_stall_minutes(),
+            unread_limit: default_unread_limit(),
+            alert_hook: None,
+        }
+    }
+}
+
+impl MonitorSettings {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
 /// Persisted forja configuration (`.forja/config.json`).
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ForjaConfig {
@@ -31,6 +114,21 @@ pub struct ForjaConfig {
 
     #[serde(default)]
     pub local: bool,
+
+    /// Project-wide gates, used when a plan defines no executable gates of its own.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub quality_gates: Vec<QualityGate>,
+
+    #[serde(default, skip_serializing_if = "InstallStrategy::is_symlink")]
+    pub install_strategy: InstallStrategy,
+
+    /// Project-mode changes to the globally installed skills.
+    #[serde(default, skip_serializing_if = "SkillOverrides::is_empty")]
+    pub skills: SkillOverrides,
+
+    /// Alert thresholds and hook of `forja monitor`.
+    #[serde(default, skip_serializing_if = "MonitorSettings::is_default")]
+    pub monitor: MonitorSettings,
 }
 
 impl ForjaConfig {
@@ -49,6 +147,10 @@ impl ForjaConfig {
             project_name,
             registry_url,
             local,
+            quality_gates: Vec::new(),
+            install_strategy: InstallStrategy::Symlink,
+            skills: SkillOverrides::default(),
+            monitor: MonitorSettings::default(),
         }
     }
 }
@@ -99,6 +201,74 @@ mod tests {
         assert_eq!(loaded.version, 2); // default
         assert_eq!(loaded.mode, ForjaMode::Global); // default
         assert!(loaded.local);
+        assert_eq!(loaded.install_strategy, InstallStrategy::Symlink);
+    }
+
+    #[test]
+    fn install_strategy_roundtrips_and_defaults_to_symlink() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("config.json");
+
+        let mut config = ForjaConfig::new(ForjaMode::Project, "https://x.git".to_string(), false);
+        save_config(&path, &config).unwrap();
+        assert!(
+            !fs::read_to_string(&path)
+                .unwrap()
+                .contains("install_strategy")
+        );
+
+        config.install_strategy = InstallStrategy::Copy;
+        save_config(&path, &config).unwrap();
+        assert!(
+            fs::read_to_string(&path)
+                .unwrap()
+                .contains(r#""install_strategy": "copy""#)
+        );
+        assert_eq!(
+            load_config(&path).unwrap().install_strategy,
+            InstallStrategy::Copy
+        );
+    }
+
+    #[test]
+    fn skill_overrides_parse_from_project_config() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("config.json");
+        fs::write(
+            &path,
+            r#"{"mode":"project","skills":{"add":["test/tdd/workflow"],"exclude":["deploy/git/pr"],
+                "override":{"code/general/feature":"code/rust/feature"}}}"#,
+        )
+        .unwrap();
+
+        let skills = load_config(&path).unwrap().skills;
+        assert_eq!(skills.add, ["test/tdd/workflow"]);
+        assert_eq!(skills.exclude, ["deploy/git/pr"]);
+        assert_eq!(skills.replace["code/general/feature"], "code/rust/feature");
+
+        let config = ForjaConfig::new(ForjaMode::Project, "https://x.git".to_string(), false);
+        save_config(&path, &config).unwrap();
+        assert!(!fs::read_to_string(&path).unwrap().contains("skills"));
+    }
+
+    #[test]
+    fn monitor_settings_fill_in_defaults() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("config.json");
+        fs::write(
+            &path,
+            r#"{"monitor":{"stall_minutes":30,"alert_hook":"notify-send forja"}}"#,
+        )
+        .unwrap();
+
+        let monitor = load_config(&path).unwrap().monitor;
+        assert_eq!(monitor.stall_minutes, 30);
+        assert_eq!(monitor.unread_limit, 5);
+        assert_eq!(monitor.alert_hook.as_deref(), Some("notify-send forja"));
+
+        let config = ForjaConfig::new(ForjaMode::Global, "https://x.git".to_string(), false);
+        save_config(&path, &config).unwrap();
+        assert!(!fs::read_to_string(&path).unwrap().contains("monitor"));
     }
 
     #[test]
@@ -0,0 +1,194 @@
+use std::fmt;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+/// Gates without an explicit timeout are killed after ten minutes.
+pub const DEFAULT_GATE_TIMEOUT_SECS: u64 = 600;
+
+/// A check that runs after each completed phase.
+///
+/// In plan JSON a gate is either a plain string — a checklist item handed to
+/// the agent, never executed — or an object with a `command` that forja runs:
+///
+/// ```json
+/// "quality_gates": [
+///   "No TODOs left behind",
+///   { "name": "tests", "command": "npm test", "cwd": "web", "timeout_secs": 300 },
+///   { "name": "lint", "command": "npm run lint", "blocking": false }
+/// ]
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(from = "GateSpec", into = "GateSpec")]
+pub struct QualityGate {
+    pub name: String,
+    /// Shell command to run; `None` for checklist-only gates.
+    pub command: Option<String>,
+    /// Working directory, relative to where execution was started.
+    pub cwd: Option<String>,
+    pub timeout_secs: Option<u64>,
+    /// A failing blocking gate fails the phase; advisory gates only warn.
+    pub blocking: bool,
+}
+
+impl QualityGate {
+    /// An executable gate that blocks the phase on failure.
+    pub fn command(name: &str, command: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            command: Some(command.to_string()),
+            cwd: None,
+            timeout_secs: None,
+            blocking: true,
+        }
+    }
+
+    /// Mark the gate advisory: failures are reported but don't fail the phase.
+    pub fn advisory(mut self) -> Self {
+        self.blocking = false;
+        self
+    }
+
+    pub fn is_executable(&self) -> bool {
+        self.command.is_some()
+    }
+
+    pub fn timeout(&self) -> Duration {
+        Duration::from_secs(self.timeout_secs.unwrap_or(DEFAULT_GATE_TIMEOUT_SECS))
+    }
+}
+
+impl From<&str> for QualityGate {
+    fn from(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            command: None,
+            cwd: None,
+            timeout_secs: None,
+            blocking: true,
+        }
+    }
+}
+
+impl fmt::Display for QualityGate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.command {
+            Some(command) if *command != self.name => write!(f, "{} (`{command}`)", self.name),
+            _ => f.write_str(&self.name),
+        }
+    }
+}
+
+/// On-disk shape of a gate: a bare string or a full object.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+enum GateSpec {
+    Checklist(String),
+    Full {
+        name: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        command: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        cwd: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        timeout_secs: Option<u64>,
+        #[serde(default = "default_blocking")]
+        blocking: bool,
+    },
+}
+
+fn default_blocking() -> bool {
+    true
+}
+
+impl From<GateSpec> for QualityGate {
+    fn from(spec: GateSpec) -> Self {
+        match spec {
+            GateSpec::Checklist(name) => QualityGate::from(name.as_str()),
+            GateSpec::Full {
+                name,
+                command,
+                cwd,
+                timeout_secs,
+                blocking,
+            } => Self {
+                name,
+                command,
+                cwd,
+                timeout_secs,
+                blocking,
+            },
+        }
+    }
+}
+
+impl From<QualityGate> for GateSpec {
+    fn from(gate: QualityGate) -> Self {
+        if gate.command.is_none() && gate.cwd.is_none() && gate.timeout_secs.is_none() {
+            return GateSpec::Checklist(gate.name);
+        }
+        GateSpec::Full {
+            name: gate.name,
+            command: gate.command,
+            cwd: gate.cwd,
+            timeout_secs: gate.timeout_secs,
+            blocking: gate.blocking,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_strings_and_objects() {
+        let gates: Vec<QualityGate> = serde_json::from_str(
+            r#"["All tests must pass",
+                {"name": "lint", "command": "npm run lint", "blocking": false, "timeout_secs": 30}]"#,
+        )
+        .unwrap();
+
+        assert!(!gates[0].is_executable());
+        assert_eq!(gates[0].name, "All tests must pass");
+        assert_eq!(gates[1].command.as_deref(), Some("npm run lint"));
+        assert!(!gates[1].blocking);
+        assert_eq!(gates[1].timeout(), Duration::from_secs(30));
+    }
+
+    #[test]
+    fn checklist_gates_round_trip_as_strings() {
+        let gates = vec![
+            QualityGate::from("No TypeScript errors"),
+            QualityGate::command("tests", "cargo test"),
+        ];
+        let json = serde_json::to_string(&gates).unwrap();
+        assert!(json.starts_with(r#"["No TypeScript errors",{"name":"tests""#));
+
+        let back: Vec<QualityGate> = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, gates);
+    }
+
+    #[test]
+    fn object_gates_default_to_blocking() {
+        let gate: QualityGate =
+            serde_json::from_str(r#"{"name":"t","command":"go test ./..."}"#).unwrap();
+        assert!(gate.blocking);
+        assert_eq!(
+            gate.timeout(),
+            Duration::from_secs(DEFAULT_GATE_TIMEOUT_SECS)
+        );
+    }
+
+    #[test]
+    fn display_includes_command_when_it_differs_from_name() {
+        assert_eq!(
+            QualityGate::command("tests", "npm test").to_string(),
+            "tests (`npm test`)"
+        );
+        assert_eq!(
+            QualityGate::from("Docs updated").to_string(),
+            "Docs updated"
+        );
+    }
+}
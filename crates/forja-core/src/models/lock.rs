@@ -0,0 +1,142 @@
+use crate::error::Result;
+use crate::paths::ForjaPaths;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// File name of the lock, kept at the project root next to `.forja/`.
+pub const LOCK_FILE: &str = "forja.lock";
+
+fn default_version() -> u32 {
+    1
+}
+
+/// Pinned registry revisions and installed skill contents (`forja.lock`).
+/// Committed with the project so every teammate gets the same agents.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ForjaLock {
+    #[serde(default = "default_version")]
+    pub version: u32,
+    #[serde(default)]
+    pub registries: Vec<LockedRegistry>,
+    #[serde(default)]
+    pub skills: Vec<LockedSkill>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LockedRegistry {
+    pub name: String,
+    pub url: String,
+    /// Commit SHA; `None` when the registry isn't a git checkout.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rev: Option<String>,
+    /// Local registries are someone's working copy and are never checked out.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub local: bool,
+    #[serde(default)]
+    pub priority: i32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LockedSkill {
+    pub id: String,
+    pub registry: String,
+    /// `sha256:<hex>` over the skill directory's files.
+    pub hash: String,
+}
+
+impl ForjaLock {
+    pub fn new(registries: Vec<LockedRegistry>, skills: Vec<LockedSkill>) -> Self {
+        Self {
+            version: default_version(),
+            registries,
+            skills,
+        }
+    }
+
+    pub fn registry(&self, name: &str) -> Option<&LockedRegistry> {
+        self.registries.iter().find(|r| r.name == name)
+    }
+}
+
+/// Where the lock lives: `<project>/forja.lock`. Global mode has no lock.
+pub fn lock_path(paths: &ForjaPaths) -> Option<PathBuf> {
+    paths.project_root.as_ref().map(|root| root.join(LOCK_FILE))
+}
+
+/// Load the lock, `None` when the file doesn't exist.
+pub fn load_lock(path: &Path) -> Result<Option<ForjaLock>> {
+    if !path.exists() {
+        return Ok(None);
+    }
+    let content = fs::read_to_string(path)?;
+    Ok(Some(serde_json::from_str(&content)?))
+}
+
+/// Load the current project's lock; `None` in global mode or without a lock.
+pub fn load_project_lock(paths: &ForjaPaths) -> Result<Option<ForjaLock>> {
+    match lock_path(paths) {
+        Some(path) => load_lock(&path),
+        None => Ok(None),
+    }
+}
+
+pub fn save_lock(path: &Path, lock: &ForjaLock) -> Result<()> {
+    let json = serde_json::to_string_pretty(lock)?;
+    fs::write(path, format!("{json}\n"))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn roundtrip() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join(LOCK_FILE);
+        let lock = ForjaLock::new(
+            vec![LockedRegistry {
+                name: "default".to_string(),
+                url: "https://github.com/dmend3z/forja.git".to_string(),
+                rev: Some("abc123".to_string()),
+                local: false,
+                priority: 0,
+            }],
+            vec![LockedSkill {
+                id: "code/general/feature".to_string(),
+                registry: "default".to_string(),
+                hash: "sha256:00".to_string(),
+            }],
+        );
+        save_lock(&path, &lock).unwrap();
+
+        let loaded = load_lock(&path).unwrap().unwrap();
+        assert_eq!(loaded, lock);
+        assert_eq!(
+            loaded.registry("default").unwrap().rev.as_deref(),
+            Some("abc123")
+        );
+        assert!(!fs::read_to_string(&path).unwrap().contains("\"local\""));
+    }
+
+    #[test]
+    fn missing_lock_is_none_but_corrupt_lock_errors() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join(LOCK_FILE);
+        assert!(load_lock(&path).unwrap().is_none());
+
+        fs::write(&path, "not json").unwrap();
+        assert!(load_lock(&path).is_err());
+    }
+
+    #[test]
+    fn global_mode_has_no_lock() {
+        let dir = TempDir::new().unwrap();
+        let project = ForjaPaths::from_project(dir.path().to_path_buf()).unwrap();
+        assert_eq!(lock_path(&project), Some(dir.path().join(LOCK_FILE)));
+        let global = ForjaPaths::global().unwrap();
+        assert!(lock_path(&global).is_none());
+    }
+}
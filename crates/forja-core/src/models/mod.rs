@@ -1,14 +1,20 @@
 pub mod active_project;
 pub mod agent_file;
 pub mod analytics;
+pub mod bundle;
 pub mod claude;
 pub mod config;
+pub mod gate;
 pub mod lint;
+pub mod lock;
 pub mod phase;
 pub mod plan;
 pub mod plugin;
 pub mod profile;
 pub mod registry;
 pub mod skill;
+pub mod source;
 pub mod spec;
 pub mod state;
+pub mod usage;
+pub mod version;
@@ -1,8 +1,13 @@
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashSet};
+use std::fmt;
 use std::fs;
 use std::path::Path;
 
 use crate::error::{ForjaError, Result};
+use crate::models::gate::QualityGate;
+use crate::models::lint::LintLevel;
+use crate::models::usage::TokenUsage;
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "lowercase")]
@@ -50,7 +55,7 @@ pub struct PlanMetadata {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub stack: Option<PlanStack>,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
-    pub quality_gates: Vec<String>,
+    pub quality_gates: Vec<QualityGate>,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub phases: Vec<PlanPhase>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -124,6 +129,217 @@ pub fn find_plan_for_spec(plans_dir: &Path, spec_id: &str) -> Result<PlanMetadat
     )))
 }
 
+// --- Plan Validation ---
+
+/// A structural problem found in a plan's phase graph.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum PlanIssue {
+    /// Two or more phases share a name, so `depends_on` references are ambiguous.
+    DuplicatePhase { name: String },
+    /// A phase depends on a name that matches no phase.
+    UnknownDependency { phase: String, dependency: String },
+    /// Phases that depend on each other in a loop and can never start.
+    Cycle { phases: Vec<String> },
+    /// Two phases touch the same file with no ordering edge between them.
+    FileConflict {
+        file: String,
+        first: String,
+        second: String,
+    },
+}
+
+impl PlanIssue {
+    /// File conflicts only risk clobbered edits; everything else makes the plan unrunnable.
+    pub fn level(&self) -> LintLevel {
+        match self {
+            PlanIssue::FileConflict { .. } => LintLevel::Warning,
+            _ => LintLevel::Error,
+        }
+    }
+}
+
+impl fmt::Display for PlanIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PlanIssue::DuplicatePhase { name } => write!(f, "duplicate phase name '{name}'"),
+            PlanIssue::UnknownDependency { phase, dependency } => {
+                write!(f, "phase '{phase}' depends on unknown phase '{dependency}'")
+            }
+            PlanIssue::Cycle { phases } => {
+                write!(f, "dependency cycle: {}", phases.join(" -> "))
+            }
+            PlanIssue::FileConflict {
+                file,
+                first,
+                second,
+            } => write!(
+                f,
+                "phases '{first}' and '{second}' both touch {file} without a dependency between them"
+            ),
+        }
+    }
+}
+
+/// Result of validating a plan's phases.
+#[derive(Debug, Clone, Serialize)]
+pub struct PlanValidation {
+    pub plan_id: String,
+    pub issues: Vec<PlanIssue>,
+}
+
+impl PlanValidation {
+    pub fn has_errors(&self) -> bool {
+        self.issues.iter().any(|i| i.level() == LintLevel::Error)
+    }
+
+    pub fn error_count(&self) -> usize {
+        self.issues
+            .iter()
+            .filter(|i| i.level() == LintLevel::Error)
+            .count()
+    }
+
+    pub fn warning_count(&self) -> usize {
+        self.issues
+            .iter()
+            .filter(|i| i.level() == LintLevel::Warning)
+            .count()
+    }
+}
+
+/// Check the phase graph for duplicate names, unknown dependencies, cycles,
+/// and unordered phases that touch the same files.
+pub fn validate_plan(plan: &PlanMetadata) -> PlanValidation {
+    let phases = &plan.phases;
+    let mut issues = Vec::new();
+
+    // Duplicate names (reported once per name)
+    let mut seen = HashSet::new();
+    let mut reported = HashSet::new();
+    for phase in phases {
+        if !seen.insert(phase.name.as_str()) && reported.insert(phase.name.as_str()) {
+            issues.push(PlanIssue::DuplicatePhase {
+                name: phase.name.clone(),
+            });
+        }
+    }
+
+    // Unknown dependencies; known ones resolve to the first phase with that name
+    let mut edges: Vec<Vec<usize>> = Vec::with_capacity(phases.len());
+    for phase in phases {
+        let mut deps = Vec::new();
+        for dep in &phase.depends_on {
+            match phases.iter().position(|p| &p.name == dep) {
+                Some(idx) => deps.push(idx),
+                None => issues.push(PlanIssue::UnknownDependency {
+                    phase: phase.name.clone(),
+                    dependency: dep.clone(),
+                }),
+            }
+        }
+        edges.push(deps);
+    }
+
+    // Cycles
+    for cycle in find_cycles(&edges) {
+        issues.push(PlanIssue::Cycle {
+            phases: cycle.iter().map(|&i| phases[i].name.clone()).collect(),
+        });
+    }
+
+    // File conflicts between phases with no ordering edge
+    let reach: Vec<HashSet<usize>> = (0..phases.len()).map(|i| reachable(&edges, i)).collect();
+    for i in 0..phases.len() {
+        for j in (i + 1)..phases.len() {
+            if reach[i].contains(&j) || reach[j].contains(&i) {
+                continue;
+            }
+            let files_i = touched_files(&phases[i]);
+            for file in touched_files(&phases[j]) {
+                if files_i.contains(file) {
+                    issues.push(PlanIssue::FileConflict {
+                        file: file.to_string(),
+                        first: phases[i].name.clone(),
+                        second: phases[j].name.clone(),
+                    });
+                }
+            }
+        }
+    }
+
+    PlanValidation {
+        plan_id: plan.id.clone(),
+        issues,
+    }
+}
+
+fn touched_files(phase: &PlanPhase) -> BTreeSet<&str> {
+    phase
+        .files_to_create
+        .iter()
+        .chain(&phase.files_to_modify)
+        .map(String::as_str)
+        .collect()
+}
+
+/// All phases `start` transitively depends on.
+fn reachable(edges: &[Vec<usize>], start: usize) -> HashSet<usize> {
+    let mut seen = HashSet::new();
+    let mut stack = edges[start].clone();
+    while let Some(n) = stack.pop() {
+        if seen.insert(n) {
+            stack.extend(&edges[n]);
+        }
+    }
+    seen
+}
+
+/// Depth-first search reporting each back edge as a cycle, listed in dependency order
+/// and closed with its first phase (e.g. `[A, B, A]`).
+fn find_cycles(edges: &[Vec<usize>]) -> Vec<Vec<usize>> {
+    #[derive(Clone, Copy, PartialEq)]
+    enum Mark {
+        Unvisited,
+        OnStack,
+        Done,
+    }
+
+    fn visit(
+        node: usize,
+        edges: &[Vec<usize>],
+        marks: &mut [Mark],
+        stack: &mut Vec<usize>,
+        cycles: &mut Vec<Vec<usize>>,
+    ) {
+        marks[node] = Mark::OnStack;
+        stack.push(node);
+        for &next in &edges[node] {
+            match marks[next] {
+                Mark::Unvisited => visit(next, edges, marks, stack, cycles),
+                Mark::OnStack => {
+                    let start = stack.iter().position(|&n| n == next).unwrap_or(0);
+                    let mut cycle = stack[start..].to_vec();
+                    cycle.push(next);
+                    cycles.push(cycle);
+                }
+                Mark::Done => {}
+            }
+        }
+        stack.pop();
+        marks[node] = Mark::Done;
+    }
+
+    let mut marks = vec![Mark::Unvisited; edges.len()];
+    let mut cycles = Vec::new();
+    for node in 0..edges.len() {
+        if marks[node] == Mark::Unvisited {
+            visit(node, edges, &mut marks, &mut Vec::new(), &mut cycles);
+        }
+    }
+    cycles
+}
+
 // --- Execution Checkpoints ---
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -149,6 +365,54 @@ pub struct PhaseCheckpoint {
     pub exit_code: Option<i32>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub error_message: Option<String>,
+    /// Spend across every attempt of this phase. Only headless runs report usage.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub usage: Option<TokenUsage>,
+    /// Some attempt ran interactively, so `usage` leaves its spend out.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub unmeasured: bool,
+    /// Every run of this phase, oldest first.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub attempts: Vec<PhaseAttempt>,
+    /// Transcript of every attempt, relative to the plan workspace.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub log: Option<String>,
+    /// Raw stream-json events, relative to the plan workspace. Headless runs only.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub events_log: Option<String>,
+    /// Repository state when the phase first started. Only recorded inside a git work tree.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub git_start: Option<GitSnapshot>,
+    /// Repository state when the last attempt finished.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub git_end: Option<GitSnapshot>,
+}
+
+/// Repository state at a phase boundary.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GitSnapshot {
+    /// `HEAD` commit.
+    pub head: String,
+    /// `git diff --shortstat` of the working tree against the phase's starting
+    /// commit: uncommitted changes at the start, the phase's changes at the end.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub diff_stat: String,
+}
+
+/// One run of a phase, kept so retries don't overwrite earlier failures.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PhaseAttempt {
+    pub started_at: String,
+    pub finished_at: String,
+    /// `None` when the process was killed by a signal.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exit_code: Option<i32>,
+    /// Why the attempt failed (non-zero exit or a blocking quality gate).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Last lines of `claude`'s stderr. Only captured for headless runs.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stderr_tail: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -158,6 +422,28 @@ pub struct ExecutionCheckpoint {
     pub last_updated: String,
     pub current_phase: Option<usize>,
     pub phases: Vec<PhaseCheckpoint>,
+    /// Sum of every phase's `usage`.
+    #[serde(default, skip_serializing_if = "TokenUsage::is_empty")]
+    pub usage: TokenUsage,
+    /// Branch completed phases are committed on, when `--commit` is used.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub git_branch: Option<String>,
+    /// Git worktree the phases run in, when `--worktree` is used.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub worktree: Option<String>,
+}
+
+impl ExecutionCheckpoint {
+    /// Add one run's usage to a phase and to the plan total.
+    pub fn record_usage(&mut self, phase_index: usize, usage: TokenUsage) {
+        *self.phases[phase_index].usage.get_or_insert_default() += usage;
+        self.usage = self.phases.iter().filter_map(|p| p.usage).sum();
+    }
+
+    /// Phases with at least one interactive attempt, whose spend `usage` can't include.
+    pub fn unmeasured_phases(&self) -> usize {
+        self.phases.iter().filter(|p| p.unmeasured).count()
+    }
 }
 
 pub fn checkpoint_path(plans_dir: &Path, plan_id: &str) -> std::path::PathBuf {
@@ -182,6 +468,13 @@ pub fn initialize_checkpoint(plan: &PlanMetadata) -> ExecutionCheckpoint {
             completed_at: None,
             exit_code: None,
             error_message: None,
+            usage: None,
+            unmeasured: false,
+            attempts: Vec::new(),
+            log: None,
+            events_log: None,
+            git_start: None,
+            git_end: None,
         })
         .collect();
 
@@ -191,18 +484,47 @@ pub fn initialize_checkpoint(plan: &PlanMetadata) -> ExecutionCheckpoint {
         last_updated: now,
         current_phase: None,
         phases,
+        usage: TokenUsage::default(),
+        git_branch: None,
+        worktree: None,
     }
 }
 
+/// Every plan in `plans_dir` that has an execution checkpoint, sorted by plan ID
+/// (oldest first). Unreadable files are skipped.
+pub fn load_executions(plans_dir: &Path) -> Vec<(PlanMetadata, ExecutionCheckpoint)> {
+    let Ok(entries) = fs::read_dir(plans_dir) else {
+        return Vec::new();
+    };
+
+    let mut executions: Vec<_> = entries
+        .filter_map(|e| e.ok())
+        .filter_map(|e| {
+            let name = e.file_name().to_string_lossy().to_string();
+            let plan_id = name.strip_suffix(".checkpoint.json")?;
+            let plan = load_plan(&plans_dir.join(format!("{plan_id}.json"))).ok()?;
+            let checkpoint = load_checkpoint(&e.path()).ok()?;
+            Some((plan, checkpoint))
+        })
+        .collect();
+
+    executions.sort_by(|a, b| a.0.id.cmp(&b.0.id));
+    executions
+}
+
 pub fn load_checkpoint(path: &Path) -> Result<ExecutionCheckpoint> {
     let content = fs::read_to_string(path)?;
     let checkpoint: ExecutionCheckpoint = serde_json::from_str(&content)?;
     Ok(checkpoint)
 }
 
+/// Write the checkpoint atomically (temp file + rename) so concurrent phases
+/// never leave a half-written file behind for `--resume` to trip over.
 pub fn save_checkpoint(path: &Path, checkpoint: &ExecutionCheckpoint) -> Result<()> {
     let json = serde_json::to_string_pretty(checkpoint)?;
-    fs::write(path, json)?;
+    let tmp = path.with_extension("json.tmp");
+    fs::write(&tmp, json)?;
+    fs::rename(&tmp, path)?;
     Ok(())
 }
 
@@ -300,6 +622,167 @@ mod tests {
         );
     }
 
+    #[test]
+    fn save_checkpoint_leaves_no_temp_file() {
+        let dir = TempDir::new().unwrap();
+        let path = checkpoint_path(dir.path(), "atomic");
+        let plan = sample_plan("atomic", PlanStatus::Pending);
+
+        save_checkpoint(&path, &initialize_checkpoint(&plan)).unwrap();
+        save_checkpoint(&path, &initialize_checkpoint(&plan)).unwrap();
+
+        assert!(path.exists());
+        assert!(!path.with_extension("json.tmp").exists());
+        assert_eq!(load_checkpoint(&path).unwrap().plan_id, "atomic");
+    }
+
+    #[test]
+    fn record_usage_accumulates_per_phase_and_total() {
+        let mut plan = sample_plan("spend", PlanStatus::Pending);
+        plan.phases = vec![phase("A", &[], &[]), phase("B", &[], &[])];
+        let mut ckpt = initialize_checkpoint(&plan);
+        let run = TokenUsage {
+            output_tokens: 10,
+            cost_usd: 0.5,
+            ..Default::default()
+        };
+
+        ckpt.record_usage(0, run);
+        ckpt.record_usage(0, run);
+        ckpt.record_usage(1, run);
+
+        assert_eq!(ckpt.phases[0].usage.unwrap().output_tokens, 20);
+        assert_eq!(ckpt.usage.output_tokens, 30);
+        assert!((ckpt.usage.cost_usd - 1.5).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn load_executions_pairs_plans_with_checkpoints() {
+        let dir = TempDir::new().unwrap();
+        for id in ["p-b", "p-a", "p-c"] {
+            let plan = sample_plan(id, PlanStatus::Pending);
+            save_plan(&dir.path().join(format!("{id}.json")), &plan).unwrap();
+            if id != "p-c" {
+                let ckpt = initialize_checkpoint(&plan);
+                save_checkpoint(&checkpoint_path(dir.path(), id), &ckpt).unwrap();
+            }
+        }
+
+        let ids: Vec<String> = load_executions(dir.path())
+            .into_iter()
+            .map(|(plan, ckpt)| {
+                assert_eq!(plan.id, ckpt.plan_id);
+                plan.id
+            })
+            .collect();
+        assert_eq!(ids, ["p-a", "p-b"]);
+    }
+
+    #[test]
+    fn checkpoint_without_usage_still_loads() {
+        let json = r#"{"plan_id":"old","started_at":"t","last_updated":"t","current_phase":null,
+            "phases":[{"phase_index":0,"phase_name":"A","status":"completed"}]}"#;
+        let ckpt: ExecutionCheckpoint = serde_json::from_str(json).unwrap();
+        assert!(ckpt.usage.is_empty());
+        assert!(ckpt.phases[0].usage.is_none());
+    }
+
+    fn phase(name: &str, deps: &[&str], files: &[&str]) -> PlanPhase {
+        PlanPhase {
+            name: name.to_string(),
+            agent_role: "coder".to_string(),
+            files_to_create: vec![],
+            files_to_modify: files.iter().map(|f| f.to_string()).collect(),
+            instructions: format!("Do {name}"),
+            depends_on: deps.iter().map(|d| d.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn validate_plan_accepts_valid_dag() {
+        let mut plan = sample_plan("valid", PlanStatus::Pending);
+        plan.phases = vec![
+            phase("Schema", &[], &["db.sql"]),
+            phase("API", &["Schema"], &["src/api.ts"]),
+            phase("UI", &["API"], &["src/api.ts"]),
+        ];
+
+        let validation = validate_plan(&plan);
+        assert!(validation.issues.is_empty(), "{:?}", validation.issues);
+        assert!(!validation.has_errors());
+    }
+
+    #[test]
+    fn validate_plan_reports_unknown_and_duplicate() {
+        let mut plan = sample_plan("typos", PlanStatus::Pending);
+        plan.phases = vec![
+            phase("Schema", &[], &[]),
+            phase("Schema", &[], &[]),
+            phase("API", &["Schemaa"], &[]),
+        ];
+
+        let validation = validate_plan(&plan);
+        assert_eq!(validation.error_count(), 2);
+        assert!(validation.issues.contains(&PlanIssue::DuplicatePhase {
+            name: "Schema".to_string()
+        }));
+        assert!(validation.issues.contains(&PlanIssue::UnknownDependency {
+            phase: "API".to_string(),
+            dependency: "Schemaa".to_string(),
+        }));
+    }
+
+    #[test]
+    fn validate_plan_reports_cycle() {
+        let mut plan = sample_plan("cycle", PlanStatus::Pending);
+        plan.phases = vec![
+            phase("A", &["C"], &[]),
+            phase("B", &["A"], &[]),
+            phase("C", &["B"], &[]),
+        ];
+
+        let validation = validate_plan(&plan);
+        assert!(validation.has_errors());
+        let cycles: Vec<_> = validation
+            .issues
+            .iter()
+            .filter_map(|i| match i {
+                PlanIssue::Cycle { phases } => Some(phases.clone()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(cycles, vec![vec!["A", "C", "B", "A"]]);
+    }
+
+    #[test]
+    fn validate_plan_warns_on_unordered_file_conflict() {
+        let mut plan = sample_plan("conflict", PlanStatus::Pending);
+        plan.phases = vec![
+            phase("Schema", &[], &["src/app.ts"]),
+            phase("UI", &[], &["src/app.ts"]),
+        ];
+
+        let validation = validate_plan(&plan);
+        assert!(!validation.has_errors());
+        assert_eq!(validation.warning_count(), 1);
+        assert_eq!(
+            validation.issues[0].to_string(),
+            "phases 'Schema' and 'UI' both touch src/app.ts without a dependency between them"
+        );
+    }
+
+    #[test]
+    fn validate_plan_allows_transitively_ordered_file_sharing() {
+        let mut plan = sample_plan("transitive", PlanStatus::Pending);
+        plan.phases = vec![
+            phase("A", &[], &["shared.rs"]),
+            phase("B", &["A"], &[]),
+            phase("C", &["B"], &["shared.rs"]),
+        ];
+
+        assert!(validate_plan(&plan).issues.is_empty());
+    }
+
     #[test]
     fn find_plan_for_spec_finds_linked_plan() {
         let dir = TempDir::new().unwrap();
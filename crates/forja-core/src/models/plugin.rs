@@ -1,4 +1,7 @@
+use std::collections::BTreeMap;
+
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 
 /// Skill manifest format used by the catalog scanner.
 /// Supports both `skill.json` (preferred) and legacy `.claude-plugin/plugin.json`.
@@ -14,6 +17,73 @@ pub struct PluginJson {
     pub license: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub keywords: Option<Vec<String>>,
+    /// IDs of skills this one needs installed (`phase/tech/name` or `<registry>:phase/tech/name`).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub requires: Vec<String>,
+    /// Hooks, permissions and MCP servers to merge into Claude Code's config.
+    #[serde(flatten)]
+    pub settings: SettingsFragment,
+}
+
+/// Claude Code configuration a skill ships in its manifest, in the same
+/// shapes `.claude/settings.json` and `.mcp.json` use. Merged on install and
+/// removed again on uninstall.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SettingsFragment {
+    /// Hook matcher groups by event, e.g. `PostToolUse`.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub hooks: BTreeMap<String, Vec<Value>>,
+    #[serde(default, skip_serializing_if = "Permissions::is_empty")]
+    pub permissions: Permissions,
+    /// MCP server definitions by name.
+    #[serde(
+        default,
+        rename = "mcpServers",
+        skip_serializing_if = "BTreeMap::is_empty"
+    )]
+    pub mcp_servers: BTreeMap<String, Value>,
+}
+
+impl SettingsFragment {
+    pub fn is_empty(&self) -> bool {
+        self.hooks.is_empty() && self.permissions.is_empty() && self.mcp_servers.is_empty()
+    }
+
+    /// One line per kind of setting, e.g. `hooks: PostToolUse, Stop`.
+    pub fn summary(&self) -> Vec<String> {
+        let mut lines = Vec::new();
+        if !self.hooks.is_empty() {
+            let events: Vec<&str> = self.hooks.keys().map(String::as_str).collect();
+            lines.push(format!("hooks: {}", events.join(", ")));
+        }
+        if !self.permissions.allow.is_empty() {
+            lines.push(format!("allow: {}", self.permissions.allow.join(", ")));
+        }
+        if !self.permissions.deny.is_empty() {
+            lines.push(format!("deny: {}", self.permissions.deny.join(", ")));
+        }
+        if !self.mcp_servers.is_empty() {
+            let names: Vec<&str> = self.mcp_servers.keys().map(String::as_str).collect();
+            lines.push(format!("MCP servers: {}", names.join(", ")));
+        }
+        lines
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Permissions {
+    /// Permission rules to allow, e.g. `Bash(cargo test:*)`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allow: Vec<String>,
+    /// Permission rules to deny, e.g. `Task(coder)` to keep a subagent out.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub deny: Vec<String>,
+}
+
+impl Permissions {
+    pub fn is_empty(&self) -> bool {
+        self.allow.is_empty() && self.deny.is_empty()
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -1,4 +1,5 @@
-use super::skill::Skill;
+use super::skill::{Skill, split_registry};
+use crate::registry::search;
 
 /// The full catalog index, built by scanning the skills/ directory.
 /// Rebuilt on every invocation — fast enough for hundreds of skills (<50ms).
@@ -20,8 +21,18 @@ impl Registry {
         Self { skills }
     }
 
+    /// Find by exact ID, or by `<registry>:phase/tech/name` even when that
+    /// registry's copy is the one that wins.
     pub fn find_by_id(&self, id: &str) -> Option<&Skill> {
-        self.skills.iter().find(|s| s.id == id)
+        if let Some(skill) = self.skills.iter().find(|s| s.id == id) {
+            return Some(skill);
+        }
+        let (Some(registry), bare) = split_registry(id) else {
+            return None;
+        };
+        self.skills
+            .iter()
+            .find(|s| s.registry == registry && s.bare_id() == bare)
     }
 
     pub fn find_by_name(&self, name: &str) -> Vec<&Skill> {
@@ -47,18 +58,11 @@ impl Registry {
         }
     }
 
+    /// Skills matching `query`, best match first (see [`search::search`]).
     pub fn search(&self, query: &str) -> Vec<&Skill> {
-        let q = query.to_lowercase();
-        self.skills
-            .iter()
-            .filter(|s| {
-                s.id.to_lowercase().contains(&q)
-                    || s.name.to_lowercase().contains(&q)
-                    || s.description.to_lowercase().contains(&q)
-                    || s.phase.as_str().contains(&q)
-                    || s.tech.to_lowercase().contains(&q)
-                    || s.keywords.iter().any(|k| k.to_lowercase().contains(&q))
-            })
+        search::search(self, query)
+            .into_iter()
+            .map(|hit| hit.skill)
             .collect()
     }
 }
@@ -73,8 +77,12 @@ mod tests {
     fn make_skill(id: &str, name: &str, keywords: Vec<&str>) -> Skill {
         Skill {
             id: id.to_string(),
+            registry: "default".to_string(),
             name: name.to_string(),
             description: "test description".to_string(),
+            version: None,
+            requires: vec![],
+            settings: Default::default(),
             phase: Phase::Code,
             tech: "general".to_string(),
             path: PathBuf::from("/tmp/test"),
@@ -158,6 +166,22 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn find_by_qualified_id() {
+        let mut shadowed = make_skill("acme:code/rust/feature", "feature", vec![]);
+        shadowed.registry = "acme".to_string();
+        let registry = Registry::new(vec![
+            make_skill("code/rust/feature", "feature", vec![]),
+            shadowed,
+        ]);
+
+        let found = registry.find_by_id("acme:code/rust/feature").unwrap();
+        assert_eq!(found.registry, "acme");
+        let found = registry.find_by_id("default:code/rust/feature").unwrap();
+        assert_eq!(found.id, "code/rust/feature");
+        assert!(registry.find_by_id("other:code/rust/feature").is_none());
+    }
+
     #[test]
     fn resolve_id_takes_precedence_over_name() {
         let registry = Registry::new(vec![
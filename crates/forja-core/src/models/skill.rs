@@ -4,14 +4,34 @@ use std::path::PathBuf;
 use serde::{Deserialize, Serialize};
 
 use super::phase::Phase;
+use super::plugin::SettingsFragment;
+use super::source::DEFAULT_REGISTRY;
+
+fn default_registry() -> String {
+    DEFAULT_REGISTRY.to_string()
+}
 
 /// A skill entry indexed from the registry catalog.
 /// Built by scanning skills/<phase>/<tech>/<skill-name>/
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Skill {
+    /// `phase/tech/name`, or `<registry>:phase/tech/name` when a
+    /// higher-priority registry ships the same ID.
     pub id: String,
+    /// Name of the registry the skill came from.
+    #[serde(default = "default_registry")]
+    pub registry: String,
     pub name: String,
     pub description: String,
+    /// Semver from `skill.json`, if the skill declares one.
+    #[serde(default)]
+    pub version: Option<String>,
+    /// Skills that must be installed alongside this one, from `skill.json`.
+    #[serde(default)]
+    pub requires: Vec<String>,
+    /// Hooks, permissions and MCP servers from `skill.json`.
+    #[serde(default, skip_serializing_if = "SettingsFragment::is_empty")]
+    pub settings: SettingsFragment,
     pub phase: Phase,
     pub tech: String,
     pub path: PathBuf,
@@ -20,6 +40,32 @@ pub struct Skill {
     pub keywords: Vec<String>,
 }
 
+impl Skill {
+    /// The ID without its registry prefix: `phase/tech/name`.
+    pub fn bare_id(&self) -> &str {
+        split_registry(&self.id).1
+    }
+
+    /// The ID with its registry prefix, which always resolves to this skill.
+    pub fn qualified_id(&self) -> String {
+        format!("{}:{}", self.registry, self.bare_id())
+    }
+
+    /// Whether a higher-priority registry ships the same bare ID, leaving
+    /// this skill reachable only through its qualified ID.
+    pub fn is_shadowed(&self) -> bool {
+        self.id.contains(':')
+    }
+}
+
+/// Split `<registry>:phase/tech/name` into its registry and bare ID.
+pub fn split_registry(id: &str) -> (Option<&str>, &str) {
+    match id.split_once(':') {
+        Some((registry, bare)) => (Some(registry), bare),
+        None => (None, id),
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum ContentType {
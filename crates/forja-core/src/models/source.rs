@@ -0,0 +1,157 @@
+use crate::error::{ForjaError, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Name of the catalog at `ForjaPaths.registry`, set up by `forja init`.
+pub const DEFAULT_REGISTRY: &str = "default";
+
+/// Priority given to `forja registry add` when none is passed: above the
+/// default catalog, so a private registry shadows the public one.
+pub const DEFAULT_ADDED_PRIORITY: i32 = 10;
+
+/// A named skill registry besides the default catalog.
+/// Cloned (or symlinked, when `local`) into `~/.forja/registries/<name>`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RegistrySource {
+    pub name: String,
+    /// Git URL, or the source directory when `local`.
+    pub url: String,
+    #[serde(default)]
+    pub local: bool,
+    /// Higher wins when two registries ship the same skill ID.
+    #[serde(default)]
+    pub priority: i32,
+}
+
+/// The registries manifest (`~/.forja/registries.json`).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RegistrySources {
+    /// Priority of the default catalog.
+    #[serde(default)]
+    pub default_priority: i32,
+    #[serde(default)]
+    pub registries: Vec<RegistrySource>,
+}
+
+impl RegistrySources {
+    pub fn find(&self, name: &str) -> Option<&RegistrySource> {
+        self.registries.iter().find(|r| r.name == name)
+    }
+
+    pub fn find_mut(&mut self, name: &str) -> Option<&mut RegistrySource> {
+        self.registries.iter_mut().find(|r| r.name == name)
+    }
+
+    pub fn remove(&mut self, name: &str) -> Option<RegistrySource> {
+        let index = self.registries.iter().position(|r| r.name == name)?;
+        Some(self.registries.remove(index))
+    }
+}
+
+/// Path of the registries manifest under a forja root: the global one, or a
+/// project's for registries only its `forja.lock` knows about.
+pub fn sources_path(global_root: &Path) -> PathBuf {
+    global_root.join("registries.json")
+}
+
+/// Checkout directory of a named registry under a forja root.
+pub fn source_dir(global_root: &Path, name: &str) -> PathBuf {
+    global_root.join("registries").join(name)
+}
+
+/// Symlink a local registry's source directory to `dir`.
+#[cfg(unix)]
+pub fn link_local(target: &Path, dir: &Path) -> Result<()> {
+    std::os::unix::fs::symlink(target, dir)?;
+    Ok(())
+}
+
+#[cfg(not(unix))]
+pub fn link_local(_target: &Path, dir: &Path) -> Result<()> {
+    Err(ForjaError::InvalidArgument(format!(
+        "local registries are linked into {} with a symlink, which needs a Unix system — use a git URL instead",
+        dir.display()
+    )))
+}
+
+/// Registry names become directory names and ID prefixes (`<name>:phase/tech/skill`).
+pub fn validate_name(name: &str) -> Result<()> {
+    let valid = !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_');
+    if !valid {
+        return Err(ForjaError::InvalidArgument(format!(
+            "registry name '{name}' must use lowercase letters, digits, '-' or '_'"
+        )));
+    }
+    if name == DEFAULT_REGISTRY {
+        return Err(ForjaError::RegistryAlreadyExists(name.to_string()));
+    }
+    Ok(())
+}
+
+/// Load the manifest; a missing or unreadable file means no extra registries.
+pub fn load_sources(path: &Path) -> RegistrySources {
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+pub fn save_sources(path: &Path, sources: &RegistrySources) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let json = serde_json::to_string_pretty(sources)?;
+    fs::write(path, json)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn roundtrip_and_lookup() {
+        let dir = TempDir::new().unwrap();
+        let path = sources_path(dir.path());
+
+        let mut sources = RegistrySources::default();
+        sources.registries.push(RegistrySource {
+            name: "acme".to_string(),
+            url: "git@github.com:acme/skills.git".to_string(),
+            local: false,
+            priority: DEFAULT_ADDED_PRIORITY,
+        });
+        save_sources(&path, &sources).unwrap();
+
+        let mut loaded = load_sources(&path);
+        assert_eq!(loaded.default_priority, 0);
+        assert_eq!(loaded.find("acme").unwrap().priority, 10);
+        assert!(loaded.remove("acme").is_some());
+        assert!(loaded.find("acme").is_none());
+    }
+
+    #[test]
+    fn missing_manifest_is_empty() {
+        let dir = TempDir::new().unwrap();
+        assert!(
+            load_sources(&sources_path(dir.path()))
+                .registries
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn validate_name_rejects_reserved_and_unsafe_names() {
+        assert!(validate_name("acme-internal").is_ok());
+        assert!(validate_name("default").is_err());
+        assert!(validate_name("Acme").is_err());
+        assert!(validate_name("a:b").is_err());
+        assert!(validate_name("../x").is_err());
+        assert!(validate_name("").is_err());
+    }
+}
@@ -1,9 +1,10 @@
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fs;
 use std::path::Path;
 
 use crate::error::Result;
+use crate::models::skill::Skill;
 
 /// Lightweight install tracking metadata per skill.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -11,6 +12,24 @@ pub struct InstallMeta {
     pub install_date: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub last_used: Option<String>,
+    /// Skill version at install (or last upgrade) time.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    /// Copy install mode: installed file name -> hash of the registry
+    /// content it was last copied or merged from.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub copies: BTreeMap<String, String>,
+}
+
+impl InstallMeta {
+    fn now() -> Self {
+        Self {
+            install_date: chrono::Utc::now().to_rfc3339(),
+            last_used: None,
+            version: None,
+            copies: BTreeMap::new(),
+        }
+    }
 }
 
 /// Persistent state stored in `~/.forja/state.json`. Tracks installed skills and team configs.
@@ -24,6 +43,35 @@ pub struct ForjaState {
     pub teams: HashMap<String, TeamEntry>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub active_profile: Option<String>,
+    /// Entries skills merged into Claude Code's config files, by entry ID
+    /// (see `forja_core::settings`).
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub settings: BTreeMap<String, OwnedSetting>,
+}
+
+/// Claude Code config file a skill's settings go into.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SettingsFile {
+    /// `.claude/settings.json`: hooks and permissions.
+    Settings,
+    /// `.mcp.json` (`~/.claude.json` globally): MCP servers.
+    Mcp,
+}
+
+/// A config entry merged in by one or more skills.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OwnedSetting {
+    pub file: SettingsFile,
+    /// Keys leading to the object (or array) holding the entry.
+    pub path: Vec<String>,
+    /// The entry's key in that object; `None` for an array item.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key: Option<String>,
+    /// The value as merged, to tell it apart from later local edits.
+    pub value: serde_json::Value,
+    /// Installed skills that ship the entry; it's removed with the last one.
+    pub owners: Vec<String>,
 }
 
 /// A configured multi-agent team with its member list and model profile.
@@ -49,6 +97,7 @@ impl Default for ForjaState {
             install_metadata: HashMap::new(),
             teams: HashMap::new(),
             active_profile: None,
+            settings: BTreeMap::new(),
         }
     }
 }
@@ -77,6 +126,7 @@ pub fn load_state(state_path: &Path) -> ForjaState {
             install_metadata: HashMap::new(),
             teams: HashMap::new(),
             active_profile: None,
+            settings: BTreeMap::new(),
         };
     }
 
@@ -89,6 +139,67 @@ pub fn save_state(state_path: &Path, state: &ForjaState) -> Result<()> {
     Ok(())
 }
 
+/// Record the install date and catalog version of freshly installed skills,
+/// keeping any copy hashes already recorded for them.
+pub fn record_installs(state_path: &Path, skills: &[&Skill]) -> Result<()> {
+    if skills.is_empty() {
+        return Ok(());
+    }
+    let mut state = load_state(state_path);
+    let now = chrono::Utc::now().to_rfc3339();
+    for skill in skills {
+        let meta = state
+            .install_metadata
+            .entry(skill.id.clone())
+            .or_insert_with(InstallMeta::now);
+        meta.install_date = now.clone();
+        meta.last_used = None;
+        meta.version = skill.version.clone();
+    }
+    save_state(state_path, &state)
+}
+
+/// Record the version a skill was upgraded to, keeping its install date.
+pub fn record_upgrade(state_path: &Path, skill: &Skill) -> Result<()> {
+    let mut state = load_state(state_path);
+    state
+        .install_metadata
+        .entry(skill.id.clone())
+        .or_insert_with(InstallMeta::now)
+        .version = skill.version.clone();
+    save_state(state_path, &state)
+}
+
+/// Record the registry content hash of files copied for `skill_id`.
+pub fn record_copies(
+    state_path: &Path,
+    skill_id: &str,
+    copies: BTreeMap<String, String>,
+) -> Result<()> {
+    if copies.is_empty() {
+        return Ok(());
+    }
+    let mut state = load_state(state_path);
+    state
+        .install_metadata
+        .entry(skill_id.to_string())
+        .or_insert_with(InstallMeta::now)
+        .copies
+        .extend(copies);
+    save_state(state_path, &state)
+}
+
+/// Drop the install metadata of uninstalled skills.
+pub fn forget_installs(state_path: &Path, ids: &[String]) -> Result<()> {
+    let mut state = load_state(state_path);
+    let before = state.install_metadata.len();
+    state.install_metadata.retain(|id, _| !ids.contains(id));
+    if state.install_metadata.len() == before {
+        return Ok(());
+    }
+    save_state(state_path, &state)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -166,4 +277,47 @@ mod tests {
         assert_eq!(final_state.installed, vec!["new/skill"]);
         assert!(final_state.teams.contains_key("test-team"));
     }
+
+    #[test]
+    fn install_metadata_tracks_versions() {
+        let skill = Skill {
+            id: "code/general/feature".to_string(),
+            registry: "default".to_string(),
+            name: "feature".to_string(),
+            description: "test".to_string(),
+            version: Some("1.0.0".to_string()),
+            requires: vec![],
+            settings: Default::default(),
+            phase: crate::models::phase::Phase::Code,
+            tech: "general".to_string(),
+            path: std::path::PathBuf::from("/tmp/test"),
+            installed: false,
+            content_types: vec![],
+            keywords: vec![],
+        };
+        let tmp = NamedTempFile::new().unwrap();
+        record_installs(tmp.path(), &[&skill]).unwrap();
+        let installed_at = load_state(tmp.path()).install_metadata[&skill.id].clone();
+        assert_eq!(installed_at.version.as_deref(), Some("1.0.0"));
+
+        let upgraded = Skill {
+            version: Some("1.1.0".to_string()),
+            ..skill.clone()
+        };
+        record_upgrade(tmp.path(), &upgraded).unwrap();
+        let meta = load_state(tmp.path()).install_metadata[&skill.id].clone();
+        assert_eq!(meta.version.as_deref(), Some("1.1.0"));
+        assert_eq!(meta.install_date, installed_at.install_date);
+
+        let copies = BTreeMap::from([("forja--a--coder.md".to_string(), "sha256:aa".to_string())]);
+        record_copies(tmp.path(), &skill.id, copies.clone()).unwrap();
+        record_installs(tmp.path(), &[&skill]).unwrap();
+        assert_eq!(
+            load_state(tmp.path()).install_metadata[&skill.id].copies,
+            copies
+        );
+
+        forget_installs(tmp.path(), std::slice::from_ref(&skill.id)).unwrap();
+        assert!(load_state(tmp.path()).install_metadata.is_empty());
+    }
 }
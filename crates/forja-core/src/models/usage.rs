@@ -0,0 +1,128 @@
+use std::fmt;
+use std::ops::AddAssign;
+
+use serde::{Deserialize, Serialize};
+
+/// Tokens and dollars consumed by one or more `claude` runs.
+///
+/// Captured from the final `result` event of `--output-format stream-json`
+/// and summed per phase, per plan and per spark.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TokenUsage {
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub cache_creation_input_tokens: u64,
+    pub cache_read_input_tokens: u64,
+    pub cost_usd: f64,
+    pub duration_ms: u64,
+    pub num_turns: u32,
+}
+
+impl TokenUsage {
+    /// All tokens sent to or received from the API, cache traffic included.
+    pub fn total_tokens(&self) -> u64 {
+        self.input_tokens
+            + self.output_tokens
+            + self.cache_creation_input_tokens
+            + self.cache_read_input_tokens
+    }
+
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+impl AddAssign for TokenUsage {
+    fn add_assign(&mut self, other: Self) {
+        self.input_tokens += other.input_tokens;
+        self.output_tokens += other.output_tokens;
+        self.cache_creation_input_tokens += other.cache_creation_input_tokens;
+        self.cache_read_input_tokens += other.cache_read_input_tokens;
+        self.cost_usd += other.cost_usd;
+        self.duration_ms += other.duration_ms;
+        self.num_turns += other.num_turns;
+    }
+}
+
+impl std::iter::Sum for TokenUsage {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::default(), |mut acc, u| {
+            acc += u;
+            acc
+        })
+    }
+}
+
+impl fmt::Display for TokenUsage {
+    /// Compact one-line summary, e.g. `12.4k in · 3.1k out · $0.42`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let input =
+            self.input_tokens + self.cache_creation_input_tokens + self.cache_read_input_tokens;
+        write!(
+            f,
+            "{} in · {} out · ${:.2}",
+            format_tokens(input),
+            format_tokens(self.output_tokens),
+            self.cost_usd
+        )
+    }
+}
+
+/// Human-friendly token count: `950`, `12.4k`, `3.2M`.
+pub fn format_tokens(n: u64) -> String {
+    match n {
+        0..1_000 => n.to_string(),
+        1_000..1_000_000 => format!("{:.1}k", n as f64 / 1_000.0),
+        _ => format!("{:.1}M", n as f64 / 1_000_000.0),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn usage(input: u64, output: u64, cost: f64) -> TokenUsage {
+        TokenUsage {
+            input_tokens: input,
+            output_tokens: output,
+            cost_usd: cost,
+            num_turns: 1,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn sum_adds_every_field() {
+        let total: TokenUsage = [usage(100, 20, 0.25), usage(50, 5, 0.5)].into_iter().sum();
+        assert_eq!(total.input_tokens, 150);
+        assert_eq!(total.output_tokens, 25);
+        assert_eq!(total.num_turns, 2);
+        assert!((total.cost_usd - 0.75).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn display_folds_cache_tokens_into_input() {
+        let u = TokenUsage {
+            cache_read_input_tokens: 12_000,
+            ..usage(400, 3_100, 0.4211)
+        };
+        assert_eq!(u.to_string(), "12.4k in · 3.1k out · $0.42");
+    }
+
+    #[test]
+    fn format_tokens_scales() {
+        assert_eq!(format_tokens(950), "950");
+        assert_eq!(format_tokens(1_500), "1.5k");
+        assert_eq!(format_tokens(3_200_000), "3.2M");
+    }
+
+    #[test]
+    fn deserializes_missing_fields_as_zero() {
+        let u: TokenUsage = serde_json::from_str(r#"{"output_tokens": 7}"#).unwrap();
+        assert_eq!(u.output_tokens, 7);
+        assert_eq!(u.input_tokens, 0);
+        assert!(!u.is_empty());
+        assert!(TokenUsage::default().is_empty());
+    }
+}
@@ -0,0 +1,111 @@
+use semver::Version;
+use std::fmt;
+
+/// How far a skill moved between two versions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Bump {
+    /// Breaking: the major version went up (or the minor one, below 1.0.0).
+    Major,
+    Minor,
+    Patch,
+    /// Files changed but the version didn't go up, or one side has no version.
+    Unversioned,
+}
+
+impl Bump {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Bump::Major => "major",
+            Bump::Minor => "minor",
+            Bump::Patch => "patch",
+            Bump::Unversioned => "unversioned",
+        }
+    }
+}
+
+impl fmt::Display for Bump {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Parse a skill version leniently: `v1.2.3`, `1.2` and `1` are accepted.
+pub fn parse_version(raw: &str) -> Option<Version> {
+    let raw = raw.trim();
+    let raw = raw.strip_prefix('v').unwrap_or(raw);
+    if let Ok(version) = Version::parse(raw) {
+        return Some(version);
+    }
+    let padded = match raw.matches('.').count() {
+        0 => format!("{raw}.0.0"),
+        1 => format!("{raw}.0"),
+        _ => return None,
+    };
+    Version::parse(&padded).ok()
+}
+
+/// Classify the move from `from` to `to`, following semver: below 1.0.0 a
+/// minor bump is breaking and a patch bump is a minor one.
+pub fn classify(from: Option<&str>, to: Option<&str>) -> Bump {
+    let (Some(from), Some(to)) = (from.and_then(parse_version), to.and_then(parse_version)) else {
+        return Bump::Unversioned;
+    };
+    if to <= from {
+        return Bump::Unversioned;
+    }
+    if to.major != from.major {
+        Bump::Major
+    } else if to.minor != from.minor {
+        if to.major == 0 {
+            Bump::Major
+        } else {
+            Bump::Minor
+        }
+    } else if to.major == 0 && to.patch != from.patch {
+        Bump::Minor
+    } else {
+        Bump::Patch
+    }
+}
+
+/// Whether `candidate` is newer than `installed`; unparseable versions never are.
+pub fn is_newer(installed: &str, candidate: &str) -> bool {
+    match (parse_version(installed), parse_version(candidate)) {
+        (Some(installed), Some(candidate)) => candidate > installed,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_accepts_short_and_prefixed_versions() {
+        assert_eq!(parse_version("1.2.3"), Some(Version::new(1, 2, 3)));
+        assert_eq!(parse_version("v2.0"), Some(Version::new(2, 0, 0)));
+        assert_eq!(parse_version("3"), Some(Version::new(3, 0, 0)));
+        assert!(parse_version("latest").is_none());
+        assert!(parse_version("").is_none());
+    }
+
+    #[test]
+    fn classify_follows_semver() {
+        assert_eq!(classify(Some("1.2.3"), Some("2.0.0")), Bump::Major);
+        assert_eq!(classify(Some("1.2.3"), Some("1.3.0")), Bump::Minor);
+        assert_eq!(classify(Some("1.2.3"), Some("1.2.4")), Bump::Patch);
+        assert_eq!(classify(Some("0.1.0"), Some("0.2.0")), Bump::Major);
+        assert_eq!(classify(Some("0.1.0"), Some("0.1.1")), Bump::Minor);
+        assert_eq!(classify(Some("0.0.1"), Some("0.0.2")), Bump::Minor);
+    }
+
+    #[test]
+    fn classify_without_an_increase_is_unversioned() {
+        assert_eq!(classify(None, Some("1.0.0")), Bump::Unversioned);
+        assert_eq!(classify(Some("1.0.0"), None), Bump::Unversioned);
+        assert_eq!(classify(Some("1.0.0"), Some("1.0.0")), Bump::Unversioned);
+        assert_eq!(classify(Some("2.0.0"), Some("1.0.0")), Bump::Unversioned);
+        assert!(is_newer("1.0.0", "1.0.1"));
+        assert!(!is_newer("1.0.0", "nightly"));
+    }
+}
@@ -27,6 +27,7 @@ pub struct ForjaPaths {
     pub claude_dir: PathBuf,
     pub claude_agents: PathBuf,
     pub claude_commands: PathBuf,
+    pub claude_skills: PathBuf,
 }
 
 impl ForjaPaths {
@@ -55,6 +56,7 @@ impl ForjaPaths {
             forja_root,
             claude_agents: claude_dir.join("agents"),
             claude_commands: claude_dir.join("commands"),
+            claude_skills: claude_dir.join("skills"),
             claude_dir,
         })
     }
@@ -74,6 +76,7 @@ impl ForjaPaths {
             forja_root,
             claude_agents: claude_dir.join("agents"),
             claude_commands: claude_dir.join("commands"),
+            claude_skills: claude_dir.join("skills"),
             claude_dir,
         })
     }
@@ -206,6 +209,7 @@ mod tests {
         assert_eq!(project.claude_dir, dir.path().join(".claude"));
         assert_eq!(project.claude_agents, dir.path().join(".claude").join("agents"));
         assert_eq!(project.claude_commands, dir.path().join(".claude").join("commands"));
+        assert_eq!(project.claude_skills, dir.path().join(".claude").join("skills"));
     }
 
     #[test]
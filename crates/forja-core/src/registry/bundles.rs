@@ -0,0 +1,156 @@
+use std::cmp::Reverse;
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+
+use crate::error::{ForjaError, Result};
+use crate::models::bundle::Bundle;
+use crate::paths::ForjaPaths;
+use crate::registry::catalog::{self, Source};
+
+/// Directory of bundle manifests at the root of a registry.
+pub const BUNDLES_DIR: &str = "bundles";
+
+/// Bundles of every configured registry, sorted by name.
+pub fn scan_all(paths: &ForjaPaths) -> Result<Vec<Bundle>> {
+    scan_sources(&catalog::sources(paths))
+}
+
+/// Merge the bundles of several registries. When two ship the same name, the
+/// higher-priority registry wins (ties go to the one listed first), as with
+/// skills; unlike skills, the shadowed bundle is dropped.
+pub fn scan_sources(sources: &[Source]) -> Result<Vec<Bundle>> {
+    let mut ordered: Vec<&Source> = sources.iter().collect();
+    ordered.sort_by_key(|s| Reverse(s.priority));
+
+    let mut seen = HashSet::new();
+    let mut bundles = Vec::new();
+    for source in ordered {
+        for bundle in scan_dir(&source.path, &source.name)? {
+            if seen.insert(bundle.name.clone()) {
+                bundles.push(bundle);
+            }
+        }
+    }
+    bundles.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(bundles)
+}
+
+/// Structure expected: bundles/<name>.json. Manifests that don't parse are skipped.
+fn scan_dir(registry_path: &Path, registry_name: &str) -> Result<Vec<Bundle>> {
+    let dir = registry_path.join(BUNDLES_DIR);
+    if !dir.is_dir() {
+        return Ok(Vec::new());
+    }
+
+    let mut bundles = Vec::new();
+    for entry in fs::read_dir(&dir)?.filter_map(|e| e.ok()) {
+        let path = entry.path();
+        if path.extension().is_none_or(|ext| ext != "json") {
+            continue;
+        }
+        let Some(name) = path.file_stem().map(|s| s.to_string_lossy().to_string()) else {
+            continue;
+        };
+        let Ok(content) = fs::read_to_string(&path) else {
+            continue;
+        };
+        if let Ok(mut bundle) = serde_json::from_str::<Bundle>(&content) {
+            bundle.name = name;
+            bundle.registry = registry_name.to_string();
+            bundles.push(bundle);
+        }
+    }
+    Ok(bundles)
+}
+
+pub fn find<'a>(bundles: &'a [Bundle], name: &str) -> Result<&'a Bundle> {
+    bundles
+        .iter()
+        .find(|b| b.name == name)
+        .ok_or_else(|| ForjaError::BundleNotFound(name.to_string()))
+}
+
+/// The bundle to propose for a stack label from `forja init` (e.g.
+/// `Next.js + TypeScript`). Components are tried in order, so the framework
+/// wins over the language.
+pub fn suggest<'a>(bundles: &'a [Bundle], stack: &str) -> Option<&'a Bundle> {
+    stack.split('+').map(str::trim).find_map(|component| {
+        bundles
+            .iter()
+            .find(|b| b.stacks.iter().any(|s| s.eq_ignore_ascii_case(component)))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write_bundle(root: &Path, name: &str, json: &str) {
+        let dir = root.join(BUNDLES_DIR);
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join(format!("{name}.json")), json).unwrap();
+    }
+
+    fn source(name: &str, path: &Path, priority: i32) -> Source {
+        Source {
+            name: name.to_string(),
+            path: path.to_path_buf(),
+            priority,
+        }
+    }
+
+    #[test]
+    fn scan_names_bundles_and_lets_priority_win() {
+        let default = TempDir::new().unwrap();
+        let private = TempDir::new().unwrap();
+        write_bundle(
+            default.path(),
+            "rust-service",
+            r#"{ "description": "Public", "skills": ["code/rust/feature"], "teams": ["solo-sprint"] }"#,
+        );
+        write_bundle(default.path(), "broken", "{ not json");
+        write_bundle(
+            private.path(),
+            "rust-service",
+            r#"{ "description": "Ours" }"#,
+        );
+
+        let bundles = scan_sources(&[
+            source("default", default.path(), 0),
+            source("acme", private.path(), 10),
+        ])
+        .unwrap();
+        assert_eq!(bundles.len(), 1);
+        assert_eq!(bundles[0].name, "rust-service");
+        assert_eq!(bundles[0].registry, "acme");
+        assert_eq!(bundles[0].description, "Ours");
+
+        let bundles = scan_sources(&[source("default", default.path(), 0)]).unwrap();
+        assert_eq!(bundles[0].skills, ["code/rust/feature"]);
+        assert_eq!(bundles[0].teams, ["solo-sprint"]);
+        assert!(matches!(
+            find(&bundles, "nope"),
+            Err(ForjaError::BundleNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn suggest_prefers_the_framework_over_the_language() {
+        let bundle = |name: &str, stacks: &[&str]| Bundle {
+            name: name.to_string(),
+            stacks: stacks.iter().map(|s| s.to_string()).collect(),
+            ..Default::default()
+        };
+        let bundles = vec![
+            bundle("typescript", &["typescript", "javascript"]),
+            bundle("nextjs-app", &["next.js"]),
+        ];
+
+        let pick = |stack: &str| suggest(&bundles, stack).map(|b| b.name.as_str());
+        assert_eq!(pick("Next.js + TypeScript"), Some("nextjs-app"));
+        assert_eq!(pick("Angular + TypeScript"), Some("typescript"));
+        assert_eq!(pick("Rust"), None);
+    }
+}
@@ -3,19 +3,126 @@ use crate::models::phase::Phase;
 use crate::models::plugin::PluginJson;
 use crate::models::registry::Registry;
 use crate::models::skill::{ContentType, Skill};
+use crate::models::source::{self, DEFAULT_REGISTRY, RegistrySource};
+use crate::paths::ForjaPaths;
+use std::cmp::Reverse;
+use std::collections::HashSet;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 const MANIFEST_FILE: &str = "skill.json";
 const LEGACY_MANIFEST_FILE: &str = "plugin.json";
 const LEGACY_MANIFEST_DIR: &str = ".claude-plugin";
 
-/// Scan the skills/ directory and build a Registry of all available skills.
-/// Structure expected: skills/<phase>/<tech>/<skill-name>/{skill.json|.claude-plugin/plugin.json}
+/// A registry checkout to scan, with the priority used to settle ID clashes.
+#[derive(Debug, Clone)]
+pub struct Source {
+    pub name: String,
+    pub path: PathBuf,
+    pub priority: i32,
+}
+
+/// Scan a single registry directory and build a Registry of all available skills.
 pub fn scan(registry_path: &Path, installed_ids: &[String]) -> Result<Registry> {
+    let source = Source {
+        name: DEFAULT_REGISTRY.to_string(),
+        path: registry_path.to_path_buf(),
+        priority: 0,
+    };
+    scan_sources(&[source], installed_ids)
+}
+
+/// Scan the default catalog plus every registry in `~/.forja/registries.json`.
+pub fn scan_all(paths: &ForjaPaths, installed_ids: &[String]) -> Result<Registry> {
+    scan_sources(&sources(paths), installed_ids)
+}
+
+/// The default catalog followed by the named registries, in manifest order.
+pub fn sources(paths: &ForjaPaths) -> Vec<Source> {
+    let Ok(global_root) = ForjaPaths::global_forja_root() else {
+        return vec![Source {
+            name: DEFAULT_REGISTRY.to_string(),
+            path: paths.registry.clone(),
+            priority: 0,
+        }];
+    };
+    let manifest = source::load_sources(&source::sources_path(&global_root));
+
+    let mut all = vec![Source {
+        name: DEFAULT_REGISTRY.to_string(),
+        path: paths.registry.clone(),
+        priority: manifest.default_priority,
+    }];
+    all.extend(named_registries(paths).into_iter().map(|(r, path)| Source {
+        name: r.name,
+        path,
+        priority: r.priority,
+    }));
+    all
+}
+
+/// Named registries and the directory each is read from. A project's own
+/// checkout under `<project>/.forja/registries/`, pinned by `forja.lock`,
+/// wins over the machine-wide one in `~/.forja/registries/`; registries only
+/// the lock knows about come from the project's `.forja/registries.json`.
+pub fn named_registries(paths: &ForjaPaths) -> Vec<(RegistrySource, PathBuf)> {
+    let Ok(global_root) = ForjaPaths::global_forja_root() else {
+        return Vec::new();
+    };
+    let mut registries = source::load_sources(&source::sources_path(&global_root)).registries;
+    let project = paths.forja_root != global_root;
+    if project {
+        let pinned = source::load_sources(&source::sources_path(&paths.forja_root));
+        for r in pinned.registries {
+            if !registries.iter().any(|g| g.name == r.name) {
+                registries.push(r);
+            }
+        }
+    }
+
+    registries
+        .into_iter()
+        .map(|r| {
+            let pinned = source::source_dir(&paths.forja_root, &r.name);
+            let dir = if project && (pinned.exists() || pinned.is_symlink()) {
+                pinned
+            } else {
+                source::source_dir(&global_root, &r.name)
+            };
+            (r, dir)
+        })
+        .collect()
+}
+
+/// Merge several registries into one. The highest-priority registry (ties go
+/// to the one listed first) keeps the plain `phase/tech/name` ID; the others
+/// are shadowed and only reachable as `<registry>:phase/tech/name`.
+pub fn scan_sources(sources: &[Source], installed_ids: &[String]) -> Result<Registry> {
+    let mut ordered: Vec<&Source> = sources.iter().collect();
+    ordered.sort_by_key(|s| Reverse(s.priority));
+
+    let mut seen = HashSet::new();
+    let mut skills = Vec::new();
+    for source in ordered {
+        for mut skill in scan_dir(&source.path, &source.name)? {
+            if !seen.insert(skill.id.clone()) {
+                skill.id = skill.qualified_id();
+            }
+            skill.installed = installed_ids.contains(&skill.id);
+            skills.push(skill);
+        }
+    }
+
+    // Keep catalog order, with shadowed copies right after the skill that wins.
+    skills.sort_by(|a, b| a.bare_id().cmp(b.bare_id()));
+    Ok(Registry::new(skills))
+}
+
+/// Structure expected: skills/<phase>/<tech>/<skill-name>/{skill.json|.claude-plugin/plugin.json}
+fn scan_dir(registry_path: &Path, registry_name: &str) -> Result<Vec<Skill>> {
     let skills_dir = registry_path.join("skills");
     if !skills_dir.exists() {
-        return Ok(Registry::new(vec![]));
+        return Ok(Vec::new());
     }
 
     let mut skills = Vec::new();
@@ -36,14 +143,14 @@ pub fn scan(registry_path: &Path, installed_ids: &[String]) -> Result<Registry>
                 let skill_name = file_name(&skill_entry);
                 let id = format!("{phase_name}/{tech}/{skill_name}");
 
-                if let Some(skill) = parse_skill(&skill_entry, &id, phase, &tech, installed_ids) {
+                if let Some(skill) = parse_skill(&skill_entry, &id, registry_name, phase, &tech) {
                     skills.push(skill);
                 }
             }
         }
     }
 
-    Ok(Registry::new(skills))
+    Ok(skills)
 }
 
 /// Check whether `dir` looks like the forja skill registry.
@@ -70,9 +177,9 @@ pub fn is_forja_registry(dir: &Path) -> bool {
 fn parse_skill(
     path: &Path,
     id: &str,
+    registry_name: &str,
     phase: Phase,
     tech: &str,
-    installed_ids: &[String],
 ) -> Option<Skill> {
     let plugin = load_manifest(path)?;
 
@@ -89,12 +196,16 @@ fn parse_skill(
 
     Some(Skill {
         id: id.to_string(),
+        registry: registry_name.to_string(),
         name: plugin.name,
         description: plugin.description,
+        version: plugin.version,
+        requires: plugin.requires,
+        settings: plugin.settings,
         phase,
         tech: tech.to_string(),
         path: path.to_path_buf(),
-        installed: installed_ids.contains(&id.to_string()),
+        installed: false,
         content_types,
         keywords: plugin.keywords.unwrap_or_default(),
     })
@@ -174,6 +285,62 @@ mod tests {
         assert!(registry.skills.is_empty());
     }
 
+    fn source(name: &str, dir: &TempDir, priority: i32) -> Source {
+        Source {
+            name: name.to_string(),
+            path: dir.path().to_path_buf(),
+            priority,
+        }
+    }
+
+    #[test]
+    fn higher_priority_registry_shadows_same_id() {
+        let public = TempDir::new().unwrap();
+        let private = TempDir::new().unwrap();
+        create_skill(public.path(), "code", "general", "feature", "Public");
+        create_skill(public.path(), "test", "tdd", "workflow", "Public only");
+        create_skill(private.path(), "code", "general", "feature", "Private");
+
+        let installed = vec!["default:code/general/feature".to_string()];
+        let registry = scan_sources(
+            &[source("default", &public, 0), source("acme", &private, 10)],
+            &installed,
+        )
+        .unwrap();
+
+        let ids: Vec<&str> = registry.skills.iter().map(|s| s.id.as_str()).collect();
+        assert_eq!(
+            ids,
+            [
+                "code/general/feature",
+                "default:code/general/feature",
+                "test/tdd/workflow"
+            ]
+        );
+        assert_eq!(registry.skills[0].registry, "acme");
+        assert!(!registry.skills[0].installed);
+        assert_eq!(registry.skills[1].description, "Public");
+        assert!(registry.skills[1].installed);
+        assert_eq!(registry.skills[2].registry, "default");
+    }
+
+    #[test]
+    fn priority_ties_go_to_the_first_listed_registry() {
+        let first = TempDir::new().unwrap();
+        let second = TempDir::new().unwrap();
+        create_skill(first.path(), "code", "general", "feature", "First");
+        create_skill(second.path(), "code", "general", "feature", "Second");
+
+        let registry = scan_sources(
+            &[source("default", &first, 0), source("acme", &second, 0)],
+            &[],
+        )
+        .unwrap();
+
+        assert_eq!(registry.skills[0].registry, "default");
+        assert_eq!(registry.skills[1].id, "acme:code/general/feature");
+    }
+
     #[test]
     fn is_forja_registry_with_valid_phases() {
         let dir = TempDir::new().unwrap();
@@ -0,0 +1,107 @@
+use std::fs;
+use std::path::Path;
+
+use crate::models::version::parse_version;
+
+/// Changelog kept in a skill directory, next to `skill.json`.
+pub const CHANGELOG_FILE: &str = "CHANGELOG.md";
+
+/// Lines of a single excerpt shown before it's cut short.
+const MAX_EXCERPT_LINES: usize = 12;
+
+/// The changelog sections for versions after `from` up to and including `to`,
+/// newest first as written. A section starts at a `#` heading whose first
+/// version-like word parses (`## 1.2.0`, `## [1.2.0] - 2026-01-31`, `### v2`).
+pub fn excerpt(skill_dir: &Path, from: Option<&str>, to: Option<&str>) -> Vec<String> {
+    let Ok(content) = fs::read_to_string(skill_dir.join(CHANGELOG_FILE)) else {
+        return Vec::new();
+    };
+    let from = from.and_then(parse_version);
+    let to = to.and_then(parse_version);
+
+    let mut lines = Vec::new();
+    let mut in_range = false;
+    for line in content.lines() {
+        if line.starts_with('#') {
+            if let Some(version) = heading_version(line) {
+                in_range = from.as_ref().is_none_or(|f| &version > f)
+                    && to.as_ref().is_none_or(|t| &version <= t);
+            }
+            if in_range {
+                lines.push(line.to_string());
+            }
+            continue;
+        }
+        if in_range && !(line.trim().is_empty() && lines.last().is_none_or(|l| l.is_empty())) {
+            lines.push(line.trim_end().to_string());
+        }
+    }
+
+    while lines.last().is_some_and(|l| l.is_empty()) {
+        lines.pop();
+    }
+    if lines.len() > MAX_EXCERPT_LINES {
+        let hidden = lines.len() - MAX_EXCERPT_LINES;
+        lines.truncate(MAX_EXCERPT_LINES);
+        lines.push(format!("… {hidden} more line(s) in {CHANGELOG_FILE}"));
+    }
+    lines
+}
+
+fn heading_version(line: &str) -> Option<semver::Version> {
+    line.trim_start_matches('#')
+        .split_whitespace()
+        .map(|word| word.trim_matches(|c| matches!(c, '[' | ']' | '(' | ')' | ':')))
+        .find_map(parse_version)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const CHANGELOG: &str = "# Changelog
+
+## [2.0.0] - 2026-03-01
+
+- Coder now writes tests first
+
+## 1.1.0
+
+- Adds a review step
+
+## 1.0.0
+
+- Initial release
+";
+
+    #[test]
+    fn excerpt_covers_versions_after_from_up_to_to() {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join(CHANGELOG_FILE), CHANGELOG).unwrap();
+
+        let lines = excerpt(dir.path(), Some("1.0.0"), Some("2.0.0"));
+        assert_eq!(
+            lines,
+            [
+                "## [2.0.0] - 2026-03-01",
+                "",
+                "- Coder now writes tests first",
+                "",
+                "## 1.1.0",
+                "",
+                "- Adds a review step",
+            ]
+        );
+
+        let lines = excerpt(dir.path(), Some("1.0.0"), Some("1.1.0"));
+        assert_eq!(lines.first().map(String::as_str), Some("## 1.1.0"));
+        assert!(!lines.iter().any(|l| l.contains("2.0.0")));
+    }
+
+    #[test]
+    fn excerpt_without_changelog_is_empty() {
+        let dir = TempDir::new().unwrap();
+        assert!(excerpt(dir.path(), Some("1.0.0"), Some("2.0.0")).is_empty());
+    }
+}
@@ -0,0 +1,296 @@
+use std::collections::HashSet;
+
+use crate::error::{ForjaError, Result};
+use crate::models::registry::Registry;
+use crate::models::skill::Skill;
+
+/// `id` and every skill it transitively requires, dependencies first and
+/// `id` last, each listed once.
+pub fn install_order<'a>(registry: &'a Registry, id: &str) -> Result<Vec<&'a Skill>> {
+    let root = registry
+        .find_by_id(id)
+        .ok_or_else(|| ForjaError::SkillNotFound(id.to_string()))?;
+    resolve(registry, root).map_err(|e| match e {
+        Unresolved::Cycle(cycle) => ForjaError::DependencyCycle(cycle.join(" -> ")),
+        Unresolved::Missing { skill, required } => {
+            ForjaError::MissingDependency(format!("{skill} requires {required}"))
+        }
+    })
+}
+
+enum Unresolved {
+    /// The IDs around the loop, the first repeated at the end.
+    Cycle(Vec<String>),
+    Missing {
+        skill: String,
+        required: String,
+    },
+}
+
+fn resolve<'a>(
+    registry: &'a Registry,
+    root: &'a Skill,
+) -> std::result::Result<Vec<&'a Skill>, Unresolved> {
+    let mut order = Vec::new();
+    let mut path = Vec::new();
+    visit(registry, root, &mut path, &mut order)?;
+    Ok(order)
+}
+
+fn visit<'a>(
+    registry: &'a Registry,
+    skill: &'a Skill,
+    path: &mut Vec<String>,
+    order: &mut Vec<&'a Skill>,
+) -> std::result::Result<(), Unresolved> {
+    if order.iter().any(|s| s.id == skill.id) {
+        return Ok(());
+    }
+    if let Some(start) = path.iter().position(|id| id == &skill.id) {
+        let mut cycle = path[start..].to_vec();
+        cycle.push(skill.id.clone());
+        return Err(Unresolved::Cycle(cycle));
+    }
+
+    path.push(skill.id.clone());
+    for required in &skill.requires {
+        let dep = registry
+            .find_by_id(required)
+            .ok_or_else(|| Unresolved::Missing {
+                skill: skill.id.clone(),
+                required: required.clone(),
+            })?;
+        visit(registry, dep, path, order)?;
+    }
+    path.pop();
+
+    order.push(skill);
+    Ok(())
+}
+
+/// Skills in the catalog that directly require `id`.
+pub fn dependents<'a>(registry: &'a Registry, id: &str) -> Vec<&'a Skill> {
+    registry
+        .skills
+        .iter()
+        .filter(|s| s.id != id)
+        .filter(|s| {
+            s.requires
+                .iter()
+                .any(|r| registry.find_by_id(r).is_some_and(|dep| dep.id == id))
+        })
+        .collect()
+}
+
+/// Installed skills that directly require `id`.
+pub fn installed_dependents<'a>(
+    registry: &'a Registry,
+    id: &str,
+    installed_ids: &[String],
+) -> Vec<&'a str> {
+    dependents(registry, id)
+        .into_iter()
+        .filter(|s| installed_ids.contains(&s.id))
+        .map(|s| s.id.as_str())
+        .collect()
+}
+
+/// Fail with `RequiredBy` while installed skills require `id`.
+pub fn check_removable(registry: &Registry, id: &str, installed_ids: &[String]) -> Result<()> {
+    let dependents = installed_dependents(registry, id, installed_ids);
+    if dependents.is_empty() {
+        Ok(())
+    } else {
+        Err(ForjaError::RequiredBy(
+            id.to_string(),
+            dependents.join(", "),
+        ))
+    }
+}
+
+/// Where a node of the dependency tree stands.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DepState {
+    Found {
+        installed: bool,
+    },
+    /// Not in any registry.
+    Missing,
+    /// Already on the path from the root: the tree loops back here.
+    Cycle,
+}
+
+/// One line of a dependency tree, `depth` 0 being the root's direct requirements.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DepLine {
+    pub depth: usize,
+    pub id: String,
+    pub state: DepState,
+}
+
+/// The requirements of `skill`, depth-first, for display. Unlike
+/// `install_order`, missing skills and cycles are reported instead of failing.
+pub fn tree(registry: &Registry, skill: &Skill) -> Vec<DepLine> {
+    let mut lines = Vec::new();
+    let mut path = vec![skill.id.clone()];
+    walk(registry, skill, 0, &mut path, &mut lines);
+    lines
+}
+
+fn walk(
+    registry: &Registry,
+    skill: &Skill,
+    depth: usize,
+    path: &mut Vec<String>,
+    lines: &mut Vec<DepLine>,
+) {
+    for required in &skill.requires {
+        let Some(dep) = registry.find_by_id(required) else {
+            lines.push(DepLine {
+                depth,
+                id: required.clone(),
+                state: DepState::Missing,
+            });
+            continue;
+        };
+        if path.contains(&dep.id) {
+            lines.push(DepLine {
+                depth,
+                id: dep.id.clone(),
+                state: DepState::Cycle,
+            });
+            continue;
+        }
+        lines.push(DepLine {
+            depth,
+            id: dep.id.clone(),
+            state: DepState::Found {
+                installed: dep.installed,
+            },
+        });
+        path.push(dep.id.clone());
+        walk(registry, dep, depth + 1, path, lines);
+        path.pop();
+    }
+}
+
+/// Every dependency cycle in the catalog, each as `[a, b, a]`, reported once.
+pub fn find_cycles(registry: &Registry) -> Vec<Vec<String>> {
+    let mut cycles = Vec::new();
+    let mut reported: HashSet<Vec<String>> = HashSet::new();
+    for skill in &registry.skills {
+        if let Err(Unresolved::Cycle(cycle)) = resolve(registry, skill) {
+            let mut members = cycle[..cycle.len() - 1].to_vec();
+            members.sort();
+            if reported.insert(members) {
+                cycles.push(cycle);
+            }
+        }
+    }
+    cycles
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::phase::Phase;
+    use std::path::PathBuf;
+
+    fn skill(id: &str, requires: &[&str]) -> Skill {
+        Skill {
+            id: id.to_string(),
+            registry: "default".to_string(),
+            name: id.rsplit('/').next().unwrap().to_string(),
+            description: String::new(),
+            version: None,
+            requires: requires.iter().map(|r| r.to_string()).collect(),
+            settings: Default::default(),
+            phase: Phase::Code,
+            tech: "general".to_string(),
+            path: PathBuf::from("/tmp/test"),
+            installed: false,
+            content_types: vec![],
+            keywords: vec![],
+        }
+    }
+
+    #[test]
+    fn install_order_puts_dependencies_first_once() {
+        let registry = Registry::new(vec![
+            skill(
+                "teams/quick-fix/team",
+                &["code/general/feature", "deploy/git/commit"],
+            ),
+            skill("code/general/feature", &["research/codebase/explorer"]),
+            skill("deploy/git/commit", &["research/codebase/explorer"]),
+            skill("research/codebase/explorer", &[]),
+        ]);
+
+        let order: Vec<&str> = install_order(&registry, "teams/quick-fix/team")
+            .unwrap()
+            .iter()
+            .map(|s| s.id.as_str())
+            .collect();
+        assert_eq!(
+            order,
+            [
+                "research/codebase/explorer",
+                "code/general/feature",
+                "deploy/git/commit",
+                "teams/quick-fix/team",
+            ]
+        );
+    }
+
+    #[test]
+    fn install_order_rejects_missing_and_cyclic_dependencies() {
+        let registry = Registry::new(vec![
+            skill("code/a/one", &["code/a/two"]),
+            skill("code/a/two", &["code/a/one"]),
+            skill("code/a/three", &["code/a/gone"]),
+        ]);
+
+        let err = install_order(&registry, "code/a/one").unwrap_err();
+        assert!(matches!(
+            err,
+            ForjaError::DependencyCycle(ref c) if c == "code/a/one -> code/a/two -> code/a/one"
+        ));
+        assert!(matches!(
+            install_order(&registry, "code/a/three").unwrap_err(),
+            ForjaError::MissingDependency(_)
+        ));
+        assert_eq!(find_cycles(&registry).len(), 1);
+    }
+
+    #[test]
+    fn dependents_and_tree() {
+        let mut team = skill(
+            "teams/quick-fix/team",
+            &["code/general/feature", "code/x/gone"],
+        );
+        team.installed = true;
+        let mut coder = skill("code/general/feature", &[]);
+        coder.installed = true;
+        let registry = Registry::new(vec![team.clone(), coder]);
+        let installed = vec![
+            "teams/quick-fix/team".to_string(),
+            "code/general/feature".to_string(),
+        ];
+
+        assert_eq!(
+            installed_dependents(&registry, "code/general/feature", &installed),
+            ["teams/quick-fix/team"]
+        );
+        assert!(installed_dependents(&registry, "teams/quick-fix/team", &installed).is_empty());
+        assert!(matches!(
+            check_removable(&registry, "code/general/feature", &installed),
+            Err(ForjaError::RequiredBy(_, _))
+        ));
+        assert!(check_removable(&registry, "teams/quick-fix/team", &installed).is_ok());
+
+        let lines = tree(&registry, &team);
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0].state, DepState::Found { installed: true });
+        assert_eq!(lines[1].state, DepState::Missing);
+    }
+}
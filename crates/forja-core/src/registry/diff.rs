@@ -4,12 +4,25 @@ use std::path::Path;
 use std::process::Command;
 
 use crate::error::{ForjaError, Result};
+use crate::models::plugin::PluginJson;
+use crate::models::version::{Bump, classify};
 
 /// A skill that changed between two git revisions.
 #[derive(Debug, Clone)]
 pub struct SkillChange {
     pub skill_id: String,
     pub change_type: ChangeType,
+    /// Version declared in `skill.json` at the old revision.
+    pub old_version: Option<String>,
+    /// Version declared in `skill.json` at the new revision.
+    pub new_version: Option<String>,
+}
+
+impl SkillChange {
+    /// How far the declared version moved.
+    pub fn bump(&self) -> Bump {
+        classify(self.old_version.as_deref(), self.new_version.as_deref())
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -52,7 +65,29 @@ pub fn compute_diff(registry_path: &Path, old_head: &str, new_head: &str) -> Res
     }
 
     let stdout = String::from_utf8_lossy(&output.stdout);
-    parse_diff_output(&stdout)
+    let mut changes = parse_diff_output(&stdout)?;
+    for change in &mut changes {
+        change.old_version = version_at(registry_path, old_head, &change.skill_id);
+        change.new_version = version_at(registry_path, new_head, &change.skill_id);
+    }
+    Ok(changes)
+}
+
+/// The version a skill declared at `rev`, read from its manifest in git.
+pub fn version_at(registry_path: &Path, rev: &str, skill_id: &str) -> Option<String> {
+    ["skill.json", ".claude-plugin/plugin.json"]
+        .iter()
+        .find_map(|manifest| {
+            let output = Command::new("git")
+                .arg("-C")
+                .arg(registry_path)
+                .args(["show", &format!("{rev}:skills/{skill_id}/{manifest}")])
+                .output()
+                .ok()
+                .filter(|o| o.status.success())?;
+            serde_json::from_slice::<PluginJson>(&output.stdout).ok()
+        })
+        .and_then(|plugin| plugin.version)
 }
 
 fn parse_diff_output(output: &str) -> Result<Vec<SkillChange>> {
@@ -95,6 +130,8 @@ fn parse_diff_output(output: &str) -> Result<Vec<SkillChange>> {
         .map(|(skill_id, change_type)| SkillChange {
             skill_id,
             change_type,
+            old_version: None,
+            new_version: None,
         })
         .collect();
 
@@ -49,11 +49,324 @@ pub fn pull(repo_path: &Path) -> Result<String> {
     Ok(String::from_utf8_lossy(&output.stdout).to_string())
 }
 
+/// Run `git -C <repo> <args>`, failing with the command's stderr.
+fn run(repo_path: &Path, args: &[&str]) -> Result<String> {
+    let output = Command::new("git")
+        .args(["-C"])
+        .arg(repo_path)
+        .args(args)
+        .output()
+        .map_err(ForjaError::Io)?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(ForjaError::Git(format!(
+            "git {} failed: {}",
+            args[0],
+            stderr.trim()
+        )));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Whether `path` is inside a git work tree with at least one commit.
+pub fn is_work_tree(path: &Path) -> bool {
+    run(path, &["rev-parse", "--verify", "-q", "HEAD"]).is_ok()
+}
+
+/// Pathspec for the whole work tree minus `exclude` (paths relative to the repo root).
+fn pathspec(exclude: &[&str]) -> Vec<String> {
+    let mut spec = vec!["--".to_string(), ":/".to_string()];
+    spec.extend(exclude.iter().map(|p| format!(":(top,exclude){p}")));
+    spec
+}
+
+/// Run `git <args> -- <pathspec>`.
+fn run_on(repo_path: &Path, args: &[&str], exclude: &[&str]) -> Result<String> {
+    let spec = pathspec(exclude);
+    let mut all: Vec<&str> = args.to_vec();
+    all.extend(spec.iter().map(String::as_str));
+    run(repo_path, &all)
+}
+
+/// Root directory of the work tree containing `path`.
+pub fn toplevel(path: &Path) -> Result<std::path::PathBuf> {
+    run(path, &["rev-parse", "--show-toplevel"]).map(Into::into)
+}
+
+/// The repository's shared `.git` directory, the same for all its worktrees.
+pub fn common_dir(path: &Path) -> Result<std::path::PathBuf> {
+    run(
+        path,
+        &["rev-parse", "--path-format=absolute", "--git-common-dir"],
+    )
+    .map(Into::into)
+}
+
+/// Whether `path` (relative to the repo root) is ignored by git.
+pub fn is_ignored(repo_path: &Path, path: &str) -> bool {
+    run(repo_path, &["check-ignore", "-q", path]).is_ok()
+}
+
+/// Whether the work tree has uncommitted changes outside `exclude`,
+/// untracked files included.
+pub fn is_dirty(repo_path: &Path, exclude: &[&str]) -> Result<bool> {
+    Ok(!run_on(repo_path, &["status", "--porcelain"], exclude)?.is_empty())
+}
+
+/// `git diff --shortstat` of the working tree against `base`,
+/// e.g. `3 files changed, 10 insertions(+)`. Empty when nothing differs.
+pub fn diff_shortstat(repo_path: &Path, base: &str) -> Result<String> {
+    run(repo_path, &["diff", "--shortstat", base])
+}
+
+/// The checked-out branch, or `None` on a detached HEAD.
+pub fn current_branch(repo_path: &Path) -> Result<Option<String>> {
+    let branch = run(repo_path, &["branch", "--show-current"])?;
+    Ok((!branch.is_empty()).then_some(branch))
+}
+
+fn branch_exists(repo_path: &Path, branch: &str) -> bool {
+    run(
+        repo_path,
+        &[
+            "rev-parse",
+            "--verify",
+            "-q",
+            &format!("refs/heads/{branch}"),
+        ],
+    )
+    .is_ok()
+}
+
+/// Switch to `branch`, creating it from HEAD if it doesn't exist.
+/// Uncommitted changes are carried over. Returns whether it was created.
+pub fn switch_branch(repo_path: &Path, branch: &str) -> Result<bool> {
+    let exists = branch_exists(repo_path, branch);
+    if exists {
+        run(repo_path, &["switch", branch])?;
+    } else {
+        run(repo_path, &["switch", "-c", branch])?;
+    }
+    Ok(!exists)
+}
+
+/// Stage everything outside `exclude` and commit it. Returns the new
+/// commit, or `None` when there was nothing to commit.
+pub fn commit_all(repo_path: &Path, message: &str, exclude: &[&str]) -> Result<Option<String>> {
+    run_on(repo_path, &["add", "-A"], exclude)?;
+    if run(repo_path, &["diff", "--cached", "--quiet"]).is_ok() {
+        return Ok(None);
+    }
+    run(repo_path, &["commit", "-m", message])?;
+    head_sha(repo_path).map(Some)
+}
+
+/// Stash uncommitted changes outside `exclude`, untracked files included.
+/// Returns whether anything was stashed.
+pub fn stash(repo_path: &Path, message: &str, exclude: &[&str]) -> Result<bool> {
+    if !is_dirty(repo_path, exclude)? {
+        return Ok(false);
+    }
+    run_on(
+        repo_path,
+        &["stash", "push", "--include-untracked", "-m", message],
+        exclude,
+    )?;
+    Ok(true)
+}
+
+/// Whether `ancestor` is `rev` or one of its ancestors.
+pub fn is_ancestor(repo_path: &Path, ancestor: &str, rev: &str) -> bool {
+    run(repo_path, &["merge-base", "--is-ancestor", ancestor, rev]).is_ok()
+}
+
+/// Point the current branch at `rev` and reset the work tree to it.
+pub fn reset_hard(repo_path: &Path, rev: &str) -> Result<()> {
+    run(repo_path, &["reset", "--hard", rev]).map(|_| ())
+}
+
+/// Check `branch` out in a new worktree at `path`, creating the branch from
+/// HEAD if it doesn't exist. Returns whether the branch was created.
+pub fn add_worktree(repo_path: &Path, path: &Path, branch: &str) -> Result<bool> {
+    let path = path.to_string_lossy();
+    let exists = branch_exists(repo_path, branch);
+    if exists {
+        run(repo_path, &["worktree", "add", &path, branch])?;
+    } else {
+        run(repo_path, &["worktree", "add", "-b", branch, &path])?;
+    }
+    Ok(!exists)
+}
+
+/// Remove a worktree. `force` discards its uncommitted changes.
+pub fn remove_worktree(repo_path: &Path, path: &Path, force: bool) -> Result<()> {
+    let path = path.to_string_lossy();
+    let mut args = vec!["worktree", "remove"];
+    if force {
+        args.push("--force");
+    }
+    args.push(&path);
+    run(repo_path, &args).map(|_| ())
+}
+
+/// Merge `branch` into the checked-out branch with a merge commit.
+/// A conflicting merge is aborted, leaving the tree as it was.
+pub fn merge(repo_path: &Path, branch: &str, message: &str) -> Result<()> {
+    let result = run(repo_path, &["merge", "--no-ff", "-m", message, branch]);
+    if result.is_err() {
+        let _ = run(repo_path, &["merge", "--abort"]);
+    }
+    result.map(|_| ())
+}
+
+/// Delete a local branch. Without `force`, git refuses unmerged branches.
+pub fn delete_branch(repo_path: &Path, branch: &str, force: bool) -> Result<()> {
+    let flag = if force { "-D" } else { "-d" };
+    run(repo_path, &["branch", flag, branch]).map(|_| ())
+}
+
+/// Check out `rev` on a detached HEAD, fetching it first when a shallow
+/// clone doesn't have it.
+pub fn checkout_rev(repo_path: &Path, rev: &str) -> Result<()> {
+    if run(repo_path, &["cat-file", "-e", &format!("{rev}^{{commit}}")]).is_err() {
+        let fetched = run(repo_path, &["fetch", "-q", "--depth", "1", "origin", rev]).is_ok()
+            || run(repo_path, &["fetch", "-q", "--unshallow", "origin"]).is_ok();
+        if !fetched {
+            run(repo_path, &["fetch", "-q", "origin"])?;
+        }
+    }
+    run(repo_path, &["checkout", "-q", "--detach", rev]).map(|_| ())
+}
+
+/// Leave a detached HEAD for the remote's default branch, so `pull` can
+/// fast-forward again after `checkout_rev`.
+pub fn attach_default_branch(repo_path: &Path) -> Result<()> {
+    if current_branch(repo_path)?.is_some() {
+        return Ok(());
+    }
+    let remote_head = run(repo_path, &["rev-parse", "--abbrev-ref", "origin/HEAD"])?;
+    let branch = remote_head.strip_prefix("origin/").unwrap_or(&remote_head);
+    run(repo_path, &["checkout", "-q", branch]).map(|_| ())
+}
+
+/// Three-way merge of single files with `git merge-file`, labelling the
+/// sides `local` and `registry`. Returns the merged text and whether it
+/// contains conflict markers. Nothing is written back to the inputs.
+pub fn merge_file(local: &Path, base: &Path, upstream: &Path) -> Result<(String, bool)> {
+    let output = Command::new("git")
+        .args(["merge-file", "-p"])
+        .args(["-L", "local", "-L", "base", "-L", "registry"])
+        .arg(local)
+        .arg(base)
+        .arg(upstream)
+        .output()
+        .map_err(ForjaError::Io)?;
+
+    // The exit code is the number of conflicts; negative (>127 here) on error.
+    match output.status.code() {
+        Some(code) if (0..128).contains(&code) => Ok((
+            String::from_utf8_lossy(&output.stdout).to_string(),
+            code > 0,
+        )),
+        _ => {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            Err(ForjaError::Git(format!(
+                "git merge-file failed: {}",
+                stderr.trim()
+            )))
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use tempfile::TempDir;
 
+    /// A repository with one commit and a local identity.
+    fn repo() -> TempDir {
+        let dir = TempDir::new().unwrap();
+        for args in [
+            &["init", "-q", "-b", "main"][..],
+            &["config", "user.email", "forja@example.com"],
+            &["config", "user.name", "forja"],
+        ] {
+            run(dir.path(), args).unwrap();
+        }
+        std::fs::write(dir.path().join("README.md"), "hello\n").unwrap();
+        commit_all(dir.path(), "initial", &[]).unwrap();
+        dir
+    }
+
+    #[test]
+    fn is_work_tree_needs_a_commit() {
+        let dir = TempDir::new().unwrap();
+        assert!(!is_work_tree(dir.path()));
+        assert!(is_work_tree(repo().path()));
+    }
+
+    #[test]
+    fn commit_stash_and_reset_round_trip() {
+        let dir = repo();
+        let path = dir.path();
+        let base = head_sha(path).unwrap();
+
+        assert!(switch_branch(path, "forja/p1").unwrap());
+        assert_eq!(current_branch(path).unwrap().as_deref(), Some("forja/p1"));
+        assert_eq!(commit_all(path, "nothing", &[]).unwrap(), None);
+
+        std::fs::write(path.join("README.md"), "hello\nworld\n").unwrap();
+        assert!(
+            diff_shortstat(path, &base)
+                .unwrap()
+                .contains("1 insertion(+)")
+        );
+        let phase = commit_all(path, "phase 1", &[]).unwrap().unwrap();
+        assert_ne!(phase, base);
+
+        std::fs::write(path.join("half.txt"), "partial").unwrap();
+        std::fs::create_dir_all(path.join(".forja/plans")).unwrap();
+        std::fs::write(path.join(".forja/plans/p1.json"), "{}").unwrap();
+        let exclude = [".forja/plans"];
+        assert!(is_dirty(path, &exclude).unwrap());
+        assert!(stash(path, "half-applied", &exclude).unwrap());
+        assert!(!is_dirty(path, &exclude).unwrap());
+        assert!(is_dirty(path, &[]).unwrap());
+        assert!(!stash(path, "clean", &exclude).unwrap());
+        assert!(path.join(".forja/plans/p1.json").exists());
+
+        reset_hard(path, &base).unwrap();
+        assert_eq!(head_sha(path).unwrap(), base);
+        assert_eq!(
+            std::fs::read_to_string(path.join("README.md")).unwrap(),
+            "hello\n"
+        );
+        assert!(!switch_branch(path, "forja/p1").unwrap());
+    }
+
+    #[test]
+    fn worktree_changes_merge_back() {
+        let dir = repo();
+        let path = dir.path();
+        let wt_parent = TempDir::new().unwrap();
+        let wt = wt_parent.path().join("p1");
+
+        assert!(add_worktree(path, &wt, "forja/p1").unwrap());
+        std::fs::write(wt.join("feature.txt"), "new\n").unwrap();
+        commit_all(&wt, "phase 1", &[]).unwrap().unwrap();
+        assert!(!path.join("feature.txt").exists());
+
+        merge(path, "forja/p1", "Merge plan p1").unwrap();
+        assert!(path.join("feature.txt").exists());
+
+        remove_worktree(path, &wt, false).unwrap();
+        assert!(!wt.exists());
+        delete_branch(path, "forja/p1", false).unwrap();
+        assert!(!branch_exists(path, "forja/p1"));
+    }
+
     #[test]
     fn clone_fails_with_invalid_url() {
         let dir = TempDir::new().unwrap();
@@ -73,4 +386,25 @@ mod tests {
         let result = pull(dir.path());
         assert!(result.is_err());
     }
+
+    #[test]
+    fn checkout_rev_pins_and_attach_returns_to_branch_tip() {
+        let origin = repo();
+        let first = head_sha(origin.path()).unwrap();
+        std::fs::write(origin.path().join("README.md"), "v2\n").unwrap();
+        let second = commit_all(origin.path(), "second", &[]).unwrap().unwrap();
+
+        let parent = TempDir::new().unwrap();
+        let clone_dir = parent.path().join("registry");
+        clone(&format!("file://{}", origin.path().display()), &clone_dir).unwrap();
+        assert_eq!(head_sha(&clone_dir).unwrap(), second);
+
+        checkout_rev(&clone_dir, &first).unwrap();
+        assert_eq!(head_sha(&clone_dir).unwrap(), first);
+        assert!(current_branch(&clone_dir).unwrap().is_none());
+
+        attach_default_branch(&clone_dir).unwrap();
+        assert_eq!(current_branch(&clone_dir).unwrap().as_deref(), Some("main"));
+        assert_eq!(head_sha(&clone_dir).unwrap(), second);
+    }
 }
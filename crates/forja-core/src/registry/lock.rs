@@ -0,0 +1,320 @@
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use sha2::{Digest, Sha256};
+
+use crate::error::{ForjaError, Result};
+use crate::models::config;
+use crate::models::lock::{self, ForjaLock, LockedRegistry, LockedSkill};
+use crate::models::source::{self, DEFAULT_REGISTRY, RegistrySource};
+use crate::paths::ForjaPaths;
+use crate::registry::{catalog, git};
+use crate::symlink::manager::load_installed_ids;
+
+/// A way the checkouts on disk differ from `forja.lock`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Drift {
+    MissingRegistry(String),
+    Revision {
+        registry: String,
+        locked: String,
+        actual: Option<String>,
+    },
+    MissingSkill(String),
+    Unlocked(String),
+    Content {
+        id: String,
+        locked: String,
+        actual: String,
+    },
+}
+
+impl fmt::Display for Drift {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Drift::MissingRegistry(name) => write!(f, "registry '{name}' is not set up"),
+            Drift::Revision {
+                registry,
+                locked,
+                actual,
+            } => write!(
+                f,
+                "registry '{registry}' is at {}, locked at {}",
+                actual.as_deref().map(short).unwrap_or("no commit"),
+                short(locked)
+            ),
+            Drift::MissingSkill(id) => write!(f, "skill '{id}' is locked but not in the catalog"),
+            Drift::Unlocked(id) => write!(f, "skill '{id}' is installed but not locked"),
+            Drift::Content { id, locked, actual } => write!(
+                f,
+                "skill '{id}' content is {}, locked as {}",
+                short_hash(actual),
+                short_hash(locked)
+            ),
+        }
+    }
+}
+
+/// Snapshot every registry's revision and the content of every installed skill.
+pub fn snapshot(paths: &ForjaPaths) -> Result<ForjaLock> {
+    let named = catalog::named_registries(paths);
+    let default_config = config::load_config(&paths.config);
+
+    let mut registries = Vec::new();
+    for src in catalog::sources(paths) {
+        if !src.path.exists() {
+            continue;
+        }
+        let (url, local) = if src.name == DEFAULT_REGISTRY {
+            default_config
+                .as_ref()
+                .map(|c| (c.registry_url.clone(), c.local))
+                .unwrap_or_else(|| (src.path.display().to_string(), src.path.is_symlink()))
+        } else {
+            named
+                .iter()
+                .find(|(r, _)| r.name == src.name)
+                .map(|(r, _)| (r.url.clone(), r.local))
+                .unwrap_or_else(|| (src.path.display().to_string(), src.path.is_symlink()))
+        };
+        registries.push(LockedRegistry {
+            name: src.name,
+            url,
+            rev: git::head_sha(&src.path).ok(),
+            local,
+            priority: src.priority,
+        });
+    }
+
+    let installed_ids = load_installed_ids(&paths.state);
+    let registry = catalog::scan_all(paths, &installed_ids)?;
+    let mut skills = Vec::new();
+    for id in &installed_ids {
+        let Some(skill) = registry.find_by_id(id) else {
+            continue;
+        };
+        skills.push(LockedSkill {
+            id: id.clone(),
+            registry: skill.registry.clone(),
+            hash: content_hash(&skill.path)?,
+        });
+    }
+    skills.sort_by(|a, b| a.id.cmp(&b.id));
+
+    Ok(ForjaLock::new(registries, skills))
+}
+
+/// Rewrite `forja.lock` from the current checkouts. No-op in global mode.
+pub fn refresh(paths: &ForjaPaths) -> Result<()> {
+    let Some(path) = lock::lock_path(paths) else {
+        return Ok(());
+    };
+    lock::save_lock(&path, &snapshot(paths)?)
+}
+
+/// Compare the registries and installed skills on disk against `lock`.
+pub fn verify(paths: &ForjaPaths, lock: &ForjaLock) -> Result<Vec<Drift>> {
+    let current = snapshot(paths)?;
+    let mut drift = Vec::new();
+
+    for locked in &lock.registries {
+        let Some(actual) = current.registry(&locked.name) else {
+            drift.push(Drift::MissingRegistry(locked.name.clone()));
+            continue;
+        };
+        if let Some(rev) = &locked.rev
+            && !locked.local
+            && actual.rev.as_ref() != Some(rev)
+        {
+            drift.push(Drift::Revision {
+                registry: locked.name.clone(),
+                locked: rev.clone(),
+                actual: actual.rev.clone(),
+            });
+        }
+    }
+
+    let installed_ids = load_installed_ids(&paths.state);
+    let registry = catalog::scan_all(paths, &installed_ids)?;
+    for locked in &lock.skills {
+        let Some(skill) = registry.find_by_id(&locked.id) else {
+            drift.push(Drift::MissingSkill(locked.id.clone()));
+            continue;
+        };
+        let actual = content_hash(&skill.path)?;
+        if actual != locked.hash {
+            drift.push(Drift::Content {
+                id: locked.id.clone(),
+                locked: locked.hash.clone(),
+                actual,
+            });
+        }
+    }
+    for id in &installed_ids {
+        if !lock.skills.iter().any(|s| &s.id == id) {
+            drift.push(Drift::Unlocked(id.clone()));
+        }
+    }
+
+    Ok(drift)
+}
+
+/// Check every git registry out at its locked revision. Named registries are
+/// pinned in the project's own checkout under `.forja/registries/`, so other
+/// projects sharing `~/.forja/registries/` are unaffected, and registries this
+/// machine lacks are recorded in the project's `.forja/registries.json` — the
+/// global manifest is never written. Local registries are left alone.
+/// Returns the registries that moved.
+pub fn restore(paths: &ForjaPaths, lock: &ForjaLock) -> Result<Vec<(String, String)>> {
+    let global_root = ForjaPaths::global_forja_root()?;
+    let global = source::load_sources(&source::sources_path(&global_root));
+    let manifest_path = source::sources_path(&paths.forja_root);
+    let mut manifest = source::load_sources(&manifest_path);
+    let mut moved = Vec::new();
+
+    for locked in &lock.registries {
+        let is_default = locked.name == DEFAULT_REGISTRY;
+        let known = is_default || global.find(&locked.name).is_some();
+        if locked.local && known {
+            continue;
+        }
+        let dir = if is_default {
+            paths.registry.clone()
+        } else {
+            source::source_dir(&paths.forja_root, &locked.name)
+        };
+
+        if !dir.exists() && !set_up(locked, &dir)? {
+            continue;
+        }
+        if !known && manifest.find(&locked.name).is_none() {
+            manifest.registries.push(RegistrySource {
+                name: locked.name.clone(),
+                url: locked.url.clone(),
+                local: locked.local,
+                priority: locked.priority,
+            });
+            source::save_sources(&manifest_path, &manifest)?;
+        }
+
+        let Some(rev) = &locked.rev else {
+            continue;
+        };
+        if locked.local || git::head_sha(&dir).ok().as_ref() == Some(rev) {
+            continue;
+        }
+        git::checkout_rev(&dir, rev)?;
+        moved.push((locked.name.clone(), rev.clone()));
+    }
+
+    Ok(moved)
+}
+
+/// Clone (or link) a locked registry that's missing here. Returns `false`
+/// for a local registry whose directory doesn't exist on this machine.
+fn set_up(locked: &LockedRegistry, dir: &Path) -> Result<bool> {
+    if let Some(parent) = dir.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    if locked.local {
+        if !Path::new(&locked.url).is_dir() {
+            return Ok(false);
+        }
+        source::link_local(Path::new(&locked.url), dir)?;
+    } else {
+        git::clone(&locked.url, dir)?;
+    }
+    Ok(true)
+}
+
+/// `sha256:<hex>` over every file under `dir`: relative path and contents,
+/// in path order, so the hash only changes when the skill does.
+pub fn content_hash(dir: &Path) -> Result<String> {
+    let mut files = Vec::new();
+    collect_files(dir, &mut files)?;
+    files.sort();
+
+    let mut hasher = Sha256::new();
+    for file in &files {
+        let rel = file.strip_prefix(dir).unwrap_or(file);
+        let contents = fs::read(file)?;
+        hasher.update(rel.to_string_lossy().as_bytes());
+        hasher.update([0]);
+        hasher.update((contents.len() as u64).to_le_bytes());
+        hasher.update(&contents);
+    }
+    Ok(format!("sha256:{:x}", hasher.finalize()))
+}
+
+fn collect_files(dir: &Path, files: &mut Vec<PathBuf>) -> Result<()> {
+    for entry in fs::read_dir(dir).map_err(ForjaError::Io)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            collect_files(&path, files)?;
+        } else {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+fn short(sha: &str) -> &str {
+    &sha[..sha.len().min(7)]
+}
+
+fn short_hash(hash: &str) -> &str {
+    let hex = hash.strip_prefix("sha256:").unwrap_or(hash);
+    short(hex)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn content_hash_tracks_paths_and_contents() {
+        let dir = TempDir::new().unwrap();
+        fs::create_dir_all(dir.path().join("agents")).unwrap();
+        fs::write(dir.path().join("agents/coder.md"), "# Coder").unwrap();
+        fs::write(dir.path().join("skill.json"), "{}").unwrap();
+
+        let first = content_hash(dir.path()).unwrap();
+        assert!(first.starts_with("sha256:"));
+        assert_eq!(first, content_hash(dir.path()).unwrap());
+
+        fs::write(dir.path().join("agents/coder.md"), "# Coder v2").unwrap();
+        let edited = content_hash(dir.path()).unwrap();
+        assert_ne!(edited, first);
+
+        fs::rename(
+            dir.path().join("agents/coder.md"),
+            dir.path().join("agents/writer.md"),
+        )
+        .unwrap();
+        assert_ne!(content_hash(dir.path()).unwrap(), edited);
+    }
+
+    #[test]
+    fn drift_messages_use_short_ids() {
+        let drift = Drift::Revision {
+            registry: "default".to_string(),
+            locked: "1234567890".to_string(),
+            actual: Some("abcdef0123".to_string()),
+        };
+        assert_eq!(
+            drift.to_string(),
+            "registry 'default' is at abcdef0, locked at 1234567"
+        );
+        let drift = Drift::Content {
+            id: "code/general/feature".to_string(),
+            locked: "sha256:aaaaaaaaaa".to_string(),
+            actual: "sha256:bbbbbbbbbb".to_string(),
+        };
+        assert_eq!(
+            drift.to_string(),
+            "skill 'code/general/feature' content is bbbbbbb, locked as aaaaaaa"
+        );
+    }
+}
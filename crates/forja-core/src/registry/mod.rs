@@ -1,3 +1,8 @@
+pub mod bundles;
 pub mod catalog;
+pub mod changelog;
+pub mod deps;
 pub mod diff;
 pub mod git;
+pub mod lock;
+pub mod search;
@@ -0,0 +1,393 @@
+use serde::Serialize;
+
+use crate::models::registry::Registry;
+use crate::models::skill::Skill;
+
+/// A skill field a search term can match, best-ranked first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Field {
+    Name,
+    Keyword,
+    Tech,
+    Phase,
+    Id,
+    Description,
+}
+
+/// The part of a field a term matched, as a byte range into the field's text.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Highlight {
+    pub field: Field,
+    /// Which keyword, for [`Field::Keyword`].
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub index: Option<usize>,
+    pub start: usize,
+    pub end: usize,
+}
+
+/// A skill matching a query, with its score and what to highlight.
+#[derive(Debug, Clone, Serialize)]
+pub struct SearchHit<'a> {
+    pub skill: &'a Skill,
+    pub score: u32,
+    pub highlights: Vec<Highlight>,
+}
+
+/// A parsed search query: `rust review`, `rust OR go`, `phase:review tech:rust`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Query {
+    /// Alternatives separated by `OR`; every (lowercased) term of one must match.
+    pub groups: Vec<Vec<String>>,
+    pub phases: Vec<String>,
+    pub techs: Vec<String>,
+    pub registries: Vec<String>,
+}
+
+impl Query {
+    pub fn parse(raw: &str) -> Self {
+        let mut query = Query::default();
+        let mut group = Vec::new();
+        for token in raw.split_whitespace() {
+            if token == "OR" || token == "|" {
+                query.groups.push(std::mem::take(&mut group));
+                continue;
+            }
+            let filter = token.split_once(':').and_then(|(key, value)| {
+                match key {
+                    "phase" => Some(&mut query.phases),
+                    "tech" => Some(&mut query.techs),
+                    "registry" => Some(&mut query.registries),
+                    _ => None,
+                }
+                .filter(|_| !value.is_empty())
+                .map(|values| (values, value))
+            });
+            match filter {
+                Some((values, value)) => {
+                    values.extend(value.split(',').map(str::to_lowercase));
+                }
+                None => group.push(token.to_lowercase()),
+            }
+        }
+        query.groups.push(group);
+        query.groups.retain(|g| !g.is_empty());
+        query
+    }
+
+    /// Whether `skill` passes the `phase:`, `tech:` and `registry:` filters.
+    pub fn accepts(&self, skill: &Skill) -> bool {
+        let allows = |values: &[String], field: &str| {
+            values.is_empty() || values.iter().any(|v| v.eq_ignore_ascii_case(field))
+        };
+        allows(&self.phases, skill.phase.as_str())
+            && allows(&self.techs, &skill.tech)
+            && allows(&self.registries, &skill.registry)
+    }
+
+    /// Score `skill` against the best-matching group, or `None` if no group
+    /// matches. A query of filters alone matches everything with score 0.
+    pub fn score(&self, skill: &Skill) -> Option<(u32, Vec<Highlight>)> {
+        if self.groups.is_empty() {
+            return Some((0, Vec::new()));
+        }
+        self.groups
+            .iter()
+            .filter_map(|group| {
+                let mut total = 0;
+                let mut highlights = Vec::new();
+                for term in group {
+                    let (score, highlight) = score_term(skill, term)?;
+                    total += score;
+                    highlights.push(highlight);
+                }
+                Some((total, highlights))
+            })
+            .max_by_key(|(score, _)| *score)
+    }
+}
+
+/// Rank the catalog against `raw` (see [`Query`]): best score first, then by ID.
+pub fn search<'a>(registry: &'a Registry, raw: &str) -> Vec<SearchHit<'a>> {
+    let query = Query::parse(raw);
+    let mut hits: Vec<SearchHit> = registry
+        .skills
+        .iter()
+        .filter(|skill| query.accepts(skill))
+        .filter_map(|skill| {
+            let (score, highlights) = query.score(skill)?;
+            Some(SearchHit {
+                skill,
+                score,
+                highlights,
+            })
+        })
+        .collect();
+    hits.sort_by(|a, b| {
+        b.score
+            .cmp(&a.score)
+            .then_with(|| a.skill.id.cmp(&b.skill.id))
+    });
+    hits
+}
+
+/// The best match of one term in `skill`: exact name, then keywords, tech,
+/// phase, ID and description, then the same fields again allowing a typo.
+fn score_term(skill: &Skill, term: &str) -> Option<(u32, Highlight)> {
+    let best = |matches: Vec<(u32, Highlight)>| {
+        matches
+            .into_iter()
+            .reduce(|best, next| if next.0 > best.0 { next } else { best })
+    };
+    best(substring_matches(skill, term)).or_else(|| best(typo_matches(skill, term)))
+}
+
+fn substring_matches(skill: &Skill, term: &str) -> Vec<(u32, Highlight)> {
+    let graded = |text: &str, field, index, [exact, prefix, contains]: [u32; 3]| {
+        let (start, end) = find_ci(text, term)?;
+        let score = if end - start == text.len() {
+            exact
+        } else if start == 0 {
+            prefix
+        } else {
+            contains
+        };
+        Some(hit(score, field, index, (start, end)))
+    };
+
+    let mut matches = Vec::new();
+    matches.extend(graded(&skill.name, Field::Name, None, [100, 70, 50]));
+    for (i, keyword) in skill.keywords.iter().enumerate() {
+        matches.extend(graded(keyword, Field::Keyword, Some(i), [60, 35, 35]));
+    }
+    matches.extend(graded(&skill.tech, Field::Tech, None, [40, 25, 25]));
+    matches.extend(graded(
+        skill.phase.as_str(),
+        Field::Phase,
+        None,
+        [30, 20, 20],
+    ));
+    matches.extend(graded(&skill.id, Field::Id, None, [15; 3]));
+    matches.extend(graded(
+        &skill.description,
+        Field::Description,
+        None,
+        [10; 3],
+    ));
+    matches
+}
+
+/// Words within one edit of `term` (two for terms of 8+ characters).
+/// Terms shorter than 4 characters must match exactly.
+fn typo_matches(skill: &Skill, term: &str) -> Vec<(u32, Highlight)> {
+    let max_typos = match term.chars().count() {
+        0..=3 => return Vec::new(),
+        4..=7 => 1,
+        _ => 2,
+    };
+    let fuzzy = |text: &str, score, field, index| {
+        words(text)
+            .find(|(_, word)| within(word, term, max_typos))
+            .map(|(start, word)| hit(score, field, index, (start, start + word.len())))
+    };
+
+    let mut matches = Vec::new();
+    matches.extend(fuzzy(&skill.name, 20, Field::Name, None));
+    for (i, keyword) in skill.keywords.iter().enumerate() {
+        matches.extend(fuzzy(keyword, 15, Field::Keyword, Some(i)));
+    }
+    matches.extend(fuzzy(&skill.tech, 12, Field::Tech, None));
+    matches.extend(fuzzy(&skill.description, 5, Field::Description, None));
+    matches
+}
+
+fn hit(
+    score: u32,
+    field: Field,
+    index: Option<usize>,
+    (start, end): (usize, usize),
+) -> (u32, Highlight) {
+    let highlight = Highlight {
+        field,
+        index,
+        start,
+        end,
+    };
+    (score, highlight)
+}
+
+/// Byte range of the first case-insensitive occurrence of the lowercase `needle`.
+fn find_ci(haystack: &str, needle: &str) -> Option<(usize, usize)> {
+    if needle.is_empty() {
+        return None;
+    }
+    'start: for (start, _) in haystack.char_indices() {
+        let mut rest = haystack[start..].char_indices();
+        let mut end = start;
+        for n in needle.chars() {
+            match rest.next() {
+                Some((i, c)) if c.to_lowercase().eq(std::iter::once(n)) => {
+                    end = start + i + c.len_utf8();
+                }
+                _ => continue 'start,
+            }
+        }
+        return Some((start, end));
+    }
+    None
+}
+
+/// Alphanumeric runs of `text` with their byte offsets.
+fn words(text: &str) -> impl Iterator<Item = (usize, &str)> {
+    let mut words = Vec::new();
+    let mut start = None;
+    for (i, c) in text.char_indices() {
+        match (c.is_alphanumeric(), start) {
+            (true, None) => start = Some(i),
+            (false, Some(s)) => {
+                words.push((s, &text[s..i]));
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    if let Some(s) = start {
+        words.push((s, &text[s..]));
+    }
+    words.into_iter()
+}
+
+/// Whether `word` is at most `max` edits (insertions, deletions,
+/// substitutions or swaps of neighbours) away from the lowercase `term`.
+fn within(word: &str, term: &str, max: usize) -> bool {
+    let a: Vec<char> = word.to_lowercase().chars().collect();
+    let b: Vec<char> = term.chars().collect();
+    if a.len().abs_diff(b.len()) > max {
+        return false;
+    }
+    let mut rows = vec![vec![0; b.len() + 1]; a.len() + 1];
+    for (i, row) in rows.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, cell) in rows[0].iter_mut().enumerate() {
+        *cell = j;
+    }
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            let mut d = (rows[i - 1][j] + 1)
+                .min(rows[i][j - 1] + 1)
+                .min(rows[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                d = d.min(rows[i - 2][j - 2] + 1);
+            }
+            rows[i][j] = d;
+        }
+    }
+    rows[a.len()][b.len()] <= max
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::phase::Phase;
+    use std::path::PathBuf;
+
+    fn skill(id: &str, description: &str, keywords: &[&str]) -> Skill {
+        let mut parts = id.split('/');
+        let phase = parts.next().unwrap();
+        Skill {
+            id: id.to_string(),
+            registry: "default".to_string(),
+            name: id.rsplit('/').next().unwrap().to_string(),
+            description: description.to_string(),
+            version: None,
+            requires: vec![],
+            settings: Default::default(),
+            phase: *Phase::all().iter().find(|p| p.as_str() == phase).unwrap(),
+            tech: parts.next().unwrap().to_string(),
+            path: PathBuf::from("/tmp/test"),
+            installed: false,
+            content_types: vec![],
+            keywords: keywords.iter().map(|k| k.to_string()).collect(),
+        }
+    }
+
+    fn ids<'a>(hits: &[SearchHit<'a>]) -> Vec<&'a str> {
+        hits.iter().map(|h| h.skill.id.as_str()).collect()
+    }
+
+    fn catalog() -> Registry {
+        Registry::new(vec![
+            skill(
+                "review/general/security",
+                "Find vulnerabilities in a diff",
+                &["test"],
+            ),
+            skill("test/rust/tdd", "Write the test first", &[]),
+            skill("code/rust/test", "Rust coder that tests as it goes", &[]),
+            skill("deploy/git/commit", "Conventional commits", &["git"]),
+        ])
+    }
+
+    #[test]
+    fn ranks_name_over_keyword_over_tech_over_description() {
+        let registry = catalog();
+        let hits = search(&registry, "test");
+        assert_eq!(
+            ids(&hits),
+            ["code/rust/test", "review/general/security", "test/rust/tdd",]
+        );
+        assert_eq!(hits[0].highlights[0].field, Field::Name);
+        assert_eq!(hits[1].highlights[0].field, Field::Keyword);
+        assert_eq!(hits[1].highlights[0].index, Some(0));
+        assert_eq!(hits[2].highlights[0].field, Field::Phase);
+    }
+
+    #[test]
+    fn tolerates_typos_and_combines_terms() {
+        let registry = catalog();
+        assert_eq!(ids(&search(&registry, "comit")), ["deploy/git/commit"]);
+        assert_eq!(
+            ids(&search(&registry, "vulnerabilites")),
+            ["review/general/security"]
+        );
+
+        assert_eq!(ids(&search(&registry, "rust first")), ["test/rust/tdd"]);
+        assert_eq!(
+            ids(&search(&registry, "commits OR diff")),
+            ["deploy/git/commit", "review/general/security"]
+        );
+    }
+
+    #[test]
+    fn filters_by_phase_and_tech() {
+        let registry = catalog();
+        assert_eq!(
+            ids(&search(&registry, "tech:rust")),
+            ["code/rust/test", "test/rust/tdd"]
+        );
+        assert_eq!(
+            ids(&search(&registry, "test phase:code tech:rust")),
+            ["code/rust/test"]
+        );
+        assert!(search(&registry, "phase:review tech:rust").is_empty());
+
+        let query = Query::parse("phase:review,test rust OR go");
+        assert_eq!(query.phases, ["review", "test"]);
+        assert_eq!(
+            query.groups,
+            [vec!["rust".to_string()], vec!["go".to_string()]]
+        );
+    }
+
+    #[test]
+    fn highlights_are_byte_ranges_into_the_field() {
+        let registry = catalog();
+        let hits = search(&registry, "VULN");
+        let highlight = &hits[0].highlights[0];
+        assert_eq!(highlight.field, Field::Description);
+        let description = &hits[0].skill.description;
+        assert_eq!(&description[highlight.start..highlight.end], "vuln");
+    }
+}
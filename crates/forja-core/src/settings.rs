@@ -1,7 +1,14 @@
+use std::collections::BTreeMap;
+use std::collections::btree_map::Entry;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+
+use serde_json::{Value, json};
 
 use crate::error::{ForjaError, Result};
+use crate::models::plugin::SettingsFragment;
+use crate::models::state::{OwnedSetting, SettingsFile, load_state, save_state};
+use crate::paths::ForjaPaths;
 
 const TEAMS_ENV_KEY: &str = "CLAUDE_CODE_EXPERIMENTAL_AGENT_TEAMS";
 
@@ -15,31 +22,428 @@ pub fn has_teams_env_var(claude_dir: &Path) -> bool {
 
 pub fn enable_teams_env_var(claude_dir: &Path) -> Result<()> {
     let settings_path = claude_dir.join("settings.json");
+    let mut root = load_document(&settings_path)?;
+    container(&mut root, &["env".to_string()], false)?
+        .as_object_mut()
+        .ok_or_else(|| ForjaError::InvalidSettings("\"env\" must be a JSON object".to_string()))?
+        .insert(TEAMS_ENV_KEY.to_string(), json!("1"));
+    save_document(&settings_path, &root)
+}
+
+/// The Claude Code config files skill settings are merged into.
+#[derive(Debug, Clone)]
+pub struct SettingsTargets {
+    /// `.claude/settings.json`: hooks and permissions.
+    pub settings: PathBuf,
+    /// `.mcp.json` at the project root, `~/.claude.json` globally: MCP servers.
+    pub mcp: PathBuf,
+    /// The state file recording which skill owns which entry.
+    pub state: PathBuf,
+}
+
+impl SettingsTargets {
+    pub fn for_paths(paths: &ForjaPaths) -> Self {
+        let mcp = match &paths.project_root {
+            Some(root) => root.join(".mcp.json"),
+            None => paths.claude_dir.with_file_name(".claude.json"),
+        };
+        Self {
+            settings: paths.claude_dir.join("settings.json"),
+            mcp,
+            state: paths.state.clone(),
+        }
+    }
+
+    fn path(&self, file: SettingsFile) -> &Path {
+        match file {
+            SettingsFile::Settings => &self.settings,
+            SettingsFile::Mcp => &self.mcp,
+        }
+    }
+}
+
+/// Merge the hooks, permissions and MCP servers `skill_id` ships into the
+/// config files, recording the skill as an owner of each entry.
+///
+/// Hooks and permission rules are appended unless already there. An MCP server
+/// that exists with a different definition is a conflict, and nothing is
+/// written. Entries that were there before the skill (the same server, or the
+/// same rule added by hand) are left unowned, so uninstalling keeps them.
+pub fn apply_fragment(
+    targets: &SettingsTargets,
+    skill_id: &str,
+    fragment: &SettingsFragment,
+) -> Result<()> {
+    if fragment.is_empty() {
+        return Ok(());
+    }
+    let mut state = load_state(&targets.state);
+    let mut documents = Documents::default();
+    let entries = entries(fragment);
+
+    let mut conflicts = Vec::new();
+    for (id, entry) in &entries {
+        let owned = state.settings.get(id);
+        if owned.is_some_and(|o| o.owners.iter().any(|owner| owner == skill_id)) {
+            continue;
+        }
+        let doc = documents.load(targets, entry.file)?;
+        if let Some(current) = lookup(doc, entry)
+            && current != &entry.value
+        {
+            let by = match owned {
+                Some(o) => format!("set by {}", o.owners.join(", ")),
+                None => "set to a different value".to_string(),
+            };
+            conflicts.push(format!(
+                "{} in {} is already {by}",
+                describe(entry),
+                targets.path(entry.file).display()
+            ));
+        }
+    }
+    if !conflicts.is_empty() {
+        return Err(ForjaError::SettingsConflict(format!(
+            "{skill_id}: {}",
+            conflicts.join("; ")
+        )));
+    }
+
+    for (id, entry) in entries {
+        if let Some(owned) = state.settings.get_mut(&id) {
+            if !owned.owners.iter().any(|owner| owner == skill_id) {
+                owned.owners.push(skill_id.to_string());
+            }
+            continue;
+        }
+        let doc = documents.load(targets, entry.file)?;
+        if lookup(doc, &entry).is_some() {
+            continue;
+        }
+        insert(doc, &entry)?;
+        documents.touch(entry.file);
+        state.settings.insert(
+            id,
+            OwnedSetting {
+                owners: vec![skill_id.to_string()],
+                ..entry
+            },
+        );
+    }
+
+    documents.save(targets)?;
+    save_state(&targets.state, &state)
+}
+
+/// Undo [`apply_fragment`]: drop `skill_id` as an owner and remove the entries
+/// no other skill owns. Entries edited since they were merged are kept and
+/// returned.
+pub fn remove_fragment(targets: &SettingsTargets, skill_id: &str) -> Result<Vec<String>> {
+    release(targets, skill_id, |_, _, _| false)
+}
+
+/// Re-merge the fragment of an installed skill, e.g. after an upgrade.
+///
+/// Entries the skill still ships stay owned by it: unchanged ones are left
+/// alone, and so are ones edited since they were merged, even when the new
+/// fragment changes them. Unedited entries it changed or dropped are
+/// released as in [`remove_fragment`], and the rest merged as in
+/// [`apply_fragment`]. Returns the edited entries left in place.
+pub fn reapply_fragment(
+    targets: &SettingsTargets,
+    skill_id: &str,
+    fragment: &SettingsFragment,
+) -> Result<Vec<String>> {
+    let shipped: BTreeMap<String, Value> = entries(fragment)
+        .into_iter()
+        .map(|(id, entry)| (id, entry.value))
+        .collect();
+    let mut outdated = Vec::new();
+    let mut kept = release(targets, skill_id, |id, owned, current| {
+        let Some(value) = shipped.get(id) else {
+            return false;
+        };
+        let edited = current.is_some_and(|c| c != &owned.value);
+        if edited && value != &owned.value {
+            outdated.push(describe(owned));
+        }
+        edited || value == &owned.value
+    })?;
+    apply_fragment(targets, skill_id, fragment)?;
+    kept.extend(outdated);
+    Ok(kept)
+}
+
+/// Whether `skill_id` owns any entry in the config files.
+pub fn is_owner(targets: &SettingsTargets, skill_id: &str) -> bool {
+    load_state(&targets.state)
+        .settings
+        .values()
+        .any(|o| o.owners.iter().any(|owner| owner == skill_id))
+}
+
+/// Drop `skill_id` as an owner of every entry `retain` rejects (called with
+/// the entry ID, the entry and its current value) and remove the entries left
+/// without owners, unless they were edited since they were merged. Returns
+/// the edited entries kept.
+fn release(
+    targets: &SettingsTargets,
+    skill_id: &str,
+    mut retain: impl FnMut(&str, &OwnedSetting, Option<&Value>) -> bool,
+) -> Result<Vec<String>> {
+    if !is_owner(targets, skill_id) {
+        return Ok(Vec::new());
+    }
 
-    let mut root: serde_json::Value = if settings_path.exists() {
-        let content = fs::read_to_string(&settings_path)?;
-        serde_json::from_str(&content).unwrap_or_else(|_| serde_json::json!({}))
-    } else {
-        fs::create_dir_all(claude_dir)?;
-        serde_json::json!({})
+    let mut state = load_state(&targets.state);
+    let mut documents = Documents::default();
+    let mut kept = Vec::new();
+    let mut released = Vec::new();
+    for (id, owned) in state.settings.iter_mut() {
+        if !owned.owners.iter().any(|owner| owner == skill_id) {
+            continue;
+        }
+        let doc = documents.load(targets, owned.file)?;
+        let current = lookup(doc, owned).cloned();
+        if retain(id, owned, current.as_ref()) {
+            continue;
+        }
+        owned.owners.retain(|owner| owner != skill_id);
+        if !owned.owners.is_empty() {
+            continue;
+        }
+        released.push(id.clone());
+        match current {
+            Some(current) if current == owned.value => {
+                remove(doc, owned);
+                documents.touch(owned.file);
+            }
+            Some(_) => kept.push(describe(owned)),
+            None => {}
+        }
+    }
+    state.settings.retain(|id, _| !released.contains(id));
+
+    documents.save(targets)?;
+    save_state(&targets.state, &state)?;
+    Ok(kept)
+}
+
+/// A fragment's entries, keyed by entry ID, e.g. `mcp:mcpServers.github`
+/// or `settings:permissions.allow[Bash(cargo test:*)]`.
+fn entries(fragment: &SettingsFragment) -> Vec<(String, OwnedSetting)> {
+    let mut entries = Vec::new();
+    let mut item = |file, path: Vec<String>, value: Value| {
+        let entry = OwnedSetting {
+            file,
+            path,
+            key: None,
+            value,
+            owners: Vec::new(),
+        };
+        entries.push((entry_id(&entry), entry));
     };
+    for (event, groups) in &fragment.hooks {
+        for group in groups {
+            item(
+                SettingsFile::Settings,
+                vec!["hooks".to_string(), event.clone()],
+                group.clone(),
+            );
+        }
+    }
+    for (list, rules) in [
+        ("allow", &fragment.permissions.allow),
+        ("deny", &fragment.permissions.deny),
+    ] {
+        for rule in rules {
+            item(
+                SettingsFile::Settings,
+                vec!["permissions".to_string(), list.to_string()],
+                json!(rule),
+            );
+        }
+    }
+    for (name, server) in &fragment.mcp_servers {
+        let entry = OwnedSetting {
+            file: SettingsFile::Mcp,
+            path: vec!["mcpServers".to_string()],
+            key: Some(name.clone()),
+            value: server.clone(),
+            owners: Vec::new(),
+        };
+        entries.push((entry_id(&entry), entry));
+    }
+    entries
+}
 
-    let env = root
-        .as_object_mut()
-        .ok_or_else(|| ForjaError::InvalidSettings("root must be a JSON object".to_string()))?
-        .entry("env")
-        .or_insert_with(|| serde_json::json!({}));
+fn entry_id(entry: &OwnedSetting) -> String {
+    let file = match entry.file {
+        SettingsFile::Settings => "settings",
+        SettingsFile::Mcp => "mcp",
+    };
+    format!("{file}:{}", describe(entry))
+}
 
-    env.as_object_mut()
-        .ok_or_else(|| ForjaError::InvalidSettings("\"env\" must be a JSON object".to_string()))?
-        .insert(TEAMS_ENV_KEY.to_string(), serde_json::json!("1"));
+/// `mcpServers.github`, or `permissions.allow[Bash(cargo test:*)]` for an array item.
+fn describe(entry: &OwnedSetting) -> String {
+    let path = entry.path.join(".");
+    match (&entry.key, &entry.value) {
+        (Some(key), _) => format!("{path}.{key}"),
+        (None, Value::String(item)) => format!("{path}[{item}]"),
+        (None, item) => format!("{path}[{item}]"),
+    }
+}
+
+/// The entry's current value: the value under its key, or the matching item.
+fn lookup<'a>(doc: &'a Value, entry: &OwnedSetting) -> Option<&'a Value> {
+    let holder = entry
+        .path
+        .iter()
+        .try_fold(doc, |value, key| value.get(key))?;
+    match &entry.key {
+        Some(key) => holder.get(key),
+        None => holder.as_array()?.iter().find(|item| **item == entry.value),
+    }
+}
+
+fn insert(doc: &mut Value, entry: &OwnedSetting) -> Result<()> {
+    let holder = container(doc, &entry.path, entry.key.is_none())?;
+    let invalid = || {
+        let kind = if entry.key.is_some() {
+            "an object"
+        } else {
+            "an array"
+        };
+        ForjaError::InvalidSettings(format!("\"{}\" must be {kind}", entry.path.join(".")))
+    };
+    match &entry.key {
+        Some(key) => {
+            holder
+                .as_object_mut()
+                .ok_or_else(invalid)?
+                .insert(key.clone(), entry.value.clone());
+        }
+        None => holder
+            .as_array_mut()
+            .ok_or_else(invalid)?
+            .push(entry.value.clone()),
+    }
+    Ok(())
+}
+
+/// Remove the entry, then any objects and arrays it leaves empty.
+fn remove(doc: &mut Value, entry: &OwnedSetting) {
+    fn walk(value: &mut Value, path: &[String], entry: &OwnedSetting) {
+        match path.split_first() {
+            Some((key, rest)) => {
+                let Some(object) = value.as_object_mut() else {
+                    return;
+                };
+                if let Some(child) = object.get_mut(key) {
+                    walk(child, rest, entry);
+                    if is_empty(child) {
+                        object.remove(key);
+                    }
+                }
+            }
+            None => match (&entry.key, value) {
+                (Some(key), Value::Object(object)) => {
+                    object.remove(key);
+                }
+                (None, Value::Array(items)) => {
+                    if let Some(at) = items.iter().position(|item| *item == entry.value) {
+                        items.remove(at);
+                    }
+                }
+                _ => {}
+            },
+        }
+    }
+    walk(doc, &entry.path, entry);
+}
+
+fn is_empty(value: &Value) -> bool {
+    match value {
+        Value::Object(object) => object.is_empty(),
+        Value::Array(items) => items.is_empty(),
+        _ => false,
+    }
+}
+
+/// The value at `path`, creating objects along the way and an empty array
+/// (or object) at the end.
+fn container<'a>(doc: &'a mut Value, path: &[String], array: bool) -> Result<&'a mut Value> {
+    let mut value = doc;
+    for (i, key) in path.iter().enumerate() {
+        let last = i + 1 == path.len();
+        value = value
+            .as_object_mut()
+            .ok_or_else(|| {
+                let parent = if i == 0 {
+                    "root".to_string()
+                } else {
+                    format!("\"{}\"", path[..i].join("."))
+                };
+                ForjaError::InvalidSettings(format!("{parent} must be a JSON object"))
+            })?
+            .entry(key.clone())
+            .or_insert_with(|| if last && array { json!([]) } else { json!({}) });
+    }
+    Ok(value)
+}
 
-    let json = serde_json::to_string_pretty(&root)?;
-    fs::write(&settings_path, json)?;
+fn load_document(path: &Path) -> Result<Value> {
+    if !path.exists() {
+        return Ok(json!({}));
+    }
+    let content = fs::read_to_string(path)?;
+    if content.trim().is_empty() {
+        return Ok(json!({}));
+    }
+    serde_json::from_str(&content)
+        .map_err(|e| ForjaError::InvalidSettings(format!("{}: {e}", path.display())))
+}
 
+fn save_document(path: &Path, doc: &Value) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, serde_json::to_string_pretty(doc)?)?;
     Ok(())
 }
 
+/// Config files loaded on first use, saved only if changed.
+#[derive(Default)]
+struct Documents {
+    loaded: BTreeMap<SettingsFile, (Value, bool)>,
+}
+
+impl Documents {
+    fn load(&mut self, targets: &SettingsTargets, file: SettingsFile) -> Result<&mut Value> {
+        let (doc, _) = match self.loaded.entry(file) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert((load_document(targets.path(file))?, false)),
+        };
+        Ok(doc)
+    }
+
+    fn touch(&mut self, file: SettingsFile) {
+        if let Some((_, changed)) = self.loaded.get_mut(&file) {
+            *changed = true;
+        }
+    }
+
+    fn save(&self, targets: &SettingsTargets) -> Result<()> {
+        for (file, (doc, changed)) in &self.loaded {
+            if *changed {
+                save_document(targets.path(*file), doc)?;
+            }
+        }
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -83,4 +487,185 @@ mod tests {
         assert_eq!(val["env"]["OTHER_VAR"], "yes");
         assert_eq!(val["env"]["CLAUDE_CODE_EXPERIMENTAL_AGENT_TEAMS"], "1");
     }
+
+    fn targets(dir: &TempDir) -> SettingsTargets {
+        SettingsTargets {
+            settings: dir.path().join(".claude/settings.json"),
+            mcp: dir.path().join(".mcp.json"),
+            state: dir.path().join("state.json"),
+        }
+    }
+
+    fn fragment() -> SettingsFragment {
+        serde_json::from_value(json!({
+            "hooks": {
+                "PostToolUse": [
+                    { "matcher": "Write", "hooks": [{ "type": "command", "command": "cargo fmt" }] }
+                ]
+            },
+            "permissions": { "allow": ["Bash(cargo test:*)"] },
+            "mcpServers": { "github": { "command": "github-mcp" } }
+        }))
+        .unwrap()
+    }
+
+    fn read(path: &Path) -> Value {
+        serde_json::from_str(&fs::read_to_string(path).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn apply_and_remove_fragment_roundtrips() {
+        let dir = TempDir::new().unwrap();
+        let targets = targets(&dir);
+        fs::create_dir_all(dir.path().join(".claude")).unwrap();
+        let existing = r#"{ "theme": "dark", "permissions": { "allow": ["Read"] } }"#;
+        fs::write(&targets.settings, existing).unwrap();
+
+        apply_fragment(&targets, "code/rust/fmt", &fragment()).unwrap();
+        let settings = read(&targets.settings);
+        assert_eq!(
+            settings["permissions"]["allow"],
+            json!(["Read", "Bash(cargo test:*)"])
+        );
+        assert_eq!(settings["hooks"]["PostToolUse"][0]["matcher"], "Write");
+        assert_eq!(
+            read(&targets.mcp)["mcpServers"]["github"]["command"],
+            "github-mcp"
+        );
+
+        // A second skill shipping the same server shares it
+        apply_fragment(&targets, "code/rust/review", &fragment()).unwrap();
+        assert!(
+            remove_fragment(&targets, "code/rust/fmt")
+                .unwrap()
+                .is_empty()
+        );
+        assert_eq!(
+            read(&targets.mcp)["mcpServers"]["github"]["command"],
+            "github-mcp"
+        );
+
+        assert!(
+            remove_fragment(&targets, "code/rust/review")
+                .unwrap()
+                .is_empty()
+        );
+        assert_eq!(
+            read(&targets.settings),
+            json!({ "theme": "dark", "permissions": { "allow": ["Read"] } })
+        );
+        assert_eq!(read(&targets.mcp), json!({}));
+        assert!(load_state(&targets.state).settings.is_empty());
+    }
+
+    #[test]
+    fn apply_and_remove_fragment_merges_deny_rules() {
+        let dir = TempDir::new().unwrap();
+        let targets = targets(&dir);
+        fs::create_dir_all(dir.path().join(".claude")).unwrap();
+        let existing = r#"{ "permissions": { "deny": ["Bash(rm:*)"] } }"#;
+        fs::write(&targets.settings, existing).unwrap();
+        let deny: SettingsFragment = serde_json::from_value(json!({
+            "permissions": { "deny": ["Bash(rm:*)", "Task(coder)"] }
+        }))
+        .unwrap();
+
+        apply_fragment(&targets, "forja:overrides", &deny).unwrap();
+        assert_eq!(
+            read(&targets.settings)["permissions"]["deny"],
+            json!(["Bash(rm:*)", "Task(coder)"])
+        );
+        assert!(
+            load_state(&targets.state)
+                .settings
+                .contains_key("settings:permissions.deny[Task(coder)]")
+        );
+
+        // The rule that was there first stays unowned and survives removal
+        assert!(
+            remove_fragment(&targets, "forja:overrides")
+                .unwrap()
+                .is_empty()
+        );
+        assert_eq!(
+            read(&targets.settings),
+            json!({ "permissions": { "deny": ["Bash(rm:*)"] } })
+        );
+    }
+
+    #[test]
+    fn apply_fragment_refuses_conflicting_servers() {
+        let dir = TempDir::new().unwrap();
+        let targets = targets(&dir);
+        let existing = r#"{ "mcpServers": { "github": { "command": "my-github" } } }"#;
+        fs::write(&targets.mcp, existing).unwrap();
+
+        let err = apply_fragment(&targets, "code/rust/fmt", &fragment()).unwrap_err();
+        assert!(
+            matches!(err, ForjaError::SettingsConflict(ref m) if m.contains("mcpServers.github"))
+        );
+        assert!(!targets.settings.exists());
+        assert_eq!(
+            read(&targets.mcp)["mcpServers"]["github"]["command"],
+            "my-github"
+        );
+    }
+
+    #[test]
+    fn reapply_fragment_keeps_edited_entries_owned() {
+        let dir = TempDir::new().unwrap();
+        let targets = targets(&dir);
+        apply_fragment(&targets, "code/rust/fmt", &fragment()).unwrap();
+
+        let mut mcp = read(&targets.mcp);
+        mcp["mcpServers"]["github"]["args"] = json!(["--verbose"]);
+        fs::write(&targets.mcp, mcp.to_string()).unwrap();
+
+        let upgraded: SettingsFragment = serde_json::from_value(json!({
+            "hooks": {
+                "PostToolUse": [
+                    { "matcher": "Write", "hooks": [{ "type": "command", "command": "cargo fmt --check" }] }
+                ]
+            },
+            "permissions": { "allow": ["Bash(cargo test:*)"] },
+            "mcpServers": { "github": { "command": "github-mcp-v2" } }
+        }))
+        .unwrap();
+        let kept = reapply_fragment(&targets, "code/rust/fmt", &upgraded).unwrap();
+
+        // The edited server stays as the user has it, still owned by the skill
+        assert_eq!(kept, ["mcpServers.github"]);
+        let server = &read(&targets.mcp)["mcpServers"]["github"];
+        assert_eq!(server["command"], "github-mcp");
+        assert_eq!(server["args"], json!(["--verbose"]));
+        assert_eq!(
+            load_state(&targets.state).settings["mcp:mcpServers.github"].owners,
+            ["code/rust/fmt"]
+        );
+        // The unedited hook is replaced by the new one
+        let hooks = &read(&targets.settings)["hooks"]["PostToolUse"];
+        assert_eq!(hooks.as_array().unwrap().len(), 1);
+        assert_eq!(hooks[0]["hooks"][0]["command"], "cargo fmt --check");
+
+        assert_eq!(
+            reapply_fragment(&targets, "code/rust/fmt", &upgraded).unwrap(),
+            ["mcpServers.github"]
+        );
+    }
+
+    #[test]
+    fn remove_fragment_keeps_edited_entries() {
+        let dir = TempDir::new().unwrap();
+        let targets = targets(&dir);
+        apply_fragment(&targets, "code/rust/fmt", &fragment()).unwrap();
+
+        let mut mcp = read(&targets.mcp);
+        mcp["mcpServers"]["github"]["args"] = json!(["--verbose"]);
+        fs::write(&targets.mcp, mcp.to_string()).unwrap();
+
+        let kept = remove_fragment(&targets, "code/rust/fmt").unwrap();
+        assert_eq!(kept, ["mcpServers.github"]);
+        assert!(read(&targets.mcp)["mcpServers"]["github"].is_object());
+        assert!(read(&targets.settings).get("hooks").is_none());
+    }
 }
@@ -1,6 +1,7 @@
 use crate::error::Result;
+use crate::models::state::record_installs;
 use crate::paths::ForjaPaths;
-use crate::registry::catalog;
+use crate::registry::{catalog, deps};
 use crate::symlink::manager::{SymlinkManager, load_installed_ids, save_installed_ids};
 
 /// Result of an auto-install operation.
@@ -10,7 +11,8 @@ pub struct AutoInstallResult {
     pub not_found: Vec<String>,
 }
 
-/// Auto-install missing agent symlinks for the given skill IDs.
+/// Auto-install missing agent symlinks for the given skill IDs and the
+/// skills they require.
 ///
 /// Returns a structured result — the caller is responsible for printing output.
 pub fn auto_install_missing(paths: &ForjaPaths, skill_ids: &[&str]) -> Result<AutoInstallResult> {
@@ -30,31 +32,46 @@ pub fn auto_install_missing(paths: &ForjaPaths, skill_ids: &[&str]) -> Result<Au
     }
 
     let mut current_ids = installed_ids;
-    let registry = catalog::scan(&paths.registry, &current_ids)?;
-    let manager = SymlinkManager::new(paths.claude_agents.clone(), paths.claude_commands.clone());
+    let registry = catalog::scan_all(paths, &current_ids)?;
+    let manager = SymlinkManager::for_paths(paths);
 
     let mut installed = Vec::new();
+    let mut fresh = Vec::new();
     let mut failed = Vec::new();
     let mut not_found = Vec::new();
 
     for skill_id in &missing {
-        match registry.find_by_id(skill_id) {
-            Some(skill) => match manager.install(skill) {
+        if registry.find_by_id(skill_id).is_none() {
+            not_found.push(skill_id.to_string());
+            continue;
+        }
+        // The skill plus whatever it requires, dependencies first.
+        let order = match deps::install_order(&registry, skill_id) {
+            Ok(order) => order,
+            Err(e) => {
+                failed.push((skill_id.to_string(), e.to_string()));
+                continue;
+            }
+        };
+        for skill in order {
+            if current_ids.contains(&skill.id) {
+                continue;
+            }
+            match manager.install(skill) {
                 Ok(_) => {
-                    current_ids.push(skill_id.to_string());
-                    installed.push(skill_id.to_string());
+                    current_ids.push(skill.id.clone());
+                    installed.push(skill.id.clone());
+                    fresh.push(skill);
                 }
                 Err(e) => {
-                    failed.push((skill_id.to_string(), e.to_string()));
+                    failed.push((skill.id.clone(), e.to_string()));
                 }
-            },
-            None => {
-                not_found.push(skill_id.to_string());
             }
         }
     }
 
     save_installed_ids(&paths.state, &current_ids)?;
+    record_installs(&paths.state, &fresh)?;
 
     Ok(AutoInstallResult {
         installed,
@@ -0,0 +1,346 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use sha2::{Digest, Sha256};
+
+use crate::error::{ForjaError, Result};
+use crate::models::skill::Skill;
+use crate::registry::git;
+use crate::symlink::manager::SymlinkManager;
+
+/// Content-addressed store of the registry originals copies were made from,
+/// under `.forja/`. These are the merge bases for `forja upgrade`.
+pub const OBJECTS_DIR: &str = "objects";
+
+const MARKER_START: &str = "<!-- forja:copy ";
+
+/// `sha256:<hex>` of a file's content.
+pub fn hash(content: &str) -> String {
+    format!("sha256:{:x}", Sha256::digest(content.as_bytes()))
+}
+
+/// Save `content` in the object store under its hash, returning the hash.
+pub fn store_object(objects_dir: &Path, content: &str) -> Result<String> {
+    let hash = hash(content);
+    let path = object_path(objects_dir, &hash);
+    if !path.exists() {
+        fs::create_dir_all(objects_dir)?;
+        fs::write(path, content)?;
+    }
+    Ok(hash)
+}
+
+pub fn load_object(objects_dir: &Path, hash: &str) -> Option<String> {
+    fs::read_to_string(object_path(objects_dir, hash)).ok()
+}
+
+fn object_path(objects_dir: &Path, hash: &str) -> PathBuf {
+    objects_dir.join(hash.strip_prefix("sha256:").unwrap_or(hash))
+}
+
+/// `content` with a marker line naming the skill it was copied from. The
+/// marker goes after the YAML frontmatter, which has to stay on top.
+pub fn render(skill_id: &str, content: &str) -> String {
+    let marker = format!("{MARKER_START}{skill_id} -->\n");
+    let at = frontmatter_end(content).unwrap_or(0);
+    let mut rendered = String::with_capacity(content.len() + marker.len());
+    rendered.push_str(&content[..at]);
+    rendered.push_str(&marker);
+    rendered.push_str(&content[at..]);
+    rendered
+}
+
+/// The inverse of [`render`]: `content` without its marker line.
+pub fn strip_marker(content: &str) -> String {
+    let mut stripped = String::with_capacity(content.len());
+    let mut found = false;
+    for line in content.split_inclusive('\n') {
+        if !found && line.starts_with(MARKER_START) {
+            found = true;
+            continue;
+        }
+        stripped.push_str(line);
+    }
+    stripped
+}
+
+/// Byte offset just past the closing `---` line of the frontmatter.
+fn frontmatter_end(content: &str) -> Option<usize> {
+    let mut lines = content.split_inclusive('\n');
+    let first = lines.next()?;
+    if first.trim_end() != "---" {
+        return None;
+    }
+    let mut offset = first.len();
+    for line in lines {
+        offset += line.len();
+        if line.trim_end() == "---" {
+            return line.ends_with('\n').then_some(offset);
+        }
+    }
+    None
+}
+
+/// Write a copy of `content` to `dest`, replacing whatever is there, with the
+/// permissions of `source` so scripts stay executable.
+/// Markdown files get the marker; supporting files of skill directories don't.
+pub fn write_copy(source: &Path, dest: &Path, skill_id: &str, content: &str) -> Result<()> {
+    // Replaced rather than written through: it may be a symlink or read-only
+    if dest.is_symlink() || dest.is_file() {
+        fs::remove_file(dest)?;
+    }
+    if let Some(parent) = dest.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    if dest.extension().is_some_and(|ext| ext == "md") {
+        fs::write(dest, render(skill_id, content))?;
+    } else {
+        fs::write(dest, content)?;
+    }
+    fs::set_permissions(dest, fs::metadata(source)?.permissions())?;
+    Ok(())
+}
+
+/// Where a copied file stands against the registry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CopyState {
+    /// Same content as the registry.
+    Clean,
+    /// Edited locally; the registry hasn't changed since it was copied.
+    Edited,
+    /// Untouched locally; the registry has changed since it was copied.
+    Outdated,
+    /// Edited locally and changed in the registry (or there's no base to tell).
+    Diverged,
+    /// Not copied into `.claude/`, or deleted since.
+    Missing,
+}
+
+impl CopyState {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CopyState::Clean => "clean",
+            CopyState::Edited => "edited locally",
+            CopyState::Outdated => "registry changed",
+            CopyState::Diverged => "edited locally and changed in the registry",
+            CopyState::Missing => "missing",
+        }
+    }
+
+    /// Whether `forja upgrade` has something to bring in.
+    pub fn needs_upgrade(&self) -> bool {
+        matches!(
+            self,
+            CopyState::Outdated | CopyState::Diverged | CopyState::Missing
+        )
+    }
+}
+
+/// One installed file of a copy-installed skill.
+#[derive(Debug, Clone)]
+pub struct CopiedFile {
+    /// Path under `.claude/agents`, `.claude/commands` or `.claude/skills`.
+    pub name: String,
+    pub source: PathBuf,
+    pub dest: PathBuf,
+    pub state: CopyState,
+}
+
+/// Compare the copies of `skill` with the registry, using the base hashes
+/// recorded in its `InstallMeta`.
+pub fn inspect(
+    manager: &SymlinkManager,
+    skill: &Skill,
+    copies: &BTreeMap<String, String>,
+) -> Result<Vec<CopiedFile>> {
+    let mut files = Vec::new();
+    for target in manager.files(skill)? {
+        // Files that aren't text are copied as-is and not tracked
+        let Ok(upstream) = fs::read_to_string(&target.source) else {
+            continue;
+        };
+        let state = if target.dest.is_symlink() || !target.dest.exists() {
+            CopyState::Missing
+        } else {
+            let local = hash(&strip_marker(&fs::read_to_string(&target.dest)?));
+            classify(
+                copies.get(&target.name).map(String::as_str),
+                &local,
+                &hash(&upstream),
+            )
+        };
+        files.push(CopiedFile {
+            name: target.name,
+            source: target.source,
+            dest: target.dest,
+            state,
+        });
+    }
+    Ok(files)
+}
+
+fn classify(base: Option<&str>, local: &str, upstream: &str) -> CopyState {
+    if local == upstream {
+        return CopyState::Clean;
+    }
+    match base {
+        Some(base) if base == local => CopyState::Outdated,
+        Some(base) if base == upstream => CopyState::Edited,
+        _ => CopyState::Diverged,
+    }
+}
+
+/// How to settle a file that changed on both sides.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Resolution {
+    /// Write the three-way merge, conflict markers included.
+    Merge,
+    KeepLocal,
+    TakeRegistry,
+    /// Leave the file as it is and ask again on the next upgrade.
+    Skip,
+}
+
+/// What upgrading a copied file did.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    Unchanged,
+    /// Missing or untouched locally: replaced with the registry version.
+    Updated,
+    Merged,
+    /// Merged, with conflict markers left to resolve.
+    Conflicted,
+    KeptLocal,
+    TookRegistry,
+    Skipped,
+}
+
+/// Bring one copied file up to date with the registry. Outdated and missing
+/// files are overwritten; diverged ones are merged against `base` and handed
+/// to `resolve` along with whether the merge was clean. Returns the outcome
+/// and the file's new base, the hash of the registry content, unless the
+/// file was skipped.
+pub fn upgrade_file(
+    objects_dir: &Path,
+    skill_id: &str,
+    file: &CopiedFile,
+    base: Option<&str>,
+    resolve: &mut dyn FnMut(&CopiedFile, bool) -> Result<Resolution>,
+) -> Result<(Outcome, Option<String>)> {
+    let upstream = fs::read_to_string(&file.source)?;
+    let upstream_hash = store_object(objects_dir, &upstream)?;
+
+    let outcome = match file.state {
+        CopyState::Clean | CopyState::Edited => Outcome::Unchanged,
+        CopyState::Outdated | CopyState::Missing => {
+            write_copy(&file.source, &file.dest, skill_id, &upstream)?;
+            Outcome::Updated
+        }
+        CopyState::Diverged => {
+            let local = strip_marker(&fs::read_to_string(&file.dest)?);
+            let base = base
+                .and_then(|hash| load_object(objects_dir, hash))
+                .unwrap_or_default();
+            let (merged, conflicted) = merge(&base, &local, &upstream)?;
+            match resolve(file, !conflicted)? {
+                Resolution::Merge => {
+                    write_copy(&file.source, &file.dest, skill_id, &merged)?;
+                    if conflicted {
+                        Outcome::Conflicted
+                    } else {
+                        Outcome::Merged
+                    }
+                }
+                Resolution::KeepLocal => Outcome::KeptLocal,
+                Resolution::Skip => return Ok((Outcome::Skipped, None)),
+                Resolution::TakeRegistry => {
+                    write_copy(&file.source, &file.dest, skill_id, &upstream)?;
+                    Outcome::TookRegistry
+                }
+            }
+        }
+    };
+    Ok((outcome, Some(upstream_hash)))
+}
+
+/// Three-way merge of `local` and `upstream` from their common `base`.
+/// Returns the merged text and whether it has conflict markers.
+pub fn merge(base: &str, local: &str, upstream: &str) -> Result<(String, bool)> {
+    static NEXT: AtomicUsize = AtomicUsize::new(0);
+    let dir = std::env::temp_dir().join(format!(
+        "forja-merge-{}-{}",
+        std::process::id(),
+        NEXT.fetch_add(1, Ordering::Relaxed)
+    ));
+    fs::create_dir_all(&dir)?;
+    let (local_path, base_path, upstream_path) =
+        (dir.join("local"), dir.join("base"), dir.join("registry"));
+    let result = fs::write(&local_path, local)
+        .and_then(|_| fs::write(&base_path, base))
+        .and_then(|_| fs::write(&upstream_path, upstream))
+        .map_err(ForjaError::Io)
+        .and_then(|_| git::merge_file(&local_path, &base_path, &upstream_path));
+    let _ = fs::remove_dir_all(&dir);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn marker_goes_after_frontmatter_and_strips_back() {
+        let agent = "---\nname: coder\n---\n\n# Coder\n";
+        let rendered = render("code/general/feature", agent);
+        assert!(
+            rendered
+                .starts_with("---\nname: coder\n---\n<!-- forja:copy code/general/feature -->\n")
+        );
+        assert_eq!(strip_marker(&rendered), agent);
+
+        let command = "# Ship it\n";
+        let rendered = render("deploy/git/commit", command);
+        assert!(rendered.starts_with(MARKER_START));
+        assert_eq!(strip_marker(&rendered), command);
+    }
+
+    #[test]
+    fn classify_tells_local_edits_from_registry_drift() {
+        assert_eq!(classify(Some("a"), "a", "a"), CopyState::Clean);
+        assert_eq!(classify(Some("a"), "b", "a"), CopyState::Edited);
+        assert_eq!(classify(Some("a"), "a", "b"), CopyState::Outdated);
+        assert_eq!(classify(Some("a"), "b", "c"), CopyState::Diverged);
+        assert_eq!(classify(None, "b", "c"), CopyState::Diverged);
+        assert_eq!(classify(None, "c", "c"), CopyState::Clean);
+    }
+
+    #[test]
+    fn object_store_and_merge() {
+        let dir = TempDir::new().unwrap();
+        let base = "one\ntwo\nthree\n";
+        let hash = store_object(dir.path(), base).unwrap();
+        assert_eq!(load_object(dir.path(), &hash).as_deref(), Some(base));
+
+        let (merged, conflicted) = merge(
+            base,
+            "one (mine)\ntwo\nthree\n",
+            "one\ntwo\nthree (theirs)\n",
+        )
+        .unwrap();
+        assert!(!conflicted);
+        assert_eq!(merged, "one (mine)\ntwo\nthree (theirs)\n");
+
+        let (merged, conflicted) = merge(
+            base,
+            "one (mine)\ntwo\nthree\n",
+            "one (theirs)\ntwo\nthree\n",
+        )
+        .unwrap();
+        assert!(conflicted);
+        assert!(merged.contains("<<<<<<< local"));
+        assert!(merged.contains(">>>>>>> registry"));
+    }
+}
@@ -0,0 +1,88 @@
+use std::path::PathBuf;
+
+use crate::error::Result;
+use crate::models::registry::Registry;
+use crate::models::skill::Skill;
+use crate::models::state::{forget_installs, record_installs};
+use crate::paths::ForjaPaths;
+use crate::registry::{deps, lock};
+use crate::symlink::manager::{SymlinkManager, load_installed_ids, save_installed_ids};
+
+/// What [`install_with_requirements`] put in place.
+pub struct Installed<'a> {
+    /// Links and copies created for the skill itself.
+    pub created: Vec<PathBuf>,
+    /// Skills it requires that were not installed yet, in install order.
+    pub required: Vec<&'a Skill>,
+}
+
+/// Install `skill` and the skills it requires that are missing,
+/// dependencies first, then save the state and refresh `forja.lock`.
+///
+/// All or nothing: if any install fails, those done by this call are
+/// undone and the state is left as it was.
+pub fn install_with_requirements<'a>(
+    paths: &ForjaPaths,
+    registry: &'a Registry,
+    skill: &'a Skill,
+) -> Result<Installed<'a>> {
+    let mut installed_ids = load_installed_ids(&paths.state);
+    let order = deps::install_order(registry, &skill.id)?;
+    let manager = SymlinkManager::for_paths(paths);
+
+    let mut fresh: Vec<&Skill> = Vec::new();
+    let mut created = Vec::new();
+    for next in order {
+        if installed_ids.contains(&next.id) {
+            continue;
+        }
+        match manager.install(next) {
+            Ok(links) => {
+                if next.id == skill.id {
+                    created = links;
+                }
+                installed_ids.push(next.id.clone());
+                fresh.push(next);
+            }
+            Err(e) => {
+                for done in fresh.iter().rev() {
+                    // Best effort: the install error is the one to report.
+                    let _ = manager.uninstall(&done.id);
+                }
+                return Err(e);
+            }
+        }
+    }
+
+    save_installed_ids(&paths.state, &installed_ids)?;
+    record_installs(&paths.state, &fresh)?;
+    lock::refresh(paths)?;
+
+    let required = fresh.into_iter().filter(|s| s.id != skill.id).collect();
+    Ok(Installed { created, required })
+}
+
+/// What [`uninstall`] took away.
+pub struct Uninstalled {
+    /// Links and copies removed.
+    pub removed: Vec<PathBuf>,
+    /// Settings entries left in place because they were edited.
+    pub kept: Vec<String>,
+}
+
+/// Remove an installed skill's links, copies and settings, then save the
+/// state and refresh `forja.lock`. Whether other skills still require it is
+/// the caller's call; see [`deps::check_removable`].
+pub fn uninstall(paths: &ForjaPaths, id: &str) -> Result<Uninstalled> {
+    let manager = SymlinkManager::for_paths(paths);
+    let kept = manager.remove_settings(id)?;
+    let removed = manager.unlink(id)?;
+
+    let mut installed_ids = load_installed_ids(&paths.state);
+    installed_ids.retain(|installed| installed != id);
+    save_installed_ids(&paths.state, &installed_ids)?;
+    forget_installs(&paths.state, &[id.to_string()])?;
+    lock::refresh(paths)?;
+
+    Ok(Uninstalled { removed, kept })
+}
@@ -0,0 +1,302 @@
+use std::fs;
+
+use crate::error::Result;
+use crate::frontmatter::parse_agent_frontmatter;
+use crate::models::config::{SkillOverrides, load_config};
+use crate::models::phase::Phase;
+use crate::models::plugin::SettingsFragment;
+use crate::models::skill::Skill;
+use crate::paths::{ForjaMode, ForjaPaths};
+use crate::registry::catalog;
+use crate::settings::{self, SettingsTargets};
+use crate::symlink::auto_install::auto_install_missing;
+use crate::symlink::manager::{SymlinkManager, load_installed_ids};
+
+/// Owner recorded for the deny rules that hide global skills from a project.
+pub const OVERRIDES_OWNER: &str = "forja:overrides";
+
+/// Where a skill in the effective set comes from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Layer {
+    /// Installed globally (`~/.forja/state.json`).
+    Global,
+    /// Installed in the project (`.forja/state.json`).
+    Project,
+    /// Listed in the project config's `skills.add`.
+    Added,
+    /// Swapped in by `skills.override` for the skill it names.
+    Override(String),
+}
+
+impl Layer {
+    pub fn label(&self) -> String {
+        match self {
+            Layer::Global => "global".to_string(),
+            Layer::Project => "project".to_string(),
+            Layer::Added => "project (added)".to_string(),
+            Layer::Override(replaces) => format!("project (overrides {replaces})"),
+        }
+    }
+}
+
+/// A skill the project sees, and the layer that put it there.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Resolved {
+    pub id: String,
+    pub layer: Layer,
+}
+
+/// An installed skill the project config leaves out.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Hidden {
+    pub id: String,
+    /// `Global` or `Project`: where it's installed.
+    pub layer: Layer,
+    /// The skill overriding it, or `None` when it's excluded.
+    pub by: Option<String>,
+}
+
+/// The resolved skill set of a project: global installs, then project
+/// installs, then the config's additions and overrides.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Effective {
+    /// Sorted by ID.
+    pub skills: Vec<Resolved>,
+    pub hidden: Vec<Hidden>,
+}
+
+/// Layer `project` installs and `overrides` on top of `global` installs.
+/// A skill installed in both layers counts as a project one; `exclude` and
+/// `override` apply to both layers.
+pub fn resolve(global: &[String], project: &[String], overrides: &SkillOverrides) -> Effective {
+    let mut effective = Effective::default();
+
+    let layered = global
+        .iter()
+        .filter(|id| !project.contains(id))
+        .map(|id| (id, Layer::Global))
+        .chain(project.iter().map(|id| (id, Layer::Project)));
+    for (id, layer) in layered {
+        if overrides.exclude.contains(id) {
+            effective.hidden.push(Hidden {
+                id: id.clone(),
+                layer,
+                by: None,
+            });
+        } else if let Some(by) = overrides.replace.get(id) {
+            effective.hidden.push(Hidden {
+                id: id.clone(),
+                layer,
+                by: Some(by.clone()),
+            });
+        } else {
+            effective.skills.push(Resolved {
+                id: id.clone(),
+                layer,
+            });
+        }
+    }
+
+    let config_layer = overrides
+        .replace
+        .iter()
+        .map(|(replaces, id)| (id, Layer::Override(replaces.clone())))
+        .chain(overrides.add.iter().map(|id| (id, Layer::Added)));
+    for (id, layer) in config_layer {
+        if overrides.exclude.contains(id) {
+            continue;
+        }
+        match effective.skills.iter_mut().find(|s| &s.id == id) {
+            Some(existing) => existing.layer = layer,
+            None => effective.skills.push(Resolved {
+                id: id.clone(),
+                layer,
+            }),
+        }
+    }
+
+    effective.skills.sort_by(|a, b| a.id.cmp(&b.id));
+    effective.hidden.sort_by(|a, b| a.id.cmp(&b.id));
+    effective
+}
+
+/// The effective skill set for `paths`. In global mode that's just the
+/// global installs; project configs only apply in project mode.
+pub fn effective(paths: &ForjaPaths) -> Result<Effective> {
+    let installed = load_installed_ids(&paths.state);
+    if paths.mode == ForjaMode::Global {
+        return Ok(resolve(&installed, &[], &SkillOverrides::default()));
+    }
+    let global = load_installed_ids(&ForjaPaths::global()?.state);
+    Ok(resolve(&global, &installed, &overrides(paths)))
+}
+
+fn overrides(paths: &ForjaPaths) -> SkillOverrides {
+    load_config(&paths.config)
+        .map(|c| c.skills)
+        .unwrap_or_default()
+}
+
+/// Bring a project in line with its config's `skills` overrides: install
+/// what `add` and `override` bring in (unless the global layer already has
+/// it), unlink project skills they hide, and deny the agents of hidden
+/// global skills in the project settings, since `~/.claude/` can't be
+/// changed per project.
+///
+/// Hiding is a view: hidden project skills stay installed in the project
+/// state, so dropping the `exclude` or `override` links them again.
+pub fn apply(paths: &ForjaPaths) -> Result<Effective> {
+    if paths.mode == ForjaMode::Global {
+        return effective(paths);
+    }
+    let global = load_installed_ids(&ForjaPaths::global()?.state);
+    let installed = load_installed_ids(&paths.state);
+    let resolved = resolve(&global, &installed, &overrides(paths));
+
+    let manager = SymlinkManager::for_paths(paths);
+    for hidden in resolved.hidden.iter().filter(|h| h.layer == Layer::Project) {
+        manager.uninstall(&hidden.id)?;
+    }
+
+    let missing: Vec<&str> = resolved
+        .skills
+        .iter()
+        .filter(|s| matches!(s.layer, Layer::Added | Layer::Override(_)))
+        .filter(|s| !global.contains(&s.id))
+        .map(|s| s.id.as_str())
+        .collect();
+    auto_install_missing(paths, &missing)?;
+
+    let registry = catalog::scan_all(paths, &[])?;
+    let mut fragment = SettingsFragment::default();
+    for hidden in resolved.hidden.iter().filter(|h| h.layer == Layer::Global) {
+        if let Some(skill) = registry.find_by_id(&hidden.id) {
+            fragment.permissions.deny.extend(
+                agent_names(skill)
+                    .into_iter()
+                    .map(|name| format!("Task({name})")),
+            );
+        }
+    }
+    let targets = SettingsTargets::for_paths(paths);
+    settings::remove_fragment(&targets, OVERRIDES_OWNER)?;
+    settings::apply_fragment(&targets, OVERRIDES_OWNER, &fragment)?;
+
+    effective(paths)
+}
+
+/// What a hidden global skill still contributes to the project. Its agents
+/// are denied, but `~/.claude/` can't change per project and settings can't
+/// deny a slash command or a skill directory, so the commands and skills it
+/// ships stay active.
+pub fn still_active(skill: &Skill) -> Vec<&'static str> {
+    let has_entries = |dir: &str| {
+        fs::read_dir(skill.path.join(dir))
+            .map(|mut entries| entries.next().is_some())
+            .unwrap_or(false)
+    };
+    let mut kinds = Vec::new();
+    if skill.phase != Phase::Teams && has_entries("commands") {
+        kinds.push("commands");
+    }
+    if has_entries("skills") {
+        kinds.push("skills");
+    }
+    kinds
+}
+
+/// The `name` in the frontmatter of each of the skill's agents.
+fn agent_names(skill: &Skill) -> Vec<String> {
+    let Ok(entries) = fs::read_dir(skill.path.join("agents")) else {
+        return Vec::new();
+    };
+    let mut names: Vec<String> = entries
+        .filter_map(|e| e.ok())
+        .filter_map(|e| fs::read_to_string(e.path()).ok())
+        .filter_map(|content| parse_agent_frontmatter(&content).ok())
+        .map(|(frontmatter, _)| frontmatter.name)
+        .collect();
+    names.sort();
+    names
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ids(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn resolve_layers_project_on_top_of_global() {
+        let overrides = SkillOverrides {
+            add: ids(&["test/tdd/workflow"]),
+            exclude: ids(&["deploy/git/pr"]),
+            replace: [(
+                "code/general/feature".to_string(),
+                "code/rust/feature".to_string(),
+            )]
+            .into(),
+        };
+        let effective = resolve(
+            &ids(&[
+                "code/general/feature",
+                "deploy/git/pr",
+                "review/security/auditor",
+            ]),
+            &ids(&["review/security/auditor", "deploy/git/commit"]),
+            &overrides,
+        );
+
+        let layers: Vec<(&str, String)> = effective
+            .skills
+            .iter()
+            .map(|s| (s.id.as_str(), s.layer.label()))
+            .collect();
+        assert_eq!(
+            layers,
+            [
+                (
+                    "code/rust/feature",
+                    "project (overrides code/general/feature)".to_string()
+                ),
+                ("deploy/git/commit", "project".to_string()),
+                ("review/security/auditor", "project".to_string()),
+                ("test/tdd/workflow", "project (added)".to_string()),
+            ]
+        );
+        assert_eq!(
+            effective.hidden,
+            [
+                Hidden {
+                    id: "code/general/feature".to_string(),
+                    layer: Layer::Global,
+                    by: Some("code/rust/feature".to_string()),
+                },
+                Hidden {
+                    id: "deploy/git/pr".to_string(),
+                    layer: Layer::Global,
+                    by: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn resolve_without_overrides_keeps_every_install() {
+        let effective = resolve(
+            &ids(&["code/general/feature"]),
+            &ids(&["code/general/feature"]),
+            &SkillOverrides::default(),
+        );
+        assert_eq!(
+            effective.skills,
+            [Resolved {
+                id: "code/general/feature".to_string(),
+                layer: Layer::Project,
+            }]
+        );
+        assert!(effective.hidden.is_empty());
+    }
+}
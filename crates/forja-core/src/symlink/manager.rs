@@ -1,69 +1,287 @@
 use crate::error::Result;
+use crate::models::config::{InstallStrategy, load_config};
 use crate::models::skill::Skill;
+use crate::models::state::record_copies;
+use crate::paths::ForjaPaths;
+use crate::settings::{self, SettingsTargets};
+use crate::symlink::copy;
+use std::collections::BTreeMap;
 use std::fs;
 use std::os::unix::fs as unix_fs;
 use std::path::{Path, PathBuf};
 
 const SYMLINK_PREFIX: &str = "forja--";
+const SKILL_FILE: &str = "SKILL.md";
 
 pub struct SymlinkManager {
     claude_agents_dir: PathBuf,
     claude_commands_dir: PathBuf,
+    claude_skills_dir: PathBuf,
+    copies: Option<CopyStore>,
+    settings: Option<SettingsTargets>,
+}
+
+/// Where copy mode keeps registry originals and the hashes of what it copied.
+struct CopyStore {
+    objects_dir: PathBuf,
+    state_path: PathBuf,
+}
+
+/// Number of `forja--` entries in each `.claude/` directory.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct InstalledCounts {
+    pub agents: usize,
+    pub commands: usize,
+    pub skills: usize,
+}
+
+/// A single file a skill installs. Skill directories are expanded file by file.
+#[derive(Debug, Clone)]
+pub struct TargetFile {
+    /// Path under `.claude/agents`, `.claude/commands` or `.claude/skills`,
+    /// e.g. `forja--deploy--git--commit--commit/SKILL.md`.
+    pub name: String,
+    pub source: PathBuf,
+    pub dest: PathBuf,
 }
 
 impl SymlinkManager {
-    pub fn new(claude_agents_dir: PathBuf, claude_commands_dir: PathBuf) -> Self {
+    pub fn new(
+        claude_agents_dir: PathBuf,
+        claude_commands_dir: PathBuf,
+        claude_skills_dir: PathBuf,
+    ) -> Self {
         Self {
             claude_agents_dir,
             claude_commands_dir,
+            claude_skills_dir,
+            copies: None,
+            settings: None,
+        }
+    }
+
+    /// A manager for `paths`, using the install strategy from its config and
+    /// merging skill settings into the Claude Code config files.
+    pub fn for_paths(paths: &ForjaPaths) -> Self {
+        let manager = Self::new(
+            paths.claude_agents.clone(),
+            paths.claude_commands.clone(),
+            paths.claude_skills.clone(),
+        )
+        .with_settings(SettingsTargets::for_paths(paths));
+        let strategy = load_config(&paths.config)
+            .map(|c| c.install_strategy)
+            .unwrap_or_default();
+        match strategy {
+            InstallStrategy::Symlink => manager,
+            InstallStrategy::Copy => manager.with_copies(
+                paths.forja_root.join(copy::OBJECTS_DIR),
+                paths.state.clone(),
+            ),
         }
     }
 
-    /// Install a skill: symlink agents/*.md and commands/*.md into ~/.claude/
+    /// Copy files instead of symlinking them, keeping the originals in
+    /// `objects_dir` and their hashes in the state file.
+    pub fn with_copies(mut self, objects_dir: PathBuf, state_path: PathBuf) -> Self {
+        self.copies = Some(CopyStore {
+            objects_dir,
+            state_path,
+        });
+        self
+    }
+
+    /// Merge the hooks, permissions and MCP servers skills ship into `targets`.
+    pub fn with_settings(mut self, targets: SettingsTargets) -> Self {
+        self.settings = Some(targets);
+        self
+    }
+
+    /// The object store, when installing copies.
+    pub fn objects_dir(&self) -> Option<&Path> {
+        self.copies.as_ref().map(|c| c.objects_dir.as_path())
+    }
+
+    /// Install a skill: symlink (or copy) agents/*.md, commands/*.md and
+    /// skills/<name>/ directories into ~/.claude/.
+    ///
+    /// An existing copy is left alone, local edits included; `forja upgrade`
+    /// brings it up to date. Settings the skill ships are merged first, so a
+    /// conflict leaves nothing installed; if linking then fails, settings
+    /// merged by this call are taken back.
     pub fn install(&self, skill: &Skill) -> Result<Vec<PathBuf>> {
+        let had_settings = self.owns_settings(&skill.id);
+        self.apply_settings(skill)?;
+        let linked = self.link(skill);
+        if linked.is_err() && !had_settings {
+            self.remove_settings(&skill.id)?;
+        }
+        linked
+    }
+
+    /// Symlink (or copy) the skill's files into place.
+    fn link(&self, skill: &Skill) -> Result<Vec<PathBuf>> {
         let mut created = Vec::new();
 
-        // Symlink agents/*.md -> ~/.claude/agents/
-        let agents_dir = skill.path.join("agents");
-        if agents_dir.exists() {
-            fs::create_dir_all(&self.claude_agents_dir)?;
-            created.extend(self.symlink_dir(&agents_dir, &self.claude_agents_dir, &skill.id)?);
+        let Some(store) = &self.copies else {
+            for (source, dest) in self.targets(skill)? {
+                if let Some(parent) = dest.parent() {
+                    fs::create_dir_all(parent)?;
+                }
+                remove_entry(&dest)?;
+                unix_fs::symlink(&source, &dest)?;
+                created.push(dest);
+            }
+            return Ok(created);
+        };
+
+        // Switching from symlinks: a linked skill directory would swallow the copies
+        for (_, dest) in self.targets(skill)? {
+            if dest.is_symlink() {
+                fs::remove_file(&dest)?;
+            }
         }
 
-        // Symlink commands/*.md -> ~/.claude/commands/
-        // (skip Teams-phase skills -- their commands are managed by `forja team`)
-        if skill.phase != crate::models::phase::Phase::Teams {
-            let commands_dir = skill.path.join("commands");
-            if commands_dir.exists() {
-                fs::create_dir_all(&self.claude_commands_dir)?;
-                created.extend(self.symlink_dir(
-                    &commands_dir,
-                    &self.claude_commands_dir,
-                    &skill.id,
-                )?);
+        let mut copies = BTreeMap::new();
+        for file in self.files(skill)? {
+            if file.dest.exists() && !file.dest.is_symlink() {
+                continue;
             }
+            let Ok(content) = fs::read_to_string(&file.source) else {
+                // Not text: copied as-is, without a marker or merge base
+                if let Some(parent) = file.dest.parent() {
+                    fs::create_dir_all(parent)?;
+                }
+                fs::copy(&file.source, &file.dest)?;
+                created.push(file.dest);
+                continue;
+            };
+            let hash = copy::store_object(&store.objects_dir, &content)?;
+            copy::write_copy(&file.source, &file.dest, &skill.id, &content)?;
+            copies.insert(file.name, hash);
+            created.push(file.dest);
         }
 
+        record_copies(&store.state_path, &skill.id, copies)?;
         Ok(created)
     }
 
-    /// Uninstall: remove all symlinks for a skill (agents + commands)
+    /// Registry entries a skill installs, each with where it goes:
+    /// agents/*.md -> ~/.claude/agents/, commands/*.md -> ~/.claude/commands/
+    /// (skip Teams-phase skills' commands -- they're managed by `forja team`),
+    /// skills/<name>/ (with a SKILL.md) -> ~/.claude/skills/.
+    pub fn targets(&self, skill: &Skill) -> Result<Vec<(PathBuf, PathBuf)>> {
+        let mut targets = self.dir_targets(
+            &skill.path.join("agents"),
+            &self.claude_agents_dir,
+            &skill.id,
+        )?;
+        if skill.phase != crate::models::phase::Phase::Teams {
+            targets.extend(self.dir_targets(
+                &skill.path.join("commands"),
+                &self.claude_commands_dir,
+                &skill.id,
+            )?);
+        }
+        targets.extend(self.skill_dir_targets(
+            &skill.path.join("skills"),
+            &self.claude_skills_dir,
+            &skill.id,
+        )?);
+        Ok(targets)
+    }
+
+    /// Like [`targets`](Self::targets), with skill directories expanded into
+    /// the files inside them.
+    pub fn files(&self, skill: &Skill) -> Result<Vec<TargetFile>> {
+        let mut files = Vec::new();
+        for (source, dest) in self.targets(skill)? {
+            let top = dest
+                .file_name()
+                .unwrap_or_default()
+                .to_string_lossy()
+                .to_string();
+            if !source.is_dir() {
+                files.push(TargetFile {
+                    name: top,
+                    source,
+                    dest,
+                });
+                continue;
+            }
+            let mut nested = Vec::new();
+            collect_files(&source, &mut nested)?;
+            for path in nested {
+                let rel = path.strip_prefix(&source).unwrap_or(&path).to_path_buf();
+                files.push(TargetFile {
+                    name: format!("{top}/{}", rel.display()),
+                    dest: dest.join(&rel),
+                    source: path,
+                });
+            }
+        }
+        Ok(files)
+    }
+
+    /// Uninstall: remove all symlinks and copies for a skill (agents, commands and skills),
+    /// and the settings entries only it owned.
     pub fn uninstall(&self, skill_id: &str) -> Result<Vec<PathBuf>> {
-        let prefix = format!("{SYMLINK_PREFIX}{}--", skill_id.replace('/', "--"));
+        self.remove_settings(skill_id)?;
+        self.unlink(skill_id)
+    }
+
+    /// Remove a skill's symlinks and copies, leaving its settings entries.
+    pub fn unlink(&self, skill_id: &str) -> Result<Vec<PathBuf>> {
+        let prefix = format!("{SYMLINK_PREFIX}{}--", link_slug(skill_id));
         let mut removed = Vec::new();
 
-        removed.extend(self.remove_matching_symlinks(&self.claude_agents_dir, &prefix)?);
-        removed.extend(self.remove_matching_symlinks(&self.claude_commands_dir, &prefix)?);
+        for dir in self.dirs() {
+            removed.extend(self.remove_matching(dir, &prefix)?);
+        }
 
         Ok(removed)
     }
 
-    /// Remove all `forja--` prefixed symlinks from both agents/ and commands/.
+    /// Merge the skill's settings fragment (see [`settings::apply_fragment`]).
+    pub fn apply_settings(&self, skill: &Skill) -> Result<()> {
+        match &self.settings {
+            Some(targets) => settings::apply_fragment(targets, &skill.id, &skill.settings),
+            None => Ok(()),
+        }
+    }
+
+    /// Re-merge an installed skill's settings fragment, returning the edited
+    /// entries left in place (see [`settings::reapply_fragment`]).
+    pub fn reapply_settings(&self, skill: &Skill) -> Result<Vec<String>> {
+        match &self.settings {
+            Some(targets) => settings::reapply_fragment(targets, &skill.id, &skill.settings),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    /// Whether the skill owns any settings entries.
+    fn owns_settings(&self, skill_id: &str) -> bool {
+        self.settings
+            .as_ref()
+            .is_some_and(|targets| settings::is_owner(targets, skill_id))
+    }
+
+    /// Take back the skill's settings entries, returning the ones kept
+    /// because they were edited (see [`settings::remove_fragment`]).
+    pub fn remove_settings(&self, skill_id: &str) -> Result<Vec<String>> {
+        match &self.settings {
+            Some(targets) => settings::remove_fragment(targets, skill_id),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    /// Remove all `forja--` prefixed symlinks from agents/, commands/ and skills/.
     #[cfg(test)]
     pub fn remove_all_forja_symlinks(&self) -> Result<Vec<PathBuf>> {
         let mut removed = Vec::new();
-        removed.extend(self.remove_matching_symlinks(&self.claude_agents_dir, SYMLINK_PREFIX)?);
-        removed.extend(self.remove_matching_symlinks(&self.claude_commands_dir, SYMLINK_PREFIX)?);
+        for dir in self.dirs() {
+            removed.extend(self.remove_matching(dir, SYMLINK_PREFIX)?);
+        }
         Ok(removed)
     }
 
@@ -71,17 +289,18 @@ impl SymlinkManager {
     /// Symlinks belonging to other projects are preserved.
     pub fn remove_project_symlinks(&self, registry_path: &Path) -> Result<Vec<PathBuf>> {
         let mut removed = Vec::new();
-        removed.extend(self.remove_symlinks_under(&self.claude_agents_dir, registry_path)?);
-        removed.extend(self.remove_symlinks_under(&self.claude_commands_dir, registry_path)?);
+        for dir in self.dirs() {
+            removed.extend(self.remove_symlinks_under(dir, registry_path)?);
+        }
         Ok(removed)
     }
 
-    /// Verify all forja symlinks in both agents/ and commands/
+    /// Verify all forja symlinks in agents/, commands/ and skills/. Copies count as healthy.
     pub fn verify(&self) -> Result<(Vec<PathBuf>, Vec<PathBuf>)> {
         let mut healthy = Vec::new();
         let mut broken = Vec::new();
 
-        for dir in [&self.claude_agents_dir, &self.claude_commands_dir] {
+        for dir in self.dirs() {
             let (h, b) = self.verify_dir(dir)?;
             healthy.extend(h);
             broken.extend(b);
@@ -90,35 +309,87 @@ impl SymlinkManager {
         Ok((healthy, broken))
     }
 
-    fn symlink_dir(
+    /// Count installed agents, commands and skill directories.
+    pub fn counts(&self) -> Result<InstalledCounts> {
+        let count = |dir: &Path| -> Result<usize> {
+            if !dir.exists() {
+                return Ok(0);
+            }
+            let mut n = 0;
+            for entry in fs::read_dir(dir)? {
+                if entry?
+                    .file_name()
+                    .to_string_lossy()
+                    .starts_with(SYMLINK_PREFIX)
+                {
+                    n += 1;
+                }
+            }
+            Ok(n)
+        };
+        Ok(InstalledCounts {
+            agents: count(&self.claude_agents_dir)?,
+            commands: count(&self.claude_commands_dir)?,
+            skills: count(&self.claude_skills_dir)?,
+        })
+    }
+
+    fn dirs(&self) -> [&PathBuf; 3] {
+        [
+            &self.claude_agents_dir,
+            &self.claude_commands_dir,
+            &self.claude_skills_dir,
+        ]
+    }
+
+    fn skill_dir_targets(
         &self,
         source_dir: &Path,
         target_dir: &Path,
         skill_id: &str,
-    ) -> Result<Vec<PathBuf>> {
-        let mut created = Vec::new();
+    ) -> Result<Vec<(PathBuf, PathBuf)>> {
+        let mut targets = Vec::new();
+        if !source_dir.exists() {
+            return Ok(targets);
+        }
+
+        for entry in fs::read_dir(source_dir)? {
+            let path = entry?.path();
+            if path.join(SKILL_FILE).is_file() {
+                let dir_name = path.file_name().unwrap().to_string_lossy();
+                let link_name = format!("{SYMLINK_PREFIX}{}--{dir_name}", link_slug(skill_id));
+                targets.push((path.clone(), target_dir.join(link_name)));
+            }
+        }
+
+        targets.sort();
+        Ok(targets)
+    }
+
+    fn dir_targets(
+        &self,
+        source_dir: &Path,
+        target_dir: &Path,
+        skill_id: &str,
+    ) -> Result<Vec<(PathBuf, PathBuf)>> {
+        let mut targets = Vec::new();
+        if !source_dir.exists() {
+            return Ok(targets);
+        }
 
         for entry in fs::read_dir(source_dir)? {
             let entry = entry?;
             let path = entry.path();
             if path.extension().is_some_and(|ext| ext == "md") {
                 let file_name = path.file_name().unwrap().to_string_lossy();
-                let link_name = format!(
-                    "{SYMLINK_PREFIX}{}--{file_name}",
-                    skill_id.replace('/', "--")
-                );
+                let link_name = format!("{SYMLINK_PREFIX}{}--{file_name}", link_slug(skill_id));
                 let link_path = target_dir.join(&link_name);
-
-                if link_path.exists() || link_path.is_symlink() {
-                    fs::remove_file(&link_path)?;
-                }
-
-                unix_fs::symlink(&path, &link_path)?;
-                created.push(link_path);
+                targets.push((path, link_path));
             }
         }
 
-        Ok(created)
+        targets.sort();
+        Ok(targets)
     }
 
     fn remove_symlinks_under(&self, dir: &Path, registry_path: &Path) -> Result<Vec<PathBuf>> {
@@ -141,7 +412,7 @@ impl SymlinkManager {
         Ok(removed)
     }
 
-    fn remove_matching_symlinks(&self, dir: &Path, prefix: &str) -> Result<Vec<PathBuf>> {
+    fn remove_matching(&self, dir: &Path, prefix: &str) -> Result<Vec<PathBuf>> {
         let mut removed = Vec::new();
 
         if !dir.exists() {
@@ -151,8 +422,9 @@ impl SymlinkManager {
         for entry in fs::read_dir(dir)? {
             let entry = entry?;
             let name = entry.file_name().to_string_lossy().to_string();
-            if name.starts_with(prefix) && entry.path().is_symlink() {
-                fs::remove_file(entry.path())?;
+            // Copies are regular files (or skill directories) under the same prefix
+            if name.starts_with(prefix) {
+                remove_entry(&entry.path())?;
                 removed.push(entry.path());
             }
         }
@@ -171,13 +443,18 @@ impl SymlinkManager {
         for entry in fs::read_dir(dir)? {
             let entry = entry?;
             let name = entry.file_name().to_string_lossy().to_string();
-            if name.starts_with(SYMLINK_PREFIX) && entry.path().is_symlink() {
+            if !name.starts_with(SYMLINK_PREFIX) {
+                continue;
+            }
+            if entry.path().is_symlink() {
                 let target = fs::read_link(entry.path())?;
                 if target.exists() {
                     healthy.push(entry.path());
                 } else {
                     broken.push(entry.path());
                 }
+            } else {
+                healthy.push(entry.path());
             }
         }
 
@@ -185,6 +462,36 @@ impl SymlinkManager {
     }
 }
 
+/// Remove a symlink, a copied file, or a copied skill directory.
+fn remove_entry(path: &Path) -> Result<()> {
+    if path.is_symlink() || path.is_file() {
+        fs::remove_file(path)?;
+    } else if path.is_dir() {
+        fs::remove_dir_all(path)?;
+    }
+    Ok(())
+}
+
+fn collect_files(dir: &Path, files: &mut Vec<PathBuf>) -> Result<()> {
+    let mut entries: Vec<PathBuf> = fs::read_dir(dir)?
+        .map(|e| e.map(|e| e.path()))
+        .collect::<std::io::Result<_>>()?;
+    entries.sort();
+    for path in entries {
+        if path.is_dir() {
+            collect_files(&path, files)?;
+        } else {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// Symlink-safe form of a skill ID: `acme:code/rust/feature` -> `acme--code--rust--feature`.
+fn link_slug(skill_id: &str) -> String {
+    skill_id.replace([':', '/'], "--")
+}
+
 /// Load installed skill IDs from state.json (backward-compatible wrapper)
 pub fn load_installed_ids(state_path: &Path) -> Vec<String> {
     use crate::models::state::load_state;
@@ -215,8 +522,12 @@ mod tests {
 
         Skill {
             id: id.to_string(),
-            name: id.split('/').last().unwrap().to_string(),
+            registry: "default".to_string(),
+            name: id.split('/').next_back().unwrap().to_string(),
             description: "test skill".to_string(),
+            version: None,
+            requires: vec![],
+            settings: Default::default(),
             phase,
             tech: "general".to_string(),
             path: skill_path,
@@ -232,9 +543,10 @@ mod tests {
         let target = TempDir::new().unwrap();
         let agents_dir = target.path().join("agents");
         let commands_dir = target.path().join("commands");
+        let skills_dir = target.path().join("skills");
 
         let skill = make_skill(&source, "code/general/feature", Phase::Code);
-        let manager = SymlinkManager::new(agents_dir.clone(), commands_dir);
+        let manager = SymlinkManager::new(agents_dir.clone(), commands_dir, skills_dir);
 
         let created = manager.install(&skill).unwrap();
 
@@ -245,15 +557,44 @@ mod tests {
         assert!(name.contains("code--general--feature"));
     }
 
+    #[test]
+    fn install_takes_settings_back_when_linking_fails() {
+        let source = TempDir::new().unwrap();
+        let target = TempDir::new().unwrap();
+        // A file where the agents directory should be makes linking fail
+        let agents_dir = target.path().join("agents");
+        fs::write(&agents_dir, "").unwrap();
+        let targets = SettingsTargets {
+            settings: target.path().join("settings.json"),
+            mcp: target.path().join(".mcp.json"),
+            state: target.path().join("state.json"),
+        };
+
+        let mut skill = make_skill(&source, "code/general/feature", Phase::Code);
+        skill.settings = serde_json::from_str(r#"{ "mcpServers": { "github": {} } }"#).unwrap();
+        let manager = SymlinkManager::new(
+            agents_dir,
+            target.path().join("commands"),
+            target.path().join("skills"),
+        )
+        .with_settings(targets.clone());
+
+        assert!(manager.install(&skill).is_err());
+        assert!(!settings::is_owner(&targets, &skill.id));
+        let mcp = fs::read_to_string(&targets.mcp).unwrap();
+        assert!(!mcp.contains("github"));
+    }
+
     #[test]
     fn uninstall_removes_matching_symlinks() {
         let source = TempDir::new().unwrap();
         let target = TempDir::new().unwrap();
         let agents_dir = target.path().join("agents");
         let commands_dir = target.path().join("commands");
+        let skills_dir = target.path().join("skills");
 
         let skill = make_skill(&source, "code/general/feature", Phase::Code);
-        let manager = SymlinkManager::new(agents_dir.clone(), commands_dir.clone());
+        let manager = SymlinkManager::new(agents_dir.clone(), commands_dir.clone(), skills_dir);
 
         manager.install(&skill).unwrap();
         assert_eq!(fs::read_dir(&agents_dir).unwrap().count(), 1);
@@ -269,9 +610,10 @@ mod tests {
         let target = TempDir::new().unwrap();
         let agents_dir = target.path().join("agents");
         let commands_dir = target.path().join("commands");
+        let skills_dir = target.path().join("skills");
 
         let skill = make_skill(&source, "code/general/feature", Phase::Code);
-        let manager = SymlinkManager::new(agents_dir, commands_dir);
+        let manager = SymlinkManager::new(agents_dir, commands_dir, skills_dir);
         manager.install(&skill).unwrap();
 
         let (healthy, broken) = manager.verify().unwrap();
@@ -284,14 +626,109 @@ mod tests {
         let target = TempDir::new().unwrap();
         let agents_dir = target.path().join("agents");
         let commands_dir = target.path().join("commands");
+        let skills_dir = target.path().join("skills");
         fs::create_dir_all(&agents_dir).unwrap();
 
         let broken_link = agents_dir.join("forja--code--general--gone--coder.md");
         unix_fs::symlink("/tmp/nonexistent_forja_test_target", &broken_link).unwrap();
 
-        let manager = SymlinkManager::new(agents_dir, commands_dir);
+        let manager = SymlinkManager::new(agents_dir, commands_dir, skills_dir);
         let (healthy, broken) = manager.verify().unwrap();
         assert!(healthy.is_empty());
         assert_eq!(broken.len(), 1);
     }
+
+    #[test]
+    fn copy_mode_installs_marked_copies_and_keeps_edits() {
+        let source = TempDir::new().unwrap();
+        let target = TempDir::new().unwrap();
+        let state = target.path().join("state.json");
+        let skill = make_skill(&source, "code/general/feature", Phase::Code);
+        let manager = SymlinkManager::new(
+            target.path().join("agents"),
+            target.path().join("commands"),
+            target.path().join("skills"),
+        )
+        .with_copies(target.path().join("objects"), state.clone());
+
+        let created = manager.install(&skill).unwrap();
+        assert_eq!(created.len(), 1);
+        assert!(!created[0].is_symlink());
+        let content = fs::read_to_string(&created[0]).unwrap();
+        assert!(content.contains("forja:copy code/general/feature"));
+        let meta = &crate::models::state::load_state(&state).install_metadata[&skill.id];
+        assert_eq!(meta.copies.len(), 1);
+
+        fs::write(&created[0], format!("{content}edited\n")).unwrap();
+        assert!(manager.install(&skill).unwrap().is_empty());
+        let kept = fs::read_to_string(&created[0]).unwrap();
+        assert!(kept.ends_with("edited\n"));
+
+        let (healthy, broken) = manager.verify().unwrap();
+        assert_eq!((healthy.len(), broken.len()), (1, 0));
+        assert_eq!(manager.uninstall(&skill.id).unwrap().len(), 1);
+        assert!(!created[0].exists());
+    }
+
+    #[test]
+    fn skill_directories_are_linked_or_copied_whole() {
+        let source = TempDir::new().unwrap();
+        let target = TempDir::new().unwrap();
+        let skill = make_skill(&source, "deploy/git/commit", Phase::Deploy);
+        let skill_dir = skill.path.join("skills/commit");
+        fs::create_dir_all(skill_dir.join("scripts")).unwrap();
+        fs::write(
+            skill_dir.join("SKILL.md"),
+            "---\nname: commit\ndescription: Commit\n---\n",
+        )
+        .unwrap();
+        fs::write(skill_dir.join("scripts/check.sh"), "#!/bin/sh\n").unwrap();
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let script = skill_dir.join("scripts/check.sh");
+            fs::set_permissions(&script, fs::Permissions::from_mode(0o755)).unwrap();
+        }
+        fs::create_dir_all(skill.path.join("skills/notes")).unwrap();
+
+        let skills_dir = target.path().join("skills");
+        let linked = target.path().join("linked");
+        let manager = SymlinkManager::new(
+            linked.join("agents"),
+            linked.join("commands"),
+            skills_dir.clone(),
+        );
+        manager.install(&skill).unwrap();
+        let link = skills_dir.join("forja--deploy--git--commit--commit");
+        assert!(link.is_symlink());
+        assert!(link.join("scripts/check.sh").exists());
+        assert_eq!(manager.counts().unwrap().skills, 1);
+        assert_eq!(manager.verify().unwrap().0.len(), 2);
+        manager.uninstall(&skill.id).unwrap();
+        assert!(!link.exists() && !link.is_symlink());
+
+        let state = target.path().join("state.json");
+        let manager = manager.with_copies(target.path().join("objects"), state.clone());
+        manager.install(&skill).unwrap();
+        assert!(link.is_dir() && !link.is_symlink());
+        let skill_md = fs::read_to_string(link.join("SKILL.md")).unwrap();
+        assert!(skill_md.contains("forja:copy deploy/git/commit"));
+        assert_eq!(
+            fs::read_to_string(link.join("scripts/check.sh")).unwrap(),
+            "#!/bin/sh\n"
+        );
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = fs::metadata(link.join("scripts/check.sh"))
+                .unwrap()
+                .permissions()
+                .mode();
+            assert_eq!(mode & 0o777, 0o755);
+        }
+        let copies = &crate::models::state::load_state(&state).install_metadata[&skill.id].copies;
+        assert!(copies.contains_key("forja--deploy--git--commit--commit/scripts/check.sh"));
+        manager.uninstall(&skill.id).unwrap();
+        assert!(!link.exists());
+    }
 }
@@ -1,4 +1,7 @@
 pub mod auto_install;
+pub mod copy;
+pub mod install;
+pub mod layers;
 pub mod manager;
 pub mod sync;
 pub mod upgrade;
@@ -2,6 +2,7 @@ use crate::error::Result;
 use crate::models::active_project::{clear_active_project, load_active_project};
 use crate::paths::{ForjaMode, ForjaPaths};
 use crate::registry::catalog;
+use crate::symlink::layers;
 use crate::symlink::manager::{SymlinkManager, load_installed_ids};
 
 /// Result of a sync operation, containing info about what happened.
@@ -17,6 +18,8 @@ pub struct SyncResult {
 /// Rebuild all `forja--` symlinks from the current context's state.
 ///
 /// In **project** mode, symlinks go to `<project>/.claude/` — no global tracking needed.
+/// Global skills stay in `~/.claude/`; the `skills` overrides in the project
+/// config are applied on top (see [`layers::apply`]).
 /// In **global** mode, symlinks go to `~/.claude/` with active-project tracking.
 ///
 /// Returns a `SyncResult` with switch information. The caller handles output.
@@ -25,16 +28,18 @@ pub fn sync_symlinks(paths: &ForjaPaths) -> Result<SyncResult> {
 
     // Project mode: symlinks are isolated per-project, no global tracking needed
     if paths.mode == ForjaMode::Project {
-        let manager =
-            SymlinkManager::new(paths.claude_agents.clone(), paths.claude_commands.clone());
-        manager.remove_project_symlinks(&paths.registry)?;
+        let manager = SymlinkManager::for_paths(paths);
+        remove_source_symlinks(&manager, paths)?;
+        let resolved = layers::apply(paths)?;
 
+        // Skills the project config hides stay installed, just unlinked
         let installed_ids = load_installed_ids(&paths.state);
         if !installed_ids.is_empty()
-            && let Ok(registry) = catalog::scan(&paths.registry, &installed_ids)
+            && let Ok(registry) = catalog::scan_all(paths, &installed_ids)
         {
             for skill in &registry.skills {
-                if installed_ids.contains(&skill.id) {
+                let hidden = resolved.hidden.iter().any(|h| h.id == skill.id);
+                if installed_ids.contains(&skill.id) && !hidden {
                     let _ = manager.install(skill);
                 }
             }
@@ -65,13 +70,13 @@ pub fn sync_symlinks(paths: &ForjaPaths) -> Result<SyncResult> {
     }
 
     // Remove only this project's symlinks (preserves other projects' symlinks)
-    let manager = SymlinkManager::new(paths.claude_agents.clone(), paths.claude_commands.clone());
-    manager.remove_project_symlinks(&paths.registry)?;
+    let manager = SymlinkManager::for_paths(paths);
+    remove_source_symlinks(&manager, paths)?;
 
     // Recreate symlinks from current state
     let installed_ids = load_installed_ids(&paths.state);
     if !installed_ids.is_empty()
-        && let Ok(registry) = catalog::scan(&paths.registry, &installed_ids)
+        && let Ok(registry) = catalog::scan_all(paths, &installed_ids)
     {
         for skill in &registry.skills {
             if installed_ids.contains(&skill.id) {
@@ -89,3 +94,11 @@ pub fn sync_symlinks(paths: &ForjaPaths) -> Result<SyncResult> {
         current_project,
     })
 }
+
+/// Remove symlinks pointing into any of this context's registries.
+fn remove_source_symlinks(manager: &SymlinkManager, paths: &ForjaPaths) -> Result<()> {
+    for source in catalog::sources(paths) {
+        manager.remove_project_symlinks(&source.path)?;
+    }
+    Ok(())
+}
@@ -1,43 +1,174 @@
-use crate::error::Result;
+use std::collections::BTreeMap;
+
+use crate::error::{ForjaError, Result};
+use crate::models::state::{load_state, record_copies, record_upgrade};
+use crate::models::version::{Bump, classify, is_newer};
 use crate::paths::ForjaPaths;
 use crate::registry::catalog;
 use crate::registry::diff::{ChangeType, compute_diff, load_previous_head};
 use crate::registry::git;
+use crate::symlink::copy::{self, CopiedFile, Outcome, Resolution};
 use crate::symlink::manager::{SymlinkManager, load_installed_ids};
 
-/// Find installed skills that have been modified since the last update.
-pub fn find_upgradable(paths: &ForjaPaths) -> Result<Vec<String>> {
-    let old_head = load_previous_head(&paths.forja_root)?;
-    let new_head = git::head_sha(&paths.registry)?;
+/// An installed skill with a newer copy in the catalog.
+#[derive(Debug, Clone)]
+pub struct Upgrade {
+    pub skill_id: String,
+    /// Version recorded at install time, else the one before the last update.
+    pub from: Option<String>,
+    /// Version the catalog has now.
+    pub to: Option<String>,
+    pub bump: Bump,
+}
 
-    if old_head == new_head {
-        return Ok(Vec::new());
+/// Find installed skills that were modified since the last update, or whose
+/// catalog version is newer than the one recorded when they were installed.
+/// In copy mode, skills whose copies are behind the registry are included too.
+pub fn find_upgradable(paths: &ForjaPaths) -> Result<Vec<Upgrade>> {
+    let installed_ids = load_installed_ids(&paths.state);
+    let metadata = load_state(&paths.state).install_metadata;
+    let registry = catalog::scan_all(paths, &installed_ids)?;
+    let recorded = |id: &str| metadata.get(id).and_then(|m| m.version.clone());
+    let current = |id: &str| registry.find_by_id(id).and_then(|s| s.version.clone());
+
+    let mut upgradable = Vec::new();
+    if let Ok(old_head) = load_previous_head(&paths.forja_root) {
+        let new_head = git::head_sha(&paths.registry)?;
+        if old_head != new_head {
+            let changes = compute_diff(&paths.registry, &old_head, &new_head)?;
+            for change in changes {
+                if change.change_type != ChangeType::Modified
+                    || !installed_ids.contains(&change.skill_id)
+                {
+                    continue;
+                }
+                let to = current(&change.skill_id).or(change.new_version);
+                let installed_at = recorded(&change.skill_id);
+                // Already upgraded to this version since the last update.
+                if installed_at.is_some() && installed_at == to && change.old_version != to {
+                    continue;
+                }
+                let from = installed_at.or(change.old_version);
+                upgradable.push(Upgrade {
+                    bump: classify(from.as_deref(), to.as_deref()),
+                    skill_id: change.skill_id,
+                    from,
+                    to,
+                });
+            }
+        }
     }
 
-    let changes = compute_diff(&paths.registry, &old_head, &new_head)?;
-    let installed_ids = load_installed_ids(&paths.state);
+    for id in &installed_ids {
+        if upgradable.iter().any(|u| &u.skill_id == id) {
+            continue;
+        }
+        let (Some(from), Some(to)) = (recorded(id), current(id)) else {
+            continue;
+        };
+        if is_newer(&from, &to) {
+            upgradable.push(Upgrade {
+                skill_id: id.clone(),
+                bump: classify(Some(&from), Some(&to)),
+                from: Some(from),
+                to: Some(to),
+            });
+        }
+    }
 
-    let upgradable: Vec<String> = changes
-        .into_iter()
-        .filter(|c| c.change_type == ChangeType::Modified && installed_ids.contains(&c.skill_id))
-        .map(|c| c.skill_id)
-        .collect();
+    let manager = SymlinkManager::for_paths(paths);
+    if manager.objects_dir().is_some() {
+        for skill in registry
+            .skills
+            .iter()
+            .filter(|s| installed_ids.contains(&s.id))
+        {
+            if upgradable.iter().any(|u| u.skill_id == skill.id) {
+                continue;
+            }
+            let copies = metadata
+                .get(&skill.id)
+                .map(|m| m.copies.clone())
+                .unwrap_or_default();
+            let files = copy::inspect(&manager, skill, &copies)?;
+            if files.iter().any(|f| f.state.needs_upgrade()) {
+                let from = recorded(&skill.id);
+                upgradable.push(Upgrade {
+                    skill_id: skill.id.clone(),
+                    bump: classify(from.as_deref(), skill.version.as_deref()),
+                    from,
+                    to: skill.version.clone(),
+                });
+            }
+        }
+    }
 
+    upgradable.sort_by(|a, b| a.skill_id.cmp(&b.skill_id));
     Ok(upgradable)
 }
 
-/// Reinstall a skill by removing and re-creating its symlinks.
-pub fn reinstall_skill(paths: &ForjaPaths, skill_id: &str) -> Result<()> {
+/// Reinstall a skill by re-merging its settings and re-creating its
+/// symlinks, and record the version it now has. Returns the settings entries
+/// left as the user edited them (see [`SymlinkManager::reapply_settings`]).
+pub fn reinstall_skill(paths: &ForjaPaths, skill_id: &str) -> Result<Vec<String>> {
     let installed_ids = load_installed_ids(&paths.state);
-    let registry = catalog::scan(&paths.registry, &installed_ids)?;
+    let registry = catalog::scan_all(paths, &installed_ids)?;
 
     let skill = registry
         .find_by_id(skill_id)
-        .ok_or_else(|| crate::error::ForjaError::SkillNotFound(skill_id.to_string()))?;
+        .ok_or_else(|| ForjaError::SkillNotFound(skill_id.to_string()))?;
 
-    let manager = SymlinkManager::new(paths.claude_agents.clone(), paths.claude_commands.clone());
-    manager.uninstall(skill_id)?;
+    let manager = SymlinkManager::for_paths(paths);
+    let kept = manager.reapply_settings(skill)?;
+    manager.unlink(skill_id)?;
     manager.install(skill)?;
+    record_upgrade(&paths.state, skill)?;
+
+    Ok(kept)
+}
+
+/// Each file's [`Outcome`], and the edited settings entries left in place.
+pub type CopiesUpgrade = (Vec<(CopiedFile, Outcome)>, Vec<String>);
+
+/// Upgrade a copy-installed skill file by file (see [`copy::upgrade_file`]),
+/// asking `resolve` about files edited on both sides. Records the new merge
+/// bases and the version the skill now has. Like [`reinstall_skill`], leaves
+/// edited settings entries in place.
+pub fn upgrade_copies(
+    paths: &ForjaPaths,
+    skill_id: &str,
+    resolve: &mut dyn FnMut(&CopiedFile, bool) -> Result<Resolution>,
+) -> Result<CopiesUpgrade> {
+    let installed_ids = load_installed_ids(&paths.state);
+    let registry = catalog::scan_all(paths, &installed_ids)?;
+    let skill = registry
+        .find_by_id(skill_id)
+        .ok_or_else(|| ForjaError::SkillNotFound(skill_id.to_string()))?;
+
+    let manager = SymlinkManager::for_paths(paths);
+    let Some(objects_dir) = manager.objects_dir() else {
+        let kept = reinstall_skill(paths, skill_id)?;
+        return Ok((Vec::new(), kept));
+    };
+    let copies = load_state(&paths.state)
+        .install_metadata
+        .get(skill_id)
+        .map(|m| m.copies.clone())
+        .unwrap_or_default();
+
+    let mut outcomes = Vec::new();
+    let mut bases = BTreeMap::new();
+    for file in copy::inspect(&manager, skill, &copies)? {
+        let base = copies.get(&file.name).map(String::as_str);
+        let (outcome, new_base) = copy::upgrade_file(objects_dir, skill_id, &file, base, resolve)?;
+        if let Some(new_base) = new_base {
+            bases.insert(file.name.clone(), new_base);
+        }
+        outcomes.push((file, outcome));
+    }
+    record_copies(&paths.state, skill_id, bases)?;
+    let kept = manager.reapply_settings(skill)?;
+    record_upgrade(&paths.state, skill)?;
 
-    Ok(())
+    Ok((outcomes, kept))
 }
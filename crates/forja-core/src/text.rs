@@ -0,0 +1,26 @@
+//! Small string helpers shared by the CLI and the spark process manager.
+
+/// Last `max` bytes of `s`, moved forward to a char boundary.
+pub fn tail(s: &str, max: usize) -> &str {
+    if s.len() <= max {
+        return s;
+    }
+    let mut start = s.len() - max;
+    while !s.is_char_boundary(start) {
+        start += 1;
+    }
+    &s[start..]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tail_keeps_the_end_on_a_char_boundary() {
+        assert_eq!(tail("short", 10), "short");
+        assert_eq!(tail("abcdef", 3), "def");
+        // 'é' is two bytes; cutting into it moves past it
+        assert_eq!(tail("aéb", 2), "b");
+    }
+}
@@ -1,3 +1,4 @@
+use forja_core::models::usage::TokenUsage;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
@@ -9,6 +10,12 @@ use serde_json::Value;
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum ChatEvent {
+    SessionInit {
+        session_id: String,
+        model: String,
+        cwd: String,
+        tools: Vec<String>,
+    },
     MessageStart {
         id: String,
         role: String,
@@ -25,16 +32,64 @@ pub enum ChatEvent {
     ToolResult {
         id: String,
         content: String,
+        #[serde(default)]
+        is_error: bool,
     },
     ContentBlockStop {
         index: usize,
     },
     MessageStop,
+    /// Final summary `claude` prints once the whole turn is done.
+    TurnComplete {
+        subtype: String,
+        is_error: bool,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        result: Option<String>,
+        duration_ms: u64,
+        duration_api_ms: u64,
+        num_turns: u32,
+        total_cost_usd: f64,
+        usage: Usage,
+    },
     Error {
         message: String,
     },
 }
 
+impl ChatEvent {
+    /// Spend reported by a `TurnComplete` event; `None` for every other event.
+    pub fn token_usage(&self) -> Option<TokenUsage> {
+        match self {
+            ChatEvent::TurnComplete {
+                duration_ms,
+                num_turns,
+                total_cost_usd,
+                usage,
+                ..
+            } => Some(TokenUsage {
+                input_tokens: usage.input_tokens,
+                output_tokens: usage.output_tokens,
+                cache_creation_input_tokens: usage.cache_creation_input_tokens,
+                cache_read_input_tokens: usage.cache_read_input_tokens,
+                cost_usd: *total_cost_usd,
+                duration_ms: *duration_ms,
+                num_turns: *num_turns,
+            }),
+            _ => None,
+        }
+    }
+}
+
+/// Token counts reported by the API for a message or a whole turn.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Usage {
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub cache_creation_input_tokens: u64,
+    pub cache_read_input_tokens: u64,
+}
+
 /// Spark-level events that wrap chat events with spark identity.
 ///
 /// The Tauri layer emits these as `app.emit("spark:{id}", event)` so
@@ -1,53 +1,300 @@
-use crate::events::ChatEvent;
+use std::collections::{HashMap, HashSet};
 
-/// Parse a single line of `claude --output-format stream-json` output
-/// into a `ChatEvent`.
+use serde_json::Value;
+
+use crate::events::{ChatEvent, Usage};
+
+/// Stateful parser for `claude --output-format stream-json` output.
 ///
-/// Returns `None` for lines that don't map to a known event type
-/// (e.g. heartbeats, unknown fields, blank lines).
+/// The CLI wraps everything in envelopes keyed by `type`:
 ///
-/// # Implementation note
+/// - `system` (`subtype: "init"`) — session ID, model, cwd and tools
+/// - `stream_event` — raw API stream events, only with `--include-partial-messages`
+/// - `assistant` — a complete assistant message (text and `tool_use` blocks)
+/// - `user` — tool results fed back to the model
+/// - `result` — final cost, usage and duration for the turn
 ///
-/// The exact stream-json format needs to be captured from a real
-/// `claude --output-format stream-json --print -- "hello"` invocation
-/// during Phase 4. This skeleton provides the API contract.
-pub fn parse_line(line: &str) -> Option<ChatEvent> {
-    let line = line.trim();
-    if line.is_empty() {
-        return None;
+/// Tool inputs stream as `input_json_delta` fragments, so the parser keeps
+/// per-block state and emits `ToolUseStart` once the block closes. Complete
+/// `assistant` messages that were already streamed are not emitted twice.
+#[derive(Debug, Default)]
+pub struct StreamParser {
+    tool_blocks: HashMap<usize, PendingToolUse>,
+    streamed_messages: HashSet<String>,
+}
+
+#[derive(Debug)]
+struct PendingToolUse {
+    id: String,
+    name: String,
+    input: Value,
+    partial_json: String,
+}
+
+impl StreamParser {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Feed one line of output and return the chat events it produces.
+    ///
+    /// Blank lines, invalid JSON and unknown envelope types produce nothing.
+    pub fn feed(&mut self, line: &str) -> Vec<ChatEvent> {
+        let line = line.trim();
+        if line.is_empty() {
+            return Vec::new();
+        }
+        let Ok(value) = serde_json::from_str::<Value>(line) else {
+            return Vec::new();
+        };
+
+        match value.get("type").and_then(Value::as_str) {
+            Some("stream_event") => match value.get("event") {
+                Some(event) => self.stream_event(event),
+                None => Vec::new(),
+            },
+            Some("system") => system_event(&value).into_iter().collect(),
+            Some("assistant") => self.assistant_message(&value),
+            Some("user") => tool_results(&value),
+            Some("result") => vec![turn_complete(&value)],
+            // Bare API stream events (no envelope)
+            Some(_) => self.stream_event(&value),
+            None => Vec::new(),
+        }
     }
 
-    let value: serde_json::Value = serde_json::from_str(line).ok()?;
-
-    // TODO: Map actual claude stream-json event types to ChatEvent variants.
-    // The field names below are provisional — replace with real ones after
-    // investigating the actual output format.
-    let event_type = value.get("type")?.as_str()?;
-
-    match event_type {
-        "message_start" => Some(ChatEvent::MessageStart {
-            id: json_str(&value, "id"),
-            role: json_str(&value, "role"),
-        }),
-        "content_block_delta" => {
-            let delta = value.get("delta")?;
-            Some(ChatEvent::TextDelta {
-                index: value.get("index")?.as_u64()? as usize,
-                text: delta.get("text")?.as_str()?.to_string(),
-            })
+    fn stream_event(&mut self, event: &Value) -> Vec<ChatEvent> {
+        let Some(event_type) = event.get("type").and_then(Value::as_str) else {
+            return Vec::new();
+        };
+
+        match event_type {
+            "message_start" => {
+                // Stream events nest the message; older output put fields at the top level
+                let message = event.get("message").unwrap_or(event);
+                let id = json_str(message, "id");
+                self.tool_blocks.clear();
+                if !id.is_empty() {
+                    self.streamed_messages.insert(id.clone());
+                }
+                vec![ChatEvent::MessageStart {
+                    id,
+                    role: json_str(message, "role"),
+                }]
+            }
+            "content_block_start" => {
+                if let (Some(index), Some(block)) = (json_index(event), event.get("content_block"))
+                    && block.get("type").and_then(Value::as_str) == Some("tool_use")
+                {
+                    self.tool_blocks.insert(
+                        index,
+                        PendingToolUse {
+                            id: json_str(block, "id"),
+                            name: json_str(block, "name"),
+                            input: block.get("input").cloned().unwrap_or(Value::Null),
+                            partial_json: String::new(),
+                        },
+                    );
+                }
+                Vec::new()
+            }
+            "content_block_delta" => {
+                let (Some(index), Some(delta)) = (json_index(event), event.get("delta")) else {
+                    return Vec::new();
+                };
+                match delta.get("type").and_then(Value::as_str) {
+                    Some("input_json_delta") => {
+                        if let Some(pending) = self.tool_blocks.get_mut(&index) {
+                            pending.partial_json.push_str(
+                                delta.get("partial_json").and_then(Value::as_str).unwrap_or(""),
+                            );
+                        }
+                        Vec::new()
+                    }
+                    // `text_delta`, or an untyped delta carrying text
+                    Some("text_delta") | None => match delta.get("text").and_then(Value::as_str) {
+                        Some(text) => vec![ChatEvent::TextDelta {
+                            index,
+                            text: text.to_string(),
+                        }],
+                        None => Vec::new(),
+                    },
+                    // thinking / signature deltas aren't rendered
+                    Some(_) => Vec::new(),
+                }
+            }
+            "content_block_stop" => {
+                let Some(index) = json_index(event) else {
+                    return Vec::new();
+                };
+                let mut events = Vec::new();
+                if let Some(pending) = self.tool_blocks.remove(&index) {
+                    let input = if pending.partial_json.trim().is_empty() {
+                        pending.input
+                    } else {
+                        serde_json::from_str(&pending.partial_json)
+                            .unwrap_or(Value::String(pending.partial_json))
+                    };
+                    events.push(ChatEvent::ToolUseStart {
+                        id: pending.id,
+                        name: pending.name,
+                        input,
+                    });
+                }
+                events.push(ChatEvent::ContentBlockStop { index });
+                events
+            }
+            "message_stop" => vec![ChatEvent::MessageStop],
+            "error" => {
+                let message = event
+                    .get("error")
+                    .map(|e| json_str(e, "message"))
+                    .unwrap_or_else(|| json_str(event, "message"));
+                vec![ChatEvent::Error { message }]
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    /// Expand a complete assistant message into the events streaming would have produced.
+    fn assistant_message(&mut self, value: &Value) -> Vec<ChatEvent> {
+        let Some(message) = value.get("message") else {
+            return Vec::new();
+        };
+        let id = json_str(message, "id");
+        if self.streamed_messages.contains(&id) {
+            return Vec::new();
+        }
+
+        let mut events = vec![ChatEvent::MessageStart {
+            id,
+            role: json_str(message, "role"),
+        }];
+        let blocks = message.get("content").and_then(Value::as_array);
+        for (index, block) in blocks.into_iter().flatten().enumerate() {
+            match block.get("type").and_then(Value::as_str) {
+                Some("text") => events.push(ChatEvent::TextDelta {
+                    index,
+                    text: json_str(block, "text"),
+                }),
+                Some("tool_use") => events.push(ChatEvent::ToolUseStart {
+                    id: json_str(block, "id"),
+                    name: json_str(block, "name"),
+                    input: block.get("input").cloned().unwrap_or(Value::Null),
+                }),
+                _ => continue,
+            }
+            events.push(ChatEvent::ContentBlockStop { index });
         }
-        "content_block_stop" => Some(ChatEvent::ContentBlockStop {
-            index: value.get("index")?.as_u64()? as usize,
-        }),
-        "message_stop" => Some(ChatEvent::MessageStop),
-        "error" => Some(ChatEvent::Error {
-            message: json_str(&value, "message"),
-        }),
-        _ => None,
+        events.push(ChatEvent::MessageStop);
+        events
+    }
+}
+
+/// Extract the conversation's session ID from a stream-json line.
+///
+/// Every envelope emitted by `claude` carries a top-level `session_id`;
+/// it's what `--resume` needs for follow-up turns.
+pub fn session_id(line: &str) -> Option<String> {
+    let value: Value = serde_json::from_str(line.trim()).ok()?;
+    let id = value.get("session_id")?.as_str()?;
+    (!id.is_empty()).then(|| id.to_string())
+}
+
+fn system_event(value: &Value) -> Option<ChatEvent> {
+    if value.get("subtype").and_then(Value::as_str) != Some("init") {
+        return None;
+    }
+    let tools = value
+        .get("tools")
+        .and_then(Value::as_array)
+        .map(|tools| {
+            tools
+                .iter()
+                .filter_map(Value::as_str)
+                .map(String::from)
+                .collect()
+        })
+        .unwrap_or_default();
+
+    Some(ChatEvent::SessionInit {
+        session_id: json_str(value, "session_id"),
+        model: json_str(value, "model"),
+        cwd: json_str(value, "cwd"),
+        tools,
+    })
+}
+
+fn tool_results(value: &Value) -> Vec<ChatEvent> {
+    let blocks = value
+        .get("message")
+        .and_then(|m| m.get("content"))
+        .and_then(Value::as_array);
+
+    blocks
+        .into_iter()
+        .flatten()
+        .filter(|b| b.get("type").and_then(Value::as_str) == Some("tool_result"))
+        .map(|block| ChatEvent::ToolResult {
+            id: json_str(block, "tool_use_id"),
+            content: tool_result_text(block.get("content")),
+            is_error: block
+                .get("is_error")
+                .and_then(Value::as_bool)
+                .unwrap_or(false),
+        })
+        .collect()
+}
+
+/// Tool result content is either a plain string or a list of content blocks.
+fn tool_result_text(content: Option<&Value>) -> String {
+    match content {
+        Some(Value::String(s)) => s.clone(),
+        Some(Value::Array(parts)) => parts
+            .iter()
+            .filter_map(|p| p.get("text").and_then(Value::as_str))
+            .collect::<Vec<_>>()
+            .join("\n"),
+        _ => String::new(),
+    }
+}
+
+fn turn_complete(value: &Value) -> ChatEvent {
+    let usage = value
+        .get("usage")
+        .and_then(|u| serde_json::from_value::<Usage>(u.clone()).ok())
+        .unwrap_or_default();
+
+    ChatEvent::TurnComplete {
+        subtype: json_str(value, "subtype"),
+        is_error: value
+            .get("is_error")
+            .and_then(Value::as_bool)
+            .unwrap_or(false),
+        result: value
+            .get("result")
+            .and_then(Value::as_str)
+            .map(String::from),
+        duration_ms: json_u64(value, "duration_ms"),
+        duration_api_ms: json_u64(value, "duration_api_ms"),
+        num_turns: json_u64(value, "num_turns") as u32,
+        total_cost_usd: value
+            .get("total_cost_usd")
+            .and_then(Value::as_f64)
+            .unwrap_or(0.0),
+        usage,
     }
 }
 
-fn json_str(value: &serde_json::Value, key: &str) -> String {
+fn json_index(value: &Value) -> Option<usize> {
+    value.get("index")?.as_u64().map(|i| i as usize)
+}
+
+fn json_u64(value: &Value, key: &str) -> u64 {
+    value.get(key).and_then(Value::as_u64).unwrap_or(0)
+}
+
+fn json_str(value: &Value, key: &str) -> String {
     value
         .get(key)
         .and_then(|v| v.as_str())
@@ -59,34 +306,133 @@ fn json_str(value: &serde_json::Value, key: &str) -> String {
 mod tests {
     use super::*;
 
+    /// Events of one line fed to a fresh parser.
+    fn parse(line: &str) -> Vec<ChatEvent> {
+        StreamParser::new().feed(line)
+    }
+
     #[test]
-    fn empty_line_returns_none() {
-        assert!(parse_line("").is_none());
-        assert!(parse_line("   ").is_none());
+    fn empty_line_yields_nothing() {
+        assert!(parse("").is_empty());
+        assert!(parse("   ").is_empty());
     }
 
     #[test]
-    fn invalid_json_returns_none() {
-        assert!(parse_line("not json").is_none());
+    fn invalid_json_yields_nothing() {
+        assert!(parse("not json").is_empty());
     }
 
     #[test]
-    fn unknown_event_type_returns_none() {
-        assert!(parse_line(r#"{"type": "unknown_thing"}"#).is_none());
+    fn unknown_event_type_yields_nothing() {
+        assert!(parse(r#"{"type": "unknown_thing"}"#).is_empty());
     }
 
     #[test]
     fn parse_message_stop() {
-        let event = parse_line(r#"{"type": "message_stop"}"#);
-        assert!(matches!(event, Some(ChatEvent::MessageStop)));
+        let events = parse(r#"{"type": "message_stop"}"#);
+        assert!(matches!(events.as_slice(), [ChatEvent::MessageStop]));
+    }
+
+    #[test]
+    fn parse_stream_event_envelope() {
+        let line = r#"{"type":"stream_event","event":{"type":"content_block_delta","index":0,"delta":{"type":"text_delta","text":"Hi"}},"session_id":"abc"}"#;
+        match parse(line).as_slice() {
+            [ChatEvent::TextDelta { index, text }] => {
+                assert_eq!(*index, 0);
+                assert_eq!(text, "Hi");
+            }
+            other => panic!("expected TextDelta, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn parse_nested_message_start() {
+        let line = r#"{"type":"stream_event","event":{"type":"message_start","message":{"id":"msg_01","role":"assistant"}}}"#;
+        match parse(line).as_slice() {
+            [ChatEvent::MessageStart { id, role }] => {
+                assert_eq!(id, "msg_01");
+                assert_eq!(role, "assistant");
+            }
+            other => panic!("expected MessageStart, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn session_id_from_envelope() {
+        let line = r#"{"type":"system","subtype":"init","session_id":"5f8d2c1e"}"#;
+        assert_eq!(session_id(line).as_deref(), Some("5f8d2c1e"));
+        assert!(session_id(r#"{"type":"message_stop"}"#).is_none());
+        assert!(session_id("not json").is_none());
     }
 
     #[test]
     fn parse_error_event() {
-        let event = parse_line(r#"{"type": "error", "message": "rate limited"}"#);
-        match event {
-            Some(ChatEvent::Error { message }) => assert_eq!(message, "rate limited"),
+        match parse(r#"{"type": "error", "message": "rate limited"}"#).as_slice() {
+            [ChatEvent::Error { message }] => assert_eq!(message, "rate limited"),
+            other => panic!("expected Error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn parse_api_error_object() {
+        let line = r#"{"type":"error","error":{"type":"overloaded_error","message":"Overloaded"}}"#;
+        match parse(line).as_slice() {
+            [ChatEvent::Error { message }] => assert_eq!(message, "Overloaded"),
             other => panic!("expected Error, got {:?}", other),
         }
     }
+
+    #[test]
+    fn input_json_delta_accumulates_until_block_stop() {
+        let mut parser = StreamParser::new();
+        let lines = [
+            r#"{"type":"content_block_start","index":1,"content_block":{"type":"tool_use","id":"toolu_1","name":"Read","input":{}}}"#,
+            r#"{"type":"content_block_delta","index":1,"delta":{"type":"input_json_delta","partial_json":"{\"file_path\":"}}"#,
+            r#"{"type":"content_block_delta","index":1,"delta":{"type":"input_json_delta","partial_json":"\"src/main.rs\"}"}}"#,
+        ];
+        for line in lines {
+            assert!(parser.feed(line).is_empty());
+        }
+
+        let events = parser.feed(r#"{"type":"content_block_stop","index":1}"#);
+        match events.as_slice() {
+            [
+                ChatEvent::ToolUseStart { id, name, input },
+                ChatEvent::ContentBlockStop { index: 1 },
+            ] => {
+                assert_eq!(id, "toolu_1");
+                assert_eq!(name, "Read");
+                assert_eq!(input["file_path"], "src/main.rs");
+            }
+            other => panic!("unexpected events: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn tool_result_joins_content_blocks() {
+        let line = r#"{"type":"user","message":{"role":"user","content":[{"type":"tool_result","tool_use_id":"toolu_1","content":[{"type":"text","text":"line 1"},{"type":"text","text":"line 2"}],"is_error":true}]}}"#;
+        match parse(line).as_slice() {
+            [
+                ChatEvent::ToolResult {
+                    id,
+                    content,
+                    is_error,
+                },
+            ] => {
+                assert_eq!(id, "toolu_1");
+                assert_eq!(content, "line 1\nline 2");
+                assert!(*is_error);
+            }
+            other => panic!("expected ToolResult, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn streamed_assistant_message_not_repeated() {
+        let mut parser = StreamParser::new();
+        parser.feed(r#"{"type":"stream_event","event":{"type":"message_start","message":{"id":"msg_01","role":"assistant"}}}"#);
+
+        let events = parser.feed(r#"{"type":"assistant","message":{"id":"msg_01","role":"assistant","content":[{"type":"text","text":"Hi"}]}}"#);
+        assert!(events.is_empty());
+    }
 }
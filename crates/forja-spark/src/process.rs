@@ -1,10 +1,18 @@
 use std::path::PathBuf;
+use std::process::Stdio;
 use std::sync::Arc;
 
+use forja_core::text::tail;
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, BufReader};
 use tokio::process::Command;
 use tokio::sync::{RwLock, broadcast};
 
-use crate::events::{SparkEvent, SparkStatus};
+use crate::SparkError;
+use crate::events::{ChatEvent, SparkEvent, SparkStatus};
+use crate::parser::{self, StreamParser};
+
+/// Max bytes of stderr attached to the `Error` event of a failed turn.
+const STDERR_TAIL_BYTES: usize = 2000;
 
 /// A managed `claude` CLI process that streams chat events.
 ///
@@ -21,65 +29,49 @@ pub struct Spark {
 }
 
 impl Spark {
-    /// Spawn a new spark with the given initial prompt.
-    ///
-    /// Launches `claude --output-format stream-json --print -- <prompt>` in
-    /// the project's working directory. Streams events via `subscribe()`.
+    /// Create a spark without launching anything yet.
     ///
-    /// # Implementation note
-    ///
-    /// The actual process spawning, stdout parsing, and session ID capture
-    /// will be implemented in Phase 4 after investigating the real
-    /// stream-json output format.
-    pub async fn spawn(project_id: String, prompt: String, cwd: PathBuf) -> crate::Result<Self> {
-        let id = uuid::Uuid::new_v4().to_string();
+    /// Use this with `subscribe()` followed by `start()` when every event
+    /// must be observed — `spawn()` may emit events before a caller subscribes.
+    pub fn new(project_id: String, cwd: PathBuf) -> Self {
         let (events_tx, _) = broadcast::channel(256);
-        let status = Arc::new(RwLock::new(SparkStatus::Starting));
-        let session_id = Arc::new(RwLock::new(None));
 
-        let spark = Self {
-            id: id.clone(),
+        Self {
+            id: uuid::Uuid::new_v4().to_string(),
             project_id,
-            session_id: session_id.clone(),
-            status: status.clone(),
-            events_tx: events_tx.clone(),
-            cwd: cwd.clone(),
-        };
-
-        // TODO (Phase 4): Spawn the claude process, pipe stdout through
-        // parser::parse_line, and emit ChatEvents on events_tx.
-        //
-        // Pseudocode:
-        // let mut child = Command::new("claude")
-        //     .args(["--output-format", "stream-json", "--print", "--", &prompt])
-        //     .current_dir(&cwd)
-        //     .stdout(Stdio::piped())
-        //     .stderr(Stdio::piped())
-        //     .spawn()?;
-        //
-        // tokio::spawn(async move {
-        //     // Read stdout line by line
-        //     // Parse each line with parser::parse_line
-        //     // Capture session_id from output
-        //     // Emit SparkEvent::Chat on events_tx
-        //     // Update status on completion
-        // });
-
-        // Suppress unused variable warnings in skeleton
-        let _ = prompt;
-        let _ = Command::new("claude");
+            session_id: Arc::new(RwLock::new(None)),
+            status: Arc::new(RwLock::new(SparkStatus::Idle)),
+            events_tx,
+            cwd,
+        }
+    }
 
+    /// Spawn a new spark with the given initial prompt.
+    ///
+    /// Launches `claude --output-format stream-json --print -- <prompt>` in
+    /// the project's working directory. Streams events via `subscribe()`.
+    pub async fn spawn(project_id: String, prompt: String, cwd: PathBuf) -> crate::Result<Self> {
+        let spark = Self::new(project_id, cwd);
+        spark.start(prompt).await?;
         Ok(spark)
     }
 
+    /// Launch the first turn of the conversation in a fresh session.
+    pub async fn start(&self, prompt: String) -> crate::Result<()> {
+        self.run_turn(&prompt, None).await
+    }
+
     /// Send a follow-up message to an existing spark (multi-turn).
     ///
     /// Uses `claude --output-format stream-json --print --resume <session_id> -- <prompt>`.
     pub async fn send_message(&self, prompt: String) -> crate::Result<()> {
-        let _session = self.session_id.read().await;
-        // TODO (Phase 4): Spawn a new claude process with --resume <session_id>
-        let _ = prompt;
-        Ok(())
+        let session = self
+            .session_id
+            .read()
+            .await
+            .clone()
+            .ok_or(SparkError::NoSession)?;
+        self.run_turn(&prompt, Some(&session)).await
     }
 
     /// Subscribe to this spark's event stream.
@@ -91,4 +83,140 @@ impl Spark {
     pub async fn status(&self) -> SparkStatus {
         *self.status.read().await
     }
+
+    /// Launch one `claude` process and stream its stdout in the background.
+    ///
+    /// Returns once the process is running; the turn ends with the status
+    /// moving to `Idle` (clean exit) or `Failed`.
+    async fn run_turn(&self, prompt: &str, resume: Option<&str>) -> crate::Result<()> {
+        {
+            let mut status = self.status.write().await;
+            // A turn that is still spawning counts as busy too
+            if matches!(*status, SparkStatus::Starting | SparkStatus::Running) {
+                return Err(SparkError::Process(
+                    "spark is busy — wait for the current turn to finish".into(),
+                ));
+            }
+            *status = SparkStatus::Starting;
+        }
+        self.emit_status(SparkStatus::Starting);
+
+        let mut cmd = Command::new("claude");
+        cmd.args([
+            "--dangerously-skip-permissions",
+            "--output-format",
+            "stream-json",
+            "--verbose",
+            "--include-partial-messages",
+            "--print",
+        ]);
+        if let Some(session) = resume {
+            cmd.args(["--resume", session]);
+        }
+        cmd.arg("--")
+            .arg(prompt)
+            .current_dir(&self.cwd)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+
+        let mut child = match cmd.spawn() {
+            Ok(child) => child,
+            Err(e) => {
+                self.set_status(SparkStatus::Failed).await;
+                return Err(if e.kind() == std::io::ErrorKind::NotFound {
+                    SparkError::ClaudeNotFound
+                } else {
+                    SparkError::Io(e)
+                });
+            }
+        };
+
+        let stdout = child.stdout.take();
+        let stderr = child.stderr.take();
+        self.set_status(SparkStatus::Running).await;
+
+        let spark_id = self.id.clone();
+        let session_id = Arc::clone(&self.session_id);
+        let status = Arc::clone(&self.status);
+        let events_tx = self.events_tx.clone();
+
+        tokio::spawn(async move {
+            // Drain stderr concurrently so a chatty process can't block on a full pipe
+            let stderr_task = tokio::spawn(async move {
+                let mut buf = String::new();
+                if let Some(mut stderr) = stderr {
+                    let _ = stderr.read_to_string(&mut buf).await;
+                }
+                buf
+            });
+
+            if let Some(stdout) = stdout {
+                let mut parser = StreamParser::new();
+                let mut lines = BufReader::new(stdout).lines();
+                while let Ok(Some(line)) = lines.next_line().await {
+                    if let Some(id) = parser::session_id(&line) {
+                        let mut current = session_id.write().await;
+                        if current.as_deref() != Some(id.as_str()) {
+                            *current = Some(id);
+                        }
+                    }
+                    for event in parser.feed(&line) {
+                        let _ = events_tx.send(SparkEvent::Chat {
+                            spark_id: spark_id.clone(),
+                            event,
+                        });
+                    }
+                }
+            }
+
+            let exit = child.wait().await;
+            let stderr = stderr_task.await.unwrap_or_default();
+
+            let final_status = match exit {
+                Ok(s) if s.success() => SparkStatus::Idle,
+                Ok(s) => {
+                    let code = s.code().map_or("signal".to_string(), |c| c.to_string());
+                    let message = match tail(stderr.trim(), STDERR_TAIL_BYTES) {
+                        "" => format!("claude exited with {code}"),
+                        tail => format!("claude exited with {code}: {tail}"),
+                    };
+                    let _ = events_tx.send(SparkEvent::Chat {
+                        spark_id: spark_id.clone(),
+                        event: ChatEvent::Error { message },
+                    });
+                    SparkStatus::Failed
+                }
+                Err(e) => {
+                    let _ = events_tx.send(SparkEvent::Chat {
+                        spark_id: spark_id.clone(),
+                        event: ChatEvent::Error {
+                            message: format!("failed to wait for claude: {e}"),
+                        },
+                    });
+                    SparkStatus::Failed
+                }
+            };
+
+            *status.write().await = final_status;
+            let _ = events_tx.send(SparkEvent::StatusChanged {
+                spark_id,
+                status: final_status,
+            });
+        });
+
+        Ok(())
+    }
+
+    async fn set_status(&self, status: SparkStatus) {
+        *self.status.write().await = status;
+        self.emit_status(status);
+    }
+
+    fn emit_status(&self, status: SparkStatus) {
+        let _ = self.events_tx.send(SparkEvent::StatusChanged {
+            spark_id: self.id.clone(),
+            status,
+        });
+    }
 }
@@ -0,0 +1,189 @@
+//! Fixture-driven coverage for `StreamParser` against recorded
+//! `claude --output-format stream-json` sessions in `tests/fixtures/`.
+
+use std::path::Path;
+
+use forja_spark::events::{ChatEvent, Usage};
+use forja_spark::parser::StreamParser;
+
+fn parse_fixture(name: &str) -> Vec<ChatEvent> {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures")
+        .join(format!("{name}.jsonl"));
+    let content = std::fs::read_to_string(path).unwrap();
+    let mut parser = StreamParser::new();
+    content.lines().flat_map(|line| parser.feed(line)).collect()
+}
+
+fn text(events: &[ChatEvent]) -> String {
+    events
+        .iter()
+        .filter_map(|e| match e {
+            ChatEvent::TextDelta { text, .. } => Some(text.as_str()),
+            _ => None,
+        })
+        .collect::<Vec<_>>()
+        .join("|")
+}
+
+fn tool_uses(events: &[ChatEvent]) -> Vec<(&str, &str, &serde_json::Value)> {
+    events
+        .iter()
+        .filter_map(|e| match e {
+            ChatEvent::ToolUseStart { id, name, input } => {
+                Some((id.as_str(), name.as_str(), input))
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+fn turn_complete(events: &[ChatEvent]) -> &ChatEvent {
+    events
+        .iter()
+        .find(|e| matches!(e, ChatEvent::TurnComplete { .. }))
+        .expect("fixture has a result event")
+}
+
+#[test]
+fn hello_session_init_and_text() {
+    let events = parse_fixture("hello");
+
+    match &events[0] {
+        ChatEvent::SessionInit {
+            session_id,
+            model,
+            tools,
+            ..
+        } => {
+            assert_eq!(session_id, "5f8d2c1e-7b1a-4c3e-9d2f-0a1b2c3d4e5f");
+            assert_eq!(model, "claude-sonnet-4-5-20250929");
+            assert!(tools.contains(&"Read".to_string()));
+        }
+        other => panic!("expected SessionInit, got {:?}", other),
+    }
+    assert_eq!(text(&events), "Hello| there!");
+    // The complete assistant message was already streamed
+    let starts = events
+        .iter()
+        .filter(|e| matches!(e, ChatEvent::MessageStart { .. }))
+        .count();
+    assert_eq!(starts, 1);
+}
+
+#[test]
+fn streamed_tool_use_accumulates_input() {
+    let events = parse_fixture("tool_use");
+
+    let tools = tool_uses(&events);
+    assert_eq!(tools.len(), 1);
+    assert_eq!(tools[0].0, "toolu_01Rd8");
+    assert_eq!(tools[0].1, "Read");
+    assert_eq!(tools[0].2["file_path"], "/tmp/project/Cargo.toml");
+
+    match events
+        .iter()
+        .find(|e| matches!(e, ChatEvent::ToolResult { .. }))
+    {
+        Some(ChatEvent::ToolResult {
+            id,
+            content,
+            is_error,
+        }) => {
+            assert_eq!(id, "toolu_01Rd8");
+            assert!(content.contains("name = \"demo\""));
+            assert!(!is_error);
+        }
+        other => panic!("expected ToolResult, got {:?}", other),
+    }
+
+    assert_eq!(
+        text(&events),
+        "Let me check the manifest.|The crate is named demo."
+    );
+}
+
+#[test]
+fn plain_output_matches_streamed_output() {
+    let streamed = parse_fixture("tool_use");
+    let plain = parse_fixture("tool_use_plain");
+
+    assert_eq!(text(&plain), text(&streamed));
+    assert_eq!(
+        format!("{:?}", tool_uses(&plain)),
+        format!("{:?}", tool_uses(&streamed))
+    );
+}
+
+#[test]
+fn result_reports_cost_usage_and_duration() {
+    let events = parse_fixture("tool_use");
+
+    match turn_complete(&events) {
+        ChatEvent::TurnComplete {
+            subtype,
+            is_error,
+            result,
+            duration_ms,
+            num_turns,
+            total_cost_usd,
+            usage,
+            ..
+        } => {
+            assert_eq!(subtype, "success");
+            assert!(!is_error);
+            assert_eq!(result.as_deref(), Some("The crate is named demo."));
+            assert_eq!(*duration_ms, 5210);
+            assert_eq!(*num_turns, 2);
+            assert!((total_cost_usd - 0.0231455).abs() < 1e-9);
+            assert_eq!(
+                *usage,
+                Usage {
+                    input_tokens: 10,
+                    output_tokens: 80,
+                    cache_creation_input_tokens: 140,
+                    cache_read_input_tokens: 26740,
+                }
+            );
+        }
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn error_result_and_failed_tool() {
+    let events = parse_fixture("max_turns");
+
+    assert!(
+        events
+            .iter()
+            .any(|e| matches!(e, ChatEvent::ToolResult { is_error: true, .. }))
+    );
+    match turn_complete(&events) {
+        ChatEvent::TurnComplete {
+            subtype,
+            is_error,
+            result,
+            ..
+        } => {
+            assert_eq!(subtype, "error_max_turns");
+            assert!(is_error);
+            assert!(result.is_none());
+        }
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn turn_complete_converts_to_token_usage() {
+    let events = parse_fixture("max_turns");
+    let usage = turn_complete(&events).token_usage().unwrap();
+
+    assert_eq!(usage.input_tokens, 4);
+    assert_eq!(usage.cache_read_input_tokens, 9000);
+    assert_eq!(usage.output_tokens, 40);
+    assert_eq!(usage.duration_ms, 3120);
+    assert_eq!(usage.num_turns, 1);
+    assert!((usage.cost_usd - 0.0087).abs() < f64::EPSILON);
+    assert!(events[0].token_usage().is_none());
+}
@@ -0,0 +1,157 @@
+//! End-to-end tests for `Spark` against a fake `claude` on `PATH` that
+//! replays recorded stream-json fixtures (see `tests/fixtures/`).
+#![cfg(unix)]
+
+use std::path::Path;
+use std::sync::Once;
+use std::time::Duration;
+
+use forja_spark::SparkError;
+use forja_spark::events::{ChatEvent, SparkEvent, SparkStatus};
+use forja_spark::process::Spark;
+use tempfile::TempDir;
+use tokio::sync::broadcast;
+
+const SESSION: &str = "5f8d2c1e-7b1a-4c3e-9d2f-0a1b2c3d4e5f";
+
+/// Put the fake `claude` first on `PATH` (once, before any test spawns a process).
+fn use_fake_claude() {
+    static INIT: Once = Once::new();
+    INIT.call_once(|| {
+        let bin = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/bin");
+        let path = format!(
+            "{}:{}",
+            bin.display(),
+            std::env::var("PATH").unwrap_or_default()
+        );
+        // SAFETY: runs exactly once, before any test in this binary reads the environment.
+        unsafe { std::env::set_var("PATH", path) };
+    });
+}
+
+/// Collect events until the turn ends (status becomes Idle or Failed).
+async fn collect_turn(rx: &mut broadcast::Receiver<SparkEvent>) -> (Vec<ChatEvent>, SparkStatus) {
+    let mut chat = Vec::new();
+    loop {
+        let event = tokio::time::timeout(Duration::from_secs(10), rx.recv())
+            .await
+            .expect("timed out waiting for spark events")
+            .expect("event channel closed");
+        match event {
+            SparkEvent::Chat { event, .. } => chat.push(event),
+            SparkEvent::StatusChanged { status, .. }
+                if matches!(status, SparkStatus::Idle | SparkStatus::Failed) =>
+            {
+                return (chat, status);
+            }
+            SparkEvent::StatusChanged { .. } => {}
+        }
+    }
+}
+
+fn recorded_args(dir: &TempDir) -> Vec<String> {
+    std::fs::read_to_string(dir.path().join("claude-args.txt"))
+        .unwrap()
+        .lines()
+        .map(String::from)
+        .collect()
+}
+
+#[tokio::test]
+async fn start_streams_text_and_captures_session() {
+    use_fake_claude();
+    let dir = TempDir::new().unwrap();
+    let spark = Spark::new("proj".into(), dir.path().to_path_buf());
+    let mut rx = spark.subscribe();
+
+    spark.start("hello".into()).await.unwrap();
+    let (events, status) = collect_turn(&mut rx).await;
+
+    assert_eq!(status, SparkStatus::Idle);
+    assert_eq!(spark.status().await, SparkStatus::Idle);
+    assert_eq!(spark.session_id.read().await.as_deref(), Some(SESSION));
+
+    let text: String = events
+        .iter()
+        .filter_map(|e| match e {
+            ChatEvent::TextDelta { text, .. } => Some(text.as_str()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(text, "Hello there!");
+    assert!(matches!(
+        events.first(),
+        Some(ChatEvent::SessionInit { .. })
+    ));
+    assert!(
+        events
+            .iter()
+            .any(|e| matches!(e, ChatEvent::MessageStart { .. }))
+    );
+    assert!(events.iter().any(|e| matches!(e, ChatEvent::MessageStop)));
+
+    let args = recorded_args(&dir);
+    assert!(
+        args.windows(2)
+            .any(|w| w == ["--output-format", "stream-json"])
+    );
+    assert!(!args.contains(&"--resume".to_string()));
+}
+
+#[tokio::test]
+async fn send_message_resumes_captured_session() {
+    use_fake_claude();
+    let dir = TempDir::new().unwrap();
+    let spark = Spark::new("proj".into(), dir.path().to_path_buf());
+    let mut rx = spark.subscribe();
+
+    spark.start("hello".into()).await.unwrap();
+    collect_turn(&mut rx).await;
+
+    spark.send_message("hello".into()).await.unwrap();
+    let (_, status) = collect_turn(&mut rx).await;
+
+    assert_eq!(status, SparkStatus::Idle);
+    let args = recorded_args(&dir);
+    assert!(args.windows(2).any(|w| w == ["--resume", SESSION]));
+}
+
+#[tokio::test]
+async fn send_message_without_session_errors() {
+    let dir = TempDir::new().unwrap();
+    let spark = Spark::new("proj".into(), dir.path().to_path_buf());
+
+    let err = spark.send_message("hi".into()).await.unwrap_err();
+    assert!(matches!(err, SparkError::NoSession));
+}
+
+#[tokio::test]
+async fn turn_is_rejected_while_another_is_starting() {
+    let dir = TempDir::new().unwrap();
+    let spark = Spark::new("proj".into(), dir.path().to_path_buf());
+    assert_eq!(spark.status().await, SparkStatus::Idle);
+
+    *spark.status.write().await = SparkStatus::Starting;
+    let err = spark.start("hello".into()).await.unwrap_err();
+    assert!(matches!(err, SparkError::Process(_)));
+}
+
+#[tokio::test]
+async fn failed_process_reports_stderr() {
+    use_fake_claude();
+    let dir = TempDir::new().unwrap();
+    let spark = Spark::new("proj".into(), dir.path().to_path_buf());
+    let mut rx = spark.subscribe();
+
+    spark.start("no-such-fixture".into()).await.unwrap();
+    let (events, status) = collect_turn(&mut rx).await;
+
+    assert_eq!(status, SparkStatus::Failed);
+    match events.last() {
+        Some(ChatEvent::Error { message }) => {
+            assert!(message.contains("exited with 1"), "{message}");
+            assert!(message.contains("no fixture"), "{message}");
+        }
+        other => panic!("expected Error, got {:?}", other),
+    }
+}
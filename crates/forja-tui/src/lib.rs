@@ -1,18 +1,14 @@
 pub mod app;
 pub mod input;
+pub mod search;
+pub mod terminal;
 pub mod ui;
 
-use std::io;
-
-use crossterm::{
-    event::{self, Event},
-    execute,
-    terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
-};
-use ratatui::{Terminal, backend::CrosstermBackend};
+use crossterm::event::{self, Event};
 
 use forja_core::error::{ForjaError, Result};
 use forja_core::models::profile::Profile;
+use forja_core::models::registry::Registry;
 use forja_core::models::state::load_state;
 use forja_core::paths::ForjaPaths;
 
@@ -33,14 +29,6 @@ pub struct PlanOutput {
 /// Open the interactive TUI for task configuration.
 /// Returns `Some(TaskOutput)` on launch, `None` on Esc/quit.
 pub fn launch() -> Result<Option<TaskOutput>> {
-    // Check for interactive terminal
-    use std::io::IsTerminal;
-    if !io::stdin().is_terminal() {
-        return Err(ForjaError::Dialoguer(
-            "TUI requires an interactive terminal. Use: forja task \"description\"".to_string(),
-        ));
-    }
-
     // Build team options from forja state
     let (team_labels, team_names) = load_team_options();
     let profile_options = vec!["fast".to_string(), "balanced".to_string(), "max".to_string()];
@@ -50,21 +38,13 @@ pub fn launch() -> Result<Option<TaskOutput>> {
     app.profile_index = 1;
 
     // Terminal setup
-    enable_raw_mode().map_err(|e| ForjaError::Dialoguer(format!("raw mode: {e}")))?;
-    let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen)
-        .map_err(|e| ForjaError::Dialoguer(format!("alternate screen: {e}")))?;
-    let backend = CrosstermBackend::new(stdout);
-    let mut terminal =
-        Terminal::new(backend).map_err(|e| ForjaError::Dialoguer(format!("terminal: {e}")))?;
+    let mut terminal = terminal::enter("forja task \"description\"")?;
 
     // Main loop
     let result = run_loop(&mut terminal, &mut app);
 
     // Terminal teardown (always runs, even on error)
-    disable_raw_mode().ok();
-    execute!(terminal.backend_mut(), LeaveAlternateScreen).ok();
-    terminal.show_cursor().ok();
+    terminal::restore(&mut terminal);
 
     // Propagate any error from the loop
     result?;
@@ -87,28 +67,11 @@ pub fn launch() -> Result<Option<TaskOutput>> {
 /// Open the interactive TUI for plan creation (textarea only, no team/profile).
 /// Returns `Some(PlanOutput)` on create, `None` on Esc/quit.
 pub fn launch_plan() -> Result<Option<PlanOutput>> {
-    use std::io::IsTerminal;
-    if !io::stdin().is_terminal() {
-        return Err(ForjaError::Dialoguer(
-            "TUI requires an interactive terminal. Use: forja plan \"description\"".to_string(),
-        ));
-    }
-
     let mut app = App::new_plan();
 
-    enable_raw_mode().map_err(|e| ForjaError::Dialoguer(format!("raw mode: {e}")))?;
-    let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen)
-        .map_err(|e| ForjaError::Dialoguer(format!("alternate screen: {e}")))?;
-    let backend = CrosstermBackend::new(stdout);
-    let mut terminal =
-        Terminal::new(backend).map_err(|e| ForjaError::Dialoguer(format!("terminal: {e}")))?;
-
+    let mut terminal = terminal::enter("forja plan \"description\"")?;
     let result = run_loop(&mut terminal, &mut app);
-
-    disable_raw_mode().ok();
-    execute!(terminal.backend_mut(), LeaveAlternateScreen).ok();
-    terminal.show_cursor().ok();
+    terminal::restore(&mut terminal);
 
     result?;
 
@@ -121,10 +84,37 @@ pub fn launch_plan() -> Result<Option<PlanOutput>> {
     }
 }
 
-fn run_loop(
-    terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
-    app: &mut App,
-) -> Result<()> {
+/// Open the skill browser over `registry`: type to search, Enter to pick.
+/// Returns the picked skill ID, `None` on Esc/quit.
+pub fn launch_search(registry: &Registry) -> Result<Option<String>> {
+    let mut app = search::SearchApp::new(registry);
+
+    let mut terminal = terminal::enter("forja search \"query\"")?;
+
+    let result = (|| -> Result<()> {
+        loop {
+            terminal
+                .draw(|frame| search::render(frame, &app))
+                .map_err(|e| ForjaError::Dialoguer(format!("draw: {e}")))?;
+
+            if let Event::Key(key) = event::read().map_err(ForjaError::Io)? {
+                search::handle_key(&mut app, key);
+            }
+
+            if app.should_quit || app.picked.is_some() {
+                return Ok(());
+            }
+        }
+    })();
+
+    terminal::restore(&mut terminal);
+
+    result?;
+
+    Ok(app.picked)
+}
+
+fn run_loop(terminal: &mut terminal::Tui, app: &mut App) -> Result<()> {
     loop {
         terminal
             .draw(|frame| ui::render(frame, app))
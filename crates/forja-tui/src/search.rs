@@ -0,0 +1,277 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph},
+};
+
+use forja_core::models::registry::Registry;
+use forja_core::registry::search::{self, Field, Highlight, SearchHit};
+
+/// State of the skill browser: the query typed so far and its ranked results.
+pub struct SearchApp<'r> {
+    registry: &'r Registry,
+    pub query: String,
+    pub hits: Vec<SearchHit<'r>>,
+    pub selected: usize,
+    pub should_quit: bool,
+    /// ID of the skill picked with Enter.
+    pub picked: Option<String>,
+}
+
+impl<'r> SearchApp<'r> {
+    pub fn new(registry: &'r Registry) -> Self {
+        Self {
+            registry,
+            query: String::new(),
+            hits: search::search(registry, ""),
+            selected: 0,
+            should_quit: false,
+            picked: None,
+        }
+    }
+
+    fn refresh(&mut self) {
+        self.hits = search::search(self.registry, &self.query);
+        self.selected = 0;
+    }
+
+    pub fn push_char(&mut self, c: char) {
+        self.query.push(c);
+        self.refresh();
+    }
+
+    pub fn pop_char(&mut self) {
+        self.query.pop();
+        self.refresh();
+    }
+
+    pub fn select_next(&mut self) {
+        if !self.hits.is_empty() {
+            self.selected = (self.selected + 1) % self.hits.len();
+        }
+    }
+
+    pub fn select_prev(&mut self) {
+        if !self.hits.is_empty() {
+            self.selected = self.selected.checked_sub(1).unwrap_or(self.hits.len() - 1);
+        }
+    }
+
+    pub fn pick(&mut self) {
+        if let Some(hit) = self.hits.get(self.selected) {
+            self.picked = Some(hit.skill.id.clone());
+        }
+    }
+}
+
+pub fn handle_key(app: &mut SearchApp, key: KeyEvent) {
+    match key.code {
+        KeyCode::Esc => app.should_quit = true,
+        KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            app.should_quit = true;
+        }
+        KeyCode::Enter => app.pick(),
+        KeyCode::Down => app.select_next(),
+        KeyCode::Up => app.select_prev(),
+        KeyCode::Backspace => app.pop_char(),
+        KeyCode::Char(c) => app.push_char(c),
+        _ => {}
+    }
+}
+
+pub fn render(frame: &mut Frame, app: &SearchApp) {
+    let chunks = Layout::vertical([
+        Constraint::Length(1), // title
+        Constraint::Length(3), // query
+        Constraint::Min(4),    // results
+        Constraint::Length(1), // help bar
+    ])
+    .split(frame.area());
+
+    let title = Paragraph::new(Line::from(vec![
+        Span::styled(
+            " forja search ",
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(
+            format!("{} results", app.hits.len()),
+            Style::default().fg(Color::DarkGray),
+        ),
+    ]));
+    frame.render_widget(title, chunks[0]);
+
+    let input = Paragraph::new(Line::from(vec![
+        Span::styled("> ", Style::default().fg(Color::Cyan)),
+        Span::raw(app.query.as_str()),
+    ]))
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan))
+            .title(" Query (terms, OR, phase:, tech:) "),
+    );
+    frame.render_widget(input, chunks[1]);
+    let cursor_x = chunks[1].x + 3 + app.query.chars().count() as u16;
+    frame.set_cursor_position((cursor_x, chunks[1].y + 1));
+
+    let items: Vec<ListItem> = app
+        .hits
+        .iter()
+        .map(|hit| {
+            let mut spans = highlighted(
+                &hit.skill.id,
+                &hit.highlights,
+                Field::Id,
+                Style::default().add_modifier(Modifier::BOLD),
+            );
+            spans.push(Span::raw("  "));
+            spans.extend(highlighted(
+                &hit.skill.description,
+                &hit.highlights,
+                Field::Description,
+                Style::default().fg(Color::DarkGray),
+            ));
+            if hit.skill.installed {
+                spans.push(Span::styled(
+                    " [installed]",
+                    Style::default().fg(Color::Green),
+                ));
+            }
+            ListItem::new(Line::from(spans))
+        })
+        .collect();
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::DarkGray))
+                .title(" Skills "),
+        )
+        .highlight_style(Style::default().bg(Color::DarkGray));
+    let mut state = ListState::default().with_selected(Some(app.selected));
+    frame.render_stateful_widget(list, chunks[2], &mut state);
+
+    let help = Paragraph::new(Line::from(vec![
+        Span::styled(" ↑/↓", Style::default().fg(Color::Cyan)),
+        Span::raw(": select  "),
+        Span::styled("Enter", Style::default().fg(Color::Green)),
+        Span::raw(": show skill  "),
+        Span::styled("Esc", Style::default().fg(Color::Red)),
+        Span::raw(": quit"),
+    ]));
+    frame.render_widget(help, chunks[3]);
+}
+
+/// `text` as spans in `base` style, with the ranges matched in `field` in yellow.
+fn highlighted<'t>(
+    text: &'t str,
+    highlights: &[Highlight],
+    field: Field,
+    base: Style,
+) -> Vec<Span<'t>> {
+    let mut ranges: Vec<(usize, usize)> = highlights
+        .iter()
+        .filter(|h| h.field == field)
+        .map(|h| (h.start, h.end))
+        .collect();
+    ranges.sort();
+
+    let mut spans = Vec::new();
+    let mut at = 0;
+    for (start, end) in ranges {
+        if start < at {
+            continue;
+        }
+        spans.push(Span::styled(&text[at..start], base));
+        spans.push(Span::styled(
+            &text[start..end],
+            base.fg(Color::Yellow).add_modifier(Modifier::BOLD),
+        ));
+        at = end;
+    }
+    spans.push(Span::styled(&text[at..], base));
+    spans
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use forja_core::models::phase::Phase;
+    use forja_core::models::skill::Skill;
+    use std::path::PathBuf;
+
+    fn skill(id: &str, description: &str) -> Skill {
+        Skill {
+            id: id.to_string(),
+            registry: "default".to_string(),
+            name: id.rsplit('/').next().unwrap().to_string(),
+            description: description.to_string(),
+            version: None,
+            requires: vec![],
+            settings: Default::default(),
+            phase: Phase::Code,
+            tech: "rust".to_string(),
+            path: PathBuf::from("/tmp/test"),
+            installed: false,
+            content_types: vec![],
+            keywords: vec![],
+        }
+    }
+
+    fn registry() -> Registry {
+        Registry::new(vec![
+            skill("code/rust/feature", "Rust coder"),
+            skill("code/rust/refactor", "Simplifies Rust code"),
+        ])
+    }
+
+    #[test]
+    fn typing_narrows_and_resets_selection() {
+        let registry = registry();
+        let mut app = SearchApp::new(&registry);
+        assert_eq!(app.hits.len(), 2);
+        app.select_next();
+        assert_eq!(app.selected, 1);
+
+        for c in "refac".chars() {
+            app.push_char(c);
+        }
+        assert_eq!(app.hits.len(), 1);
+        assert_eq!(app.selected, 0);
+        app.pop_char();
+        assert_eq!(app.query, "refa");
+    }
+
+    #[test]
+    fn selection_wraps_and_enter_picks() {
+        let registry = registry();
+        let mut app = SearchApp::new(&registry);
+        app.select_prev();
+        assert_eq!(app.selected, 1);
+        handle_key(&mut app, KeyEvent::from(KeyCode::Enter));
+        assert_eq!(app.picked.as_deref(), Some("code/rust/refactor"));
+    }
+
+    #[test]
+    fn highlighted_splits_matched_ranges() {
+        let highlights = [Highlight {
+            field: Field::Description,
+            index: None,
+            start: 5,
+            end: 10,
+        }];
+        let spans = highlighted(
+            "Rust coder",
+            &highlights,
+            Field::Description,
+            Style::default(),
+        );
+        let texts: Vec<&str> = spans.iter().map(|s| s.content.as_ref()).collect();
+        assert_eq!(texts, ["Rust ", "coder", ""]);
+    }
+}
@@ -0,0 +1,35 @@
+use std::io::{self, IsTerminal, Stdout};
+
+use crossterm::{
+    execute,
+    terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
+};
+use ratatui::{Terminal, backend::CrosstermBackend};
+
+use forja_core::error::{ForjaError, Result};
+
+pub type Tui = Terminal<CrosstermBackend<Stdout>>;
+
+/// Switch to raw mode on the alternate screen. `fallback` is the
+/// non-interactive command suggested when stdin isn't a terminal.
+pub fn enter(fallback: &str) -> Result<Tui> {
+    if !io::stdin().is_terminal() {
+        return Err(ForjaError::Dialoguer(format!(
+            "TUI requires an interactive terminal. Use: {fallback}"
+        )));
+    }
+
+    enable_raw_mode().map_err(|e| ForjaError::Dialoguer(format!("raw mode: {e}")))?;
+    let mut stdout = io::stdout();
+    execute!(stdout, EnterAlternateScreen)
+        .map_err(|e| ForjaError::Dialoguer(format!("alternate screen: {e}")))?;
+    let backend = CrosstermBackend::new(stdout);
+    Terminal::new(backend).map_err(|e| ForjaError::Dialoguer(format!("terminal: {e}")))
+}
+
+/// Give the terminal back. Always call it, even when the TUI loop failed.
+pub fn restore(terminal: &mut Tui) {
+    disable_raw_mode().ok();
+    execute!(terminal.backend_mut(), LeaveAlternateScreen).ok();
+    terminal.show_cursor().ok();
+}
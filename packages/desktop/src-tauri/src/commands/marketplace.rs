@@ -1,10 +1,11 @@
 use forja_core::frontmatter;
 use forja_core::models::agent_file::{AgentFile, SkillDetail};
 use forja_core::models::skill::Skill;
-use forja_core::models::state::{load_state, save_state, InstallMeta};
 use forja_core::paths::ForjaPaths;
-use forja_core::registry::catalog;
-use forja_core::symlink::manager::{load_installed_ids, save_installed_ids, SymlinkManager};
+use forja_core::registry::{catalog, deps};
+use forja_core::registry::search::{self, Highlight};
+use forja_core::symlink::install;
+use forja_core::symlink::manager::load_installed_ids;
 use serde::{Deserialize, Serialize};
 use std::fs;
 use tauri::Emitter;
@@ -34,13 +35,28 @@ pub fn list_skills(registry_path: String) -> Result<Vec<Skill>, String> {
     Ok(registry.skills)
 }
 
+/// A ranked search result, with the matched ranges for highlighting.
+#[derive(Debug, Clone, Serialize)]
+pub struct SkillMatch {
+    pub skill: Skill,
+    pub score: u32,
+    pub highlights: Vec<Highlight>,
+}
+
 #[tauri::command]
-pub fn search_skills(registry_path: String, query: String) -> Result<Vec<Skill>, String> {
+pub fn search_skills(registry_path: String, query: String) -> Result<Vec<SkillMatch>, String> {
     let path = std::path::PathBuf::from(&registry_path);
     let paths = ForjaPaths::global().map_err(|e| e.to_string())?;
     let installed_ids = load_installed_ids(&paths.state);
     let registry = catalog::scan(&path, &installed_ids).map_err(|e| e.to_string())?;
-    let results: Vec<Skill> = registry.search(&query).into_iter().cloned().collect();
+    let results = search::search(&registry, &query)
+        .into_iter()
+        .map(|hit| SkillMatch {
+            skill: hit.skill.clone(),
+            score: hit.score,
+            highlights: hit.highlights,
+        })
+        .collect();
     Ok(results)
 }
 
@@ -72,7 +88,7 @@ pub fn get_skill_detail(registry_path: String, skill_id: String) -> Result<Skill
 pub fn install_skill(registry_path: String, skill_id: String) -> Result<(), String> {
     let path = std::path::PathBuf::from(&registry_path);
     let paths = ForjaPaths::global().map_err(|e| e.to_string())?;
-    let mut installed_ids = load_installed_ids(&paths.state);
+    let installed_ids = load_installed_ids(&paths.state);
 
     if installed_ids.contains(&skill_id) {
         return Err(format!("Skill already installed: {skill_id}"));
@@ -83,22 +99,8 @@ pub fn install_skill(registry_path: String, skill_id: String) -> Result<(), Stri
         .find_by_id(&skill_id)
         .ok_or_else(|| format!("Skill not found: {skill_id}"))?;
 
-    let manager = SymlinkManager::new(paths.claude_agents.clone(), paths.claude_commands.clone());
-    manager.install(skill).map_err(|e| e.to_string())?;
-
-    installed_ids.push(skill_id.clone());
-    save_installed_ids(&paths.state, &installed_ids).map_err(|e| e.to_string())?;
-
-    // Record install metadata
-    let mut state = load_state(&paths.state);
-    state.install_metadata.insert(
-        skill_id,
-        InstallMeta {
-            install_date: chrono::Utc::now().to_rfc3339(),
-            last_used: None,
-        },
-    );
-    save_state(&paths.state, &state).map_err(|e| e.to_string())?;
+    // Required skills come along, and nothing is left behind on failure
+    install::install_with_requirements(&paths, &registry, skill).map_err(|e| e.to_string())?;
 
     Ok(())
 }
@@ -106,22 +108,15 @@ pub fn install_skill(registry_path: String, skill_id: String) -> Result<(), Stri
 #[tauri::command]
 pub fn uninstall_skill(skill_id: String) -> Result<(), String> {
     let paths = ForjaPaths::global().map_err(|e| e.to_string())?;
-    let mut installed_ids = load_installed_ids(&paths.state);
+    let installed_ids = load_installed_ids(&paths.state);
 
     if !installed_ids.contains(&skill_id) {
         return Err(format!("Skill not installed: {skill_id}"));
     }
 
-    let manager = SymlinkManager::new(paths.claude_agents.clone(), paths.claude_commands.clone());
-    manager.uninstall(&skill_id).map_err(|e| e.to_string())?;
-
-    installed_ids.retain(|id| id != &skill_id);
-    save_installed_ids(&paths.state, &installed_ids).map_err(|e| e.to_string())?;
-
-    // Remove install metadata
-    let mut state = load_state(&paths.state);
-    state.install_metadata.remove(&skill_id);
-    save_state(&paths.state, &state).map_err(|e| e.to_string())?;
+    let registry = catalog::scan_all(&paths, &installed_ids).map_err(|e| e.to_string())?;
+    deps::check_removable(&registry, &skill_id, &installed_ids).map_err(|e| e.to_string())?;
+    install::uninstall(&paths, &skill_id).map_err(|e| e.to_string())?;
 
     Ok(())
 }
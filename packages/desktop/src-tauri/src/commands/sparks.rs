@@ -1,4 +1,6 @@
-use forja_spark::events::SparkStatus;
+use forja_core::models::usage::TokenUsage;
+use forja_spark::events::{ChatEvent, SparkStatus};
+use forja_spark::parser::StreamParser;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs;
@@ -26,6 +28,8 @@ pub struct SparkInfo {
     pub finished_at: Option<String>,
     pub output: Option<String>,
     pub error: Option<String>,
+    #[serde(default)]
+    pub usage: Option<TokenUsage>,
 }
 
 pub type SparkStore = Arc<Mutex<HashMap<String, SparkInfo>>>;
@@ -82,6 +86,9 @@ fn build_cli_args(spark_type: SparkType, description: &str) -> Vec<String> {
 
     vec![
         "--dangerously-skip-permissions".into(),
+        "--output-format".into(),
+        "stream-json".into(),
+        "--verbose".into(),
         "--print".into(),
         "--".into(),
         prompt,
@@ -110,6 +117,7 @@ pub async fn start_spark(
         finished_at: None,
         output: None,
         error: None,
+        usage: None,
     };
 
     store
@@ -163,19 +171,21 @@ pub async fn start_spark(
                 s.finished_at = Some(finished_at);
                 match result {
                     Ok(output) => {
-                        let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+                        let stdout = String::from_utf8_lossy(&output.stdout);
                         let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+                        let (result, usage) = parse_stream_output(&stdout);
+                        s.usage = usage;
 
                         if output.status.success() {
                             s.status = SparkStatus::Stopped;
-                            s.output = if stdout.is_empty() {
-                                None
-                            } else {
-                                Some(stdout)
-                            };
+                            s.output = result.filter(|r| !r.is_empty());
                         } else {
                             s.status = SparkStatus::Failed;
-                            s.error = Some(if stderr.is_empty() { stdout } else { stderr });
+                            s.error = Some(if stderr.is_empty() {
+                                result.unwrap_or_default()
+                            } else {
+                                stderr
+                            });
                         }
                     }
                     Err(e) => {
@@ -198,6 +208,25 @@ pub async fn start_spark(
     Ok(spark)
 }
 
+/// Final result text and token usage from a `--output-format stream-json` transcript.
+fn parse_stream_output(stdout: &str) -> (Option<String>, Option<TokenUsage>) {
+    let mut parser = StreamParser::new();
+    let mut result = None;
+    let mut usage = None;
+    for event in stdout.lines().flat_map(|line| parser.feed(line)) {
+        if let Some(run) = event.token_usage() {
+            usage = Some(run);
+        }
+        if let ChatEvent::TurnComplete {
+            result: Some(text), ..
+        } = event
+        {
+            result = Some(text);
+        }
+    }
+    (result, usage)
+}
+
 fn is_terminal(status: SparkStatus) -> bool {
     matches!(status, SparkStatus::Stopped | SparkStatus::Failed)
 }
@@ -36,6 +36,17 @@ export interface SparkInfo {
   finished_at: string | null;
   output: string | null;
   error: string | null;
+  usage: TokenUsage | null;
+}
+
+export interface TokenUsage {
+  input_tokens: number;
+  output_tokens: number;
+  cache_creation_input_tokens: number;
+  cache_read_input_tokens: number;
+  cost_usd: number;
+  duration_ms: number;
+  num_turns: number;
 }
 
 export async function startSpark(
@@ -110,6 +121,8 @@ export interface Skill {
   id: string;
   name: string;
   description: string;
+  version: string | null;
+  requires: string[];
   phase: Phase;
   tech: string;
   path: string;
@@ -118,6 +131,29 @@ export interface Skill {
   keywords: string[];
 }
 
+export type SearchField =
+  | "name"
+  | "keyword"
+  | "tech"
+  | "phase"
+  | "id"
+  | "description";
+
+/** Byte range of `field` (or of `keywords[index]`) a query term matched. */
+export interface SearchHighlight {
+  field: SearchField;
+  index?: number;
+  start: number;
+  end: number;
+}
+
+/** A search result, best first. */
+export interface SkillMatch {
+  skill: Skill;
+  score: number;
+  highlights: SearchHighlight[];
+}
+
 export interface AgentFrontmatter {
   name: string;
   description?: string;
@@ -146,6 +182,7 @@ export interface ForjaPaths {
 export interface InstallMeta {
   install_date: string;
   last_used: string | null;
+  version?: string;
 }
 
 export async function getForjaPaths(): Promise<ForjaPaths> {
@@ -159,8 +196,8 @@ export async function listSkills(registryPath: string): Promise<Skill[]> {
 export async function searchSkills(
   registryPath: string,
   query: string,
-): Promise<Skill[]> {
-  return invoke<Skill[]>("search_skills", { registryPath, query });
+): Promise<SkillMatch[]> {
+  return invoke<SkillMatch[]>("search_skills", { registryPath, query });
 }
 
 export async function getSkillDetail(
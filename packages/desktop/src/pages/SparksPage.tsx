@@ -205,6 +205,8 @@ export function SparksPage() {
                         </CardTitle>
                         <p className="text-xs text-muted-foreground mt-0.5">
                           {new Date(spark.created_at).toLocaleTimeString()}
+                          {spark.usage &&
+                            ` · ${formatTokens(spark.usage.input_tokens + spark.usage.cache_creation_input_tokens + spark.usage.cache_read_input_tokens)} in · ${formatTokens(spark.usage.output_tokens)} out · $${spark.usage.cost_usd.toFixed(2)}`}
                         </p>
                       </div>
                     </div>
@@ -246,3 +248,9 @@ export function SparksPage() {
     </div>
   );
 }
+
+function formatTokens(n: number): string {
+  if (n < 1_000) return String(n);
+  if (n < 1_000_000) return `${(n / 1_000).toFixed(1)}k`;
+  return `${(n / 1_000_000).toFixed(1)}M`;
+}